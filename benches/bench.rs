@@ -9,6 +9,122 @@ fn readme(c: &mut Criterion) {
     });
 }
 
+fn giant_paragraph(c: &mut Criterion) {
+    // One paragraph, with lots of attention and line endings in it.
+    let mut doc = String::new();
+    let mut index = 0;
+    while index < 50_000 {
+        doc.push_str(match index % 11 {
+            0 => "*a* ",
+            3 => "**b** ",
+            7 => "_c\n",
+            _ => "word ",
+        });
+        index += 1;
+    }
+
+    let mut group = c.benchmark_group("giant_paragraph");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("giant_paragraph", "50k words"),
+        &doc,
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.finish();
+}
+
+fn giant_whitespace(c: &mut Criterion) {
+    // Lots of spaces and tabs, in indents and between words.
+    let mut doc = String::new();
+    let mut index = 0;
+    while index < 50_000 {
+        doc.push_str(match index % 7 {
+            0 => "  \t  a",
+            3 => "\n   \t",
+            5 => "\n> \t- \tb",
+            _ => "    c    ",
+        });
+        index += 1;
+    }
+
+    let mut group = c.benchmark_group("giant_whitespace");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("giant_whitespace", "50k runs"),
+        &doc,
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.finish();
+}
+
+fn giant_code_text(c: &mut Criterion) {
+    // Lots of code (text), which is each subtokenized and edited in.
+    let doc = "`a` b ".repeat(50_000);
+
+    let mut group = c.benchmark_group("giant_code_text");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("giant_code_text", "50k spans"),
+        &doc,
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.finish();
+}
+
+fn giant_prose(c: &mut Criterion) {
+    // Lots of paragraphs of plain prose, without markup.
+    let doc = "Lorem ipsum dolor sit amet, consectetur adipiscing elit,\nsed do eiusmod tempor incididunt ut labore et dolore magna aliqua.\n\n"
+        .repeat(10_000);
+
+    let mut group = c.benchmark_group("giant_prose");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("giant_prose", "10k paragraphs"),
+        &doc,
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.finish();
+}
+
+fn many_comments(c: &mut Criterion) {
+    // Lots of short documents, like comments.
+    let docs: Vec<String> = (0..1000)
+        .map(|index| {
+            format!(
+                "Thanks @user{}, *looks* good!\n\n- [x] a `b`\n- [ ] see <https://c.d/{}>",
+                index, index
+            )
+        })
+        .collect();
+    let options = markdown::Options::gfm();
+
+    let mut group = c.benchmark_group("many_comments");
+    group.bench_with_input(BenchmarkId::new("to_html", "1k docs"), &docs, |b, docs| {
+        b.iter(|| {
+            for doc in docs {
+                markdown::to_html_with_options(doc, &options).unwrap();
+            }
+        });
+    });
+    group.bench_with_input(BenchmarkId::new("compiler", "1k docs"), &docs, |b, docs| {
+        let mut compiler = markdown::Compiler::new();
+        b.iter(|| {
+            for doc in docs {
+                compiler.render(doc, &options).unwrap();
+            }
+        });
+    });
+    group.finish();
+}
+
 // fn one_and_a_half_mb(c: &mut Criterion) {
 //     let doc = fs::read_to_string("../a-dump-of-markdown/markdown.md").unwrap();
 //     let mut group = c.benchmark_group("giant");
@@ -20,5 +136,13 @@ fn readme(c: &mut Criterion) {
 // }
 // , one_and_a_half_mb
 
-criterion_group!(benches, readme);
+criterion_group!(
+    benches,
+    readme,
+    giant_paragraph,
+    giant_whitespace,
+    giant_code_text,
+    giant_prose,
+    many_comments
+);
 criterion_main!(benches);
@@ -0,0 +1,125 @@
+//! Turn many markdown documents into HTML, one after another, reusing memory.
+
+use crate::configuration::Options;
+use crate::event::Event;
+use crate::parser;
+use crate::to_html;
+use alloc::{string::String, vec::Vec};
+
+/// Factor by which a buffer can be bigger than what the last document
+/// needed, before it is shrunk.
+const SHRINK_FACTOR: usize = 4;
+
+/// Capacity that buffers are always allowed to keep.
+const SHRINK_MINIMUM: usize = 1024;
+
+/// Compiler that turns markdown into HTML, reusing its memory between
+/// documents.
+///
+/// Turning markdown into HTML allocates a list of events and a string of
+/// HTML.
+/// When turning lots of small documents into HTML, such as comments, that
+/// setup can take a big part of the time.
+/// A compiler keeps those buffers, and clears them instead of making new ones
+/// for each document.
+/// The result of [`render()`][Compiler::render] is the same as
+/// [`to_html_with_options()`][crate::to_html_with_options].
+///
+/// Buffers grow to fit the biggest document.
+/// When a document is much smaller than the capacity of a buffer, the buffer
+/// is shrunk, so that one big document does not hold on to memory forever.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{Compiler, Options};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = Options::default();
+/// let mut compiler = Compiler::new();
+///
+/// assert_eq!(compiler.render("# a", &options)?, "<h1>a</h1>");
+/// assert_eq!(compiler.render("*b*", &options)?, "<p><em>b</em></p>");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Compiler {
+    /// Events of the last document.
+    events: Vec<Event>,
+    /// HTML of the last document.
+    value: String,
+}
+
+impl Compiler {
+    /// Create a compiler.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turn markdown into HTML, with configuration.
+    ///
+    /// The HTML is kept in the compiler, until the next call.
+    ///
+    /// ## Errors
+    ///
+    /// `render()` never errors with normal markdown because markdown does not
+    /// have syntax errors, so feel free to `unwrap()`.
+    /// However, MDX does have syntax errors.
+    /// When MDX is turned on, there are several errors that can occur with how
+    /// expressions, ESM, and JSX are written.
+    pub fn render(&mut self, value: &str, options: &Options) -> Result<&str, String> {
+        let parse_state = parser::parse_into(value, &options.parse, &mut self.events)?;
+        let buffer = core::mem::take(&mut self.value);
+        self.value = to_html::compile_into(&self.events, parse_state.bytes, options, buffer);
+
+        if self.events.capacity() > (self.events.len() * SHRINK_FACTOR).max(SHRINK_MINIMUM) {
+            self.events
+                .shrink_to((self.events.len() * 2).max(SHRINK_MINIMUM));
+        }
+
+        if self.value.capacity() > (self.value.len() * SHRINK_FACTOR).max(SHRINK_MINIMUM) {
+            self.value
+                .shrink_to((self.value.len() * 2).max(SHRINK_MINIMUM));
+        }
+
+        Ok(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn shrink() {
+        let options = Options::default();
+        let mut compiler = Compiler::new();
+        let big = format!("{}\n", "*a* ".repeat(10_000));
+
+        compiler.render(&big, &options).unwrap();
+        let events = compiler.events.capacity();
+        let value = compiler.value.capacity();
+        assert!(
+            events > SHRINK_MINIMUM,
+            "should grow events for big documents"
+        );
+        assert!(value > SHRINK_MINIMUM, "should grow HTML for big documents");
+
+        compiler.render(&big, &options).unwrap();
+        assert_eq!(
+            (compiler.events.capacity(), compiler.value.capacity()),
+            (events, value),
+            "should reuse memory for similar documents"
+        );
+
+        assert_eq!(compiler.render("b", &options).unwrap(), "<p>b</p>");
+        assert_eq!(
+            (compiler.events.capacity(), compiler.value.capacity()),
+            (SHRINK_MINIMUM, SHRINK_MINIMUM),
+            "should shrink memory for much smaller documents"
+        );
+    }
+}
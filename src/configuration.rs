@@ -1,8 +1,11 @@
+use crate::headings::SlugPolicy;
 use crate::util::{
+    encode::ControlCharacterPolicy,
     line_ending::LineEnding,
     mdx::{EsmParse as MdxEsmParse, ExpressionParse as MdxExpressionParse},
 };
-use alloc::{boxed::Box, fmt, string::String};
+use alloc::{boxed::Box, fmt, string::String, sync::Arc, vec, vec::Vec};
+use core::ops::Deref;
 
 /// Control which constructs are enabled.
 ///
@@ -99,6 +102,49 @@ pub struct Constructs {
     ///     ^^^^^^^^^^
     /// ```
     pub definition: bool,
+    /// Directive (container).
+    ///
+    /// ```markdown
+    /// > | :::a[b]{#c}
+    ///     ^^^^^^^^^^^
+    /// > | d
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    pub directive_container: bool,
+    /// Directive (leaf).
+    ///
+    /// ```markdown
+    /// > | ::a[b]{#c}
+    ///     ^^^^^^^^^^
+    /// ```
+    pub directive_leaf: bool,
+    /// Directive (text).
+    ///
+    /// ```markdown
+    /// > | a :b[c]{#d} e
+    ///       ^^^^^^^^^
+    /// ```
+    pub directive_text: bool,
+    /// Emoji.
+    ///
+    /// ```markdown
+    /// > | a :smile: b
+    ///       ^^^^^^^
+    /// ```
+    pub emoji: bool,
+    /// Fenced div.
+    ///
+    /// ```markdown
+    /// > | ::: {.a}
+    ///     ^^^^^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    pub fenced_div: bool,
     /// Frontmatter.
     ///
     /// ````markdown
@@ -208,6 +254,13 @@ pub struct Constructs {
     ///       ^^
     /// ```
     pub label_start_image: bool,
+    /// Label start (inline footnote).
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    pub label_start_inline_footnote: bool,
     /// Label start (link).
     ///
     /// ```markdown
@@ -349,6 +402,11 @@ impl Default for Constructs {
             code_fenced: true,
             code_text: true,
             definition: true,
+            directive_container: false,
+            directive_leaf: false,
+            directive_text: false,
+            emoji: false,
+            fenced_div: false,
             frontmatter: false,
             gfm_autolink_literal: false,
             gfm_label_start_footnote: false,
@@ -363,6 +421,7 @@ impl Default for Constructs {
             html_flow: true,
             html_text: true,
             label_start_image: true,
+            label_start_inline_footnote: false,
             label_start_link: true,
             label_end: true,
             list_item: true,
@@ -432,6 +491,220 @@ impl Constructs {
             ..Self::default()
         }
     }
+
+    /// Math.
+    ///
+    /// This turns on `CommonMark` and math (flow and text), which follow
+    /// [`micromark-extension-math`](https://github.com/micromark/micromark-extension-math).
+    pub fn math() -> Self {
+        Self {
+            math_flow: true,
+            math_text: true,
+            ..Self::default()
+        }
+    }
+
+    /// Directives.
+    ///
+    /// This turns on `CommonMark` and directives (container, leaf, and text),
+    /// which follow
+    /// [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive).
+    pub fn directives() -> Self {
+        Self {
+            directive_container: true,
+            directive_leaf: true,
+            directive_text: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Function that transforms text.
+///
+/// Can be passed as [`text_transform`][CompileOptions::text_transform] in
+/// [`CompileOptions`][] to change text before it is encoded.
+///
+/// Cloning it is cheap: the clones share the function.
+#[derive(Clone)]
+pub struct TextTransform(Arc<TextTransformFn>);
+
+/// Function in [`TextTransform`][].
+type TextTransformFn = dyn Fn(&str) -> String + Send + Sync;
+
+impl TextTransform {
+    /// Create a text transform from a function.
+    pub fn new<F>(function: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        Self(Arc::new(function))
+    }
+}
+
+impl Deref for TextTransform {
+    type Target = TextTransformFn;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for TextTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt("[Function]", f)
+    }
+}
+
+/// Function that renders math.
+///
+/// Can be passed as [`math_renderer`][CompileOptions::math_renderer] in
+/// [`CompileOptions`][] to turn math into HTML.
+/// Gets the value of the math and whether it is display math (flow, `true`)
+/// or inline math (text, `false`).
+///
+/// Cloning it is cheap: the clones share the function.
+#[derive(Clone)]
+pub struct MathRenderer(Arc<MathRendererFn>);
+
+/// Function in [`MathRenderer`][].
+type MathRendererFn = dyn Fn(&str, bool) -> String + Send + Sync;
+
+impl MathRenderer {
+    /// Create a math renderer from a function.
+    pub fn new<F>(function: F) -> Self
+    where
+        F: Fn(&str, bool) -> String + Send + Sync + 'static,
+    {
+        Self(Arc::new(function))
+    }
+}
+
+impl Deref for MathRenderer {
+    type Target = MathRendererFn;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for MathRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt("[Function]", f)
+    }
+}
+
+/// Kind of element, passed to
+/// [`element_attributes`][CompileOptions::element_attributes].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ElementKind {
+    /// Block quote (`<blockquote>`).
+    BlockQuote,
+    /// Code (flow) (`<pre>`).
+    Code,
+    /// Fenced div (`<div>`).
+    FencedDiv,
+    /// Heading (`<h1>` through `<h6>`), with its rank (`1` through `6`).
+    Heading(u8),
+    /// List item (`<li>`).
+    ListItem,
+    /// Math (flow) (`<pre>`).
+    Math,
+    /// Ordered list (`<ol>`).
+    OrderedList,
+    /// Paragraph (`<p>`).
+    ///
+    /// Paragraphs in tight lists are not compiled to elements, so the function
+    /// is not called for them.
+    Paragraph,
+    /// GFM: table (`<table>`).
+    Table,
+    /// Thematic break (`<hr />`).
+    ThematicBreak,
+    /// Unordered list (`<ul>`).
+    UnorderedList,
+}
+
+/// Function that gets attributes for an element.
+///
+/// Can be passed as
+/// [`element_attributes`][CompileOptions::element_attributes] in
+/// [`CompileOptions`][] to add attributes to block elements.
+///
+/// Cloning it is cheap: the clones share the function.
+#[derive(Clone)]
+pub struct ElementAttributes(Arc<ElementAttributesFn>);
+
+/// Function in [`ElementAttributes`][].
+type ElementAttributesFn = dyn Fn(ElementKind) -> Vec<(String, String)> + Send + Sync;
+
+impl ElementAttributes {
+    /// Create element attributes from a function.
+    pub fn new<F>(function: F) -> Self
+    where
+        F: Fn(ElementKind) -> Vec<(String, String)> + Send + Sync + 'static,
+    {
+        Self(Arc::new(function))
+    }
+}
+
+impl Deref for ElementAttributes {
+    type Target = ElementAttributesFn;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for ElementAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt("[Function]", f)
+    }
+}
+
+/// Kind of URL, passed to [`url_rewriter`][CompileOptions::url_rewriter].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UrlKind {
+    /// Image (`src` of `<img>`).
+    Image,
+    /// Link (`href` of `<a>`), including autolinks.
+    Link,
+}
+
+/// Function that rewrites URLs.
+///
+/// Can be passed as [`url_rewriter`][CompileOptions::url_rewriter] in
+/// [`CompileOptions`][] to change the URLs of links and images.
+/// Gets the URL and its [kind][UrlKind].
+///
+/// Cloning it is cheap: the clones share the function.
+#[derive(Clone)]
+pub struct UrlRewriter(Arc<UrlRewriterFn>);
+
+/// Function in [`UrlRewriter`][].
+type UrlRewriterFn = dyn Fn(&str, UrlKind) -> String + Send + Sync;
+
+impl UrlRewriter {
+    /// Create a URL rewriter from a function.
+    pub fn new<F>(function: F) -> Self
+    where
+        F: Fn(&str, UrlKind) -> String + Send + Sync + 'static,
+    {
+        Self(Arc::new(function))
+    }
+}
+
+impl Deref for UrlRewriter {
+    type Target = UrlRewriterFn;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for UrlRewriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt("[Function]", f)
+    }
 }
 
 /// Configuration that describes how to compile to HTML.
@@ -518,6 +791,9 @@ pub struct CompileOptions {
     /// (for images: `http`, `https`; for links: `http`, `https`, `irc`,
     /// `ircs`, `mailto`, `xmpp`), are safe.
     /// All other URLs are dangerous and dropped.
+    /// To allow certain other protocols, use
+    /// [`allowed_image_protocols`][Self::allowed_image_protocols] and
+    /// [`allowed_link_protocols`][Self::allowed_link_protocols].
     ///
     /// ## Examples
     ///
@@ -550,536 +826,1800 @@ pub struct CompileOptions {
     /// ```
     pub allow_dangerous_protocol: bool,
 
-    /// Default line ending to use when compiling to HTML, for line endings not
-    /// in `value`.
+    /// Which protocols (schemes) are safe in images.
     ///
-    /// Generally, `markdown-rs` copies line endings (`\r`, `\n`, `\r\n`) in
-    /// the markdown document over to the compiled HTML.
-    /// In some cases, such as `> a`, CommonMark requires that extra line
-    /// endings are added: `<blockquote>\n<p>a</p>\n</blockquote>`.
+    /// The default is `None`, which means `http` and `https`.
     ///
-    /// To create that line ending, the document is checked for the first line
-    /// ending that is used.
-    /// If there is no line ending, `default_line_ending` is used.
-    /// If that isn’t configured, `\n` is used.
+    /// Pass a list of protocols, such as `vec!["https".into(), "data".into()]`,
+    /// to use other protocols in images instead.
+    /// URLs of images with other protocols are dropped, unless
+    /// [`allow_dangerous_protocol`][Self::allow_dangerous_protocol] is on, in
+    /// which case all protocols are allowed.
+    /// Protocols are compared case-insensitively.
+    /// URLs that have no protocol (which means it’s relative to the current
+    /// page, such as `./some/image.png`, or to its protocol, such as
+    /// `//example.com/image.png`) are always allowed.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `markdown-rs` uses `\n` by default:
+    /// // `markdown-rs` drops images with other protocols than `http` and `https` by default:
     /// assert_eq!(
-    ///     to_html("> a"),
-    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    ///     to_html("![](ipfs://a)"),
+    ///     "<p><img src=\"\" alt=\"\" /></p>"
     /// );
     ///
-    /// // Define `default_line_ending` to configure the default:
+    /// // Pass `allowed_image_protocols` to allow other protocols:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "> a",
+    ///         "![](ipfs://a)",
     ///         &Options {
     ///             compile: CompileOptions {
-    ///               default_line_ending: LineEnding::CarriageReturnLineFeed,
+    ///               allowed_image_protocols: Some(vec!["https".into(), "ipfs".into()]),
     ///               ..CompileOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<blockquote>\r\n<p>a</p>\r\n</blockquote>"
+    ///     "<p><img src=\"ipfs://a\" alt=\"\" /></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub default_line_ending: LineEnding,
+    pub allowed_image_protocols: Option<Vec<String>>,
 
-    /// Textual label to use for the footnotes section.
+    /// Which protocols (schemes) are safe in links.
     ///
-    /// The default value is `"Footnotes"`.
-    /// Change it when the markdown is not in English.
+    /// The default is `None`, which means `http`, `https`, `irc`, `ircs`,
+    /// `mailto`, and `xmpp`.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// Pass a list of protocols, such as `vec!["https".into(), "magnet".into()]`,
+    /// to use other protocols in links instead.
+    /// URLs of links with other protocols are dropped, unless
+    /// [`allow_dangerous_protocol`][Self::allow_dangerous_protocol] is on, in
+    /// which case all protocols are allowed.
+    /// Protocols are compared case-insensitively.
+    /// URLs that have no protocol (which means it’s relative to the current
+    /// page, such as `./some/page.html`, or to its protocol, such as
+    /// `//example.com`) are always allowed.
+    ///
+    /// This option wins over the deprecated
+    /// [`allowed_link_schemes`][Self::allowed_link_schemes]: when this is
+    /// set, that is ignored.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `"Footnotes"` is used by default:
+    /// // `markdown-rs` drops links with unknown protocols by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("[a](magnet:?b) [c](javascript:d)"),
+    ///     "<p><a href=\"\">a</a> <a href=\"\">c</a></p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label` to use something else:
+    /// // Pass `allowed_link_protocols` to allow other protocols:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "[a](magnet:?b) [c](javascript:d)",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               allowed_link_protocols: Some(vec!["https".into(), "magnet".into()]),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p><a href=\"magnet:?b\">a</a> <a href=\"\">c</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label: Option<String>,
+    pub allowed_link_protocols: Option<Vec<String>>,
 
-    /// HTML tag name to use for the footnote label element.
+    /// Which protocols (schemes) are allowed in links.
     ///
-    /// The default value is `"h2"`.
-    /// Change it to match your document structure.
+    /// > 👉 **Note**: deprecated, use
+    /// > [`allowed_link_protocols`][Self::allowed_link_protocols] instead,
+    /// > which also controls which protocols are allowed in links.
+    /// > When that is set, this option is ignored.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// The default is `None`, which allows all protocols that are not dropped
+    /// by [`allow_dangerous_protocol`][Self::allow_dangerous_protocol].
+    ///
+    /// Pass a list of protocols, such as `vec!["https".into()]`, to only turn
+    /// links and autolinks with those protocols into `<a>` elements.
+    /// Links and autolinks with other protocols are instead rendered as their
+    /// text content.
+    /// Protocols are compared case-insensitively.
+    /// URLs that have no protocol (which means it’s relative to the current
+    /// page, such as `./some/page.html`) are always allowed.
+    /// Images are not affected.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `"h2"` is used by default:
+    /// // `markdown-rs` links to safe protocols by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("[a](irc://b) [c](https://d)"),
+    ///     "<p><a href=\"irc://b\">a</a> <a href=\"https://d\">c</a></p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// // Pass `allowed_link_schemes` to only link to certain protocols:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "[a](irc://b) [c](https://d)",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label_tag_name: Some("h1".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               allowed_link_schemes: Some(vec!["https".into()]),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>a <a href=\"https://d\">c</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_tag_name: Option<String>,
+    #[deprecated(note = "use `allowed_link_protocols` instead")]
+    pub allowed_link_schemes: Option<Vec<String>>,
 
-    /// Attributes to use on the footnote label.
+    /// Whether to add a `dir` attribute to paragraphs, headings, and list
+    /// items, based on the direction of their text.
     ///
-    /// The default value is `"class=\"sr-only\""`.
-    /// Change it to show the label and add other attributes.
-    ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass an empty string.
-    /// You can also add different attributes.
+    /// The default is `false`.
+    /// Pass `true` to add `dir="rtl"` when the first letter in an element is
+    /// written right-to-left (such as Arabic or Hebrew), and `dir="ltr"` when
+    /// it is written left-to-right (such as Latin).
+    /// Elements without letters get no `dir` attribute.
     ///
-    /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
-    /// > calls use it with `aria-describedby` to provide an accessible label.
+    /// This is useful for documents that mix right-to-left and left-to-right
+    /// text.
+    /// Browsers can do something similar with `dir="auto"`, but that also
+    /// looks at the text in `alt` and such.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `"class=\"sr-only\""` is used by default:
+    /// // `markdown-rs` does not add `dir` by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("# שלום\n\nhello"),
+    ///     "<h1>שלום</h1>\n<p>hello</p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_attributes` to use something else:
+    /// // Turn `auto_dir` on to add it:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "# שלום\n\nhello",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               auto_dir: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<h1 dir=\"rtl\">שלום</h1>\n<p dir=\"ltr\">hello</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_attributes: Option<String>,
+    pub auto_dir: bool,
 
-    /// Textual label to describe the backreference back to footnote calls.
-    ///
-    /// The default value is `"Back to content"`.
-    /// Change it when the markdown is not in English.
+    /// Whether to compile a trailing attribution line in block quotes as a
+    /// `<footer>`.
     ///
-    /// This label is used in the `aria-label` attribute on each backreference
-    /// (the `↩` links).
-    /// It affects users of assistive technology.
+    /// The default is `false`.
+    /// Pass `true` to turn the last line of a block quote into a `<footer>`
+    /// when it starts with an em dash (`—`) or two dashes (`--`), such as
+    /// `> — Ada Lovelace`.
+    /// Only the last line of the last paragraph in a block quote is checked:
+    /// attribution markers elsewhere are left alone.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `"Back to content"` is used by default:
+    /// // `markdown-rs` compiles attributions as text by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("> a\n> — b"),
+    ///     "<blockquote>\n<p>a\n— b</p>\n</blockquote>"
     /// );
     ///
-    /// // Pass `gfm_footnote_back_label` to use something else:
+    /// // Turn `block_quote_citation` on to compile them as footers:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "> a\n> — b",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_back_label: Some("Arrière".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               block_quote_citation: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<blockquote>\n<p>a</p>\n<footer>— b</footer>\n</blockquote>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_back_label: Option<String>,
+    pub block_quote_citation: bool,
 
-    /// Prefix to use before the `id` attribute on footnotes to prevent them
-    /// from *clobbering*.
+    /// Whether to compile soft breaks (line endings in paragraphs and
+    /// headings) as hard breaks.
     ///
-    /// The default is `"user-content-"`.
-    /// Pass `Some("".into())` for trusted markdown and when you are careful
-    /// with polyfilling.
-    /// You could pass a different prefix.
+    /// The default is `false`.
+    /// Pass `true` to render every line ending in text as a `<br />`, as
+    /// GitHub does in comments and many chat apps do in messages.
+    /// Line endings in code, HTML, and between blocks are left alone, and
+    /// line endings after hard breaks (`a\\` or `a  `) are not doubled.
     ///
-    /// DOM clobbering is this:
+    /// ## Examples
     ///
-    /// ```html
-    /// <p id="x"></p>
-    /// <script>alert(x) // `x` now refers to the `p#x` DOM element</script>
     /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), String> {
     ///
-    /// The above example shows that elements are made available by browsers,
-    /// by their ID, on the `window` object.
-    /// This is a security risk because you might be expecting some other
-    /// variable at that place.
-    /// It can also break polyfills.
-    /// Using a prefix solves these problems.
+    /// // `markdown-rs` keeps soft breaks as line endings by default:
+    /// assert_eq!(
+    ///     to_html("a\nb"),
+    ///     "<p>a\nb</p>"
+    /// );
+    ///
+    /// // Turn `breaks` on to compile them as hard breaks:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\nb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               breaks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a<br />\nb</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub breaks: bool,
+
+    /// Whether to compile items in tight lists without line endings directly
+    /// inside `<li>` and `</li>`.
+    ///
+    /// The default is `false`.
+    /// `CommonMark` requires line endings around block content (other than
+    /// paragraphs) in list items, also when the list is tight.
+    /// Some other markdown renderers don’t add them.
+    /// Pass `true` to match their output.
+    ///
+    /// Line endings between the children of an item, and in loose lists, are
+    /// not affected.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `"user-content-"` is used by default:
+    /// // `markdown-rs` follows CommonMark by default:
+    /// assert_eq!(
+    ///     to_html("* # a\n* b"),
+    ///     "<ul>\n<li>\n<h1>a</h1>\n</li>\n<li>b</li>\n</ul>"
+    /// );
+    ///
+    /// // Turn `compact_list_items` on to leave those line endings out:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
+    ///         "* # a\n* b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               compact_list_items: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<ul>\n<li><h1>a</h1></li>\n<li>b</li>\n</ul>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub compact_list_items: bool,
+
+    /// How to handle control characters that are not allowed in HTML.
     ///
-    /// // Pass `gfm_footnote_clobber_prefix` to use something else:
+    /// The default is [`ControlCharacterPolicy::Keep`][], which passes them
+    /// through.
+    /// Pass [`ControlCharacterPolicy::Replace`][] to replace them with the
+    /// replacement character (`U+FFFD`), or
+    /// [`ControlCharacterPolicy::Remove`][] to drop them, so that the output
+    /// is valid HTML.
+    /// This applies to the whole output, including HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, ControlCharacterPolicy, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` passes control characters through by default:
+    /// assert_eq!(
+    ///     to_html("a\u{1}b"),
+    ///     "<p>a\u{1}b</p>"
+    /// );
+    ///
+    /// // Pass `control_character_policy` to do something else:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "a\u{1}b",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               gfm_footnote_clobber_prefix: Some("".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///               control_character_policy: ControlCharacterPolicy::Remove,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>ab</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_clobber_prefix: Option<String>,
+    pub control_character_policy: ControlCharacterPolicy,
 
-    /// Whether to support the GFM tagfilter.
+    /// Default line ending to use when compiling to HTML, for line endings not
+    /// in `value`.
     ///
-    /// This option does nothing if `allow_dangerous_html` is not turned on.
-    /// The default is `false`, which does not apply the GFM tagfilter to HTML.
-    /// Pass `true` for output that is a bit closer to GitHub’s actual output.
+    /// Generally, `markdown-rs` copies line endings (`\r`, `\n`, `\r\n`) in
+    /// the markdown document over to the compiled HTML.
+    /// In some cases, such as `> a`, CommonMark requires that extra line
+    /// endings are added: `<blockquote>\n<p>a</p>\n</blockquote>`.
     ///
-    /// The tagfilter is kinda weird and kinda useless.
-    /// The tag filter is a naïve attempt at XSS protection.
-    /// You should use a proper HTML sanitizing algorithm instead.
+    /// To create that line ending, the document is checked for the first line
+    /// ending that is used.
+    /// If there is no line ending, `default_line_ending` is used.
+    /// If that isn’t configured, `\n` is used.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // With `allow_dangerous_html`, `markdown-rs` passes HTML through untouched:
+    /// // `markdown-rs` uses `\n` by default:
+    /// assert_eq!(
+    ///     to_html("> a"),
+    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Define `default_line_ending` to configure the default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "<iframe>",
+    ///         "> a",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
+    ///               default_line_ending: LineEnding::CarriageReturnLineFeed,
     ///               ..CompileOptions::default()
-    ///             }
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<iframe>"
+    ///     "<blockquote>\r\n<p>a</p>\r\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub default_line_ending: LineEnding,
+
+    /// Function to get extra attributes for block elements with.
+    ///
+    /// The default is `None`, which adds no attributes.
+    ///
+    /// Pass a function to add attributes to elements based on their
+    /// [kind][ElementKind], such as to add microdata (`itemprop`).
+    /// It is called for each block element when its opening tag is compiled.
+    /// Attribute values are encoded, names are not: the function must return
+    /// valid attribute names.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, ElementAttributes, ElementKind, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // No attributes are added by default:
+    /// assert_eq!(
+    ///     to_html("# a"),
+    ///     "<h1>a</h1>"
     /// );
     ///
-    /// // Pass `gfm_tagfilter: true` to make some of that safe:
+    /// // Pass `element_attributes` to add attributes:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "<iframe>",
+    ///         "# a",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
     ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
-    ///               gfm_tagfilter: true,
+    ///               element_attributes: Some(ElementAttributes::new(|kind| match kind {
+    ///                   ElementKind::Heading(1) => vec![("itemprop".into(), "name".into())],
+    ///                   _ => vec![],
+    ///               })),
     ///               ..CompileOptions::default()
-    ///             }
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "&lt;iframe>"
+    ///     "<h1 itemprop=\"name\">a</h1>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
+    pub element_attributes: Option<ElementAttributes>,
+
+    /// Whether to render the `title` of YAML frontmatter as a heading.
     ///
-    /// ## References
+    /// The default is `false`, which does not show frontmatter.
     ///
-    /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
-    /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
-    pub gfm_tagfilter: bool,
-}
+    /// Pass `true` to turn a `title` field in YAML frontmatter into an `<h1>`
+    /// where the frontmatter was.
+    /// Only single-line titles are supported, which can be quoted
+    /// (`title: "a"`); see [`to_frontmatter()`][crate::to_frontmatter] to
+    /// get the frontmatter yourself.
+    /// When there is no frontmatter, or no title in it, nothing is added.
+    ///
+    /// > 👉 **Note**: frontmatter is not on by default, turn it on with
+    /// > [`frontmatter`][Constructs::frontmatter] in [`Constructs`][].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// let parse = || ParseOptions {
+    ///     constructs: Constructs {
+    ///         frontmatter: true,
+    ///         ..Constructs::default()
+    ///     },
+    ///     ..ParseOptions::default()
+    /// };
+    ///
+    /// // Frontmatter is not shown by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "---\ntitle: Venus\n---\n\na",
+    ///         &Options {
+    ///             parse: parse(),
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a</p>"
+    /// );
+    ///
+    /// // Pass `frontmatter_title` to show its title:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "---\ntitle: Venus\n---\n\na",
+    ///         &Options {
+    ///             parse: parse(),
+    ///             compile: CompileOptions {
+    ///               frontmatter_title: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///         }
+    ///     )?,
+    ///     "<h1>Venus</h1>\n<p>a</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub frontmatter_title: bool,
 
-impl CompileOptions {
-    /// GFM.
+    /// Textual label to use for the footnotes section.
     ///
-    /// GFM stands for **GitHub flavored markdown**.
-    /// On the compilation side, GFM turns on the GFM tag filter.
-    /// The tagfilter is useless, but it’s included here for consistency, and
-    /// this method exists for parity to parse options.
+    /// The default value is `"Footnotes"`.
+    /// Change it when the markdown is not in English.
     ///
-    /// For more information, see the GFM specification:
-    /// <https://github.github.com/gfm/>.
-    pub fn gfm() -> Self {
-        Self {
-            gfm_tagfilter: true,
-            ..Self::default()
-        }
-    }
-}
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `"Footnotes"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label: Option<String>,
 
-/// Configuration that describes how to parse from markdown.
-///
-/// You can use this:
-///
-/// *   To control what markdown constructs are turned on and off
-/// *   To control some of those constructs
-/// *   To add support for certain programming languages when parsing MDX
-///
-/// In most cases, you will want to use the default trait or `gfm` method.
-///
-/// ## Examples
-///
-/// ```
-/// use markdown::ParseOptions;
-/// # fn main() {
-///
-/// // Use the default trait to parse markdown according to `CommonMark`:
-/// let commonmark = ParseOptions::default();
-///
-/// // Use the `gfm` method to parse markdown according to GFM:
-/// let gfm = ParseOptions::gfm();
-/// # }
-/// ```
-#[allow(clippy::struct_excessive_bools)]
-pub struct ParseOptions {
-    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
-    /// Which constructs to enable and disable.
+    /// HTML tag name to use for the footnote label element.
     ///
-    /// The default is to follow `CommonMark`.
+    /// The default value is `"h2"`.
+    /// Change it to match your document structure.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `markdown-rs` follows CommonMark by default:
+    /// // `"h2"` is used by default:
     /// assert_eq!(
-    ///     to_html("    indented code?"),
-    ///     "<pre><code>indented code?\n</code></pre>"
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label_tag_name: Some("h1".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label_tag_name: Option<String>,
+
+    /// Attributes to use on the footnote label.
+    ///
+    /// The default value is `"class=\"sr-only\""`.
+    /// Change it to show the label and add other attributes.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass an empty string.
+    /// You can also add different attributes.
+    ///
+    /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
+    /// > calls use it with `aria-describedby` to provide an accessible label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `"class=\"sr-only\""` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_attributes` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label_attributes: Option<String>,
+
+    /// Textual label to describe the backreference back to footnote calls.
+    ///
+    /// The default value is `"Back to content"`.
+    /// Change it when the markdown is not in English.
+    ///
+    /// This label is used in the `aria-label` attribute on each backreference
+    /// (the `↩` links).
+    /// It affects users of assistive technology.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `"Back to content"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_back_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_back_label: Some("Arrière".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_back_label: Option<String>,
+
+    /// Prefix to use before the `id` attribute on footnotes to prevent them
+    /// from *clobbering*.
+    ///
+    /// The default is `"user-content-"`.
+    /// Pass `Some("".into())` for trusted markdown and when you are careful
+    /// with polyfilling.
+    /// You could pass a different prefix.
+    ///
+    /// DOM clobbering is this:
+    ///
+    /// ```html
+    /// <p id="x"></p>
+    /// <script>alert(x) // `x` now refers to the `p#x` DOM element</script>
+    /// ```
+    ///
+    /// The above example shows that elements are made available by browsers,
+    /// by their ID, on the `window` object.
+    /// This is a security risk because you might be expecting some other
+    /// variable at that place.
+    /// It can also break polyfills.
+    /// Using a prefix solves these problems.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `"user-content-"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_clobber_prefix` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_clobber_prefix: Some("".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_clobber_prefix: Option<String>,
+
+    /// Whether to add DPUB-ARIA roles to footnote calls, the footnote
+    /// section, and backreferences.
+    ///
+    /// The default is `false`, which matches GitHub, and does not add roles.
+    /// Pass `true` to add `role="doc-noteref"` to footnote calls,
+    /// `role="doc-endnotes"` to the section, and `role="doc-backlink"` to
+    /// backreferences (the `↩` links).
+    /// It affects users of assistive technology.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // No roles are added by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_roles: true` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_roles: true,
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\" role=\"doc-noteref\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\" role=\"doc-endnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" role=\"doc-backlink\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [*Digital Publishing WAI-ARIA Module 1.0*](https://www.w3.org/TR/dpub-aria-1.0/)
+    pub gfm_footnote_roles: bool,
+
+    /// Whether to support the GFM tagfilter.
+    ///
+    /// This option does nothing if `allow_dangerous_html` is not turned on.
+    /// The default is `false`, which does not apply the GFM tagfilter to HTML.
+    /// Pass `true` for output that is a bit closer to GitHub’s actual output.
+    ///
+    /// The tagfilter is kinda weird and kinda useless.
+    /// The tag filter is a naïve attempt at XSS protection.
+    /// You should use a proper HTML sanitizing algorithm instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // With `allow_dangerous_html`, `markdown-rs` passes HTML through untouched:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<iframe>",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<iframe>"
+    /// );
+    ///
+    /// // Pass `gfm_tagfilter: true` to make some of that safe:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<iframe>",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               gfm_tagfilter: true,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "&lt;iframe>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
+    /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
+    pub gfm_tagfilter: bool,
+
+    /// HTML to use for hard breaks.
+    ///
+    /// The default value is `"<br />"`.
+    /// Change it to, for example, `"<br>"` when generating HTML instead of
+    /// XHTML, or to something else entirely.
+    ///
+    /// > 👉 **Note**: this value is not encoded: it is used as HTML.
+    /// > Make sure it is safe.
+    /// > The line ending that follows a hard break is always kept.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `"<br />"` is used by default:
+    /// assert_eq!(
+    ///     to_html("a\\\nb"),
+    ///     "<p>a<br />\nb</p>"
+    /// );
+    ///
+    /// // Pass `hard_break_markup` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\\\nb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               hard_break_markup: Some("<br>".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a<br>\nb</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub hard_break_markup: Option<String>,
+
+    /// Whether to add `id` attributes to headings.
+    ///
+    /// The default is `false`, which adds no `id`s.
+    ///
+    /// Pass `true` to add the slugs that [`to_headings()`][crate::to_headings]
+    /// gets for headings as their `id`s, so that they can be linked to
+    /// (`[a](#b)`).
+    /// Slugs are made like GitHub does (see
+    /// [`slug_policy`][CompileOptions::slug_policy]) and are unique in a
+    /// document.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // No `id`s are added by default:
+    /// assert_eq!(
+    ///     to_html("# Hello, *world*!"),
+    ///     "<h1>Hello, <em>world</em>!</h1>"
+    /// );
+    ///
+    /// // Turn `heading_ids` on to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# Hello, *world*!",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 id=\"hello-world\">Hello, <em>world</em>!</h1>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub heading_ids: bool,
+
+    /// Function to render math with.
+    ///
+    /// The default is `None`, which passes math through as code, with classes
+    /// (`<code class="language-math math-inline">` and
+    /// `<pre><code class="language-math math-display">`) that client-side
+    /// math engines can look for.
+    ///
+    /// Pass a function to render math on the server instead, such as with a
+    /// binding to `KaTeX`.
+    /// The function gets the value of the math, and whether it is display
+    /// math (flow, `true`) or inline math (text, `false`).
+    /// What it returns is used as HTML, without any escaping: make sure it is
+    /// safe.
+    ///
+    /// > 👉 **Note**: math is not on by default, turn it on with
+    /// > [`math_flow`][Constructs::math_flow] and
+    /// > [`math_text`][Constructs::math_text] in [`Constructs`][].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, MathRenderer, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// let parse = || ParseOptions {
+    ///     constructs: Constructs {
+    ///         math_text: true,
+    ///         ..Constructs::default()
+    ///     },
+    ///     ..ParseOptions::default()
+    /// };
+    ///
+    /// // Math is passed through by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a < b$",
+    ///         &Options {
+    ///             parse: parse(),
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a &lt; b</code></p>"
+    /// );
+    ///
+    /// // Pass `math_renderer` to render it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a < b$",
+    ///         &Options {
+    ///             parse: parse(),
+    ///             compile: CompileOptions {
+    ///               math_renderer: Some(MathRenderer::new(|value, display| {
+    ///                   let display = if display { "block" } else { "inline" };
+    ///                   format!("<math display=\"{}\">{}</math>", display, value.replace('<', "&lt;"))
+    ///               })),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///         }
+    ///     )?,
+    ///     "<p><math display=\"inline\">a &lt; b</math></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_renderer: Option<MathRenderer>,
+
+    /// How to handle non-ASCII letters and digits in heading slugs.
+    ///
+    /// The default is [`SlugPolicy::Keep`][], which keeps them, lowercased,
+    /// like GitHub does.
+    /// This is used by [`to_headings()`][crate::to_headings].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_headings, CompileOptions, Options, SlugPolicy};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // Non-ASCII letters are kept by default:
+    /// assert_eq!(
+    ///     to_headings("# Ñandú", &Options::default())?[0].slug,
+    ///     "ñandú"
+    /// );
+    ///
+    /// // Pass `slug_policy` to do something else:
+    /// assert_eq!(
+    ///     to_headings(
+    ///         "# Ñandú",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               slug_policy: SlugPolicy::Transliterate,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?[0].slug,
+    ///     "nandu"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub slug_policy: SlugPolicy,
+
+    /// Whether to add where block elements are in the source to them.
+    ///
+    /// The default is `false`, which does not add positional info.
+    ///
+    /// Pass `true` to add a `data-sourcepos` attribute to block elements
+    /// (paragraphs, headings, code, thematic breaks, block quotes, lists, list
+    /// items, tables, and fenced divs), like `cmark` does, which is useful to
+    /// sync scrolling between markdown and HTML.
+    /// The value is `line:column-line:column`, where lines and columns are
+    /// 1-indexed, the end is the last character (so it is inclusive), and tabs
+    /// count up to the next tab stop, like columns in
+    /// [`Point`][crate::unist::Point].
+    /// HTML (flow) has no element of its own and gets no positional info.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // Positional info is not added by default:
+    /// assert_eq!(
+    ///     to_html("# a\n\nb\nc"),
+    ///     "<h1>a</h1>\n<p>b\nc</p>"
+    /// );
+    ///
+    /// // Pass `source_positions` to add it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# a\n\nb\nc",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               source_positions: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 data-sourcepos=\"1:1-1:3\">a</h1>\n<p data-sourcepos=\"3:1-4:1\">b\nc</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub source_positions: bool,
+
+    /// Function to transform text with.
+    ///
+    /// The default is `None`, which leaves text as it is.
+    ///
+    /// Pass a function to change the content of text before it is encoded,
+    /// such as to turn emoji shortcodes (`:smile:`) into emoji.
+    /// It is called with each piece of plain text: it is not called with
+    /// code, math, HTML, URLs, titles, or the info and meta of code (fenced).
+    /// A piece of text ends where other constructs (such as emphasis or
+    /// character escapes) start.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options, TextTransform};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // Text is kept as it is by default:
+    /// assert_eq!(
+    ///     to_html("a :smile: `:smile:`"),
+    ///     "<p>a :smile: <code>:smile:</code></p>"
+    /// );
+    ///
+    /// // Pass `text_transform` to change text:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a :smile: `:smile:`",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               text_transform: Some(TextTransform::new(|d| d.replace(":smile:", "😄"))),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a 😄 <code>:smile:</code></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub text_transform: Option<TextTransform>,
+
+    /// Whether to remove the final line ending in code (flow) and math (flow).
+    ///
+    /// The default is `false`, which keeps it: as `CommonMark` requires, the
+    /// content of code is followed by a line ending, whether the code is
+    /// closed or not.
+    ///
+    /// Pass `true` to remove that final line ending.
+    /// Only one line ending is removed: blank lines at the end of the code are
+    /// kept.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // The final line ending is kept by default:
+    /// assert_eq!(
+    ///     to_html("```\na\n```"),
+    ///     "<pre><code>a\n</code></pre>"
+    /// );
+    ///
+    /// // Turn `trim_code_trailing_newline` on to remove it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "```\na\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               trim_code_trailing_newline: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code>a</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub trim_code_trailing_newline: bool,
+
+    /// Function to rewrite URLs with.
+    ///
+    /// The default is `None`, which leaves URLs as they are.
+    ///
+    /// Pass a function to change the URLs of links and images, such as to
+    /// resolve relative URLs against a base URL, or to load images through a
+    /// proxy.
+    /// It is called with the URL (character references and escapes already
+    /// resolved) and whether it is the `href` of a link or the `src` of an
+    /// image.
+    /// Links and images that use definitions are passed the URL of the
+    /// definition.
+    ///
+    /// URLs with dangerous protocols are dropped before the function is
+    /// called (see
+    /// [`allow_dangerous_protocol`][Self::allow_dangerous_protocol]), and
+    /// what the function returns is not checked again: it is trusted.
+    /// It is still encoded, so it cannot break out of the attribute.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options, UrlKind, UrlRewriter};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // URLs are kept as they are by default:
+    /// assert_eq!(
+    ///     to_html("[a](b) ![c](d)"),
+    ///     "<p><a href=\"b\">a</a> <img src=\"d\" alt=\"c\" /></p>"
+    /// );
+    ///
+    /// // Pass `url_rewriter` to change them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](b) ![c](d)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               url_rewriter: Some(UrlRewriter::new(|url, kind| match kind {
+    ///                   UrlKind::Image => format!("https://proxy.example/?url={}", url),
+    ///                   UrlKind::Link => format!("https://example.com/{}", url),
+    ///               })),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://example.com/b\">a</a> <img src=\"https://proxy.example/?url=d\" alt=\"c\" /></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub url_rewriter: Option<UrlRewriter>,
+}
+
+impl CompileOptions {
+    /// GFM.
+    ///
+    /// GFM stands for **GitHub flavored markdown**.
+    /// On the compilation side, GFM turns on the GFM tag filter.
+    /// The tagfilter is useless, but it’s included here for consistency, and
+    /// this method exists for parity to parse options.
+    ///
+    /// For more information, see the GFM specification:
+    /// <https://github.github.com/gfm/>.
+    pub fn gfm() -> Self {
+        Self {
+            gfm_tagfilter: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Configuration that describes how to parse from markdown.
+///
+/// You can use this:
+///
+/// *   To control what markdown constructs are turned on and off
+/// *   To control some of those constructs
+/// *   To add support for certain programming languages when parsing MDX
+///
+/// In most cases, you will want to use the default trait or `gfm` method.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ParseOptions;
+/// # fn main() {
+///
+/// // Use the default trait to parse markdown according to `CommonMark`:
+/// let commonmark = ParseOptions::default();
+///
+/// // Use the `gfm` method to parse markdown according to GFM:
+/// let gfm = ParseOptions::gfm();
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+pub struct ParseOptions {
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+    /// Which constructs to enable and disable.
+    ///
+    /// The default is to follow `CommonMark`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` follows CommonMark by default:
+    /// assert_eq!(
+    ///     to_html("    indented code?"),
+    ///     "<pre><code>indented code?\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `constructs` to choose what to enable and disable:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "    indented code?",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 code_indented: false,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>indented code?</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub constructs: Constructs,
+
+    /// Whether to warn about accessibility problems.
+    ///
+    /// This option only affects [`to_diagnostics()`][crate::to_diagnostics].
+    ///
+    /// The default is `false`.
+    /// Pass `true` to warn about images without alt text
+    /// (`missing-image-alt`), and links whose text does not describe where
+    /// they go, such as `click here` (`non-descriptive-link-text`).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_diagnostics, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` does not check accessibility by default:
+    /// assert_eq!(
+    ///     to_diagnostics("![](cat.png)", &ParseOptions::default())?.len(),
+    ///     0
+    /// );
+    ///
+    /// // Turn `accessibility_lints` on to check it:
+    /// assert_eq!(
+    ///     to_diagnostics(
+    ///         "![](cat.png)",
+    ///         &ParseOptions {
+    ///           accessibility_lints: true,
+    ///           ..ParseOptions::default()
+    ///         }
+    ///     )?[0].rule_id,
+    ///     "missing-image-alt"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub accessibility_lints: bool,
+
+    /// Whether to support letters and roman numerals as the values of
+    /// ordered list items.
+    ///
+    /// The default is `false`, which only allows decimal values
+    /// (`1.`, `2)`).
+    /// Pass `true` to also allow a single letter (`a.`, `B)`), or lowercase
+    /// or uppercase roman numerals (`iv.`, `IX)`).
+    /// When compiling to HTML, this adds a `type` attribute to `<ol>`
+    /// (`a`, `A`, `i`, or `I`).
+    /// `i` and `I` are roman numerals, except in lists of letters.
+    ///
+    /// Like `1.`, only `a.`, `A.`, `i.`, and `I.` (or with `)`) can
+    /// interrupt a paragraph.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` only supports decimal values by default:
+    /// assert_eq!(
+    ///     to_html("a. b"),
+    ///     "<p>a. b</p>"
+    /// );
+    ///
+    /// // Turn `alpha_ordered_lists` on to support letters and roman numerals:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a. b",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               alpha_ordered_lists: true,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<ol type=\"a\">\n<li>b</li>\n</ol>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub alpha_ordered_lists: bool,
+
+    /// Extra emoji shortcodes.
+    ///
+    /// This option does nothing if `emoji` is not turned on in `constructs`.
+    ///
+    /// The default is an empty list, which supports the common shortcodes
+    /// that GitHub supports, such as `:smile:` and `:+1:`.
+    /// Pass a list of names (without colons) and values to support more
+    /// shortcodes, or to change what known shortcodes turn into.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` supports common shortcodes:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         ":smile: :ferris:",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 emoji: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>😄 :ferris:</p>"
+    /// );
+    ///
+    /// // Pass `emoji_shortcodes` to support more:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         ":smile: :ferris:",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 emoji: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               emoji_shortcodes: vec![("ferris".into(), "🦀".into())],
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>😄 🦀</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub emoji_shortcodes: Vec<(String, String)>,
+
+    /// Whether to support GFM strikethrough with a single tilde
+    ///
+    /// This option does nothing if `gfm_strikethrough` is not turned on in
+    /// `constructs`.
+    /// This option does not affect strikethrough with double tildes.
+    ///
+    /// The default is `true`, which follows how markdown on `github.com`
+    /// works, as strikethrough with single tildes is supported.
+    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
+    /// strikethrough with single tildes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` supports single tildes by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><del>a</del></p>"
+    /// );
+    ///
+    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_strikethrough_single_tilde: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>~a~</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_strikethrough_single_tilde: bool,
+
+    /// Whether to support GFM tables without a head row
+    ///
+    /// This option does nothing if `gfm_table` is not turned on in
+    /// `constructs`.
+    ///
+    /// The default is `false`, which follows GFM, as a table must start with
+    /// a head row followed by a delimiter row.
+    /// Pass `true` to also allow a table to start with the delimiter row, in
+    /// which case all rows are body rows.
+    /// A head row, when valid, is still preferred.
+    /// Headerless tables cannot interrupt paragraphs, and need at least one
+    /// body row: a delimiter row on its own is a paragraph.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` follows GFM by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "| - |\n| a |",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>| - |\n| a |</p>"
+    /// );
+    ///
+    /// // Pass `gfm_table_headerless: true` to turn that on:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "| - |\n| a |",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_table_headerless: true,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<table>\n<tbody>\n<tr>\n<td>a</td>\n</tr>\n</tbody>\n</table>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_table_headerless: bool,
+
+    /// Whether to support math (text) with a single dollar
+    ///
+    /// This option does nothing if `math_text` is not turned on in
+    /// `constructs`.
+    /// This option does not affect math (text) with two or more dollars.
+    ///
+    /// The default is `true`, which is more close to how code (text) and
+    /// Pandoc work, as it allows math with a single dollar to form.
+    /// However, single dollars can interfere with “normal” dollars in text.
+    /// Pass `false`, to only allow math (text) to form when two or more
+    /// dollars are used.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` supports single dollars by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    /// );
+    ///
+    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               math_text_single_dollar: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>$a$</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_text_single_dollar: bool,
+
+    /// Whether to only support single dollars around math (text) next to
+    /// content, like Pandoc.
+    ///
+    /// The default is `false`, which follows `micromark-extension-math`.
+    /// When `true`, a single dollar only opens math if it is followed by a
+    /// character that is not whitespace, and only closes math if it is
+    /// preceded by a character that is not whitespace, and not followed by a
+    /// digit.
+    /// That prevents dollar amounts (`$20 and $30`) from being seen as math.
+    /// It is on in [`ParseOptions::math`][].
+    ///
+    /// This only applies to single dollars, so it does nothing if
+    /// [`math_text_single_dollar`][ParseOptions::math_text_single_dollar] is
+    /// off.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` sees dollar amounts as math by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$20 and $30",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">20 and </code>30</p>"
     /// );
     ///
-    /// // Pass `constructs` to choose what to enable and disable:
+    /// // Turn `math_text_single_dollar_strict` on to prevent that:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "    indented code?",
+    ///         "$20 and $30",
     ///         &Options {
     ///             parse: ParseOptions {
     ///               constructs: Constructs {
-    ///                 code_indented: false,
+    ///                 math_text: true,
     ///                 ..Constructs::default()
     ///               },
+    ///               math_text_single_dollar_strict: true,
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>indented code?</p>"
+    ///     "<p>$20 and $30</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub constructs: Constructs,
+    pub math_text_single_dollar_strict: bool,
 
-    /// Whether to support GFM strikethrough with a single tilde
+    /// Maximum number of attempts to parse constructs.
     ///
-    /// This option does nothing if `gfm_strikethrough` is not turned on in
-    /// `constructs`.
-    /// This option does not affect strikethrough with double tildes.
+    /// Markdown is parsed by trying constructs, and falling back to something
+    /// else (often a paragraph or text) when they turn out to not match.
+    /// Input crafted to start lots of constructs that never match can take a
+    /// long time to parse.
     ///
-    /// The default is `true`, which follows how markdown on `github.com`
-    /// works, as strikethrough with single tildes is supported.
-    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
-    /// strikethrough with single tildes.
+    /// The default is `None`, which does not limit attempts.
+    /// Pass a number to limit attempts in a document: when that many
+    /// attempts were made, constructs that can fall back to something else
+    /// immediately do so, which means the rest of the document is parsed
+    /// differently (mostly as text).
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `markdown-rs` supports single tildes by default:
+    /// // `markdown-rs` does not limit attempts by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "~a~",
-    ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               ..ParseOptions::default()
-    ///             },
-    ///             ..Options::default()
-    ///         }
-    ///     )?,
-    ///     "<p><del>a</del></p>"
+    ///     to_html("# a\n# b"),
+    ///     "<h1>a</h1>\n<h1>b</h1>"
     /// );
     ///
-    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// // Pass `max_attempts` to limit them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "# a\n# b",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               gfm_strikethrough_single_tilde: false,
+    ///               max_attempts: Some(0),
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>~a~</p>"
+    ///     "<p># a\n# b</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_strikethrough_single_tilde: bool,
+    pub max_attempts: Option<usize>,
 
-    /// Whether to support math (text) with a single dollar
+    /// Maximum number of passes to parse content in other content.
     ///
-    /// This option does nothing if `math_text` is not turned on in
-    /// `constructs`.
-    /// This option does not affect math (text) with two or more dollars.
+    /// Markdown is parsed in passes: first the structure of the document
+    /// (block quotes, lists, and the like), then, in another pass, the
+    /// content in that (paragraphs and definitions), then the text in those
+    /// (emphasis, links, and the like), and so on.
+    /// Normal documents need at most a couple of passes.
     ///
-    /// The default is `true`, which is more close to how code (text) and
-    /// Pandoc work, as it allows math with a single dollar to form.
-    /// However, single dollars can interfere with “normal” dollars in text.
-    /// Pass `false`, to only allow math (text) to form when two or more
-    /// dollars are used.
+    /// The default is `None`, which does not limit passes.
+    /// Pass a number to limit passes in a document: when that many passes
+    /// were made, what is not parsed yet is left as text (except for
+    /// paragraphs and definitions, which are always parsed).
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), String> {
     ///
-    /// // `markdown-rs` supports single dollars by default:
+    /// // `markdown-rs` does not limit passes by default:
+    /// assert_eq!(
+    ///     to_html("*a*"),
+    ///     "<p><em>a</em></p>"
+    /// );
+    ///
+    /// // Pass `max_depth` to limit them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "*a*",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
+    ///               max_depth: Some(0),
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    ///     "<p>*a*</p>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub max_depth: Option<usize>,
+
+    /// Maximum nesting of constructs in other constructs.
+    ///
+    /// Some constructs can contain themselves, or each other: block quotes
+    /// and list items, images and inline footnotes, emphasis and strong,
+    /// and so on.
+    /// Input crafted to nest lots of them, such as thousands of `>`, makes
+    /// deep syntax trees, which can take a long time to build and can
+    /// overflow the stack when they are walked or dropped.
+    ///
+    /// The default is `Some(1024)`, which is much more than what documents
+    /// use.
+    /// Each kind of nesting is limited separately: containers, labels (such
+    /// as images), and attention (such as emphasis) can each be nested that
+    /// deep, and no more passes are made than that (see
+    /// [`max_depth`][ParseOptions::max_depth]).
+    /// When the limit is hit, what would nest deeper is left as text.
+    /// Pass `None` to not limit nesting.
     ///
-    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// // `markdown-rs` limits nesting to 1024 by default:
+    /// assert_eq!(
+    ///     to_html("> > a"),
+    ///     "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `max_nesting` to change that:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "> > a",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               math_text_single_dollar: false,
+    ///               max_nesting: Some(1),
     ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>$a$</p>"
+    ///     "<blockquote>\n<p>&gt; a</p>\n</blockquote>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub math_text_single_dollar: bool,
+    pub max_nesting: Option<usize>,
 
     /// Function to parse expressions with.
     ///
@@ -1108,18 +2648,30 @@ pub struct ParseOptions {
     /// For an example that adds support for JavaScript with SWC, see
     /// `tests/test_utils/mod.rs`.
     pub mdx_esm_parse: Option<Box<MdxEsmParse>>,
-    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` and
+    // `clone_without_mdx_parse` below.
 }
 
 impl fmt::Debug for ParseOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ParseOptions")
             .field("constructs", &self.constructs)
+            .field("accessibility_lints", &self.accessibility_lints)
+            .field("alpha_ordered_lists", &self.alpha_ordered_lists)
+            .field("emoji_shortcodes", &self.emoji_shortcodes)
             .field(
                 "gfm_strikethrough_single_tilde",
                 &self.gfm_strikethrough_single_tilde,
             )
+            .field("gfm_table_headerless", &self.gfm_table_headerless)
             .field("math_text_single_dollar", &self.math_text_single_dollar)
+            .field(
+                "math_text_single_dollar_strict",
+                &self.math_text_single_dollar_strict,
+            )
+            .field("max_attempts", &self.max_attempts)
+            .field("max_depth", &self.max_depth)
+            .field("max_nesting", &self.max_nesting)
             .field(
                 "mdx_expression_parse",
                 &self.mdx_expression_parse.as_ref().map(|_d| "[Function]"),
@@ -1137,8 +2689,16 @@ impl Default for ParseOptions {
     fn default() -> Self {
         Self {
             constructs: Constructs::default(),
+            accessibility_lints: false,
+            alpha_ordered_lists: false,
+            emoji_shortcodes: vec![],
             gfm_strikethrough_single_tilde: true,
+            gfm_table_headerless: false,
             math_text_single_dollar: true,
+            math_text_single_dollar_strict: false,
+            max_attempts: None,
+            max_depth: None,
+            max_nesting: Some(1024),
             mdx_expression_parse: None,
             mdx_esm_parse: None,
         }
@@ -1186,6 +2746,73 @@ impl ParseOptions {
             ..Self::default()
         }
     }
+
+    /// Math.
+    ///
+    /// This turns on `CommonMark` and math (flow and text), with
+    /// [`math_text_single_dollar_strict`][ParseOptions::math_text_single_dollar_strict]
+    /// on, so that dollar amounts are not seen as math.
+    pub fn math() -> Self {
+        Self {
+            constructs: Constructs::math(),
+            math_text_single_dollar_strict: true,
+            ..Self::default()
+        }
+    }
+
+    /// Directives.
+    ///
+    /// This turns on `CommonMark` and directives (container, leaf, and text).
+    pub fn directives() -> Self {
+        Self {
+            constructs: Constructs::directives(),
+            ..Self::default()
+        }
+    }
+
+    /// Get a function that makes copies of these options, without the MDX
+    /// parse functions.
+    ///
+    /// Those functions cannot be shared between threads, and so neither can
+    /// these options, but the returned function can.
+    /// Every other field is destructured here, so that a new field cannot be
+    /// forgotten.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn clone_without_mdx_parse(&self) -> impl Fn() -> ParseOptions + Send + Sync {
+        let ParseOptions {
+            ref constructs,
+            accessibility_lints,
+            alpha_ordered_lists,
+            ref emoji_shortcodes,
+            gfm_strikethrough_single_tilde,
+            gfm_table_headerless,
+            math_text_single_dollar,
+            math_text_single_dollar_strict,
+            max_attempts,
+            max_depth,
+            max_nesting,
+            mdx_expression_parse: _,
+            mdx_esm_parse: _,
+        } = *self;
+        let constructs = constructs.clone();
+        let emoji_shortcodes = emoji_shortcodes.clone();
+
+        move || ParseOptions {
+            constructs: constructs.clone(),
+            accessibility_lints,
+            alpha_ordered_lists,
+            emoji_shortcodes: emoji_shortcodes.clone(),
+            gfm_strikethrough_single_tilde,
+            gfm_table_headerless,
+            math_text_single_dollar,
+            math_text_single_dollar_strict,
+            max_attempts,
+            max_depth,
+            max_nesting,
+            mdx_expression_parse: None,
+            mdx_esm_parse: None,
+        }
+    }
 }
 
 /// Configuration that describes how to parse from markdown and compile to
@@ -1232,6 +2859,235 @@ impl Options {
             compile: CompileOptions::gfm(),
         }
     }
+
+    /// GitHub.
+    ///
+    /// Like [`gfm()`][Options::gfm], so with GFM constructs and the GFM tag
+    /// filter, and also with [`heading_ids`][CompileOptions::heading_ids] on,
+    /// so that output closely matches how GitHub renders markdown files.
+    ///
+    /// Soft breaks (line endings in paragraphs) are kept as line endings, as
+    /// GitHub does in files.
+    /// Slugs of headings are made like GitHub does, but GitHub prefixes `id`s
+    /// with `user-content-` and adds anchor links, which are not done here.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("# Hello, ~~world~~!", &Options::github())?,
+    ///     "<h1 id=\"hello-world\">Hello, <del>world</del>!</h1>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn github() -> Self {
+        Self {
+            parse: ParseOptions::gfm(),
+            compile: CompileOptions {
+                heading_ids: true,
+                ..CompileOptions::gfm()
+            },
+        }
+    }
+
+    /// Math.
+    ///
+    /// This turns on `CommonMark` and math (flow and text), without seeing
+    /// dollar amounts as math.
+    /// Math compiles to `<code class="language-math math-inline">` (text) and
+    /// `<pre><code class="language-math math-display">` (flow), which can be
+    /// picked up by `KaTeX` or `MathJax` on the client.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("$x^2$ costs $20, or $30.", &Options::math())?,
+    ///     "<p><code class=\"language-math math-inline\">x^2</code> costs $20, or $30.</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn math() -> Self {
+        Self {
+            parse: ParseOptions::math(),
+            ..Self::default()
+        }
+    }
+
+    /// Directives.
+    ///
+    /// This turns on `CommonMark` and directives (container, leaf, and text).
+    /// Directives are not compiled to HTML: they are dropped.
+    /// Use [`to_mdast`][crate::to_mdast] with
+    /// [`ParseOptions::directives()`][ParseOptions::directives] to get them
+    /// as nodes, with their name, label, and attributes, and handle them
+    /// yourself.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("a :abbr[HTML]{title=\"b\"} c", &Options::directives())?,
+    ///     "<p>a  c</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn directives() -> Self {
+        Self {
+            parse: ParseOptions::directives(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Configuration that describes how to serialize a syntax tree to markdown.
+///
+/// Serializing is done by [`to_markdown()`][crate::to_markdown], which uses
+/// ATX headings (unless they contain line endings), fenced code, and three
+/// asterisks for thematic breaks.
+/// The markers that have a common alternative can be configured here.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::MarkdownOptions;
+/// # fn main() {
+///
+/// // Use the default trait to get `-`, `*`, and `` ` ``:
+/// let markdown = MarkdownOptions::default();
+///
+/// // Use other markers:
+/// let custom = MarkdownOptions {
+///     bullet: '*',
+///     emphasis: '_',
+///     fence: '~',
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarkdownOptions {
+    /// Marker to use for unordered list items (`*`, `+`, or `-`).
+    ///
+    /// When two unordered lists are next to each other, the second uses
+    /// another marker (`*` for `-`, `-` for the others), so that they are
+    /// not joined.
+    ///
+    /// The default is `-`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, to_mdast, MarkdownOptions, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// let tree = to_mdast("* a\n* b", &ParseOptions::default())?;
+    ///
+    /// // `markdown-rs` uses `-` by default:
+    /// assert_eq!(
+    ///     to_markdown(&tree, &MarkdownOptions::default())?,
+    ///     "- a\n- b\n"
+    /// );
+    ///
+    /// // Pass `bullet` to use another marker:
+    /// assert_eq!(
+    ///     to_markdown(&tree, &MarkdownOptions {
+    ///         bullet: '+',
+    ///         ..MarkdownOptions::default()
+    ///     })?,
+    ///     "+ a\n+ b\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub bullet: char,
+
+    /// Marker to use for emphasis and strong (`*` or `_`).
+    ///
+    /// The default is `*`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, to_mdast, MarkdownOptions, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// let tree = to_mdast("_a_ and __b__", &ParseOptions::default())?;
+    ///
+    /// // `markdown-rs` uses `*` by default:
+    /// assert_eq!(
+    ///     to_markdown(&tree, &MarkdownOptions::default())?,
+    ///     "*a* and **b**\n"
+    /// );
+    ///
+    /// // Pass `emphasis` to use `_`:
+    /// assert_eq!(
+    ///     to_markdown(&tree, &MarkdownOptions {
+    ///         emphasis: '_',
+    ///         ..MarkdownOptions::default()
+    ///     })?,
+    ///     "_a_ and __b__\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub emphasis: char,
+
+    /// Marker to use for fenced code (`` ` `` or `~`).
+    ///
+    /// Code with an info string that contains a grave accent is always
+    /// fenced with tildes.
+    ///
+    /// The default is `` ` ``.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, to_mdast, MarkdownOptions, ParseOptions};
+    /// # fn main() -> Result<(), String> {
+    ///
+    /// let tree = to_mdast("~~~js\na\n~~~", &ParseOptions::default())?;
+    ///
+    /// // `markdown-rs` uses `` ` `` by default:
+    /// assert_eq!(
+    ///     to_markdown(&tree, &MarkdownOptions::default())?,
+    ///     "```js\na\n```\n"
+    /// );
+    ///
+    /// // Pass `fence` to use `~`:
+    /// assert_eq!(
+    ///     to_markdown(&tree, &MarkdownOptions {
+    ///         fence: '~',
+    ///         ..MarkdownOptions::default()
+    ///     })?,
+    ///     "~~~js\na\n~~~\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fence: char,
+}
+
+impl Default for MarkdownOptions {
+    /// `-` for bullets, `*` for emphasis, and `` ` `` for fences.
+    fn default() -> Self {
+        Self {
+            bullet: '-',
+            emphasis: '*',
+            fence: '`',
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1245,6 +3101,8 @@ mod tests {
         Constructs::default();
         Constructs::gfm();
         Constructs::mdx();
+        Constructs::math();
+        Constructs::directives();
 
         let constructs = Constructs::default();
         assert!(constructs.attention, "should default to `CommonMark` (1)");
@@ -1281,6 +3139,23 @@ mod tests {
         );
         assert!(constructs.mdx_jsx_flow, "should support `mdx` shortcut (3)");
         assert!(!constructs.frontmatter, "should support `mdx` shortcut (4)");
+
+        let constructs = Constructs::math();
+        assert!(constructs.attention, "should support `math` shortcut (1)");
+        assert!(constructs.math_flow, "should support `math` shortcut (2)");
+        assert!(constructs.math_text, "should support `math` shortcut (3)");
+
+        let constructs = Constructs::directives();
+        assert!(
+            constructs.attention,
+            "should support `directives` shortcut (1)"
+        );
+        assert!(
+            constructs.directive_container
+                && constructs.directive_leaf
+                && constructs.directive_text,
+            "should support `directives` shortcut (2)"
+        );
     }
 
     #[test]
@@ -1288,6 +3163,8 @@ mod tests {
         ParseOptions::default();
         ParseOptions::gfm();
         ParseOptions::mdx();
+        ParseOptions::math();
+        ParseOptions::directives();
 
         let options = ParseOptions::default();
         assert!(
@@ -1331,9 +3208,25 @@ mod tests {
             "should support `mdx` shortcut (3)"
         );
 
+        let options = ParseOptions::math();
+        assert!(
+            options.constructs.math_text,
+            "should support `math` shortcut (1)"
+        );
+        assert!(
+            options.math_text_single_dollar_strict,
+            "should support `math` shortcut (2)"
+        );
+
+        let options = ParseOptions::directives();
+        assert!(
+            options.constructs.directive_text,
+            "should support `directives` shortcut"
+        );
+
         assert_eq!(
             format!("{:?}", ParseOptions::default()),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: None, mdx_esm_parse: None }",
+            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, directive_container: false, directive_leaf: false, directive_text: false, emoji: false, fenced_div: false, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_inline_footnote: false, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, accessibility_lints: false, alpha_ordered_lists: false, emoji_shortcodes: [], gfm_strikethrough_single_tilde: true, gfm_table_headerless: false, math_text_single_dollar: true, math_text_single_dollar_strict: false, max_attempts: None, max_depth: None, max_nesting: Some(1024), mdx_expression_parse: None, mdx_esm_parse: None }",
             "should support `Debug` trait"
         );
         assert_eq!(
@@ -1346,7 +3239,7 @@ mod tests {
                 })),
                 ..Default::default()
             }),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
+            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, directive_container: false, directive_leaf: false, directive_text: false, emoji: false, fenced_div: false, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_inline_footnote: false, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, accessibility_lints: false, alpha_ordered_lists: false, emoji_shortcodes: [], gfm_strikethrough_single_tilde: true, gfm_table_headerless: false, math_text_single_dollar: true, math_text_single_dollar_strict: false, max_attempts: None, max_depth: None, max_nesting: Some(1024), mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
             "should support `Debug` trait on mdx functions"
         );
     }
@@ -1365,6 +3258,14 @@ mod tests {
             !options.gfm_tagfilter,
             "should default to safe `CommonMark` (2)"
         );
+        assert!(
+            !options.frontmatter_title,
+            "should default to not showing frontmatter"
+        );
+        assert!(
+            !options.source_positions,
+            "should default to not adding positional info"
+        );
 
         let options = CompileOptions::gfm();
         assert!(
@@ -1416,5 +3317,33 @@ mod tests {
             !options.compile.allow_dangerous_html,
             "should support safe `gfm` shortcut (4)"
         );
+
+        let options = Options::math();
+        assert!(
+            options.parse.constructs.math_flow,
+            "should support safe `math` shortcut (1)"
+        );
+        assert!(
+            !options.compile.allow_dangerous_html,
+            "should support safe `math` shortcut (2)"
+        );
+
+        let options = Options::directives();
+        assert!(
+            options.parse.constructs.directive_container,
+            "should support safe `directives` shortcut (1)"
+        );
+        assert!(
+            !options.compile.allow_dangerous_html,
+            "should support safe `directives` shortcut (2)"
+        );
+    }
+
+    #[test]
+    fn test_markdown_options() {
+        let options = MarkdownOptions::default();
+        assert_eq!(options.bullet, '-', "should default to `-` for bullets");
+        assert_eq!(options.emphasis, '*', "should default to `*` for emphasis");
+        assert_eq!(options.fence, '`', "should default to `` ` `` for fences");
     }
 }
@@ -76,16 +76,18 @@
 //! [html-strong]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-strong-element
 //! [html-del]: https://html.spec.whatwg.org/multipage/edits.html#the-del-element
 
+use crate::diagnostic::Diagnostic;
 use crate::event::{Event, Kind, Name, Point};
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
+use crate::unist::{Point as UnistPoint, Position};
 use crate::util::char::{
     after_index as char_after_index, before_index as char_before_index, classify_opt,
     Kind as CharacterKind,
 };
-use alloc::{vec, vec::Vec};
+use alloc::{format, string::String, vec, vec::Vec};
 
 /// Attentention sequence that we can take markers from.
 #[derive(Debug)]
@@ -108,6 +110,13 @@ struct Sequence {
     open: bool,
     /// Whether this sequence can close attention.
     close: bool,
+    /// Whether this sequence could open or close attention, before any
+    /// sequences were matched.
+    flanking: bool,
+    /// Index of the previous sequence that is not fully used.
+    previous: Option<usize>,
+    /// Index of the next sequence that is not fully used.
+    next: Option<usize>,
 }
 
 /// At start of attention.
@@ -156,6 +165,10 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
 
     // Now walk through them and match them.
     let mut close = 0;
+    // Openers of matches so far, with how deep attention is nested there.
+    // Matches are made from left to right, so earlier matches with an opener
+    // after a new opener are in that new attention.
+    let mut matched: Vec<(usize, usize)> = vec![];
 
     while close < sequences.len() {
         let sequence_close = &sequences[close];
@@ -166,8 +179,8 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
             let mut open = close;
 
             // Now walk back to find an opener.
-            while open > 0 {
-                open -= 1;
+            while let Some(previous) = sequences[open].previous {
+                open = previous;
 
                 let sequence_open = &sequences[open];
 
@@ -200,6 +213,24 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                         continue;
                     }
 
+                    let mut inside = matched.len();
+                    let mut depth = 1;
+                    while inside > 0 && matched[inside - 1].0 >= open {
+                        inside -= 1;
+                        depth = depth.max(matched[inside].1 + 1);
+                    }
+
+                    // Too deep: leave the rest as data.
+                    if matches!(
+                        tokenizer.parse_state.options.max_nesting,
+                        Some(max) if depth > max
+                    ) {
+                        break;
+                    }
+
+                    matched.truncate(inside);
+                    matched.push((open, depth));
+
                     // We found a match!
                     next_index = match_sequences(tokenizer, &mut sequences, open, close);
 
@@ -208,6 +239,12 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
             }
         }
 
+        // Unmatched closers that cannot open are not needed anymore:
+        // unlink them, so that later closers do not walk past them again.
+        if next_index > close && sequences[close].size > 0 && !sequences[close].open {
+            unlink(&mut sequences, close);
+        }
+
         close = next_index;
     }
 
@@ -215,8 +252,39 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     let mut index = 0;
     while index < sequences.len() {
         let sequence = &sequences[index];
+
+        // Fully used.
+        if sequence.size == 0 {
+            index += 1;
+            continue;
+        }
         tokenizer.events[sequence.index].name = Name::Data;
         tokenizer.events[sequence.index + 1].name = Name::Data;
+
+        // Warn about sequences that look like they were meant to match.
+        // Only when asked, as making the message takes time.
+        if sequence.flanking && tokenizer.parse_state.diagnose {
+            tokenizer.tokenize_state.diagnostics.push(Diagnostic {
+                position: Position {
+                    start: UnistPoint::new(
+                        sequence.start_point.line,
+                        sequence.start_point.column,
+                        sequence.start_point.index,
+                    ),
+                    end: UnistPoint::new(
+                        sequence.end_point.line,
+                        sequence.end_point.column,
+                        sequence.end_point.index,
+                    ),
+                },
+                reason: format!(
+                    "Unexpected unmatched attention sequence `{}`",
+                    String::from(char::from(sequence.marker)).repeat(sequence.size)
+                ),
+                rule_id: "unmatched-attention".into(),
+            });
+        }
+
         index += 1;
     }
 
@@ -228,7 +296,7 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
 fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
     let mut index = 0;
     let mut stack = vec![];
-    let mut sequences = vec![];
+    let mut sequences: Vec<Sequence> = vec![];
 
     while index < tokenizer.events.len() {
         let enter = &tokenizer.events[index];
@@ -252,23 +320,33 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
                 let close = before == CharacterKind::Other
                     || (before == CharacterKind::Punctuation && after != CharacterKind::Other);
 
+                let (open, close) = if marker == b'_' {
+                    (
+                        open && (before != CharacterKind::Other || !close),
+                        close && (after != CharacterKind::Other || !open),
+                    )
+                } else {
+                    (open, close)
+                };
+
+                let previous = sequences.len().checked_sub(1);
+
+                if let Some(previous) = previous {
+                    sequences[previous].next = Some(previous + 1);
+                }
+
                 sequences.push(Sequence {
                     index,
                     stack: stack.clone(),
                     start_point: enter.point.clone(),
                     end_point: exit.point.clone(),
                     size: exit.point.index - enter.point.index,
-                    open: if marker == b'_' {
-                        open && (before != CharacterKind::Other || !close)
-                    } else {
-                        open
-                    },
-                    close: if marker == b'_' {
-                        close && (after != CharacterKind::Other || !open)
-                    } else {
-                        close
-                    },
+                    open,
+                    close,
+                    flanking: open || close,
                     marker,
+                    previous,
+                    next: None,
                 });
             }
         } else if enter.kind == Kind::Enter {
@@ -287,14 +365,14 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
 #[allow(clippy::too_many_lines)]
 fn match_sequences(
     tokenizer: &mut Tokenizer,
-    sequences: &mut Vec<Sequence>,
+    sequences: &mut [Sequence],
     open: usize,
     close: usize,
 ) -> usize {
     // Where to move to next.
     // Stay on this closing sequence for the next iteration: it
     // might close more things.
-    // It’s changed if sequences are removed.
+    // It’s changed if the closing sequence is fully used.
     let mut next = close;
 
     // Number of markers to use from the sequence.
@@ -316,11 +394,17 @@ fn match_sequences(
     // possible to open anything.
     // Theoretically we should mark as `close: false` too, but
     // we don’t look for closers backwards, so it’s not needed.
-    let mut between = open + 1;
+    let mut between = sequences[close].previous;
+
+    while let Some(index) = between {
+        if index == open {
+            break;
+        }
 
-    while between < close {
-        sequences[between].open = false;
-        between += 1;
+        // Already seen as a closer, and now it cannot open either.
+        sequences[index].open = false;
+        between = sequences[index].previous;
+        unlink(sequences, index);
     }
 
     let (group_name, seq_name, text_name) = if sequences[open].marker == b'~' {
@@ -412,9 +496,12 @@ fn match_sequences(
     );
 
     // Remove closing sequence if fully used.
+    // Fully used sequences are unlinked instead of removed from `sequences`,
+    // as removing is slow when there are many sequences.
     if sequences[close].size == 0 {
-        sequences.remove(close);
+        unlink(sequences, close);
         tokenizer.map.add(close_index, 2, vec![]);
+        next += 1;
     } else {
         // Shift remaining closing sequence forward.
         // Do it here because a sequence can open and close different
@@ -424,13 +511,25 @@ fn match_sequences(
     }
 
     if sequences[open].size == 0 {
-        sequences.remove(open);
+        unlink(sequences, open);
         tokenizer.map.add(open_index, 2, vec![]);
-        // Everything shifts one to the left, account for it in next iteration.
-        next -= 1;
     } else {
         tokenizer.events[open_index + 1].point = sequences[open].end_point.clone();
     }
 
     next
 }
+
+/// Unlink a fully used (or otherwise useless) sequence from its siblings.
+fn unlink(sequences: &mut [Sequence], index: usize) {
+    let previous = sequences[index].previous;
+    let next = sequences[index].next;
+
+    if let Some(previous) = previous {
+        sequences[previous].next = next;
+    }
+
+    if let Some(next) = next {
+        sequences[next].previous = previous;
+    }
+}
@@ -0,0 +1,319 @@
+//! Directive (container) occurs in the [document][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (container) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the closing fence must have at least as many colons as the
+//! ; opening fence.
+//! directive_container_start ::= fence_open eol
+//! directive_container_cont ::= fence_close eol | line
+//!
+//! fence_open ::= sequence directive *space_or_tab
+//! fence_close ::= sequence *space_or_tab
+//! ; Restriction: at least `3` markers.
+//! sequence ::= 3*':'
+//! ```
+//!
+//! See [directive][partial_directive] for the grammar of the name, label, and
+//! attributes.
+//!
+//! Directives are a proposed generic syntax for extensions to markdown.
+//! This one is for blocks with content, such as `:::note`.
+//! What a directive means is not defined: they are not compiled to HTML, but
+//! they are available in mdast, so that they can be handled there.
+//!
+//! Like [fenced divs][fenced_div], directives (container) are not prefixed:
+//! every line until the closing fence is part of the directive, and the
+//! content is flow.
+//! A closing fence closes the innermost directive (container) or fenced div
+//! that it is long enough for.
+//! The name must directly follow the opening sequence: when fenced divs are
+//! also turned on, `:::a` is a directive, but `::: a` is a fenced div.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveContainer`][Name::DirectiveContainer]
+//! *   [`DirectiveContainerFence`][Name::DirectiveContainerFence]
+//! *   [`DirectiveSequence`][Name::DirectiveSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//! *   See [directive][partial_directive] for more
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [*Generic directives/plugins syntax* on `talk.commonmark.org`](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [document]: crate::construct::document
+//! [fenced_div]: crate::construct::fenced_div
+//! [partial_directive]: crate::construct::partial_directive
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{Container, Tokenizer};
+use crate::util::constant::{DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN, TAB_SIZE};
+
+/// Start of directive (container).
+///
+/// ```markdown
+/// > | :::a
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_container {
+        tokenizer.enter(Name::DirectiveContainer);
+        tokenizer.enter(Name::DirectiveContainerFence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::DirectiveContainerSequenceBefore),
+                State::Nok,
+            );
+            State::Retry(space_or_tab_min_max(tokenizer, 0, indent_max(tokenizer)))
+        } else {
+            State::Retry(StateName::DirectiveContainerSequenceBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening fence, after optional whitespace, at sequence.
+///
+/// ```markdown
+/// > | :::a
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn sequence_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveSequence);
+        State::Retry(StateName::DirectiveContainerSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening fence sequence.
+///
+/// ```markdown
+/// > | :::a
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        State::Next(StateName::DirectiveContainerSequence)
+    } else if tokenizer.tokenize_state.size >= DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN {
+        // Keep the size around on the container, to match the closing fence.
+        tokenizer.tokenize_state.document_container_stack
+            [tokenizer.tokenize_state.document_continued]
+            .size = tokenizer.tokenize_state.size;
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::DirectiveSequence);
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerOpenAfter),
+            State::Nok,
+        );
+        State::Retry(StateName::DirectiveNameStart)
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// In opening fence, after name, label, and attributes.
+///
+/// ```markdown
+/// > | :::a
+///         ^
+///   | b
+///   | :::
+/// ```
+pub fn open_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerOpenEnd),
+            State::Nok,
+        );
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveContainerOpenEnd)
+    }
+}
+
+/// After opening fence, at eol.
+///
+/// ```markdown
+/// > | :::a
+///         ^
+///   | b
+///   | :::
+/// ```
+pub fn open_end(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::DirectiveContainerFence);
+            State::Ok
+        }
+        Some(_) => State::Nok,
+    }
+}
+
+/// Start of directive (container) continuation.
+///
+/// Every line continues the directive, and a closing fence is eaten.
+/// The line after the closing fence does not continue it.
+///
+/// ```markdown
+///   | :::a
+/// > | b
+///     ^
+/// > | :::
+///     ^
+/// ```
+pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
+    let container = &tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued];
+
+    // A size of `0` means the closing fence was found.
+    if container.size == 0 {
+        State::Nok
+    }
+    // Closing fences are not seen in code and such.
+    else if tokenizer
+        .tokenize_state
+        .document_child
+        .as_ref()
+        .unwrap()
+        .concrete
+    {
+        State::Ok
+    } else {
+        tokenizer.attempt(State::Ok, State::Ok);
+        State::Retry(StateName::DirectiveContainerCloseStart)
+    }
+}
+
+/// In continuation, at a closing fence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::DirectiveContainerFence);
+
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerCloseSequenceBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab_min_max(tokenizer, 0, indent_max(tokenizer)))
+    } else {
+        State::Retry(StateName::DirectiveContainerCloseSequenceBefore)
+    }
+}
+
+/// In closing fence, after optional whitespace, at sequence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_sequence_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveSequence);
+        State::Retry(StateName::DirectiveContainerCloseSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In closing fence sequence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        return State::Next(StateName::DirectiveContainerCloseSequence);
+    }
+
+    let size = tokenizer.tokenize_state.size;
+    tokenizer.tokenize_state.size = 0;
+    let stack = &tokenizer.tokenize_state.document_container_stack;
+    let continued = tokenizer.tokenize_state.document_continued;
+
+    // Long enough for this directive, and not for a directive or div inside
+    // it.
+    if size >= stack[continued].size
+        && !stack[continued + 1..].iter().any(|container| {
+            matches!(container.kind, Container::Directive | Container::FencedDiv)
+                && container.size > 0
+                && size >= container.size
+        })
+    {
+        tokenizer.exit(Name::DirectiveSequence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::DirectiveContainerCloseAfter),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::DirectiveContainerCloseAfter)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After closing fence, at eol.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///        ^
+/// ```
+pub fn close_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::DirectiveContainerFence);
+            tokenizer.tokenize_state.document_container_stack
+                [tokenizer.tokenize_state.document_continued]
+                .size = 0;
+            State::Ok
+        }
+        Some(_) => State::Nok,
+    }
+}
+
+/// Get the max size of the indent before a fence.
+fn indent_max(tokenizer: &Tokenizer) -> usize {
+    if tokenizer.parse_state.options.constructs.code_indented {
+        TAB_SIZE - 1
+    } else {
+        usize::MAX
+    }
+}
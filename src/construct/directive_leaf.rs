@@ -0,0 +1,137 @@
+//! Directive (leaf) occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (leaf) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive_leaf ::= '::' directive *space_or_tab
+//! ```
+//!
+//! As this construct occurs in flow, like all flow constructs, it must be
+//! followed by an eol (line ending) or eof (end of file).
+//!
+//! See [directive][partial_directive] for the grammar of the name, label, and
+//! attributes.
+//!
+//! Directives are a proposed generic syntax for extensions to markdown.
+//! This one is for blocks without content, such as `::youtube[Video]{#a}`.
+//! What a directive means is not defined: they are not compiled to HTML, but
+//! they are available in mdast, so that they can be handled there.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveLeaf`][Name::DirectiveLeaf]
+//! *   [`DirectiveSequence`][Name::DirectiveSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//! *   See [directive][partial_directive] for more
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [*Generic directives/plugins syntax* on `talk.commonmark.org`](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [flow]: crate::construct::flow
+//! [partial_directive]: crate::construct::partial_directive
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::constant::TAB_SIZE;
+
+/// Start of directive (leaf).
+///
+/// ```markdown
+/// > | ::a[b]{#c}
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_leaf {
+        tokenizer.enter(Name::DirectiveLeaf);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DirectiveLeafBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    TAB_SIZE - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DirectiveLeafBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at sequence.
+///
+/// ```markdown
+/// > | ::a[b]{#c}
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveSequence);
+        tokenizer.consume();
+        State::Next(StateName::DirectiveLeafSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In sequence.
+///
+/// ```markdown
+/// > | ::a[b]{#c}
+///      ^
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.consume();
+        tokenizer.exit(Name::DirectiveSequence);
+        tokenizer.attempt(State::Next(StateName::DirectiveLeafAfter), State::Nok);
+        State::Next(StateName::DirectiveNameStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After name, label, and attributes.
+///
+/// ```markdown
+/// > | ::a[b]{#c}
+///               ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::DirectiveLeafEnd), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveLeafEnd)
+    }
+}
+
+/// At eol.
+///
+/// ```markdown
+/// > | ::a[b]{#c}
+///               ^
+/// ```
+pub fn end(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::DirectiveLeaf);
+            // Feel free to interrupt.
+            tokenizer.interrupt = false;
+            State::Ok
+        }
+        Some(_) => State::Nok,
+    }
+}
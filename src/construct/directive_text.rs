@@ -0,0 +1,73 @@
+//! Directive (text) occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (text) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the marker cannot be preceded by a `:`, unless that one is
+//! ; escaped.
+//! directive_text ::= ':' directive
+//! ```
+//!
+//! See [directive][partial_directive] for the grammar of the name, label, and
+//! attributes.
+//!
+//! Directives are a proposed generic syntax for extensions to markdown.
+//! This one is for phrasing content, such as `:abbr[HTML]{title="…"}`.
+//! What a directive means is not defined: they are not compiled to HTML, but
+//! they are available in mdast, so that they can be handled there.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveText`][Name::DirectiveText]
+//! *   [`DirectiveSequence`][Name::DirectiveSequence]
+//! *   See [directive][partial_directive] for more
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [*Generic directives/plugins syntax* on `talk.commonmark.org`](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [text]: crate::construct::text
+//! [partial_directive]: crate::construct::partial_directive
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive (text).
+///
+/// ```markdown
+/// > | a :b[c] d
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_text
+        && tokenizer.current == Some(b':')
+        // Not after an unescaped `:`.
+        && (tokenizer.previous != Some(b':')
+            || matches!(tokenizer.events.last(), Some(event) if event.name == Name::CharacterEscape))
+    {
+        tokenizer.enter(Name::DirectiveText);
+        tokenizer.enter(Name::DirectiveSequence);
+        tokenizer.consume();
+        tokenizer.exit(Name::DirectiveSequence);
+        tokenizer.attempt(State::Next(StateName::DirectiveTextAfter), State::Nok);
+        State::Next(StateName::DirectiveNameStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After directive (text).
+///
+/// ```markdown
+/// > | a :b[c] d
+///            ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::DirectiveText);
+    State::Ok
+}
@@ -8,6 +8,8 @@
 //! *   [Block quote][crate::construct::block_quote]
 //! *   [List item][crate::construct::list_item]
 //! *   [GFM: Footnote definition][crate::construct::gfm_footnote_definition]
+//! *   [Directive (container)][crate::construct::directive_container]
+//! *   [Fenced div][crate::construct::fenced_div]
 
 use crate::event::{Content, Event, Kind, Link, Name};
 use crate::state::{Name as StateName, State};
@@ -59,6 +61,11 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         tokenizer.parse_state,
     )));
 
+    // Define where the first line starts, so that where the next lines start
+    // is stored at the right place when frontmatter goes through them, even
+    // if it turns out to not be frontmatter.
+    tokenizer.define_skip(tokenizer.point.clone());
+
     tokenizer.attempt(
         State::Next(StateName::DocumentBeforeFrontmatter),
         State::Next(StateName::DocumentBeforeFrontmatter),
@@ -102,6 +109,8 @@ pub fn container_existing_before(tokenizer: &mut Tokenizer) -> State {
             Container::BlockQuote => StateName::BlockQuoteContStart,
             Container::GfmFootnoteDefinition => StateName::GfmFootnoteDefinitionContStart,
             Container::ListItem => StateName::ListItemContStart,
+            Container::FencedDiv => StateName::FencedDivContStart,
+            Container::Directive => StateName::DirectiveContainerContStart,
         };
 
         tokenizer.attempt(
@@ -154,6 +163,14 @@ pub fn container_new_before(tokenizer: &mut Tokenizer) -> State {
         }
     }
 
+    // Too deep: no new containers, the rest is flow.
+    if matches!(
+        tokenizer.parse_state.options.max_nesting,
+        Some(max) if tokenizer.tokenize_state.document_continued >= max
+    ) {
+        return State::Retry(StateName::DocumentContainersAfter);
+    }
+
     // Check for a new container.
     // Block quote?
     // Add a new container at the end of the stack.
@@ -233,7 +250,57 @@ pub fn container_new_before_not_list(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -> State {
-    // It wasn’t a new block quote, list item, or footnote definition.
+    // Directive (container)?
+    // We replace the empty footnote definition container for this new
+    // directive one.
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued] = ContainerState {
+        kind: Container::Directive,
+        blank_initial: false,
+        size: 0,
+    };
+
+    tokenizer.attempt(
+        State::Next(StateName::DocumentContainerNewAfter),
+        State::Next(StateName::DocumentContainerNewBeforeNotDirectiveContainer),
+    );
+    State::Retry(StateName::DirectiveContainerStart)
+}
+
+/// At new container, but not a block quote, list item, footnote definition,
+/// or directive (container).
+//
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+pub fn container_new_before_not_directive_container(tokenizer: &mut Tokenizer) -> State {
+    // Fenced div?
+    // We replace the empty directive container for this new fenced div one.
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued] = ContainerState {
+        kind: Container::FencedDiv,
+        blank_initial: false,
+        size: 0,
+    };
+
+    tokenizer.attempt(
+        State::Next(StateName::DocumentContainerNewAfter),
+        State::Next(StateName::DocumentContainerNewBeforeNotFencedDiv),
+    );
+    State::Retry(StateName::FencedDivStart)
+}
+
+/// At new container, but not a block quote, list item, footnote definition,
+/// directive (container), or fenced div.
+//
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+pub fn container_new_before_not_fenced_div(tokenizer: &mut Tokenizer) -> State {
+    // It wasn’t a new block quote, list item, footnote definition, directive
+    // (container), or fenced div.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Drop what was in the middle.
     tokenizer
@@ -253,7 +320,8 @@ pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -
 ///       ^
 /// ```
 pub fn container_new_after(tokenizer: &mut Tokenizer) -> State {
-    // It was a new block quote, list item, or footnote definition.
+    // It was a new block quote, list item, footnote definition, directive
+    // (container), or fenced div.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Take the new container.
     let container = tokenizer
@@ -486,6 +554,13 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), Strin
             .take()
             .unwrap_or(State::Next(StateName::FlowStart));
 
+        // A new container means the next line does not continue the flow,
+        // which is like a lazy line: constructs such as fenced code must not
+        // take the line ending.
+        if *phase == Phase::Prefix {
+            child.lazy = true;
+        }
+
         child.flush(state, false)?;
     }
 
@@ -500,6 +575,8 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), Strin
                 Container::BlockQuote => Name::BlockQuote,
                 Container::GfmFootnoteDefinition => Name::GfmFootnoteDefinition,
                 Container::ListItem => Name::ListItem,
+                Container::FencedDiv => Name::FencedDiv,
+                Container::Directive => Name::DirectiveContainer,
             };
 
             exits.push(Event {
@@ -0,0 +1,113 @@
+//! Emoji occur in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Emoji form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! emoji ::= ':' name ':'
+//!
+//! ; Note: Limited to any known name (see `constants.rs`, and
+//! ; `emoji_shortcodes` in `ParseOptions`).
+//! name ::= 1*(ascii_alphanumeric | '+' | '-' | '_')
+//! ```
+//!
+//! Emoji are not part of `CommonMark` or GFM, but GitHub supports them on
+//! their website.
+//! Like much of markdown, there are no “invalid” emoji: shortcodes with
+//! unknown names are not emoji, but are instead literal text.
+//!
+//! Emoji are parsed sensitive to casing.
+//! See [`EMOJI`][emoji] for which names match by default.
+//!
+//! ## HTML
+//!
+//! Emoji relate to no HTML element: they turn into the emoji itself, such as
+//! that `:smile:` turns into `😄`.
+//!
+//! ## Recommendation
+//!
+//! If possible, use the emoji itself instead of a shortcode.
+//!
+//! ## Tokens
+//!
+//! *   [`Emoji`][Name::Emoji]
+//! *   [`EmojiMarker`][Name::EmojiMarker]
+//! *   [`EmojiValue`][Name::EmojiValue]
+//!
+//! ## References
+//!
+//! *   [`github/gemoji`](https://github.com/github/gemoji)
+//!
+//! [text]: crate::construct::text
+//! [emoji]: crate::util::constant::EMOJI
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{emoji::decode_emoji, slice::Slice};
+
+/// Start of emoji.
+///
+/// ```markdown
+/// > | a :smile: b
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.emoji && tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::Emoji);
+        tokenizer.enter(Name::EmojiMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::EmojiMarker);
+        tokenizer.enter(Name::EmojiValue);
+        State::Next(StateName::EmojiValue)
+    } else {
+        State::Nok
+    }
+}
+
+/// In value, before `:`.
+///
+/// ```markdown
+/// > | a :smile: b
+///        ^^^^^
+/// ```
+pub fn value(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b':') if tokenizer.tokenize_state.size > 0 => {
+            // Guaranteed to be valid ASCII bytes.
+            let slice = Slice::from_indices(
+                tokenizer.parse_state.bytes,
+                tokenizer.point.index - tokenizer.tokenize_state.size,
+                tokenizer.point.index,
+            );
+            tokenizer.tokenize_state.size = 0;
+
+            if decode_emoji(
+                slice.as_str(),
+                &tokenizer.parse_state.options.emoji_shortcodes,
+            )
+            .is_none()
+            {
+                return State::Nok;
+            }
+
+            tokenizer.exit(Name::EmojiValue);
+            tokenizer.enter(Name::EmojiMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::EmojiMarker);
+            tokenizer.exit(Name::Emoji);
+            State::Ok
+        }
+        Some(b'+' | b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.tokenize_state.size += 1;
+            tokenizer.consume();
+            State::Next(StateName::EmojiValue)
+        }
+        _ => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+    }
+}
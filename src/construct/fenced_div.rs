@@ -0,0 +1,444 @@
+//! Fenced div occurs in the [document][] content type.
+//!
+//! ## Grammar
+//!
+//! Fenced divs form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the closing fence must have at least as many colons as the
+//! ; opening fence.
+//! fenced_div_start ::= fence_open eol
+//! fenced_div_cont ::= fence_close eol | line
+//!
+//! fence_open ::= sequence *space_or_tab attributes *space_or_tab [ sequence *space_or_tab ]
+//! fence_close ::= sequence *space_or_tab
+//! ; Restriction: at least `3` markers.
+//! sequence ::= 3*':'
+//! attributes ::= '{' *( line - '}' ) '}' | 1*( text - '{' )
+//! ```
+//!
+//! Fenced divs are not prefixed: every line until the closing fence is part
+//! of the div.
+//! Unlike [code (fenced)][raw_flow], the content is not code but flow, so it
+//! can contain paragraphs, lists, more fenced divs, and so on.
+//! The opening fence must have attributes: a fence without attributes is
+//! always a closing fence.
+//!
+//! A closing fence closes the innermost fenced div that it is long enough
+//! for.
+//! So, when fenced divs are nested, use more colons for outer fences.
+//!
+//! Closing fences are not seen in code (fenced) or other concrete
+//! constructs, which are closed by the closing fence of the code itself.
+//!
+//! The attributes are interpreted when compiling, and not parsed further
+//! here.
+//! A word is a class (`::: warning`).
+//! Braces can contain classes (`.a`), an ID (`#b`), and other attributes
+//! (`c=d`, `e="f g"`), separated by whitespace (`::: {.a #b c=d}`).
+//!
+//! ## HTML
+//!
+//! Fenced div relates to the `<div>` element in HTML.
+//! See [*§ 4.4.15 The `div` element*][html_div] in the HTML spec for more
+//! info.
+//!
+//! ## Recommendation
+//!
+//! Use three colons for the innermost fenced divs, and one more colon for each
+//! level of nesting around them.
+//!
+//! ## Tokens
+//!
+//! *   [`FencedDiv`][Name::FencedDiv]
+//! *   [`FencedDivAttributes`][Name::FencedDivAttributes]
+//! *   [`FencedDivFence`][Name::FencedDivFence]
+//! *   [`FencedDivFenceSequence`][Name::FencedDivFenceSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//!
+//! ## References
+//!
+//! *   [*§ Divs and Spans* in `Pandoc`](https://pandoc.org/MANUAL.html#divs-and-spans)
+//! *   [*§ Divs* in `Quarto`](https://quarto.org/docs/authoring/markdown-basics.html#divs-and-spans)
+//!
+//! [document]: crate::construct::document
+//! [raw_flow]: crate::construct::raw_flow
+//! [html_div]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-div-element
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::{Container, Tokenizer};
+use crate::util::constant::{FENCED_DIV_SEQUENCE_SIZE_MIN, TAB_SIZE};
+
+/// Start of fenced div.
+///
+/// ```markdown
+/// > | ::: {.a}
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.fenced_div {
+        tokenizer.enter(Name::FencedDiv);
+        tokenizer.enter(Name::FencedDivFence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::FencedDivSequenceBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(tokenizer, 0, indent_max(tokenizer)))
+        } else {
+            State::Retry(StateName::FencedDivSequenceBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening fence, after optional whitespace, at sequence.
+///
+/// ```markdown
+/// > | ::: {.a}
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn sequence_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::FencedDivFenceSequence);
+        State::Retry(StateName::FencedDivSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening fence sequence.
+///
+/// ```markdown
+/// > | ::: {.a}
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        State::Next(StateName::FencedDivSequence)
+    } else if tokenizer.tokenize_state.size >= FENCED_DIV_SEQUENCE_SIZE_MIN {
+        // Keep the size around on the container, to match the closing fence.
+        tokenizer.tokenize_state.document_container_stack
+            [tokenizer.tokenize_state.document_continued]
+            .size = tokenizer.tokenize_state.size;
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.exit(Name::FencedDivFenceSequence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::FencedDivAttributesBefore),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::FencedDivAttributesBefore)
+        }
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// In opening fence, after sequence (and optional whitespace), at
+/// attributes.
+///
+/// ```markdown
+/// > | ::: {.a}
+///         ^
+///   | b
+///   | :::
+/// ```
+pub fn attributes_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        // Fences without attributes are closing fences.
+        None | Some(b'\n') => State::Nok,
+        Some(b'{') => {
+            tokenizer.enter(Name::FencedDivAttributes);
+            tokenizer.consume();
+            State::Next(StateName::FencedDivAttributesBraced)
+        }
+        Some(_) => {
+            tokenizer.enter(Name::FencedDivAttributes);
+            State::Retry(StateName::FencedDivAttributesWord)
+        }
+    }
+}
+
+/// In braced attributes.
+///
+/// ```markdown
+/// > | ::: {.a}
+///          ^
+///   | b
+///   | :::
+/// ```
+pub fn attributes_braced(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Nok,
+        Some(b'}') => {
+            tokenizer.consume();
+            tokenizer.exit(Name::FencedDivAttributes);
+            State::Next(StateName::FencedDivAttributesAfter)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::FencedDivAttributesBraced)
+        }
+    }
+}
+
+/// In word attributes.
+///
+/// ```markdown
+/// > | ::: a
+///         ^
+///   | b
+///   | :::
+/// ```
+pub fn attributes_word(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\t' | b'\n' | b' ') => {
+            tokenizer.exit(Name::FencedDivAttributes);
+            State::Retry(StateName::FencedDivAttributesAfter)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::FencedDivAttributesWord)
+        }
+    }
+}
+
+/// In opening fence, after attributes.
+///
+/// ```markdown
+/// > | ::: {.a} :::
+///             ^
+///   | b
+///   | :::
+/// ```
+pub fn attributes_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::FencedDivTrailingSequenceBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::FencedDivTrailingSequenceBefore)
+    }
+}
+
+/// In opening fence, after attributes (and optional whitespace), at an
+/// optional trailing sequence.
+///
+/// ```markdown
+/// > | ::: {.a} :::
+///              ^
+///   | b
+///   | :::
+/// ```
+pub fn trailing_sequence_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::FencedDivFenceSequence);
+        State::Retry(StateName::FencedDivTrailingSequence)
+    } else {
+        State::Retry(StateName::FencedDivOpenAfter)
+    }
+}
+
+/// In trailing sequence.
+///
+/// ```markdown
+/// > | ::: {.a} :::
+///              ^
+///   | b
+///   | :::
+/// ```
+pub fn trailing_sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.consume();
+        State::Next(StateName::FencedDivTrailingSequence)
+    } else {
+        tokenizer.exit(Name::FencedDivFenceSequence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::FencedDivOpenAfter), State::Nok);
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::FencedDivOpenAfter)
+        }
+    }
+}
+
+/// After opening fence, at eol.
+///
+/// ```markdown
+/// > | ::: {.a}
+///             ^
+///   | b
+///   | :::
+/// ```
+pub fn open_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::FencedDivFence);
+            State::Ok
+        }
+        Some(_) => State::Nok,
+    }
+}
+
+/// Start of fenced div continuation.
+///
+/// Every line continues the fenced div, and a closing fence is eaten.
+/// The line after the closing fence does not continue it.
+///
+/// ```markdown
+///   | ::: {.a}
+/// > | b
+///     ^
+/// > | :::
+///     ^
+/// ```
+pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
+    let container = &tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued];
+
+    // A size of `0` means the closing fence was found.
+    if container.size == 0 {
+        State::Nok
+    }
+    // Closing fences are not seen in code and such.
+    else if tokenizer
+        .tokenize_state
+        .document_child
+        .as_ref()
+        .unwrap()
+        .concrete
+    {
+        State::Ok
+    } else {
+        tokenizer.attempt(State::Ok, State::Ok);
+        State::Retry(StateName::FencedDivCloseStart)
+    }
+}
+
+/// In continuation, at a closing fence.
+///
+/// ```markdown
+///   | ::: {.a}
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::FencedDivFence);
+
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::FencedDivCloseSequenceBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab_min_max(tokenizer, 0, indent_max(tokenizer)))
+    } else {
+        State::Retry(StateName::FencedDivCloseSequenceBefore)
+    }
+}
+
+/// In closing fence, after optional whitespace, at sequence.
+///
+/// ```markdown
+///   | ::: {.a}
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_sequence_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::FencedDivFenceSequence);
+        State::Retry(StateName::FencedDivCloseSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In closing fence sequence.
+///
+/// ```markdown
+///   | ::: {.a}
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size += 1;
+        tokenizer.consume();
+        return State::Next(StateName::FencedDivCloseSequence);
+    }
+
+    let size = tokenizer.tokenize_state.size;
+    tokenizer.tokenize_state.size = 0;
+    let stack = &tokenizer.tokenize_state.document_container_stack;
+    let continued = tokenizer.tokenize_state.document_continued;
+
+    // Long enough for this div, and not for a div or directive inside it.
+    if size >= stack[continued].size
+        && !stack[continued + 1..].iter().any(|container| {
+            matches!(container.kind, Container::Directive | Container::FencedDiv)
+                && container.size > 0
+                && size >= container.size
+        })
+    {
+        tokenizer.exit(Name::FencedDivFenceSequence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::FencedDivCloseAfter), State::Nok);
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::FencedDivCloseAfter)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After closing fence, at eol.
+///
+/// ```markdown
+///   | ::: {.a}
+///   | b
+/// > | :::
+///        ^
+/// ```
+pub fn close_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::FencedDivFence);
+            tokenizer.tokenize_state.document_container_stack
+                [tokenizer.tokenize_state.document_continued]
+                .size = 0;
+            State::Ok
+        }
+        Some(_) => State::Nok,
+    }
+}
+
+/// Get the max size of the indent before a fence.
+fn indent_max(tokenizer: &Tokenizer) -> usize {
+    if tokenizer.parse_state.options.constructs.code_indented {
+        TAB_SIZE - 1
+    } else {
+        usize::MAX
+    }
+}
@@ -55,6 +55,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         .constructs
         .gfm_label_start_footnote
         && tokenizer.current == Some(b'[')
+        && !tokenizer.label_starts_full()
     {
         tokenizer.enter(Name::GfmFootnoteCallLabel);
         tokenizer.enter(Name::LabelMarker);
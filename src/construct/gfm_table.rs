@@ -30,6 +30,13 @@
 //! It is not required for a table to have a body: it can end right after the
 //! delimiter row.
 //!
+//! When `gfm_table_headerless` is on, a table can also start at the delimiter
+//! row, in which case it has no head and all rows are body rows.
+//! Such tables cannot interrupt a paragraph.
+//! They also need at least one body row: a delimiter row on its own is turned
+//! back into a paragraph when resolving, so what cannot interrupt a paragraph
+//! cannot follow the delimiter row either.
+//!
 //! Each column can be marked with an alignment.
 //! The alignment marker is a colon (`:`) used before and/or after delimiter row
 //! filler.
@@ -231,7 +238,10 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, skip::opt_back as skip_opt_back};
+use crate::util::{
+    constant::TAB_SIZE,
+    skip::{counterpart as skip_counterpart, opt as skip_opt, opt_back as skip_opt_back},
+};
 use alloc::vec;
 
 /// Start of a GFM table.
@@ -263,6 +273,11 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         {
             State::Retry(StateName::GfmTableBodyRowStart)
         } else {
+            // Headerless tables cannot interrupt paragraphs.
+            if tokenizer.parse_state.options.gfm_table_headerless && !tokenizer.interrupt {
+                tokenizer.attempt(State::Ok, State::Next(StateName::GfmTableHeaderlessBefore));
+            }
+
             State::Retry(StateName::GfmTableHeadRowBefore)
         }
     } else {
@@ -270,6 +285,20 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// Before delimiter row, without a head row.
+///
+/// Only used when `gfm_table_headerless` is on.
+///
+/// ```markdown
+/// > | | - |
+///     ^
+///   | | a |
+/// ```
+pub fn headerless_before(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::GfmTableHead);
+    State::Retry(StateName::GfmTableHeadDelimiterStart)
+}
+
 /// Before table head row.
 ///
 /// ```markdown
@@ -625,12 +654,22 @@ pub fn head_delimiter_cell_after(tokenizer: &mut Tokenizer) -> State {
             // Exit when:
             // * there was no `:` or `|` at all (it’s a thematic break or setext
             //   underline instead)
-            // * the header cell count is not the delimiter cell count
+            // * the header cell count is not the delimiter cell count (a
+            //   head row always has at least one cell, so a count of zero
+            //   means the table is headerless)
             if !tokenizer.tokenize_state.seen
-                || tokenizer.tokenize_state.size != tokenizer.tokenize_state.size_b
+                || (tokenizer.tokenize_state.size != 0
+                    && tokenizer.tokenize_state.size != tokenizer.tokenize_state.size_b)
             {
                 State::Retry(StateName::GfmTableHeadDelimiterNok)
             } else {
+                // A headerless table turns back into a paragraph when no body
+                // row follows, so what cannot interrupt a paragraph cannot
+                // follow it either.
+                if tokenizer.tokenize_state.size == 0 {
+                    tokenizer.interrupt = true;
+                }
+
                 // Reset.
                 tokenizer.tokenize_state.seen = false;
                 tokenizer.tokenize_state.size = 0;
@@ -703,6 +742,8 @@ pub fn body_row_break(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         None | Some(b'\n') => {
             tokenizer.exit(Name::GfmTableRow);
+            // Feel free to interrupt.
+            tokenizer.interrupt = false;
             State::Ok
         }
         Some(b'\t' | b' ') => {
@@ -778,6 +819,72 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     let mut after_head_awaiting_first_body_row = false;
     let mut last_table_end = 0;
     let mut last_table_has_body = false;
+    let mut in_headerless_head = false;
+
+    // A headerless table needs a body row: turn a delimiter row on its own
+    // back into a paragraph.
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if event.kind == Kind::Enter
+            && event.name == Name::GfmTableHead
+            && tokenizer.events[index + 1].name == Name::GfmTableDelimiterRow
+        {
+            let exit = skip_counterpart(&tokenizer.events, index);
+            let after = skip_opt(
+                &tokenizer.events,
+                exit + 1,
+                &[Name::LineEnding, Name::SpaceOrTab, Name::BlockQuotePrefix],
+            );
+
+            if !matches!(tokenizer.events.get(after), Some(event) if event.kind == Kind::Enter && event.name == Name::GfmTableRow)
+            {
+                let start = tokenizer.events[index].point.clone();
+                let end = tokenizer.events[exit].point.clone();
+                tokenizer.map.add(
+                    index,
+                    exit - index + 1,
+                    vec![
+                        Event {
+                            kind: Kind::Enter,
+                            name: Name::Paragraph,
+                            point: start.clone(),
+                            link: None,
+                        },
+                        Event {
+                            kind: Kind::Enter,
+                            name: Name::Data,
+                            point: start,
+                            link: Some(Link {
+                                previous: None,
+                                next: None,
+                                content: Content::Text,
+                            }),
+                        },
+                        Event {
+                            kind: Kind::Exit,
+                            name: Name::Data,
+                            point: end.clone(),
+                            link: None,
+                        },
+                        Event {
+                            kind: Kind::Exit,
+                            name: Name::Paragraph,
+                            point: end,
+                            link: None,
+                        },
+                    ],
+                );
+            }
+
+            index = exit;
+        }
+
+        index += 1;
+    }
+
+    tokenizer.map.consume(&mut tokenizer.events);
+    index = 0;
 
     while index < tokenizer.events.len() {
         let event = &tokenizer.events[index];
@@ -801,7 +908,12 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                     point: tokenizer.events[index].point.clone(),
                     link: None,
                 };
-                tokenizer.map.add(index, 0, vec![enter]);
+
+                // Headerless: replace the head, which only has a delimiter row.
+                in_headerless_head = tokenizer.events[index + 1].name == Name::GfmTableDelimiterRow;
+                tokenizer
+                    .map
+                    .add(index, usize::from(in_headerless_head), vec![enter]);
             } else if matches!(event.name, Name::GfmTableRow | Name::GfmTableDelimiterRow) {
                 in_delimiter_row = event.name == Name::GfmTableDelimiterRow;
                 in_row = true;
@@ -858,6 +970,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
         } else if event.name == Name::GfmTableHead {
             after_head_awaiting_first_body_row = true;
             last_table_end = index;
+
+            if in_headerless_head {
+                in_headerless_head = false;
+                tokenizer.map.add(index, 1, vec![]);
+            }
         } else if matches!(event.name, Name::GfmTableRow | Name::GfmTableDelimiterRow) {
             in_row = false;
             last_table_end = index;
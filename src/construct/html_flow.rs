@@ -812,7 +812,11 @@ pub fn continuation_cdata_inside(tokenizer: &mut Tokenizer) -> State {
 ///                   ^
 /// ```
 pub fn continuation_declaration_inside(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.tokenize_state.marker == COMMENT && tokenizer.current == Some(b'-') {
+    // More dashes or brackets can come before the `>` that closes comments
+    // (`--->`) or cdata (`]]]>`).
+    if (tokenizer.tokenize_state.marker == COMMENT && tokenizer.current == Some(b'-'))
+        || (tokenizer.tokenize_state.marker == CDATA && tokenizer.current == Some(b']'))
+    {
         tokenizer.consume();
         State::Next(StateName::HtmlFlowContinuationDeclarationInside)
     } else if tokenizer.current == Some(b'>') {
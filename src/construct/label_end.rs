@@ -46,8 +46,9 @@
 //! attribute in case of a [label start (link)][label_start_link], and an
 //! `src` attribute in case of a [label start (image)][label_start_image].
 //! The title is formed, optionally, on either `<a>` or `<img>`.
-//! When matched with a [gfm label start (footnote)][gfm_label_start_footnote],
-//! no reference or resource can follow the label end.
+//! When matched with a [gfm label start (footnote)][gfm_label_start_footnote]
+//! or a [label start (inline footnote)][label_start_inline_footnote], no
+//! reference or resource can follow the label end.
 //!
 //! For info on how to encode characters in URLs, see
 //! [`destination`][destination].
@@ -57,6 +58,10 @@
 //!
 //! In case of a matched [gfm label start (footnote)][gfm_label_start_footnote],
 //! a counter is injected.
+//! In case of a matched
+//! [label start (inline footnote)][label_start_inline_footnote], a counter is
+//! also injected, and the interpreted content is placed in the footnote
+//! section.
 //! In case of a matched [label start (link)][label_start_link], the interpreted
 //! content between it and the label end, is placed between the opening and
 //! closing tags.
@@ -109,8 +114,9 @@
 //! It can also match with [label start (image)][label_start_image], in which
 //! case they form an `<img>` element.
 //! See [*§ 4.8.3 The `img` element*][html_img] in the HTML spec for more info.
-//! It can also match with [gfm label start (footnote)][gfm_label_start_footnote],
-//! in which case they form `<sup>` and `<a>` elements in HTML.
+//! It can also match with [gfm label start (footnote)][gfm_label_start_footnote]
+//! or [label start (inline footnote)][label_start_inline_footnote], in which
+//! case they form `<sup>` and `<a>` elements in HTML.
 //! See [*§ 4.5.19 The `sub` and `sup` elements*][html_sup] and
 //! [*§ 4.5.1 The `a` element*][html_a] in the HTML spec for more info.
 //!
@@ -130,6 +136,7 @@
 //! *   [`Data`][Name::Data]
 //! *   [`GfmFootnoteCall`][Name::GfmFootnoteCall]
 //! *   [`Image`][Name::Image]
+//! *   [`InlineFootnote`][Name::InlineFootnote]
 //! *   [`Label`][Name::Label]
 //! *   [`LabelEnd`][Name::LabelEnd]
 //! *   [`LabelMarker`][Name::LabelMarker]
@@ -171,6 +178,7 @@
 //! [label_start_image]: crate::construct::label_start_image
 //! [label_start_link]: crate::construct::label_start_link
 //! [gfm_label_start_footnote]: crate::construct::gfm_label_start_footnote
+//! [label_start_inline_footnote]: crate::construct::label_start_inline_footnote
 //! [definition]: crate::construct::definition
 //! [autolink]: crate::construct::autolink
 //! [sanitize_uri]: crate::util::sanitize_uri::sanitize
@@ -253,6 +261,12 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
     let start_index = tokenizer.tokenize_state.label_starts.len() - 1;
     let start = &tokenizer.tokenize_state.label_starts[start_index];
 
+    // Inline footnotes contain their content: they are never followed by a
+    // resource or reference.
+    if start.kind == LabelKind::InlineFootnote {
+        return State::Retry(StateName::LabelEndOk);
+    }
+
     let indices = (
         tokenizer.events[start.start.1].point.index,
         tokenizer.events[tokenizer.tokenize_state.end].point.index,
@@ -351,11 +365,14 @@ pub fn ok(tokenizer: &mut Tokenizer) -> State {
     // longer viable for use (as they would otherwise contain a link).
     // These link starts are still looking for balanced closing brackets, so
     // we can’t remove them, but we can mark them.
+    // Inline footnotes are not marked, as their content ends up in the
+    // footnote section, where links are fine.
     if label_start.kind != LabelKind::Image {
         let mut index = 0;
         while index < tokenizer.tokenize_state.label_starts.len() {
             let label_start = &mut tokenizer.tokenize_state.label_starts[index];
-            if label_start.kind != LabelKind::Image {
+            if label_start.kind != LabelKind::Image && label_start.kind != LabelKind::InlineFootnote
+            {
                 label_start.inactive = true;
             }
             index += 1;
@@ -693,6 +710,8 @@ fn inject_labels(tokenizer: &mut Tokenizer, labels: &[Label]) {
         let label = &labels[index];
         let group_name = if label.kind == LabelKind::GfmFootnote {
             Name::GfmFootnoteCall
+        } else if label.kind == LabelKind::InlineFootnote {
+            Name::InlineFootnote
         } else if label.kind == LabelKind::Image {
             Name::Image
         } else {
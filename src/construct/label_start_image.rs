@@ -44,7 +44,9 @@ use crate::tokenizer::{LabelKind, LabelStart, Tokenizer};
 ///       ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.parse_state.options.constructs.label_start_image && tokenizer.current == Some(b'!')
+    if tokenizer.parse_state.options.constructs.label_start_image
+        && tokenizer.current == Some(b'!')
+        && !tokenizer.label_starts_full()
     {
         tokenizer.enter(Name::LabelImage);
         tokenizer.enter(Name::LabelImageMarker);
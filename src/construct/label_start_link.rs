@@ -43,7 +43,9 @@ use crate::tokenizer::{LabelKind, LabelStart, Tokenizer};
 ///       ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.parse_state.options.constructs.label_start_link && tokenizer.current == Some(b'[')
+    if tokenizer.parse_state.options.constructs.label_start_link
+        && tokenizer.current == Some(b'[')
+        && !tokenizer.label_starts_full()
     {
         let start = tokenizer.events.len();
         tokenizer.enter(Name::LabelLink);
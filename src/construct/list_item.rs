@@ -11,6 +11,11 @@
 //! ; only the first `space_or_tab` is part of the start.
 //! list_item_start ::= '*' | '+' | '-' | 1*9(ascii_decimal) ('.' | ')') [1*4 space_or_tab]
 //!
+//! ; Extension: with `alpha_ordered_lists`, values can also be letters or roman numerals.
+//! ; Restriction: several letters must all be roman numerals of the same case.
+//! list_item_value_alpha ::= ascii_alpha | 2*9(roman)
+//! roman ::= 'C' | 'D' | 'I' | 'L' | 'M' | 'V' | 'X' | 'c' | 'd' | 'i' | 'l' | 'm' | 'v' | 'x'
+//!
 //! ; Restriction: blank line allowed, except when this is the first continuation after a blank start.
 //! ; Restriction: if not blank, the line must be indented, exactly `n` times.
 //! list_item_cont ::= [n(space_or_tab)]
@@ -37,6 +42,12 @@
 //! Use a single space after a marker.
 //! Never use lazy continuation.
 //!
+//! With [`alpha_ordered_lists`][crate::ParseOptions::alpha_ordered_lists],
+//! ordered list items can use letters (`a.`) and roman numerals (`iv.`).
+//! Items of different kinds form different lists, except that a single
+//! letter that is also a roman numeral (such as `i` or `v`) continues
+//! either kind.
+//!
 //! ## Tokens
 //!
 //! *   [`ListItem`][Name::ListItem]
@@ -66,6 +77,7 @@ use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
 use crate::util::{
     constant::{LIST_ITEM_VALUE_SIZE_MAX, TAB_SIZE},
+    ordered_list::{is_ambiguous, is_roman, kind, Kind as ValueKind},
     skip,
     slice::{Position, Slice},
 };
@@ -117,6 +129,10 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
     // Ordered.
     else if tokenizer.current == Some(b'1')
         || (matches!(tokenizer.current, Some(b'0'..=b'9')) && !tokenizer.interrupt)
+        || (tokenizer.parse_state.options.alpha_ordered_lists
+            && (matches!(tokenizer.current, Some(b'A' | b'I' | b'a' | b'i'))
+                || (matches!(tokenizer.current, Some(b'A'..=b'Z' | b'a'..=b'z'))
+                    && !tokenizer.interrupt)))
     {
         State::Retry(StateName::ListItemBeforeOrdered)
     } else {
@@ -161,8 +177,8 @@ pub fn value(tokenizer: &mut Tokenizer) -> State {
     {
         tokenizer.exit(Name::ListItemValue);
         State::Retry(StateName::ListItemMarker)
-    } else if matches!(tokenizer.current, Some(b'0'..=b'9'))
-        && tokenizer.tokenize_state.size + 1 < LIST_ITEM_VALUE_SIZE_MAX
+    } else if tokenizer.tokenize_state.size + 1 < LIST_ITEM_VALUE_SIZE_MAX
+        && value_continues(tokenizer)
     {
         tokenizer.tokenize_state.size += 1;
         tokenizer.consume();
@@ -173,6 +189,31 @@ pub fn value(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// Check whether the current byte can be part of an ordered list item value.
+///
+/// Values are decimal, a single letter, or roman numerals of the same case.
+fn value_continues(tokenizer: &Tokenizer) -> bool {
+    let alpha = tokenizer.parse_state.options.alpha_ordered_lists;
+    let first = if tokenizer.tokenize_state.size == 0 {
+        None
+    } else {
+        // The value is the last event, and we are in it.
+        Some(tokenizer.parse_state.bytes[tokenizer.events[tokenizer.events.len() - 1].point.index])
+    };
+
+    match (first, tokenizer.current) {
+        (None | Some(b'0'..=b'9'), Some(b'0'..=b'9')) => true,
+        (None, Some(byte)) => alpha && byte.is_ascii_alphabetic(),
+        (Some(first), Some(byte)) => {
+            alpha
+                && is_roman(first)
+                && is_roman(byte)
+                && first.is_ascii_lowercase() == byte.is_ascii_lowercase()
+        }
+        _ => false,
+    }
+}
+
 /// At list item marker.
 ///
 /// ```markdown
@@ -318,11 +359,28 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
-    tokenizer.check(
-        State::Next(StateName::ListItemContBlank),
-        State::Next(StateName::ListItemContFilled),
-    );
-    State::Retry(StateName::BlankLineStart)
+    // Check whether the rest of the line is blank.
+    // Nested list items each check that on the same line, so where the
+    // whitespace ends is kept, instead of walking it for each item.
+    let bytes = tokenizer.parse_state.bytes;
+    let index = tokenizer.point.index;
+    let end = match tokenizer.tokenize_state.document_whitespace {
+        Some((start, end)) if start <= index && index <= end => end,
+        _ => {
+            let mut end = index;
+            while matches!(bytes.get(end), Some(b'\t' | b' ')) {
+                end += 1;
+            }
+            tokenizer.tokenize_state.document_whitespace = Some((index, end));
+            end
+        }
+    };
+
+    if matches!(bytes.get(end), None | Some(b'\n' | b'\r')) {
+        State::Retry(StateName::ListItemContBlank)
+    } else {
+        State::Retry(StateName::ListItemContFilled)
+    }
 }
 
 /// Start of blank list item continuation.
@@ -372,22 +430,54 @@ pub fn cont_filled(tokenizer: &mut Tokenizer) -> State {
 
 /// Find adjacent list items with the same marker.
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
-    let mut lists_wip: Vec<(u8, usize, usize, usize)> = vec![];
-    let mut lists: Vec<(u8, usize, usize, usize)> = vec![];
+    let mut lists_wip: Vec<(u8, usize, usize, usize, ValueKind)> = vec![];
+    let mut lists: Vec<(u8, usize, usize, usize, ValueKind)> = vec![];
     let mut index = 0;
     let mut balance = 0;
 
+    // Find where list items end, in one go, as walking to the end of each
+    // item is slow when they are deeply nested.
+    let mut ends = vec![];
+    let mut stack = vec![];
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if event.name == Name::ListItem {
+            if event.kind == Kind::Enter {
+                stack.push(ends.len());
+                ends.push(0);
+            } else {
+                ends[stack.pop().unwrap()] = index;
+            }
+        }
+
+        index += 1;
+    }
+
+    let mut item = 0;
+    index = 0;
+
     // Merge list items.
     while index < tokenizer.events.len() {
         let event = &tokenizer.events[index];
 
         if event.name == Name::ListItem {
             if event.kind == Kind::Enter {
-                let end = skip::opt(&tokenizer.events, index, &[Name::ListItem]) - 1;
+                let end = ends[item];
+                item += 1;
                 let marker = skip::to(&tokenizer.events, index, &[Name::ListItemMarker]);
+                let value = if tokenizer.events[marker - 1].name == Name::ListItemValue {
+                    Slice::from_position(
+                        tokenizer.parse_state.bytes,
+                        &Position::from_exit_event(&tokenizer.events, marker - 1),
+                    )
+                    .bytes
+                } else {
+                    &[]
+                };
                 // Guaranteed to be a valid ASCII byte.
                 let marker = tokenizer.parse_state.bytes[tokenizer.events[marker].point.index];
-                let current = (marker, balance, index, end);
+                let current = (marker, balance, index, end, kind(value));
 
                 let mut list_index = lists_wip.len();
                 let mut matched = false;
@@ -406,7 +496,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                         ],
                     );
 
-                    if previous.0 == current.0 && previous.1 == current.1 && before == current.2 {
+                    if previous.0 == current.0
+                        && previous.1 == current.1
+                        && before == current.2
+                        && (previous.4 == current.4 || is_ambiguous(value, previous.4))
+                    {
                         let previous_mut = &mut lists_wip[list_index];
                         previous_mut.3 = current.3;
                         lists.append(&mut lists_wip.split_off(list_index + 1));
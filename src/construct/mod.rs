@@ -62,12 +62,18 @@
 //!
 //! The following constructs are extensions found in markdown:
 //!
+//! *   [directive (container)][directive_container]
+//! *   [directive (leaf)][directive_leaf]
+//! *   [directive (text)][directive_text]
+//! *   [emoji][]
+//! *   [fenced div][fenced_div]
 //! *   [frontmatter][]
 //! *   [gfm autolink literal][gfm_autolink_literal]
 //! *   [gfm footnote definition][gfm_footnote_definition]
 //! *   [gfm label start footnote][gfm_label_start_footnote]
 //! *   [gfm table][gfm_table]
 //! *   [gfm task list item check][gfm_task_list_item_check]
+//! *   [label start (inline footnote)][label_start_inline_footnote]
 //! *   [mdx esm][mdx_esm]
 //! *   [mdx expression (flow)][mdx_expression_flow]
 //! *   [mdx expression (text)][mdx_expression_text]
@@ -79,6 +85,7 @@
 //! *   [bom][partial_bom]
 //! *   [data][partial_data]
 //! *   [destination][partial_destination]
+//! *   [directive][partial_directive]
 //! *   [label][partial_label]
 //! *   [mdx expression][partial_mdx_expression]
 //! *   [mdx jsx][partial_mdx_jsx]
@@ -157,7 +164,12 @@ pub mod character_reference;
 pub mod code_indented;
 pub mod content;
 pub mod definition;
+pub mod directive_container;
+pub mod directive_leaf;
+pub mod directive_text;
 pub mod document;
+pub mod emoji;
+pub mod fenced_div;
 pub mod flow;
 pub mod frontmatter;
 pub mod gfm_autolink_literal;
@@ -172,6 +184,7 @@ pub mod html_flow;
 pub mod html_text;
 pub mod label_end;
 pub mod label_start_image;
+pub mod label_start_inline_footnote;
 pub mod label_start_link;
 pub mod list_item;
 pub mod mdx_esm;
@@ -183,6 +196,7 @@ pub mod paragraph;
 pub mod partial_bom;
 pub mod partial_data;
 pub mod partial_destination;
+pub mod partial_directive;
 pub mod partial_label;
 pub mod partial_mdx_expression;
 pub mod partial_mdx_jsx;
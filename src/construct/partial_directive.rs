@@ -0,0 +1,432 @@
+//! Directive occurs in [directive (container)][directive_container],
+//! [directive (leaf)][directive_leaf], and [directive (text)][directive_text].
+//!
+//! ## Grammar
+//!
+//! The name, label, and attributes of a directive form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive ::= name [ label ] [ attributes ]
+//!
+//! ; Restriction: the last byte cannot be `-` or `_`.
+//! name ::= ascii_alphabetic *( ascii_alphanumeric | '-' | '_' )
+//! ; Restriction: brackets must be balanced, unless escaped.
+//! label ::= '[' *( label_byte | label_escape ) ']'
+//! label_byte ::= code - eol - '\\'
+//! label_escape ::= '\\' [ '[' | '\\' | ']' ]
+//! attributes ::= '{' *( attribute_byte | quoted ) '}'
+//! attribute_byte ::= code - eol - '"' - '\'' - '}'
+//! quoted ::= '"' *( code - eol - '"' ) '"' | '\'' *( code - eol - '\'' ) '\''
+//! ```
+//!
+//! The label is interpreted as the [text][] content type.
+//! That means that [attention][], [code (text)][raw_text], and such, are
+//! allowed.
+//! Unlike labels of links, labels of directives cannot contain line endings.
+//!
+//! The attributes are interpreted when compiling, and not parsed further
+//! here.
+//! Braces can contain classes (`.a`), an ID (`#b`), and other attributes
+//! (`c=d`, `e="f g"`), separated by whitespace (`{.a #b c=d}`).
+//!
+//! ## Tokens
+//!
+//! *   [`Data`][Name::Data]
+//! *   [`DirectiveAttributes`][Name::DirectiveAttributes]
+//! *   [`DirectiveLabel`][Name::DirectiveLabel]
+//! *   [`DirectiveLabelMarker`][Name::DirectiveLabelMarker]
+//! *   [`DirectiveLabelString`][Name::DirectiveLabelString]
+//! *   [`DirectiveName`][Name::DirectiveName]
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [*Generic directives/plugins syntax* on `talk.commonmark.org`](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [directive_container]: crate::construct::directive_container
+//! [directive_leaf]: crate::construct::directive_leaf
+//! [directive_text]: crate::construct::directive_text
+//! [text]: crate::construct::text
+//! [attention]: crate::construct::attention
+//! [raw_text]: crate::construct::raw_text
+
+use crate::event::{Content, Link, Name};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use alloc::{vec, vec::Vec};
+
+/// Start of directive name.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///      ^
+/// ```
+pub fn name_start(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'A'..=b'Z' | b'a'..=b'z')) {
+        tokenizer.enter(Name::DirectiveName);
+        tokenizer.consume();
+        State::Next(StateName::DirectiveNameInside)
+    } else {
+        State::Nok
+    }
+}
+
+/// In directive name.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///       ^
+/// ```
+pub fn name_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveNameInside)
+        }
+        // Names cannot end in a dash or underscore.
+        _ if matches!(tokenizer.previous, Some(b'-' | b'_')) => State::Nok,
+        _ => {
+            tokenizer.exit(Name::DirectiveName);
+            State::Retry(StateName::DirectiveLabelBefore)
+        }
+    }
+}
+
+/// After directive name, at optional label.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///       ^
+/// ```
+pub fn label_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.enter(Name::DirectiveLabel);
+        tokenizer.enter(Name::DirectiveLabelMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DirectiveLabelMarker);
+        State::Next(StateName::DirectiveLabelStart)
+    } else {
+        State::Retry(StateName::DirectiveAttributesBefore)
+    }
+}
+
+/// In label, after `[`.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///        ^
+/// ```
+pub fn label_start(tokenizer: &mut Tokenizer) -> State {
+    if within(
+        &tokenizer.tokenize_state.directive_label_unclosed,
+        tokenizer.point.index,
+    ) {
+        State::Nok
+    } else if tokenizer.current == Some(b']') {
+        State::Retry(StateName::DirectiveLabelEnd)
+    } else {
+        tokenizer.enter(Name::DirectiveLabelString);
+        tokenizer.enter_link(
+            Name::Data,
+            Link {
+                previous: None,
+                next: None,
+                content: Content::Text,
+            },
+        );
+        State::Retry(StateName::DirectiveLabelInside)
+    }
+}
+
+/// In label.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///        ^
+/// ```
+pub fn label_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.size_b = 0;
+            label_unclosed(tokenizer);
+            State::Nok
+        }
+        Some(b']') if tokenizer.tokenize_state.size_b == 0 => {
+            tokenizer.exit(Name::Data);
+            tokenizer.exit(Name::DirectiveLabelString);
+            State::Retry(StateName::DirectiveLabelEnd)
+        }
+        Some(byte) => {
+            if byte == b'[' {
+                tokenizer.tokenize_state.size_b += 1;
+            } else if byte == b']' {
+                tokenizer.tokenize_state.size_b -= 1;
+            }
+
+            tokenizer.consume();
+
+            State::Next(if byte == b'\\' {
+                StateName::DirectiveLabelEscape
+            } else {
+                StateName::DirectiveLabelInside
+            })
+        }
+    }
+}
+
+/// After `\`, at a special character.
+///
+/// ```markdown
+/// > | :a[b\]c]
+///          ^
+/// ```
+pub fn label_escape(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[' | b'\\' | b']') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveLabelInside)
+        }
+        _ => State::Retry(StateName::DirectiveLabelInside),
+    }
+}
+
+/// At `]`.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///         ^
+/// ```
+pub fn label_end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::DirectiveLabelMarker);
+    tokenizer.consume();
+    tokenizer.exit(Name::DirectiveLabelMarker);
+    tokenizer.exit(Name::DirectiveLabel);
+    State::Next(StateName::DirectiveAttributesBefore)
+}
+
+/// After directive name or label, at optional attributes.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///          ^
+/// ```
+pub fn attributes_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current != Some(b'{') {
+        State::Ok
+    } else if within(
+        &tokenizer.tokenize_state.directive_attributes_unclosed,
+        tokenizer.point.index + 1,
+    ) {
+        State::Nok
+    } else {
+        tokenizer.enter(Name::DirectiveAttributes);
+        tokenizer.consume();
+        State::Next(StateName::DirectiveAttributesInside)
+    }
+}
+
+/// In attributes.
+///
+/// ```markdown
+/// > | :a[b]{#c}
+///           ^
+/// ```
+pub fn attributes_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            attributes_unclosed(tokenizer);
+            State::Nok
+        }
+        Some(b'}') => {
+            tokenizer.consume();
+            tokenizer.exit(Name::DirectiveAttributes);
+            State::Ok
+        }
+        Some(byte) => {
+            tokenizer.consume();
+
+            if matches!(byte, b'"' | b'\'') {
+                tokenizer.tokenize_state.marker = byte;
+                State::Next(StateName::DirectiveAttributesValueQuoted)
+            } else {
+                State::Next(StateName::DirectiveAttributesInside)
+            }
+        }
+    }
+}
+
+/// In quoted attribute value.
+///
+/// ```markdown
+/// > | :a{b="c"}
+///           ^
+/// ```
+pub fn attributes_value_quoted(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.marker = 0;
+            attributes_unclosed(tokenizer);
+            State::Nok
+        }
+        Some(byte) => {
+            tokenizer.consume();
+
+            if byte == tokenizer.tokenize_state.marker {
+                tokenizer.tokenize_state.marker = 0;
+                State::Next(StateName::DirectiveAttributesInside)
+            } else {
+                State::Next(StateName::DirectiveAttributesValueQuoted)
+            }
+        }
+    }
+}
+
+/// Remember where other labels cannot be closed, after a label could not be
+/// closed.
+///
+/// Labels cannot span lines, so a label starting later on this line, at a
+/// place where the bracket balance does not drop below what it is there,
+/// cannot be closed either.
+/// Remembering that prevents scanning the rest of the line for each label,
+/// which would be quadratic for lines such as `:a[:a[:a[`.
+fn label_unclosed(tokenizer: &mut Tokenizer) {
+    // Scanning does not add events, so the last event is where it started.
+    let start = tokenizer.events[tokenizer.events.len() - 1].point.index;
+    let end = tokenizer.point.index;
+    let bytes = &tokenizer.parse_state.bytes[start..end];
+    // Bracket balance before each byte, or `None` after a `\`.
+    let mut sizes = Vec::with_capacity(bytes.len() + 1);
+    let mut size = 0;
+    let mut escape = false;
+
+    for byte in bytes {
+        if escape {
+            sizes.push(None);
+            escape = false;
+
+            if matches!(byte, b'[' | b'\\' | b']') {
+                continue;
+            }
+        } else {
+            sizes.push(Some(size));
+        }
+
+        match byte {
+            b'[' => size += 1,
+            b']' => size -= 1,
+            b'\\' => escape = true,
+            _ => {}
+        }
+    }
+
+    sizes.push(Some(size));
+
+    let mut ranges = vec![];
+    let mut min = size;
+    let mut index = sizes.len();
+
+    while index > 0 {
+        index -= 1;
+
+        if let Some(size) = sizes[index] {
+            if size <= min {
+                min = size;
+                include(&mut ranges, start + index);
+            }
+        }
+    }
+
+    ranges.reverse();
+    remember(
+        &mut tokenizer.tokenize_state.directive_label_unclosed,
+        start,
+        &ranges,
+    );
+}
+
+/// Remember where other attributes cannot be closed, after attributes could
+/// not be closed.
+///
+/// Attributes cannot span lines, so attributes starting later on this line,
+/// at a place that is not in quotes, cannot be closed either.
+fn attributes_unclosed(tokenizer: &mut Tokenizer) {
+    // Scanning does not add events, so the last event is at `{`.
+    let start = tokenizer.events[tokenizer.events.len() - 1].point.index + 1;
+    let end = tokenizer.point.index;
+    let mut ranges = vec![];
+    let mut marker = 0;
+    let mut index = start;
+
+    while index < end {
+        let byte = tokenizer.parse_state.bytes[index];
+
+        if marker == 0 {
+            include(&mut ranges, index);
+
+            if matches!(byte, b'"' | b'\'') {
+                marker = byte;
+            }
+        } else if byte == marker {
+            marker = 0;
+        }
+
+        index += 1;
+    }
+
+    if marker == 0 {
+        include(&mut ranges, end);
+    }
+
+    remember(
+        &mut tokenizer.tokenize_state.directive_attributes_unclosed,
+        start,
+        &ranges,
+    );
+}
+
+/// Add `index` to ranges, which are built from left to right or from right
+/// to left.
+fn include(ranges: &mut Vec<(usize, usize)>, index: usize) {
+    if let Some(last) = ranges.last_mut() {
+        if last.1 == index {
+            last.1 += 1;
+            return;
+        } else if last.0 == index + 1 {
+            last.0 -= 1;
+            return;
+        }
+    }
+
+    ranges.push((index, index + 1));
+}
+
+/// Merge new ranges into sorted ranges, dropping those before `from`.
+///
+/// Text is tokenized from left to right, so earlier ranges are not needed
+/// anymore.
+fn remember(list: &mut Vec<(usize, usize)>, from: usize, ranges: &[(usize, usize)]) {
+    let previous = core::mem::take(list);
+    let mut previous = previous
+        .into_iter()
+        .filter(|range| range.1 > from)
+        .peekable();
+    let mut next = ranges.iter().copied().peekable();
+
+    while let Some(range) = match (previous.peek(), next.peek()) {
+        (Some(a), Some(b)) if a.0 > b.0 => next.next(),
+        (Some(_), _) => previous.next(),
+        _ => next.next(),
+    } {
+        match list.last_mut() {
+            Some(tail) if tail.1 >= range.0 => tail.1 = tail.1.max(range.1),
+            _ => list.push(range),
+        }
+    }
+}
+
+/// Check whether `index` is in sorted ranges.
+fn within(list: &[(usize, usize)], index: usize) -> bool {
+    let position = list.partition_point(|range| range.1 <= index);
+    matches!(list.get(position), Some(range) if range.0 <= index)
+}
@@ -60,8 +60,25 @@ pub fn space_or_tab_min_max(tokenizer: &mut Tokenizer, min: usize, max: usize) -
     )
 }
 
+/// Between `x` and `y` `space_or_tab`, measuring how many were consumed.
+///
+/// When this is `ok`, the size (which is `0` if there was no `space_or_tab`)
+/// is available as `tokenizer.tokenize_state.space_or_tab_measured`, which
+/// the caller is expected to `take()`.
+/// This lets constructs get their indent without going through events.
+///
+/// ```bnf
+/// space_or_tab_measure ::= x*y( ' ' '\t' )
+/// ```
+pub fn space_or_tab_measure(tokenizer: &mut Tokenizer, min: usize, max: usize) -> StateName {
+    let name = space_or_tab_min_max(tokenizer, min, max);
+    tokenizer.tokenize_state.space_or_tab_measure = true;
+    name
+}
+
 /// `space_or_tab`, with the given options.
 pub fn space_or_tab_with_options(tokenizer: &mut Tokenizer, options: Options) -> StateName {
+    tokenizer.tokenize_state.space_or_tab_measure = false;
     tokenizer.tokenize_state.space_or_tab_connect = options.connect;
     tokenizer.tokenize_state.space_or_tab_content = options.content;
     tokenizer.tokenize_state.space_or_tab_min = options.min;
@@ -139,10 +156,16 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
     let state = if tokenizer.tokenize_state.space_or_tab_size
         >= tokenizer.tokenize_state.space_or_tab_min
     {
+        if tokenizer.tokenize_state.space_or_tab_measure {
+            tokenizer.tokenize_state.space_or_tab_measured =
+                Some(tokenizer.tokenize_state.space_or_tab_size);
+        }
+
         State::Ok
     } else {
         State::Nok
     };
+    tokenizer.tokenize_state.space_or_tab_measure = false;
     tokenizer.tokenize_state.space_or_tab_connect = false;
     tokenizer.tokenize_state.space_or_tab_content = None;
     tokenizer.tokenize_state.space_or_tab_size = 0;
@@ -184,6 +184,11 @@ fn trim_data(
         if index > 0 || slice.before > 0 {
             let enter_point = tokenizer.events[exit_index - 1].point.clone();
             let mut exit_point = enter_point.clone();
+            // Move past a partially consumed tab.
+            if enter_point.vs > 0 {
+                exit_point.index += 1;
+                exit_point.column += slice.before;
+            }
             exit_point.index += index;
             exit_point.column += index;
             exit_point.vs = 0;
@@ -138,14 +138,13 @@
 //! [html_code]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-code-element
 //! [html_pre]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-pre-element
 
-use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::construct::partial_space_or_tab::{
+    space_or_tab, space_or_tab_measure, space_or_tab_min_max,
+};
 use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::{
-    constant::{CODE_FENCED_SEQUENCE_SIZE_MIN, MATH_FLOW_SEQUENCE_SIZE_MIN, TAB_SIZE},
-    slice::{Position, Slice},
-};
+use crate::util::constant::{CODE_FENCED_SEQUENCE_SIZE_MIN, MATH_FLOW_SEQUENCE_SIZE_MIN, TAB_SIZE};
 
 /// Start of raw.
 ///
@@ -164,7 +163,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 State::Next(StateName::RawFlowBeforeSequenceOpen),
                 State::Nok,
             );
-            return State::Retry(space_or_tab_min_max(
+            return State::Retry(space_or_tab_measure(
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
@@ -192,18 +191,11 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 ///   | ~~~
 /// ```
 pub fn before_sequence_open(tokenizer: &mut Tokenizer) -> State {
-    let tail = tokenizer.events.last();
-    let mut prefix = 0;
-
-    if let Some(event) = tail {
-        if event.name == Name::SpaceOrTab {
-            prefix = Slice::from_position(
-                tokenizer.parse_state.bytes,
-                &Position::from_exit_event(&tokenizer.events, tokenizer.events.len() - 1),
-            )
-            .len();
-        }
-    }
+    let prefix = tokenizer
+        .tokenize_state
+        .space_or_tab_measured
+        .take()
+        .unwrap_or(0);
 
     // Code (fenced).
     if (tokenizer.parse_state.options.constructs.code_fenced
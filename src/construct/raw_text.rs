@@ -89,6 +89,10 @@
 //! You can set [`parse_options.math_text_single_dollar: false`][parse_options]
 //! to improve this, as it prevents single dollars from being seen as math, and
 //! thus prevents normal dollars in text from being seen as math.
+//! Or, you can set
+//! [`parse_options.math_text_single_dollar_strict: true`][parse_options],
+//! which only allows single dollars next to content (like Pandoc), which
+//! prevents dollar amounts (`$20 and $30`) from being seen as math.
 //!
 //! ## Tokens
 //!
@@ -168,10 +172,13 @@ pub fn sequence_open(tokenizer: &mut Tokenizer) -> State {
         tokenizer.consume();
         State::Next(StateName::RawTextSequenceOpen)
     }
-    // Not enough markers in the sequence.
+    // Not enough markers in the sequence, or a single dollar not followed by
+    // content.
     else if tokenizer.tokenize_state.marker == b'$'
         && tokenizer.tokenize_state.size == 1
-        && !tokenizer.parse_state.options.math_text_single_dollar
+        && (!tokenizer.parse_state.options.math_text_single_dollar
+            || (tokenizer.parse_state.options.math_text_single_dollar_strict
+                && matches!(tokenizer.current, None | Some(b'\t' | b'\n' | b' '))))
     {
         tokenizer.tokenize_state.marker = 0;
         tokenizer.tokenize_state.size = 0;
@@ -250,7 +257,9 @@ pub fn sequence_close(tokenizer: &mut Tokenizer) -> State {
         State::Next(StateName::RawTextSequenceClose)
     } else {
         tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
-        if tokenizer.tokenize_state.size == tokenizer.tokenize_state.size_b {
+        if tokenizer.tokenize_state.size == tokenizer.tokenize_state.size_b
+            && !strict_single_dollar_not_closing(tokenizer)
+        {
             tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
             tokenizer.tokenize_state.marker = 0;
             tokenizer.tokenize_state.size = 0;
@@ -269,3 +278,27 @@ pub fn sequence_close(tokenizer: &mut Tokenizer) -> State {
         }
     }
 }
+
+/// Check whether a single dollar cannot close math (text) in strict mode,
+/// because it is preceded by whitespace, or followed by a digit.
+///
+/// ```markdown
+/// > | $20 and $30
+///             ^
+/// ```
+fn strict_single_dollar_not_closing(tokenizer: &Tokenizer) -> bool {
+    if tokenizer.tokenize_state.marker == b'$'
+        && tokenizer.tokenize_state.size == 1
+        && tokenizer.parse_state.options.math_text_single_dollar_strict
+    {
+        // The enter of the closing sequence, which is never at the start,
+        // as there is an opening sequence before it.
+        let start = tokenizer.events[tokenizer.events.len() - 2].point.index;
+        matches!(
+            tokenizer.parse_state.bytes[start - 1],
+            b'\t' | b'\n' | b'\r' | b' '
+        ) || matches!(tokenizer.current, Some(b'0'..=b'9'))
+    } else {
+        false
+    }
+}
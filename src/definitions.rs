@@ -0,0 +1,72 @@
+//! Collect definitions from a syntax tree.
+
+use crate::mdast::Node;
+use crate::unist::Position;
+use alloc::{string::String, vec, vec::Vec};
+
+/// Definition in a document, as flat data.
+///
+/// Like the [`Definition`][crate::mdast::Definition] node in mdast, but it
+/// also says whether an earlier definition with the same identifier exists.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_definitions, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let definitions = to_definitions("[Venus]: <https://example.com> \"b\"", &ParseOptions::default())?;
+///
+/// assert_eq!(definitions[0].identifier, "venus");
+/// assert_eq!(definitions[0].label, "Venus");
+/// assert_eq!(definitions[0].url, "https://example.com");
+/// assert_eq!(definitions[0].title, Some("b".into()));
+/// assert!(!definitions[0].shadowed);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Definition {
+    /// Normalized label, which references match.
+    pub identifier: String,
+    /// Label, with character escapes and character references parsed.
+    pub label: String,
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource.
+    pub title: Option<String>,
+    /// Where the definition occurred.
+    pub position: Position,
+    /// Whether an earlier definition has the same identifier.
+    ///
+    /// Such definitions are not used: the first one wins.
+    pub shadowed: bool,
+}
+
+/// Collect definitions in `tree`, in document order.
+pub fn collect(tree: &Node) -> Vec<Definition> {
+    let mut definitions = vec![];
+    visit(tree, &mut definitions);
+    definitions
+}
+
+/// Collect definitions in `node` and its descendants.
+fn visit(node: &Node, definitions: &mut Vec<Definition>) {
+    if let Node::Definition(definition) = node {
+        let shadowed = definitions
+            .iter()
+            .any(|d| d.identifier == definition.identifier);
+        definitions.push(Definition {
+            identifier: definition.identifier.clone(),
+            label: definition.label.clone().unwrap_or_default(),
+            url: definition.url.clone(),
+            title: definition.title.clone(),
+            position: definition.position.clone().unwrap(),
+            shadowed,
+        });
+    } else if let Some(children) = node.children() {
+        for child in children {
+            visit(child, definitions);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Warnings about markdown that is valid but likely a mistake.
+
+use crate::unist::Position;
+use alloc::{fmt, string::String};
+
+/// Warning about something in markdown.
+///
+/// Markdown does not have syntax errors: everything is valid.
+/// But some things are likely mistakes, such as a stray `**` that is shown
+/// as is because it is never closed.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_diagnostics, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let diagnostics = to_diagnostics("**a*", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     diagnostics[0].to_string(),
+///     "1:1: Unexpected unmatched attention sequence `*` (unmatched-attention)"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// Where the problem occurred.
+    pub position: Position,
+    /// Human readable description of the problem.
+    pub reason: String,
+    /// Name of the check that found the problem, such as
+    /// `unmatched-attention`.
+    pub rule_id: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} ({})",
+            self.position.start.line, self.position.start.column, self.reason, self.rule_id
+        )
+    }
+}
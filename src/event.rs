@@ -1,6 +1,18 @@
 //! Semantic labels of things happening.
+//!
+//! Events are what [`to_events()`][crate::to_events] returns.
+//!
+//! ## Stability
+//!
+//! The shape of [`Event`][], [`Kind`][], [`Point`][], and [`Link`][] is
+//! stable.
+//! [`Name`][] is not: new names can be added, and names can be changed, when
+//! the parser changes, in minor versions.
+//! Match on the names you need, with a fallback for the rest.
 
 use crate::util::constant::TAB_SIZE;
+use crate::util::{skip, slice::Position};
+use alloc::string::String;
 
 /// Semantic label of a span.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -846,6 +858,271 @@ pub enum Name {
     ///             ^
     /// ```
     DefinitionTitleString,
+    /// Directive attributes.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]{#c}
+    ///          ^^^^
+    /// ```
+    DirectiveAttributes,
+    /// Whole directive (container).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [document content][crate::construct::document]
+    /// *   **Content model**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [flow content][crate::construct::flow]
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainer,
+    /// Directive (container) fence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainer`][Name::DirectiveContainer]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`DirectiveSequence`][Name::DirectiveSequence],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainerFence,
+    /// Directive label.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     [`DirectiveLabelMarker`][Name::DirectiveLabelMarker],
+    ///     [`DirectiveLabelString`][Name::DirectiveLabelString]
+    /// *   **Construct**:
+    ///     [`directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]{#c}
+    ///       ^^^
+    /// ```
+    DirectiveLabel,
+    /// Directive label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveLabel`][Name::DirectiveLabel]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]{#c}
+    ///       ^ ^
+    /// ```
+    DirectiveLabelMarker,
+    /// Directive label string.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveLabel`][Name::DirectiveLabel]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]{#c}
+    ///        ^
+    /// ```
+    DirectiveLabelString,
+    /// Whole directive (leaf).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [flow content][crate::construct::flow]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`DirectiveSequence`][Name::DirectiveSequence],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`directive_leaf`][crate::construct::directive_leaf]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::a[b]{#c}
+    ///     ^^^^^^^^^^
+    /// ```
+    DirectiveLeaf,
+    /// Directive name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]{#c}
+    ///      ^
+    /// ```
+    DirectiveName,
+    /// Directive sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container],
+    ///     [`directive_leaf`][crate::construct::directive_leaf],
+    ///     [`directive_text`][crate::construct::directive_text]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]{#c}
+    ///     ^
+    /// ```
+    DirectiveSequence,
+    /// Whole directive (text).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`DirectiveSequence`][Name::DirectiveSequence]
+    /// *   **Construct**:
+    ///     [`directive_text`][crate::construct::directive_text]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :b[c]{#d} e
+    ///       ^^^^^^^^^
+    /// ```
+    DirectiveText,
+    /// Emoji.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`EmojiMarker`][Name::EmojiMarker],
+    ///     [`EmojiValue`][Name::EmojiValue]
+    /// *   **Construct**:
+    ///     [`emoji`][crate::construct::emoji]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :smile: b
+    ///       ^^^^^^^
+    /// ```
+    Emoji,
+    /// Emoji marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Emoji`][Name::Emoji]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`emoji`][crate::construct::emoji]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :smile: b
+    ///       ^     ^
+    /// ```
+    EmojiMarker,
+    /// Emoji value.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Emoji`][Name::Emoji]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`emoji`][crate::construct::emoji]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :smile: b
+    ///        ^^^^^
+    /// ```
+    EmojiValue,
     /// Emphasis.
     ///
     /// ## Info
@@ -901,6 +1178,93 @@ pub enum Name {
     ///      ^
     /// ```
     EmphasisText,
+    /// Whole fenced div.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [document content][crate::construct::document]
+    /// *   **Content model**:
+    ///     [`FencedDivFence`][Name::FencedDivFence],
+    ///     [flow content][crate::construct::flow]
+    /// *   **Construct**:
+    ///     [`fenced_div`][crate::construct::fenced_div]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::: {.a}
+    ///     ^^^^^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    FencedDiv,
+    /// Fenced div attributes.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`FencedDivFence`][Name::FencedDivFence]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`fenced_div`][crate::construct::fenced_div]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::: {.a}
+    ///         ^^^^
+    ///   | b
+    ///   | :::
+    /// ```
+    FencedDivAttributes,
+    /// Fenced div fence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`FencedDiv`][Name::FencedDiv]
+    /// *   **Content model**:
+    ///     [`FencedDivAttributes`][Name::FencedDivAttributes],
+    ///     [`FencedDivFenceSequence`][Name::FencedDivFenceSequence],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`fenced_div`][crate::construct::fenced_div]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::: {.a}
+    ///     ^^^^^^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    FencedDivFence,
+    /// Fenced div fence sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`FencedDivFence`][Name::FencedDivFence]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`fenced_div`][crate::construct::fenced_div]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ::: {.a}
+    ///     ^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    FencedDivFenceSequence,
     /// Whole frontmatter.
     ///
     /// ## Info
@@ -1906,15 +2270,35 @@ pub enum Name {
     ///       ^^^^^^^
     /// ```
     Image,
+    /// Whole inline footnote.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`Label`][Name::Label]
+    /// *   **Construct**:
+    ///     [`label_end`][crate::construct::label_end]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^^^
+    /// ```
+    InlineFootnote,
     /// Label.
     ///
     /// ## Info
     ///
     /// *   **Context**:
     ///     [`Image`][Name::Image],
+    ///     [`InlineFootnote`][Name::InlineFootnote],
     ///     [`Link`][Name::Link]
     /// *   **Content model**:
     ///     [`LabelImage`][Name::LabelImage],
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote],
     ///     [`LabelLink`][Name::LabelLink],
     ///     [`LabelEnd`][Name::LabelEnd],
     ///     [`LabelText`][Name::LabelText]
@@ -1989,6 +2373,43 @@ pub enum Name {
     ///       ^
     /// ```
     LabelImageMarker,
+    /// Label start (inline footnote).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Label`][Name::Label]
+    /// *   **Content model**:
+    ///     [`LabelInlineFootnoteMarker`][Name::LabelInlineFootnoteMarker],
+    ///     [`LabelMarker`][Name::LabelMarker]
+    /// *   **Construct**:
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    LabelInlineFootnote,
+    /// Label start (inline footnote) marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^
+    /// ```
+    LabelInlineFootnoteMarker,
     /// Label start (link).
     ///
     /// ## Info
@@ -2013,12 +2434,14 @@ pub enum Name {
     ///
     /// *   **Context**:
     ///     [`LabelImage`][Name::LabelImage],
+    ///     [`LabelInlineFootnote`][Name::LabelInlineFootnote],
     ///     [`LabelLink`][Name::LabelLink],
     ///     [`LabelEnd`][Name::LabelEnd]
     /// *   **Content model**:
     ///     void
     /// *   **Construct**:
     ///     [`label_start_image`][crate::construct::label_start_image],
+    ///     [`label_start_inline_footnote`][crate::construct::label_start_inline_footnote],
     ///     [`label_start_link`][crate::construct::label_start_link],
     ///     [`label_end`][crate::construct::label_end]
     ///
@@ -3262,7 +3685,7 @@ pub enum Name {
     ///                 ^
     /// ```
     ResourceTitleString,
-    /// SpaceOrTab.
+    /// Space or tab.
     ///
     /// ## Info
     ///
@@ -3375,7 +3798,7 @@ pub enum Name {
 }
 
 /// List of void events, used to make sure everything is working well.
-pub const VOID_EVENTS: [Name; 76] = [
+pub const VOID_EVENTS: [Name; 85] = [
     Name::AttentionSequence,
     Name::AutolinkEmail,
     Name::AutolinkMarker,
@@ -3399,7 +3822,15 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::DefinitionLabelMarker,
     Name::DefinitionMarker,
     Name::DefinitionTitleMarker,
+    Name::DirectiveAttributes,
+    Name::DirectiveLabelMarker,
+    Name::DirectiveName,
+    Name::DirectiveSequence,
+    Name::EmojiMarker,
+    Name::EmojiValue,
     Name::EmphasisSequence,
+    Name::FencedDivAttributes,
+    Name::FencedDivFenceSequence,
     Name::FrontmatterChunk,
     Name::GfmAutolinkLiteralEmail,
     Name::GfmAutolinkLiteralProtocol,
@@ -3422,6 +3853,7 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::HtmlFlowData,
     Name::HtmlTextData,
     Name::LabelImageMarker,
+    Name::LabelInlineFootnoteMarker,
     Name::LabelMarker,
     Name::LineEnding,
     Name::ListItemMarker,
@@ -3503,6 +3935,7 @@ pub struct Point {
 impl Point {
     /// Create a new point, that is shifted from the close earlier current
     /// point, to `index.`
+    #[must_use]
     pub fn shift_to(&self, bytes: &[u8], index: usize) -> Point {
         let mut next = self.clone();
         debug_assert!(index > next.index, "expected to shift forward");
@@ -3552,3 +3985,81 @@ pub struct Event {
     /// Link to another event.
     pub link: Option<Link>,
 }
+
+/// Get the markdown of the event at `index` in `events`.
+///
+/// The event can be an enter or an exit: the source between it and its
+/// counterpart is returned.
+/// `value` must be the document that `events` were made from.
+/// Tabs that are partially in the event are included whole.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::source, to_events, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let value = "> a";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// // The first event is the enter of the block quote.
+/// assert_eq!(source(value, &events, 0), "> a");
+/// # Ok(())
+/// # }
+/// ```
+pub fn source(value: &str, events: &[Event], index: usize) -> String {
+    let other = skip::counterpart(events, index);
+    let (start, end) = if index < other {
+        (index, other)
+    } else {
+        (other, index)
+    };
+
+    Position {
+        start: &events[start].point,
+        end: &events[end].point,
+    }
+    .to_source_string(value.as_bytes())
+}
+
+/// Get the markdown that `events` were made from.
+///
+/// The markdown of events with nothing in them (such as data, whitespace,
+/// and line endings) is joined, so the result is `value`, byte for byte,
+/// when `events` are all events of `value`.
+/// When `events` is a part of all events (such as from an enter to its
+/// exit), the markdown of that part is returned.
+/// `value` must be the document that `events` were made from.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::to_source, to_events, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let value = "> a\r\n>\tb  \n\n";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// assert_eq!(to_source(value, &events), value);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_source(value: &str, events: &[Event]) -> String {
+    let mut result = String::with_capacity(value.len());
+    // Where the markdown is taken up to: events can overlap.
+    let mut index = 0;
+
+    for pair in events.windows(2) {
+        if pair[0].kind == Kind::Enter && pair[1].kind == Kind::Exit {
+            let start = pair[0].point.index.max(index);
+            let end = pair[1].point.index;
+
+            if end > start {
+                result.push_str(&value[start..end]);
+                index = end;
+            }
+        }
+    }
+
+    result
+}
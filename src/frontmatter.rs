@@ -0,0 +1,125 @@
+//! Get frontmatter from a syntax tree.
+
+use crate::mdast::Node;
+use crate::unist::Position;
+use alloc::string::{String, ToString};
+
+/// YAML frontmatter of a document.
+///
+/// Unlike the [`Yaml`][crate::mdast::Yaml] node in mdast, this also contains
+/// the `title` field, if there is one.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_frontmatter, Constructs, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = ParseOptions {
+///     constructs: Constructs {
+///         frontmatter: true,
+///         ..Constructs::default()
+///     },
+///     ..ParseOptions::default()
+/// };
+///
+/// let frontmatter = to_frontmatter("---\ntitle: Venus\n---", &options)?.unwrap();
+///
+/// assert_eq!(frontmatter.value, "title: Venus");
+/// assert_eq!(frontmatter.title, Some("Venus".into()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frontmatter {
+    /// Content, without fences.
+    pub value: String,
+    /// Value of the `title` field.
+    pub title: Option<String>,
+    /// Where the frontmatter occurred.
+    pub position: Position,
+}
+
+/// Get YAML frontmatter in `tree`.
+pub fn collect(tree: &Node) -> Option<Frontmatter> {
+    if let Some(Node::Yaml(yaml)) = tree.children().and_then(|children| children.first()) {
+        Some(Frontmatter {
+            value: yaml.value.clone(),
+            title: title(&yaml.value),
+            position: yaml.position.clone().unwrap(),
+        })
+    } else {
+        None
+    }
+}
+
+/// Get the value of the `title` field in YAML.
+///
+/// This is not a YAML parser: it looks for a `title` key that is not
+/// indented, with a value on the same line.
+/// That value can be quoted, and can be followed by a comment.
+pub fn title(value: &str) -> Option<String> {
+    for line in value.lines() {
+        if let Some(rest) = line.strip_prefix("title:") {
+            // `title:a` is not a key.
+            if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+                continue;
+            }
+
+            let rest = rest.trim_matches([' ', '\t']);
+
+            let title = if rest.starts_with('"') {
+                quoted(rest, '"')?
+                    .replace("\\\"", "\"")
+                    .replace("\\\\", "\\")
+            } else if rest.starts_with('\'') {
+                quoted(rest, '\'')?.replace("''", "'")
+            }
+            // Block scalars (`|`, `>`) are not supported.
+            else if rest.starts_with(['|', '>']) {
+                return None;
+            } else {
+                let end = rest.find(" #").unwrap_or(rest.len());
+                rest[..end].trim_end_matches([' ', '\t']).to_string()
+            };
+
+            return if title.is_empty() { None } else { Some(title) };
+        }
+    }
+
+    None
+}
+
+/// Get what is inside `quote`s, ignoring a comment after the closing one.
+fn quoted(value: &str, quote: char) -> Option<&str> {
+    let rest = &value[1..];
+    let mut index = 0;
+    let bytes = rest.as_bytes();
+
+    while index < bytes.len() {
+        if quote == '"' && bytes[index] == b'\\' {
+            index += 2;
+            continue;
+        }
+
+        if bytes[index] == quote as u8 {
+            // Doubled single quotes are an escape.
+            if quote == '\'' && bytes.get(index + 1) == Some(&b'\'') {
+                index += 2;
+                continue;
+            }
+
+            let after = rest[index + 1..].trim_start_matches([' ', '\t']);
+
+            return if after.is_empty() || after.starts_with('#') {
+                Some(&rest[..index])
+            } else {
+                None
+            };
+        }
+
+        index += 1;
+    }
+
+    None
+}
@@ -0,0 +1,236 @@
+//! Collect headings from a syntax tree.
+
+use crate::mdast::Node;
+use crate::unist::Position;
+use alloc::{collections::BTreeSet, format, string::String, string::ToString, vec, vec::Vec};
+
+/// Heading in a document, as flat data.
+///
+/// Unlike the [`Heading`][crate::mdast::Heading] node in mdast, this does not
+/// contain children: it contains the text of the heading, and a slug that can
+/// be used as an anchor.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_headings, Options};
+/// # fn main() -> Result<(), String> {
+///
+/// let headings = to_headings("# Hello, *world*!", &Options::default())?;
+///
+/// assert_eq!(headings[0].depth, 1);
+/// assert_eq!(headings[0].text, "Hello, world!");
+/// assert_eq!(headings[0].slug, "hello-world");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Heading {
+    /// Rank (between `1` and `6`, both including).
+    pub depth: u8,
+    /// Text content, without markup.
+    pub text: String,
+    /// Unique slug of the text, such as `hello-world`.
+    pub slug: String,
+    /// Where the heading occurred.
+    pub position: Position,
+}
+
+/// How to handle non-ASCII letters and digits in slugs.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_headings, CompileOptions, Options, SlugPolicy};
+/// # fn main() -> Result<(), String> {
+///
+/// let headings = to_headings(
+///     "# Café",
+///     &Options {
+///         compile: CompileOptions {
+///           slug_policy: SlugPolicy::Transliterate,
+///           ..CompileOptions::default()
+///         },
+///         ..Options::default()
+///     }
+/// )?;
+///
+/// assert_eq!(headings[0].slug, "cafe");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum SlugPolicy {
+    /// Keep them, lowercased, like GitHub does.
+    ///
+    /// Such slugs must be percent-encoded when used in URLs.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// # Café 日本
+    /// ```
+    ///
+    /// Yields `café-日本`.
+    #[default]
+    Keep,
+    /// Turn Latin letters with diacritics (and ligatures, such as `æ` and
+    /// `ß`) into ASCII, and remove other non-ASCII characters.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// # Café 日本
+    /// ```
+    ///
+    /// Yields `cafe-`.
+    Transliterate,
+    /// Remove them.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// # Café 日本
+    /// ```
+    ///
+    /// Yields `caf-`.
+    Strip,
+}
+
+/// Collect headings in `tree`, in document order.
+pub fn collect(tree: &Node, policy: &SlugPolicy) -> Vec<Heading> {
+    let mut headings = vec![];
+    let mut slugs = BTreeSet::new();
+    visit(tree, policy, &mut headings, &mut slugs);
+    headings
+}
+
+/// Get the slug of the heading `node`, that is unique in `slugs`.
+///
+/// Headings must be passed in document order, to get the same slugs as
+/// [`collect`][].
+pub fn slug_heading(node: &Node, policy: &SlugPolicy, slugs: &mut BTreeSet<String>) -> String {
+    slug(&text(node), policy, slugs)
+}
+
+/// Collect headings in `node` and its descendants.
+fn visit(
+    node: &Node,
+    policy: &SlugPolicy,
+    headings: &mut Vec<Heading>,
+    slugs: &mut BTreeSet<String>,
+) {
+    if let Node::Heading(heading) = node {
+        let text = text(node);
+        headings.push(Heading {
+            depth: heading.depth,
+            slug: slug(&text, policy, slugs),
+            text,
+            position: heading.position.clone().unwrap(),
+        });
+    } else if let Some(children) = node.children() {
+        for child in children {
+            visit(child, policy, headings, slugs);
+        }
+    }
+}
+
+/// Get the text content of `node`.
+///
+/// Like `to_string`, but includes the alt text of images, and ignores HTML and
+/// inline footnotes.
+fn text(node: &Node) -> String {
+    match node {
+        Node::Image(x) => x.alt.clone(),
+        Node::ImageReference(x) => x.alt.clone(),
+        Node::Html(_) | Node::InlineFootnote(_) => String::new(),
+        _ => {
+            if let Some(children) = node.children() {
+                children.iter().map(text).collect()
+            } else {
+                node.to_string()
+            }
+        }
+    }
+}
+
+/// Turn `value` into a slug, like GitHub does, that is unique in `slugs`.
+///
+/// Letters are lowercased, spaces are turned into dashes, and other
+/// characters except for letters, digits, dashes, and underscores are
+/// removed.
+/// Non-ASCII letters and digits are handled according to `policy`.
+/// When the slug already exists, `-1`, `-2`, and so on are added.
+fn slug(value: &str, policy: &SlugPolicy, slugs: &mut BTreeSet<String>) -> String {
+    let mut base = String::new();
+
+    for char in value.chars() {
+        if char == ' ' {
+            base.push('-');
+        } else if char == '-' || char == '_' {
+            base.push(char);
+        } else if char.is_alphanumeric() {
+            for char in char.to_lowercase() {
+                if char.is_ascii() {
+                    base.push(char);
+                } else {
+                    match policy {
+                        SlugPolicy::Keep => base.push(char),
+                        SlugPolicy::Transliterate => {
+                            if let Some(value) = transliterate(char) {
+                                base.push_str(value);
+                            }
+                        }
+                        SlugPolicy::Strip => {}
+                    }
+                }
+            }
+        }
+    }
+
+    let mut result = base.clone();
+    let mut count = 0;
+
+    while slugs.contains(&result) {
+        count += 1;
+        result = format!("{}-{}", base, count);
+    }
+
+    slugs.insert(result.clone());
+    result
+}
+
+/// Turn a lowercase Latin letter into ASCII.
+///
+/// Supports the letters in Latin-1 Supplement and Latin Extended-A.
+fn transliterate(char: char) -> Option<&'static str> {
+    let value = match char {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ð' | 'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' | 'ĸ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+
+    Some(value)
+}
@@ -0,0 +1,602 @@
+//! Update a syntax tree after an edit, reusing what did not change.
+//!
+//! Markdown is parsed in two phases: first blocks (and definitions), then the
+//! phrasing content inside them.
+//! Whether `[a]` is a reference depends on whether a definition for `a`
+//! exists *anywhere* in the document.
+//! So, when definitions are added or removed, blocks that are far away from
+//! an edit can change too.
+//!
+//! This module parses only the blocks in a changed range, and then figures
+//! out which definitions (and GFM footnote definitions) changed.
+//! Other blocks are reused, except for those that could contain a reference
+//! to a changed definition, which are parsed again.
+//!
+//! [`ParseResult`][] finds the range to parse itself, from an edit.
+
+use crate::mdast::{AttributeContent, AttributeValue, Node, Root};
+use crate::parser::parse_with_definitions;
+use crate::to_mdast::compile;
+use crate::unist::{Point, Position};
+use crate::util::{location::Location, normalize_identifier::normalize_identifier};
+use crate::{to_mdast, ParseOptions};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::ops::Range;
+
+/// Markdown and its syntax tree, which can be edited.
+///
+/// Meant for editors, which parse a document again after each change.
+/// Each [`edit()`][ParseResult::edit] parses the blocks around the edit,
+/// reusing other blocks, and the tree is the same as the tree of
+/// [`to_mdast()`][crate::to_mdast] for the edited document.
+///
+/// Blocks before and after the edit are parsed too, as an edit can join them
+/// with the edited blocks (such as when a blank line is removed).
+/// When they turn out different (such as when a fence is opened that is not
+/// closed), more blocks are parsed, up to the whole document.
+/// When it cannot be known which blocks are affected, the whole document is
+/// parsed.
+/// When definitions change, blocks that could reference them are parsed
+/// again.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, ParseOptions, ParseResult};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = ParseOptions::default();
+/// let mut result = ParseResult::new("# a\n\nb [c]\n\nd\n", &options)?;
+///
+/// // Insert a definition after `d`:
+/// result.edit(14..14, "\n[c]: e\n")?;
+///
+/// assert_eq!(result.value(), "# a\n\nb [c]\n\nd\n\n[c]: e\n");
+/// assert_eq!(result.tree(), &to_mdast(result.value(), &options)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ParseResult<'a> {
+    /// Configuration.
+    options: &'a ParseOptions,
+    /// Markdown.
+    value: String,
+    /// Syntax tree of `value`.
+    tree: Node,
+}
+
+impl<'a> ParseResult<'a> {
+    /// Parse `value`, with configuration.
+    ///
+    /// ## Errors
+    ///
+    /// `new()` never errors with normal markdown because markdown does not
+    /// have syntax errors, so feel free to `unwrap()`.
+    /// However, MDX does have syntax errors.
+    /// When MDX is turned on, there are several errors that can occur with how
+    /// JSX, expressions, or ESM are written.
+    pub fn new(value: &str, options: &'a ParseOptions) -> Result<Self, String> {
+        Ok(Self {
+            options,
+            value: value.into(),
+            tree: to_mdast(value, options)?,
+        })
+    }
+
+    /// Replace `range` (byte offsets in the current markdown) with
+    /// `replacement`, and update the tree.
+    ///
+    /// ## Errors
+    ///
+    /// `edit()` errors when `range` is not in the markdown, or does not start
+    /// and end at character boundaries.
+    /// With MDX, it also errors like [`new()`][ParseResult::new], in which case
+    /// nothing is changed.
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> Result<(), String> {
+        if range.start > range.end
+            || !self.value.is_char_boundary(range.start)
+            || !self.value.is_char_boundary(range.end)
+        {
+            return Err(format!(
+                "Cannot edit `{}..{}`, expected a range in the document ({} bytes), at character boundaries",
+                range.start,
+                range.end,
+                self.value.len()
+            ));
+        }
+
+        let mut value =
+            String::with_capacity(self.value.len() - (range.end - range.start) + replacement.len());
+        value.push_str(&self.value[..range.start]);
+        value.push_str(replacement);
+        value.push_str(&self.value[range.end..]);
+
+        let tree = match reparse(
+            &self.value,
+            &value,
+            &self.tree,
+            &range,
+            replacement.len(),
+            self.options,
+        ) {
+            Some(tree) => tree,
+            None => to_mdast(&value, self.options)?,
+        };
+
+        self.value = value;
+        self.tree = tree;
+        Ok(())
+    }
+
+    /// Get the markdown.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Get the syntax tree.
+    #[must_use]
+    pub fn tree(&self) -> &Node {
+        &self.tree
+    }
+}
+
+/// Identifiers of definitions.
+#[derive(Debug, Default)]
+struct Definitions {
+    /// Identifiers of definitions.
+    definitions: Vec<String>,
+    /// Identifiers of GFM footnote definitions.
+    gfm_footnote_definitions: Vec<String>,
+}
+
+/// Turn `value` into a syntax tree, reusing `previous`.
+///
+/// `range` is where `value` changed.
+/// It must start and end at line boundaries.
+/// `previous` is the tree of the document before the edit.
+///
+/// Returns `None` when the tree cannot be reused, or when an error occurs, in
+/// which case the whole document must be parsed.
+pub fn update(
+    value: &str,
+    previous: &Node,
+    range: &Range<usize>,
+    options: &ParseOptions,
+) -> Option<Node> {
+    let bytes = value.as_bytes();
+
+    if range.start > range.end
+        || range.end > bytes.len()
+        || !at_line_start(bytes, range.start)
+        || !at_line_start(bytes, range.end)
+    {
+        return None;
+    }
+
+    let children = if let Node::Root(root) = previous {
+        &root.children
+    } else {
+        return None;
+    };
+    let previous_end = &previous.position()?.end;
+    // Where the range ends in the previous document.
+    let previous_range_end = (range.end + previous_end.offset).checked_sub(bytes.len())?;
+
+    if previous_range_end < range.start {
+        return None;
+    }
+
+    let location = Location::new(bytes);
+    let end = location.to_point(bytes.len())?;
+    let start = location.to_point(range.start)?;
+    let mut head = vec![];
+    let mut tail = vec![];
+    let mut removed = Definitions::default();
+
+    for child in children {
+        let position = child.position()?;
+
+        if position.end.offset <= range.start {
+            head.push(child.clone());
+        } else if position.start.offset >= previous_range_end {
+            let mut child = child.clone();
+            shift(
+                &mut child,
+                (previous_end.line, previous_end.offset),
+                (end.line, end.offset),
+            );
+            tail.push(child);
+        } else if position.start.offset >= range.start && position.end.offset <= previous_range_end
+        {
+            collect(child, &mut removed);
+        } else {
+            // Block crosses the range.
+            return None;
+        }
+    }
+
+    let mut kept = Definitions::default();
+    for child in head.iter().chain(tail.iter()) {
+        collect(child, &mut kept);
+    }
+
+    // Parse the changed blocks, up to the next block.
+    let middle_end = if let Some(child) = tail.first() {
+        line_start(bytes, child.position()?.start.offset)
+    } else {
+        bytes.len()
+    };
+    let middle_root = parse(
+        value,
+        &(range.start..middle_end),
+        start.line,
+        &kept,
+        options,
+    )?;
+    // The last line is not parsed when blocks follow, so it did not change.
+    // Otherwise, the end comes from the parsed blocks, as columns there
+    // account for tabs.
+    let root_end = if tail.is_empty() {
+        middle_root.position?.end
+    } else {
+        Point::new(end.line, previous_end.column, end.offset)
+    };
+    let mut middle = middle_root.children;
+
+    let mut added = Definitions::default();
+    for child in &middle {
+        collect(child, &mut added);
+    }
+
+    let changed = changed(&kept, &removed, &added);
+    let mut all = kept;
+    all.definitions.append(&mut added.definitions);
+    all.gfm_footnote_definitions
+        .append(&mut added.gfm_footnote_definitions);
+
+    let middle_range = head.len()..(head.len() + middle.len());
+    let mut children = head;
+    children.append(&mut middle);
+    children.append(&mut tail);
+
+    // Parse unchanged blocks again if they could reference changed definitions.
+    if !changed.is_empty() {
+        let mut index = 0;
+
+        while index < children.len() {
+            if !middle_range.contains(&index) {
+                let block_start = line_start(bytes, children[index].position()?.start.offset);
+                // Include what follows up to the next block, as that affects
+                // where, for example, lists end.
+                let block_end = if index + 1 < children.len() {
+                    line_start(bytes, children[index + 1].position()?.start.offset)
+                } else {
+                    bytes.len()
+                };
+                let block = block_start..block_end;
+
+                if could_reference(&value[block.clone()], &changed) {
+                    let line = location.to_point(block_start)?.line;
+                    let mut nodes = parse(value, &block, line, &all, options)?.children;
+
+                    if nodes.len() != 1 {
+                        return None;
+                    }
+
+                    children[index] = nodes.pop().unwrap();
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    Some(Node::Root(Root {
+        children,
+        position: Some(Position {
+            start: Point::new(1, 1, 0),
+            end: root_end,
+        }),
+    }))
+}
+
+/// Number of blocks before and after an edit that are parsed too.
+///
+/// Parsing a block can leave state behind that affects the next block, such
+/// as whether a paragraph can be interrupted, while that block itself looks
+/// the same.
+/// Two blocks make sure such a change shows up.
+const CONTEXT: usize = 2;
+
+/// Turn `after` into a syntax tree, reusing `previous`, the tree of `before`,
+/// where `range` in `before` was replaced by `inserted` bytes.
+///
+/// The blocks of `previous` around `range` are parsed, with
+/// [`CONTEXT`][] blocks before and after it.
+/// Those must come out the same, which shows that what comes before the edit
+/// did not change how it is parsed, and that the edit did not change what
+/// comes after it.
+/// If some of them changed, more blocks on that side are parsed, up to the
+/// start or end of the document.
+///
+/// Returns `None` when the tree cannot be reused.
+fn reparse(
+    before: &str,
+    after: &str,
+    previous: &Node,
+    range: &Range<usize>,
+    inserted: usize,
+    options: &ParseOptions,
+) -> Option<Node> {
+    let children = if let Node::Root(root) = previous {
+        &root.children
+    } else {
+        return None;
+    };
+
+    if children.is_empty() {
+        return None;
+    }
+
+    let old_bytes = before.as_bytes();
+    let new_bytes = after.as_bytes();
+    let shift_offset = |offset: usize| offset + inserted - (range.end - range.start);
+
+    // The first block that ends at or after the edit.
+    let mut edited = 0;
+    while edited < children.len() && children[edited].position()?.end.offset < range.start {
+        edited += 1;
+    }
+    let edited = edited.min(children.len() - 1);
+
+    // The last block that starts at or before the edit.
+    let mut last = edited;
+    while last + 1 < children.len() && children[last + 1].position()?.start.offset <= range.end {
+        last += 1;
+    }
+
+    // The first parsed block, and the first block after `last` that is not.
+    let mut first = edited.saturating_sub(CONTEXT);
+    let mut next = last + 1 + CONTEXT;
+    // How many blocks to add to a side that changed.
+    let mut grow = 1;
+
+    loop {
+        let start = line_start(
+            old_bytes,
+            children[first].position()?.start.offset.min(range.start),
+        );
+        let end = if let Some(child) = children.get(next) {
+            line_start(new_bytes, shift_offset(child.position()?.start.offset))
+        } else {
+            new_bytes.len()
+        };
+
+        let tree = update(after, previous, &(start..end), options)?;
+        let new_children = tree.children()?;
+
+        // Check that the blocks before the edit did not change.
+        // Nothing comes before the first block.
+        let same_before = first == 0
+            || (first..edited).all(|index| new_children.get(index) == Some(&children[index]));
+
+        // Check that the blocks after the edit did not change.
+        // Nothing comes after the last block.
+        let mut same_after = true;
+        if next < children.len() {
+            for (index, child) in children.iter().enumerate().take(next).skip(last + 1) {
+                let tail = children.len() - index;
+                let new_index = new_children.len().checked_sub(tail)?;
+                let old_start = &child.position()?.start;
+                let new_offset = line_start(new_bytes, shift_offset(old_start.offset));
+                let new_line = Location::new(new_bytes).to_point(new_offset)?.line;
+                let mut expected = child.clone();
+                shift(
+                    &mut expected,
+                    (old_start.line, line_start(old_bytes, old_start.offset)),
+                    (new_line, new_offset),
+                );
+
+                if new_children[new_index] != expected {
+                    same_after = false;
+                    break;
+                }
+            }
+        }
+
+        if same_before && same_after {
+            return Some(tree);
+        }
+
+        if !same_before {
+            first = first.saturating_sub(grow);
+        }
+
+        if !same_after {
+            next += grow;
+        }
+
+        grow *= 2;
+    }
+}
+
+/// Parse the blocks in `range`, with `definitions` known to exist.
+///
+/// `line` is the line where `range` starts.
+/// The root that is returned is positioned in `value`.
+/// Returns `None` when the blocks cannot be parsed on their own, or when an
+/// error occurs (positional info in it would be relative to `range`).
+fn parse(
+    value: &str,
+    range: &Range<usize>,
+    line: usize,
+    definitions: &Definitions,
+    options: &ParseOptions,
+) -> Option<Root> {
+    let slice = &value[range.clone()];
+
+    // Frontmatter can only occur at the start of the document.
+    if options.constructs.frontmatter
+        && range.start != 0
+        && (slice.starts_with("---") || slice.starts_with("+++"))
+    {
+        return None;
+    }
+
+    let (events, parse_state) = parse_with_definitions(
+        slice,
+        options,
+        definitions
+            .definitions
+            .iter()
+            .map(|d| normalize_identifier(d))
+            .collect(),
+        definitions
+            .gfm_footnote_definitions
+            .iter()
+            .map(|d| normalize_identifier(d))
+            .collect(),
+    )
+    .ok()?;
+    let mut node = compile(&events, parse_state.bytes, options).ok()?;
+    shift(&mut node, (1, 0), (line, range.start));
+
+    if let Node::Root(root) = node {
+        Some(root)
+    } else {
+        unreachable!("expected root")
+    }
+}
+
+/// Collect the identifiers of definitions in `node`.
+fn collect(node: &Node, definitions: &mut Definitions) {
+    match node {
+        Node::Definition(x) => definitions.definitions.push(x.identifier.clone()),
+        Node::FootnoteDefinition(x) => definitions
+            .gfm_footnote_definitions
+            .push(x.identifier.clone()),
+        _ => {}
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            collect(child, definitions);
+        }
+    }
+}
+
+/// Get identifiers that were defined before but not after, or vice versa.
+fn changed(kept: &Definitions, removed: &Definitions, added: &Definitions) -> Vec<String> {
+    let mut result = vec![];
+    let groups = [
+        (&kept.definitions, &removed.definitions, &added.definitions),
+        (
+            &kept.gfm_footnote_definitions,
+            &removed.gfm_footnote_definitions,
+            &added.gfm_footnote_definitions,
+        ),
+    ];
+
+    for (kept, removed, added) in groups {
+        for identifier in removed.iter().chain(added.iter()) {
+            if !kept.contains(identifier)
+                && removed.contains(identifier) != added.contains(identifier)
+                && !result.contains(identifier)
+            {
+                result.push(identifier.clone());
+            }
+        }
+    }
+
+    result
+}
+
+/// Check whether `value` could contain a reference to one of `identifiers`.
+///
+/// Labels are normalized the same way, so the normalized identifier of a
+/// reference is in the normalized value.
+/// Whitespace is ignored, which is a bit more lenient, but makes this work
+/// regardless of where whitespace is collapsed.
+fn could_reference(value: &str, identifiers: &[String]) -> bool {
+    if !value.contains('[') {
+        return false;
+    }
+
+    let value = strip_whitespace(&normalize_identifier(value).to_lowercase());
+    identifiers
+        .iter()
+        .any(|identifier| value.contains(&strip_whitespace(identifier)))
+}
+
+/// Remove whitespace from `value`.
+fn strip_whitespace(value: &str) -> String {
+    value.chars().filter(|char| !char.is_whitespace()).collect()
+}
+
+/// Move positional info in `node` (and its descendants) from `from` to `to`,
+/// both `(line, offset)` pairs.
+///
+/// Columns are not changed, so both must be at the start of a line.
+pub fn shift(node: &mut Node, from: (usize, usize), to: (usize, usize)) {
+    if let Some(position) = node.position_mut() {
+        position.start.line = position.start.line + to.0 - from.0;
+        position.start.offset = position.start.offset + to.1 - from.1;
+        position.end.line = position.end.line + to.0 - from.0;
+        position.end.offset = position.end.offset + to.1 - from.1;
+    }
+
+    match node {
+        Node::MdxjsEsm(x) => shift_stops(&mut x.stops, from.1, to.1),
+        Node::MdxFlowExpression(x) => shift_stops(&mut x.stops, from.1, to.1),
+        Node::MdxTextExpression(x) => shift_stops(&mut x.stops, from.1, to.1),
+        Node::MdxJsxFlowElement(x) => shift_attributes(&mut x.attributes, from.1, to.1),
+        Node::MdxJsxTextElement(x) => shift_attributes(&mut x.attributes, from.1, to.1),
+        _ => {}
+    }
+
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            shift(child, from, to);
+        }
+    }
+}
+
+/// Move stops in expressions in JSX attributes from `from` to `to`.
+fn shift_attributes(attributes: &mut [AttributeContent], from: usize, to: usize) {
+    for attribute in attributes {
+        match attribute {
+            AttributeContent::Expression { stops, .. } => shift_stops(stops, from, to),
+            AttributeContent::Property(property) => {
+                if let Some(AttributeValue::Expression(expression)) = &mut property.value {
+                    shift_stops(&mut expression.stops, from, to);
+                }
+            }
+        }
+    }
+}
+
+/// Move the absolute offsets of `stops` from `from` to `to`.
+fn shift_stops(stops: &mut [(usize, usize)], from: usize, to: usize) {
+    for stop in stops {
+        stop.1 = stop.1 + to - from;
+    }
+}
+
+/// Check if `index` is at the start of a line (or the end of `bytes`).
+fn at_line_start(bytes: &[u8], index: usize) -> bool {
+    index == 0
+        || index == bytes.len()
+        || bytes[index - 1] == b'\n'
+        || (bytes[index - 1] == b'\r' && bytes[index] != b'\n')
+}
+
+/// Get the start of the line that `index` is on.
+fn line_start(bytes: &[u8], mut index: usize) -> usize {
+    while index > 0 && !matches!(bytes[index - 1], b'\n' | b'\r') {
+        index -= 1;
+    }
+
+    index
+}
@@ -11,6 +11,38 @@
 //!     constructs (GFM, MDX, and the like)
 //! *   [`to_mdast()`][]
 //!     — turn markdown into a syntax tree
+//! *   [`to_mdast_blocks()`][]
+//!     — like `to_mdast` but only parses block structure, which is faster
+//! *   [`to_mdast_text_at()`][]
+//!     — like `to_mdast` but parses phrasing content that is embedded at a
+//!     known place in some bigger document
+//! *   [`to_mdast_incremental()`][]
+//!     — like `to_mdast` but reuses the tree from before an edit
+//! *   [`ParseResult`][]
+//!     — keep a syntax tree up to date while markdown is edited
+//! *   [`to_events()`][]
+//!     — turn markdown into the flat list of events that everything else
+//!     is made from
+//! *   [`to_diagnostics()`][]
+//!     — find things in markdown that are likely mistakes
+//! *   [`to_definitions()`][]
+//!     — get the definitions in markdown, with their URLs and titles
+//! *   [`to_headings()`][]
+//!     — get the headings in markdown, with their text and slugs
+//! *   [`to_frontmatter()`][]
+//!     — get the YAML frontmatter in markdown, with its title
+//! *   [`to_plaintext()`][]
+//!     — get the text in markdown, without markup
+//! *   [`to_markdown()`][]
+//!     — turn a syntax tree back into markdown
+//! *   [`to_html_batch()`][]
+//!     — like `to_html_with_options` but for many documents at once
+//! *   [`Parser`][]
+//!     — like `to_html_with_options` but takes markdown in chunks
+//! *   [`Compiler`][]
+//!     — like `to_html_with_options` but reuses memory between documents
+//! *   [`is_multiblock()`][]
+//!     — cheaply check whether markdown is more than a single paragraph
 //!
 //! ## Features
 //!
@@ -21,6 +53,9 @@
 //! *   **`log`**
 //!     — enable logging (includes `dep:log`);
 //!     you can show logs with `RUST_LOG=debug`
+//! *   **`parallel`**
+//!     — turn documents into HTML in parallel in [`to_html_batch()`][]
+//!     (includes `dep:rayon`)
 
 #![no_std]
 #![deny(clippy::pedantic)]
@@ -33,18 +68,27 @@
 )]
 
 extern crate alloc;
+mod compiler;
 mod configuration;
 mod construct;
-mod event;
+mod definitions;
+mod diagnostic;
+mod frontmatter;
+mod headings;
+mod incremental;
 mod parser;
+mod plaintext;
 mod resolve;
 mod state;
+mod stream;
 mod subtokenize;
 mod to_html;
+mod to_markdown;
 mod to_mdast;
 mod tokenizer;
 mod util;
 
+pub mod event;
 pub mod mdast; // To do: externalize?
 pub mod unist; // To do: externalize.
 
@@ -57,16 +101,32 @@ pub use util::sanitize_uri::sanitize;
 #[doc(hidden)]
 pub use util::location::Location;
 
-pub use util::line_ending::LineEnding;
+pub use util::encode::{escape_html, ControlCharacterPolicy};
+
+pub use util::line_ending::{detect_line_ending, LineEnding};
+
+pub use util::multiblock::is_multiblock;
 
 pub use util::mdx::{
     EsmParse as MdxEsmParse, ExpressionKind as MdxExpressionKind,
     ExpressionParse as MdxExpressionParse, Signal as MdxSignal,
 };
 
-pub use configuration::{CompileOptions, Constructs, Options, ParseOptions};
+pub use compiler::Compiler;
+pub use configuration::{
+    CompileOptions, Constructs, ElementAttributes, ElementKind, MarkdownOptions, MathRenderer,
+    Options, ParseOptions, TextTransform, UrlKind, UrlRewriter,
+};
+pub use definitions::Definition;
+pub use diagnostic::Diagnostic;
+pub use frontmatter::Frontmatter;
+pub use headings::{Heading, SlugPolicy};
+pub use incremental::ParseResult;
+pub use stream::Parser;
 
-use alloc::string::String;
+use alloc::{string::String, vec, vec::Vec};
+use core::ops::Range;
+use event::Event;
 
 /// Turn markdown into HTML.
 ///
@@ -122,11 +182,86 @@ pub fn to_html(value: &str) -> String {
 /// ```
 pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, String> {
     let (events, parse_state) = parser::parse(value, &options.parse)?;
-    Ok(to_html::compile(
-        &events,
-        parse_state.bytes,
-        &options.compile,
-    ))
+    Ok(to_html::compile(&events, parse_state.bytes, options))
+}
+
+/// Turn several markdown documents into HTML, with configuration.
+///
+/// Each document is independent: definitions in one document do not affect
+/// another.
+/// The result is the same as calling [`to_html_with_options()`][] for each
+/// document, in order.
+///
+/// With the `parallel` feature, documents are compiled in parallel, so
+/// functions in [`CompileOptions`][] can be called from several threads at
+/// once.
+/// That does not work with the MDX parse functions (which cannot be shared
+/// between threads), in which case documents are compiled one after another.
+///
+/// ## Errors
+///
+/// `to_html_batch()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// When MDX is turned on, the first error that occurs in a document is
+/// returned.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_batch, Options};
+/// # fn main() -> Result<(), String> {
+///
+/// let result = to_html_batch(&["# a", "*b*"], &Options::default())?;
+///
+/// assert_eq!(result, vec!["<h1>a</h1>", "<p><em>b</em></p>"]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_batch(values: &[&str], options: &Options) -> Result<Vec<String>, String> {
+    #[cfg(feature = "parallel")]
+    {
+        if options.parse.mdx_esm_parse.is_none() && options.parse.mdx_expression_parse.is_none() {
+            use rayon::prelude::*;
+
+            // Parse options with functions cannot be shared between threads,
+            // so each thread makes its own, without them.
+            // Compile options can be shared, functions included.
+            let parse = options.parse.clone_without_mdx_parse();
+            let compile = &options.compile;
+
+            let results: Vec<Result<String, String>> = values
+                .par_iter()
+                .map_init(
+                    || {
+                        let options = Options {
+                            parse: parse(),
+                            compile: compile.clone(),
+                        };
+                        (options, vec![])
+                    },
+                    |(options, events), value| to_html_reuse(value, options, events),
+                )
+                .collect();
+            return results.into_iter().collect();
+        }
+    }
+
+    let mut events = vec![];
+    values
+        .iter()
+        .map(|value| to_html_reuse(value, options, &mut events))
+        .collect()
+}
+
+/// Turn markdown into HTML, with configuration, reusing the memory of
+/// `events` from a previous document.
+fn to_html_reuse(
+    value: &str,
+    options: &Options,
+    events: &mut Vec<Event>,
+) -> Result<String, String> {
+    let parse_state = parser::parse_into(value, &options.parse, events)?;
+    Ok(to_html::compile(events, parse_state.bytes, options))
 }
 
 /// Turn markdown into a syntax tree.
@@ -154,6 +289,520 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, St
 /// ```
 pub fn to_mdast(value: &str, options: &ParseOptions) -> Result<mdast::Node, String> {
     let (events, parse_state) = parser::parse(value, options)?;
-    let node = to_mdast::compile(&events, parse_state.bytes)?;
+    let node = to_mdast::compile(&events, parse_state.bytes, options)?;
+    Ok(node)
+}
+
+/// Turn markdown into a syntax tree of blocks, skipping phrasing content.
+///
+/// Like [`to_mdast()`][], but text in paragraphs, headings, table cells, and
+/// the like is not parsed further: emphasis, links, code (text), character
+/// references, and so on, are all left as literal text.
+/// This is much faster on large documents, for example when only building an
+/// outline.
+///
+/// ## Errors
+///
+/// `to_mdast_blocks()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_mdast_blocks, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let tree = to_mdast_blocks("# Hey, *you*!", &ParseOptions::default())?;
+///
+/// if let Node::Root(root) = tree {
+///     assert_eq!(root.children[0].to_string(), "Hey, *you*!");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_blocks(value: &str, options: &ParseOptions) -> Result<mdast::Node, String> {
+    let (events, parse_state) = parser::parse_blocks(value, options)?;
+    let node = to_mdast::compile(&events, parse_state.bytes, options)?;
+    Ok(node)
+}
+
+/// Turn markdown into a syntax tree of phrasing content, starting at `point`.
+///
+/// Like [`to_mdast()`][], but `value` is parsed as the inside of a paragraph
+/// (emphasis, links, code (text), and such), and positional info starts at
+/// `point` instead of at the start of a document.
+/// This is useful for embedded content whose place in a bigger document is
+/// known, such as a table cell or an attribute value.
+/// The column of `point` also affects how tabs are expanded.
+///
+/// The returned root contains the phrasing nodes.
+/// There are no definitions, so references are not resolved.
+///
+/// ## Errors
+///
+/// `to_mdast_text_at()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX or expressions are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_mdast_text_at, unist::Point, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let tree = to_mdast_text_at("*a*", &Point::new(3, 5, 20), &ParseOptions::default())?;
+///
+/// if let Node::Root(root) = tree {
+///     assert_eq!(
+///         root.children[0].position().unwrap().start,
+///         Point::new(3, 5, 20)
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_text_at(
+    value: &str,
+    point: &unist::Point,
+    options: &ParseOptions,
+) -> Result<mdast::Node, String> {
+    let start = event::Point {
+        line: point.line,
+        column: point.column,
+        index: 0,
+        vs: 0,
+    };
+    let (mut events, parse_state) = parser::tokenize_text_at(value, &start, options)?;
+    let end = events
+        .last()
+        .map_or(start.clone(), |event| event.point.clone());
+
+    // Phrasing content is compiled in a paragraph, which is then unwrapped.
+    events.insert(
+        0,
+        event::Event {
+            kind: event::Kind::Enter,
+            name: event::Name::Paragraph,
+            point: start,
+            link: None,
+        },
+    );
+    events.push(event::Event {
+        kind: event::Kind::Exit,
+        name: event::Name::Paragraph,
+        point: end,
+        link: None,
+    });
+
+    let mut node = to_mdast::compile(&events, parse_state.bytes, options)?;
+    incremental::shift(&mut node, (1, 0), (1, point.offset));
+
+    if let mdast::Node::Root(root) = &mut node {
+        if let Some(mdast::Node::Paragraph(paragraph)) = root.children.pop() {
+            root.children = paragraph.children;
+        }
+    }
+
     Ok(node)
 }
+
+/// Turn markdown into events.
+///
+/// Events are what the parser produces, and what HTML and mdast are
+/// compiled from.
+/// Each construct is an [`Enter`][event::Kind::Enter] and a matching
+/// [`Exit`][event::Kind::Exit], with everything inside it in between.
+/// Content in content (such as emphasis in a paragraph) is already expanded
+/// in place.
+/// Use [`event::source()`][] to get the markdown of a construct, and
+/// [`event::to_source()`][] to get the markdown of all events.
+///
+/// > 👉 **Note**: events are more detailed than mdast and closer to how the
+/// > parser works, so they change more often: see [`event`][] for what is
+/// > (not) stable.
+///
+/// ## Errors
+///
+/// `to_events()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::{source, Kind, Name}, to_events, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let value = "# Hey, *you*!";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// let index = events
+///     .iter()
+///     .position(|d| d.kind == Kind::Exit && d.name == Name::Emphasis)
+///     .unwrap();
+///
+/// assert_eq!(source(value, &events, index), "*you*");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_events(value: &str, options: &ParseOptions) -> Result<Vec<event::Event>, String> {
+    let (events, _) = parser::parse(value, options)?;
+    Ok(events)
+}
+
+/// Turn markdown into events, reusing the memory of `events`.
+///
+/// `events` is cleared first, and then filled with the events of `value`,
+/// the same as what [`to_events()`][] returns.
+/// Useful when parsing many documents one after another, such as on a
+/// server, as the list keeps its capacity between documents.
+///
+/// ## Errors
+///
+/// `parse_into()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::Name, parse_into, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = ParseOptions::default();
+/// let mut events = vec![];
+///
+/// parse_into("# a", &options, &mut events)?;
+/// assert_eq!(events[0].name, Name::HeadingAtx);
+///
+/// parse_into("*b*", &options, &mut events)?;
+/// assert_eq!(events[0].name, Name::Paragraph);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_into(
+    value: &str,
+    options: &ParseOptions,
+    events: &mut Vec<Event>,
+) -> Result<(), String> {
+    parser::parse_into(value, options, events)?;
+    Ok(())
+}
+
+/// Turn markdown into a syntax tree, reusing the tree from before an edit.
+///
+/// Meant for editors, which parse a document again after each change.
+/// `previous` is the tree (from [`to_mdast()`][] or this function) of the
+/// document before the edit, and `range` is where `value` changed, which
+/// must start and end at line boundaries.
+/// Blocks in `range` are parsed; other blocks are reused as is, with their
+/// positional info moved.
+///
+/// Whether `[a]` is a reference depends on definitions anywhere in the
+/// document.
+/// So, when definitions (or GFM footnote definitions) in `range` are added or
+/// removed, blocks outside it that could reference them are parsed again.
+///
+/// The caller must make sure that the edit does not change how blocks outside
+/// `range` are parsed, for example by extending `range` to include an
+/// enclosing list or block quote.
+/// When `range` cuts through a block of `previous`, everything is parsed.
+///
+/// ## Errors
+///
+/// `to_mdast_incremental()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast, to_mdast_incremental, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = ParseOptions::default();
+/// let tree = to_mdast("[a]\n\nb\n", &options)?;
+///
+/// // Add a definition for `a` in a new third block:
+/// let value = "[a]\n\nb\n\n[a]: c\n";
+/// let tree = to_mdast_incremental(value, &tree, 6..value.len(), &options)?;
+///
+/// // The reference in the first block is resolved:
+/// assert_eq!(tree, to_mdast(value, &options)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_incremental(
+    value: &str,
+    previous: &mdast::Node,
+    range: Range<usize>,
+    options: &ParseOptions,
+) -> Result<mdast::Node, String> {
+    match incremental::update(value, previous, &range, options) {
+        Some(node) => Ok(node),
+        None => to_mdast(value, options),
+    }
+}
+
+/// Find things in markdown that are likely mistakes.
+///
+/// Markdown does not have syntax errors, but some things are probably not
+/// what the author meant.
+/// Currently, the following is reported:
+///
+/// *   `unmatched-attention`
+///     — sequences of `*`, `_`, or `~` that could open or close emphasis,
+///     strong, or strikethrough, but are left unmatched (such as a stray
+///     `**`, or the extra `*` in `**a*`), and are shown as is
+///
+/// When [`accessibility_lints`][ParseOptions::accessibility_lints] is on, the
+/// following is reported too:
+///
+/// *   `missing-image-alt`
+///     — images without alt text (such as `![](cat.png)`)
+/// *   `non-descriptive-link-text`
+///     — links without text, or with text that does not describe where they
+///     go (such as `[click here](a.html)`)
+///
+/// Diagnostics are sorted by where they start.
+///
+/// ## Errors
+///
+/// `to_diagnostics()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_diagnostics, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let diagnostics = to_diagnostics("a **b* c", &ParseOptions::default())?;
+///
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].rule_id, "unmatched-attention");
+/// assert_eq!(diagnostics[0].position.start.column, 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_diagnostics(value: &str, options: &ParseOptions) -> Result<Vec<Diagnostic>, String> {
+    let (events, mut parse_state) = parser::parse_with_diagnostics(value, options)?;
+
+    if options.accessibility_lints {
+        parse_state
+            .diagnostics
+            .append(&mut util::accessibility::lint(&events, parse_state.bytes));
+    }
+
+    parse_state
+        .diagnostics
+        .sort_by_key(|d| d.position.start.offset);
+    Ok(parse_state.diagnostics)
+}
+
+/// Get the headings in markdown.
+///
+/// Headings are returned in document order, including headings in block
+/// quotes and list items.
+/// Each [`Heading`][] has its rank (`depth`), its text without markup, a slug
+/// that is unique in the document, and where it occurred.
+///
+/// Slugs are made like GitHub makes them: letters are lowercased, spaces are
+/// turned into dashes, and other characters except for letters, digits,
+/// dashes, and underscores are removed.
+/// When a slug is already used, `-1`, `-2`, and so on are added.
+/// Pass [`slug_policy`][CompileOptions::slug_policy] to configure what
+/// happens to non-ASCII letters and digits.
+///
+/// ## Errors
+///
+/// `to_headings()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_headings, Options};
+/// # fn main() -> Result<(), String> {
+///
+/// let headings = to_headings("# a\n\nb\n-\n\n# a", &Options::default())?;
+///
+/// assert_eq!(
+///     headings.iter().map(|d| (d.depth, d.slug.as_str())).collect::<Vec<_>>(),
+///     vec![(1, "a"), (2, "b"), (1, "a-1")]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_headings(value: &str, options: &Options) -> Result<Vec<Heading>, String> {
+    Ok(headings::collect(
+        &to_mdast(value, &options.parse)?,
+        &options.compile.slug_policy,
+    ))
+}
+
+/// Get the definitions in markdown.
+///
+/// Returns a [`Definition`][] for each definition (such as `[a]: b "c"`), in
+/// document order, including those in block quotes and lists.
+/// When several definitions have the same identifier, the first one is used
+/// by references, and the others are marked as
+/// [`shadowed`][Definition::shadowed], which is useful to report them.
+///
+/// ## Errors
+///
+/// `to_definitions()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_definitions, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let definitions = to_definitions("[a]: b\n[A]: c", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     definitions.iter().map(|d| (d.url.as_str(), d.shadowed)).collect::<Vec<_>>(),
+///     vec![("b", false), ("c", true)]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_definitions(value: &str, options: &ParseOptions) -> Result<Vec<Definition>, String> {
+    Ok(definitions::collect(&to_mdast(value, options)?))
+}
+
+/// Turn markdown into plain text.
+///
+/// Only text is kept: markup, URLs, and raw HTML (whatever
+/// [`allow_dangerous_html`][CompileOptions::allow_dangerous_html] is) are
+/// dropped, character escapes and character references are decoded, code is
+/// kept as is, and images are replaced by their alt text.
+/// Blocks (such as paragraphs, headings, and list items) are separated by a
+/// line ending, and table cells by a tab.
+/// Footnotes, frontmatter, and MDX expressions and ESM are dropped.
+///
+/// This is useful for search indexes, or for descriptions in `<meta>` tags.
+///
+/// ## Errors
+///
+/// `to_plaintext()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_plaintext, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// assert_eq!(
+///     to_plaintext("# Hi, *Saturn*!\n\n* [a](b)\n* ![c](d) &amp; `e`", &ParseOptions::default())?,
+///     "Hi, Saturn!\na\nc & e"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_plaintext(value: &str, options: &ParseOptions) -> Result<String, String> {
+    Ok(plaintext::compile(&to_mdast(value, options)?))
+}
+
+/// Turn a syntax tree into markdown.
+///
+/// The result is canonical markdown: headings are ATX (`# a`), unless they
+/// contain line endings, code is fenced, thematic breaks are `***`, and
+/// references keep their kind (full, collapsed, or shortcut).
+/// Which markers are used for bullets, emphasis, and fences can be configured
+/// with [`MarkdownOptions`][].
+/// Text is escaped where it would otherwise be seen as markup, so turning the
+/// result into a syntax tree again gives the same tree (positions aside).
+///
+/// ## Errors
+///
+/// `to_markdown()` errors when markers in `options` are not valid, or when
+/// nodes occur where they cannot be serialized, such as a `ListItem` outside
+/// of a `List`, or flow content in phrasing content.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_markdown, to_mdast, MarkdownOptions, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let tree = to_mdast("Hello\n=====\n\n+ *a*\n+ [b][]\n\n[b]: c", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     to_markdown(&tree, &MarkdownOptions::default())?,
+///     "# Hello\n\n- *a*\n- [b][]\n\n[b]: c\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_markdown(tree: &mdast::Node, options: &MarkdownOptions) -> Result<String, String> {
+    to_markdown::compile(tree, options)
+}
+
+/// Get the YAML frontmatter in markdown.
+///
+/// Returns [`Frontmatter`][] with the content of the frontmatter, and the
+/// value of its `title` field, if there is one.
+/// Returns `None` if there is no YAML frontmatter.
+///
+/// > 👉 **Note**: frontmatter is not on by default, turn it on with
+/// > [`frontmatter`][Constructs::frontmatter] in [`Constructs`][].
+///
+/// ## Errors
+///
+/// `to_frontmatter()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_frontmatter, Constructs, ParseOptions};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = ParseOptions {
+///     constructs: Constructs {
+///         frontmatter: true,
+///         ..Constructs::default()
+///     },
+///     ..ParseOptions::default()
+/// };
+///
+/// let frontmatter = to_frontmatter("---\ntitle: \"Hello, world!\"\n---\n\na", &options)?;
+///
+/// assert_eq!(frontmatter.unwrap().title, Some("Hello, world!".into()));
+/// assert_eq!(to_frontmatter("a", &options)?, None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_frontmatter(value: &str, options: &ParseOptions) -> Result<Option<Frontmatter>, String> {
+    Ok(frontmatter::collect(&to_mdast(value, options)?))
+}
@@ -98,6 +98,10 @@ pub enum Node {
     BlockQuote(BlockQuote),
     /// Footnote definition.
     FootnoteDefinition(FootnoteDefinition),
+    /// Fenced div.
+    FencedDiv(FencedDiv),
+    /// Directive (container).
+    ContainerDirective(ContainerDirective),
     /// MDX: JSX element (container).
     MdxJsxFlowElement(MdxJsxFlowElement),
     /// List.
@@ -126,6 +130,10 @@ pub enum Node {
     MdxTextExpression(MdxTextExpression),
     /// Footnote reference.
     FootnoteReference(FootnoteReference),
+    /// Inline footnote.
+    InlineFootnote(InlineFootnote),
+    /// Directive (text).
+    TextDirective(TextDirective),
     /// Html (phrasing).
     Html(Html),
     /// Image.
@@ -158,6 +166,8 @@ pub enum Node {
     Table(Table),
     /// Thematic break.
     ThematicBreak(ThematicBreak),
+    /// Directive (leaf).
+    LeafDirective(LeafDirective),
 
     // Table content.
     /// Table row.
@@ -185,6 +195,10 @@ impl fmt::Debug for Node {
             Node::Root(x) => x.fmt(f),
             Node::BlockQuote(x) => x.fmt(f),
             Node::FootnoteDefinition(x) => x.fmt(f),
+            Node::FencedDiv(x) => x.fmt(f),
+            Node::ContainerDirective(x) => x.fmt(f),
+            Node::LeafDirective(x) => x.fmt(f),
+            Node::TextDirective(x) => x.fmt(f),
             Node::MdxJsxFlowElement(x) => x.fmt(f),
             Node::List(x) => x.fmt(f),
             Node::MdxjsEsm(x) => x.fmt(f),
@@ -197,6 +211,7 @@ impl fmt::Debug for Node {
             Node::Emphasis(x) => x.fmt(f),
             Node::MdxTextExpression(x) => x.fmt(f),
             Node::FootnoteReference(x) => x.fmt(f),
+            Node::InlineFootnote(x) => x.fmt(f),
             Node::Html(x) => x.fmt(f),
             Node::Image(x) => x.fmt(f),
             Node::ImageReference(x) => x.fmt(f),
@@ -231,10 +246,15 @@ impl ToString for Node {
             Node::Root(x) => children_to_string(&x.children),
             Node::BlockQuote(x) => children_to_string(&x.children),
             Node::FootnoteDefinition(x) => children_to_string(&x.children),
+            Node::FencedDiv(x) => children_to_string(&x.children),
+            Node::ContainerDirective(x) => children_to_string(&x.children),
+            Node::LeafDirective(x) => children_to_string(&x.children),
+            Node::TextDirective(x) => children_to_string(&x.children),
             Node::MdxJsxFlowElement(x) => children_to_string(&x.children),
             Node::List(x) => children_to_string(&x.children),
             Node::Delete(x) => children_to_string(&x.children),
             Node::Emphasis(x) => children_to_string(&x.children),
+            Node::InlineFootnote(x) => children_to_string(&x.children),
             Node::MdxJsxTextElement(x) => children_to_string(&x.children),
             Node::Link(x) => children_to_string(&x.children),
             Node::LinkReference(x) => children_to_string(&x.children),
@@ -286,6 +306,11 @@ impl Node {
             Node::Link(x) => Some(&x.children),
             Node::LinkReference(x) => Some(&x.children),
             Node::FootnoteDefinition(x) => Some(&x.children),
+            Node::FencedDiv(x) => Some(&x.children),
+            Node::ContainerDirective(x) => Some(&x.children),
+            Node::LeafDirective(x) => Some(&x.children),
+            Node::TextDirective(x) => Some(&x.children),
+            Node::InlineFootnote(x) => Some(&x.children),
             Node::Table(x) => Some(&x.children),
             Node::TableRow(x) => Some(&x.children),
             Node::TableCell(x) => Some(&x.children),
@@ -311,6 +336,11 @@ impl Node {
             Node::Link(x) => Some(&mut x.children),
             Node::LinkReference(x) => Some(&mut x.children),
             Node::FootnoteDefinition(x) => Some(&mut x.children),
+            Node::FencedDiv(x) => Some(&mut x.children),
+            Node::ContainerDirective(x) => Some(&mut x.children),
+            Node::LeafDirective(x) => Some(&mut x.children),
+            Node::TextDirective(x) => Some(&mut x.children),
+            Node::InlineFootnote(x) => Some(&mut x.children),
             Node::Table(x) => Some(&mut x.children),
             Node::TableRow(x) => Some(&mut x.children),
             Node::TableCell(x) => Some(&mut x.children),
@@ -328,6 +358,10 @@ impl Node {
             Node::Root(x) => x.position.as_ref(),
             Node::BlockQuote(x) => x.position.as_ref(),
             Node::FootnoteDefinition(x) => x.position.as_ref(),
+            Node::FencedDiv(x) => x.position.as_ref(),
+            Node::ContainerDirective(x) => x.position.as_ref(),
+            Node::LeafDirective(x) => x.position.as_ref(),
+            Node::TextDirective(x) => x.position.as_ref(),
             Node::MdxJsxFlowElement(x) => x.position.as_ref(),
             Node::List(x) => x.position.as_ref(),
             Node::MdxjsEsm(x) => x.position.as_ref(),
@@ -340,6 +374,7 @@ impl Node {
             Node::Emphasis(x) => x.position.as_ref(),
             Node::MdxTextExpression(x) => x.position.as_ref(),
             Node::FootnoteReference(x) => x.position.as_ref(),
+            Node::InlineFootnote(x) => x.position.as_ref(),
             Node::Html(x) => x.position.as_ref(),
             Node::Image(x) => x.position.as_ref(),
             Node::ImageReference(x) => x.position.as_ref(),
@@ -367,6 +402,10 @@ impl Node {
             Node::Root(x) => x.position.as_mut(),
             Node::BlockQuote(x) => x.position.as_mut(),
             Node::FootnoteDefinition(x) => x.position.as_mut(),
+            Node::FencedDiv(x) => x.position.as_mut(),
+            Node::ContainerDirective(x) => x.position.as_mut(),
+            Node::LeafDirective(x) => x.position.as_mut(),
+            Node::TextDirective(x) => x.position.as_mut(),
             Node::MdxJsxFlowElement(x) => x.position.as_mut(),
             Node::List(x) => x.position.as_mut(),
             Node::MdxjsEsm(x) => x.position.as_mut(),
@@ -379,6 +418,7 @@ impl Node {
             Node::Emphasis(x) => x.position.as_mut(),
             Node::MdxTextExpression(x) => x.position.as_mut(),
             Node::FootnoteReference(x) => x.position.as_mut(),
+            Node::InlineFootnote(x) => x.position.as_mut(),
             Node::Html(x) => x.position.as_mut(),
             Node::Image(x) => x.position.as_mut(),
             Node::ImageReference(x) => x.position.as_mut(),
@@ -406,6 +446,10 @@ impl Node {
             Node::Root(x) => x.position = position,
             Node::BlockQuote(x) => x.position = position,
             Node::FootnoteDefinition(x) => x.position = position,
+            Node::FencedDiv(x) => x.position = position,
+            Node::ContainerDirective(x) => x.position = position,
+            Node::LeafDirective(x) => x.position = position,
+            Node::TextDirective(x) => x.position = position,
             Node::MdxJsxFlowElement(x) => x.position = position,
             Node::List(x) => x.position = position,
             Node::MdxjsEsm(x) => x.position = position,
@@ -418,6 +462,7 @@ impl Node {
             Node::Emphasis(x) => x.position = position,
             Node::MdxTextExpression(x) => x.position = position,
             Node::FootnoteReference(x) => x.position = position,
+            Node::InlineFootnote(x) => x.position = position,
             Node::Html(x) => x.position = position,
             Node::Image(x) => x.position = position,
             Node::ImageReference(x) => x.position = position,
@@ -1053,6 +1098,130 @@ pub struct FootnoteDefinition {
     pub label: Option<String>,
 }
 
+/// Directive (container).
+///
+/// ```markdown
+/// > | :::a[b]{#c}
+///     ^^^^^^^^^^^
+/// > | d
+///     ^
+/// > | :::
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "containerDirective")
+)]
+pub struct ContainerDirective {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Name (`a`).
+    pub name: String,
+    /// Label (`b`), as phrasing content.
+    pub label: Vec<Node>,
+    /// ID (`#c`), if any.
+    pub id: Option<String>,
+    /// Classes (`.c`).
+    pub classes: Vec<String>,
+    /// Other attributes (`c=d`), in order.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Directive (leaf).
+///
+/// ```markdown
+/// > | ::a[b]{#c}
+///     ^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "leafDirective")
+)]
+pub struct LeafDirective {
+    // Parent.
+    /// Content model: the label (`b`).
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Name (`a`).
+    pub name: String,
+    /// ID (`#c`), if any.
+    pub id: Option<String>,
+    /// Classes (`.c`).
+    pub classes: Vec<String>,
+    /// Other attributes (`c=d`), in order.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Directive (text).
+///
+/// ```markdown
+/// > | a :b[c]{#d} e
+///       ^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "textDirective")
+)]
+pub struct TextDirective {
+    // Parent.
+    /// Content model: the label (`c`).
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// Name (`b`).
+    pub name: String,
+    /// ID (`#d`), if any.
+    pub id: Option<String>,
+    /// Classes (`.d`).
+    pub classes: Vec<String>,
+    /// Other attributes (`d=e`), in order.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Fenced div.
+///
+/// ```markdown
+/// > | ::: a
+///     ^^^^^
+/// > | b
+///     ^
+/// > | :::
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "fencedDiv")
+)]
+pub struct FencedDiv {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Extra.
+    /// ID (`#a`), if any.
+    pub id: Option<String>,
+    /// Classes (`.a`, or a word).
+    pub classes: Vec<String>,
+    /// Other attributes (`a=b`), in order.
+    pub attributes: Vec<(String, String)>,
+}
+
 /// GFM: footnote reference.
 ///
 /// ```markdown
@@ -1084,6 +1253,29 @@ pub struct FootnoteReference {
     pub label: Option<String>,
 }
 
+/// Inline footnote.
+///
+/// ```markdown
+/// > | ^[a]
+///     ^^^^
+/// ```
+///
+/// > 👉 **Note**: this node is called `footnote` in mdast, where it is
+/// > deprecated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "footnote")
+)]
+pub struct InlineFootnote {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
 /// GFM: table.
 ///
 /// ```markdown
@@ -1895,6 +2087,167 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fenced_div() {
+        let mut node = Node::FencedDiv(FencedDiv {
+            position: None,
+            children: vec![],
+            id: None,
+            classes: vec![],
+            attributes: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "FencedDiv { children: [], position: None, id: None, classes: [], attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "FencedDiv { children: [], position: Some(1:1-1:2 (0-1)), id: None, classes: [], attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn container_directive() {
+        let mut node = Node::ContainerDirective(ContainerDirective {
+            position: None,
+            children: vec![],
+            name: "a".into(),
+            label: vec![],
+            id: None,
+            classes: vec![],
+            attributes: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ContainerDirective { children: [], position: None, name: \"a\", label: [], id: None, classes: [], attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ContainerDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", label: [], id: None, classes: [], attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn leaf_directive() {
+        let mut node = Node::LeafDirective(LeafDirective {
+            position: None,
+            children: vec![],
+            name: "a".into(),
+            id: None,
+            classes: vec![],
+            attributes: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "LeafDirective { children: [], position: None, name: \"a\", id: None, classes: [], attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "LeafDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", id: None, classes: [], attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn text_directive() {
+        let mut node = Node::TextDirective(TextDirective {
+            position: None,
+            children: vec![],
+            name: "a".into(),
+            id: None,
+            classes: vec![],
+            attributes: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "TextDirective { children: [], position: None, name: \"a\", id: None, classes: [], attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "TextDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", id: None, classes: [], attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn inline_footnote() {
+        let mut node = Node::InlineFootnote(InlineFootnote {
+            position: None,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineFootnote { children: [], position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineFootnote { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
     #[test]
     fn emphasis() {
         let mut node = Node::Emphasis(Emphasis {
@@ -1,12 +1,14 @@
 //! Turn bytes of markdown into events.
 
-use crate::event::{Event, Point};
+use crate::diagnostic::Diagnostic;
+use crate::event::{Content, Event, Point};
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::subtokenize;
 use crate::tokenizer::Tokenizer;
 use crate::util::location::Location;
 use crate::ParseOptions;
 use alloc::{string::String, vec, vec::Vec};
+use core::cell::Cell;
 
 /// Info needed, in all content types, when parsing markdown.
 ///
@@ -24,6 +26,12 @@ pub struct ParseState<'a> {
     pub definitions: Vec<String>,
     /// Set of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// Whether to collect warnings, which only `to_diagnostics` uses.
+    pub diagnose: bool,
+    /// List of warnings.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Number of attempts made so far, in all tokenizers.
+    pub attempts: Cell<usize>,
 }
 
 /// Turn a string of markdown into events.
@@ -32,6 +40,126 @@ pub struct ParseState<'a> {
 pub fn parse<'a>(
     value: &'a str,
     options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), String> {
+    parse_impl(value, options, None, None, vec![], vec![], vec![], false)
+}
+
+/// Turn a string of markdown into events, and collect warnings about it in
+/// `diagnostics` of the parse state.
+///
+/// Other ways to parse do not collect warnings, as making them takes time.
+pub fn parse_with_diagnostics<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), String> {
+    parse_impl(value, options, None, None, vec![], vec![], vec![], true)
+}
+
+/// Turn a string of markdown into events, reusing the memory of `events`.
+///
+/// `events` is cleared first, and then filled with the events of `value`.
+/// Useful when parsing many documents one after another.
+pub fn parse_into<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+    events: &mut Vec<Event>,
+) -> Result<ParseState<'a>, String> {
+    let mut buffer = core::mem::take(events);
+    buffer.clear();
+    let (result, parse_state) =
+        parse_impl(value, options, None, None, vec![], vec![], buffer, false)?;
+    *events = result;
+    Ok(parse_state)
+}
+
+/// Turn a string of markdown into events, without parsing phrasing content.
+///
+/// Only [content][crate::construct::content] is subtokenized, which turns it
+/// into definitions and paragraphs.
+/// String and text content are left as opaque data.
+pub fn parse_blocks<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), String> {
+    parse_impl(
+        value,
+        options,
+        None,
+        Some(&Content::Content),
+        vec![],
+        vec![],
+        vec![],
+        false,
+    )
+}
+
+/// Turn a string of markdown into events, with definitions that are known to
+/// exist elsewhere.
+///
+/// Used to parse part of a document: references in it can match
+/// `definitions` and `gfm_footnote_definitions` (both normalized) as well as
+/// definitions in the part itself.
+pub fn parse_with_definitions<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+    definitions: Vec<String>,
+    gfm_footnote_definitions: Vec<String>,
+) -> Result<(Vec<Event>, ParseState<'a>), String> {
+    parse_impl(
+        value,
+        options,
+        None,
+        None,
+        definitions,
+        gfm_footnote_definitions,
+        vec![],
+        false,
+    )
+}
+
+/// Turn a string of markdown into events, as [text][crate::construct::text]
+/// content that starts at `point`.
+///
+/// Used to parse embedded content, such as part of a table cell, whose place
+/// in some bigger document is known.
+/// The line and column of `point` are used for the first byte of `value`,
+/// which matters for positional info and for how tabs are expanded.
+/// Indices still refer to bytes in `value`, so that the events can be
+/// compiled with those bytes: add the offset of `point` to them afterwards.
+pub fn tokenize_text_at<'a>(
+    value: &'a str,
+    point: &Point,
+    options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), String> {
+    parse_impl(
+        value,
+        options,
+        Some(point),
+        None,
+        vec![],
+        vec![],
+        vec![],
+        false,
+    )
+}
+
+/// Turn a string of markdown into events, subtokenizing content that
+/// matches `filter` (or all content when `None`), starting with some known
+/// definitions, and adding events to `events` (which must be empty).
+///
+/// `value` is parsed as document content, or, when `point` is given, as text
+/// content that starts there.
+/// Warnings are only collected when `diagnose` is on.
+#[allow(clippy::too_many_arguments)]
+fn parse_impl<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+    point: Option<&Point>,
+    filter: Option<&Content>,
+    definitions: Vec<String>,
+    gfm_footnote_definitions: Vec<String>,
+    events: Vec<Event>,
+    diagnose: bool,
 ) -> Result<(Vec<Event>, ParseState<'a>), String> {
     let bytes = value.as_bytes();
 
@@ -43,36 +171,67 @@ pub fn parse<'a>(
         } else {
             None
         },
-        definitions: vec![],
-        gfm_footnote_definitions: vec![],
+        definitions,
+        gfm_footnote_definitions,
+        diagnose,
+        diagnostics: vec![],
+        attempts: Cell::new(0),
     };
 
-    let start = Point {
-        line: 1,
-        column: 1,
-        index: 0,
-        vs: 0,
+    let (start, name) = if let Some(point) = point {
+        (
+            Point {
+                line: point.line,
+                column: point.column,
+                index: 0,
+                vs: 0,
+            },
+            StateName::TextStart,
+        )
+    } else {
+        (
+            Point {
+                line: 1,
+                column: 1,
+                index: 0,
+                vs: 0,
+            },
+            StateName::DocumentStart,
+        )
     };
     let mut tokenizer = Tokenizer::new(start, &parse_state);
+    tokenizer.events = events;
 
-    let state = tokenizer.push(
-        (0, 0),
-        (parse_state.bytes.len(), 0),
-        State::Next(StateName::DocumentStart),
-    );
+    let state = tokenizer.push((0, 0), (parse_state.bytes.len(), 0), State::Next(name));
     let mut result = tokenizer.flush(state, true)?;
     let mut events = tokenizer.events;
+    let mut passes = 0;
 
     loop {
         let fn_defs = &mut parse_state.gfm_footnote_definitions;
         let defs = &mut parse_state.definitions;
         fn_defs.append(&mut result.gfm_footnote_definitions);
         defs.append(&mut result.definitions);
+        parse_state.diagnostics.append(&mut result.diagnostics);
 
         if result.done {
             return Ok((events, parse_state));
         }
 
-        result = subtokenize(&mut events, &parse_state, &None)?;
+        // When too many passes were made, only content (which turns into
+        // definitions and paragraphs) is still subtokenized: other linked
+        // events are left as opaque data, so they compile as text.
+        // Content nested in content (such as directives in labels of
+        // directives) needs a pass per level, so nesting limits passes too.
+        let filter = if matches!(options.max_depth, Some(max) if passes >= max)
+            || matches!(options.max_nesting, Some(max) if passes >= max)
+        {
+            Some(&Content::Content)
+        } else {
+            filter
+        };
+
+        passes += 1;
+        result = subtokenize(&mut events, &parse_state, &filter.cloned())?;
     }
 }
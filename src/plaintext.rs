@@ -0,0 +1,83 @@
+//! Turn a syntax tree into plain text.
+
+use crate::mdast::Node;
+use alloc::{string::String, vec::Vec};
+
+/// Turn `tree` into plain text.
+///
+/// Blocks are separated by a line ending, and cells in tables by a tab.
+pub fn compile(tree: &Node) -> String {
+    let mut value = String::new();
+    flow(tree, &mut value);
+    value
+}
+
+/// Add the text of the block `node` to `value`.
+fn flow(node: &Node, value: &mut String) {
+    match node {
+        Node::Root(_)
+        | Node::BlockQuote(_)
+        | Node::ContainerDirective(_)
+        | Node::FencedDiv(_)
+        | Node::List(_)
+        | Node::ListItem(_)
+        | Node::MdxJsxFlowElement(_) => {
+            for child in node.children().unwrap() {
+                flow(child, value);
+            }
+        }
+        Node::Table(table) => {
+            for row in &table.children {
+                let cells = row
+                    .children()
+                    .unwrap()
+                    .iter()
+                    .map(phrasing)
+                    .collect::<Vec<_>>();
+                block(&cells.join("\t"), value);
+            }
+        }
+        Node::Code(x) => block(&x.value, value),
+        Node::Math(x) => block(&x.value, value),
+        Node::Heading(_) | Node::LeafDirective(_) | Node::Paragraph(_) => {
+            block(&phrasing(node), value);
+        }
+        // Not text: HTML, frontmatter, definitions, footnote definitions, MDX
+        // ESM and expressions, and thematic breaks.
+        _ => {}
+    }
+}
+
+/// Add the text of a block to `value`, on its own line.
+fn block(text: &str, value: &mut String) {
+    if !text.is_empty() {
+        if !value.is_empty() {
+            value.push('\n');
+        }
+
+        value.push_str(text);
+    }
+}
+
+/// Get the text of the phrasing content in `node`.
+///
+/// Like `to_string`, but includes the alt text of images, turns breaks into
+/// line endings, and ignores HTML, expressions, and footnotes.
+fn phrasing(node: &Node) -> String {
+    match node {
+        Node::Image(x) => x.alt.clone(),
+        Node::ImageReference(x) => x.alt.clone(),
+        Node::Break(_) => "\n".into(),
+        Node::Text(x) => x.value.clone(),
+        Node::InlineCode(x) => x.value.clone(),
+        Node::InlineMath(x) => x.value.clone(),
+        Node::Html(_)
+        | Node::InlineFootnote(_)
+        | Node::FootnoteReference(_)
+        | Node::MdxTextExpression(_) => String::new(),
+        _ => node
+            .children()
+            .map(|children| children.iter().map(phrasing).collect())
+            .unwrap_or_default(),
+    }
+}
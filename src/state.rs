@@ -2,7 +2,7 @@
 
 use crate::construct;
 use crate::tokenizer::Tokenizer;
-use alloc::string::String;
+use alloc::{format, string::String};
 
 /// Result of a state.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -24,15 +24,18 @@ pub enum State {
 impl State {
     /// Turn a final state into a result.
     ///
-    /// This doesn’t work on future states ([`State::Next`], [`State::Retry`]),
-    /// or on an attempt ([`State::Nok`]).
+    /// It turns the final result into an error if crashed.
     ///
-    /// But it turns the final result into an error if crashed.
+    /// Future states ([`State::Next`], [`State::Retry`]) and attempts
+    /// ([`State::Nok`]) are not expected here.
+    /// If they do occur, which is a bug, they are turned into an error too,
+    /// instead of panicking on the input.
     pub fn to_result(&self) -> Result<(), String> {
         match self {
-            State::Nok | State::Next(_) | State::Retry(_) => {
-                unreachable!("cannot turn intermediate state into result")
-            }
+            State::Nok | State::Next(_) | State::Retry(_) => Err(format!(
+                "Unexpected unfinished state `{:?}` when done tokenizing, please report this as a bug in `markdown-rs`",
+                self
+            )),
             State::Ok => Ok(()),
             State::Error(x) => Err(x.into()),
         }
@@ -114,6 +117,37 @@ pub enum Name {
     DestinationRaw,
     DestinationRawEscape,
 
+    DirectiveNameStart,
+    DirectiveNameInside,
+    DirectiveLabelBefore,
+    DirectiveLabelStart,
+    DirectiveLabelInside,
+    DirectiveLabelEscape,
+    DirectiveLabelEnd,
+    DirectiveAttributesBefore,
+    DirectiveAttributesInside,
+    DirectiveAttributesValueQuoted,
+
+    DirectiveContainerStart,
+    DirectiveContainerSequenceBefore,
+    DirectiveContainerSequence,
+    DirectiveContainerOpenAfter,
+    DirectiveContainerOpenEnd,
+    DirectiveContainerContStart,
+    DirectiveContainerCloseStart,
+    DirectiveContainerCloseSequenceBefore,
+    DirectiveContainerCloseSequence,
+    DirectiveContainerCloseAfter,
+
+    DirectiveLeafStart,
+    DirectiveLeafBefore,
+    DirectiveLeafSequence,
+    DirectiveLeafAfter,
+    DirectiveLeafEnd,
+
+    DirectiveTextStart,
+    DirectiveTextAfter,
+
     DocumentStart,
     DocumentBeforeFrontmatter,
     DocumentContainerExistingBefore,
@@ -122,13 +156,35 @@ pub enum Name {
     DocumentContainerNewBeforeNotBlockQuote,
     DocumentContainerNewBeforeNotList,
     DocumentContainerNewBeforeNotGfmFootnoteDefinition,
+    DocumentContainerNewBeforeNotDirectiveContainer,
+    DocumentContainerNewBeforeNotFencedDiv,
     DocumentContainerNewAfter,
     DocumentContainersAfter,
     DocumentFlowInside,
     DocumentFlowEnd,
 
+    EmojiStart,
+    EmojiValue,
+
+    FencedDivStart,
+    FencedDivSequenceBefore,
+    FencedDivSequence,
+    FencedDivAttributesBefore,
+    FencedDivAttributesBraced,
+    FencedDivAttributesWord,
+    FencedDivAttributesAfter,
+    FencedDivTrailingSequenceBefore,
+    FencedDivTrailingSequence,
+    FencedDivOpenAfter,
+    FencedDivContStart,
+    FencedDivCloseStart,
+    FencedDivCloseSequenceBefore,
+    FencedDivCloseSequence,
+    FencedDivCloseAfter,
+
     FlowStart,
     FlowBeforeGfmTable,
+    FlowBeforeDirectiveLeaf,
     FlowBeforeCodeIndented,
     FlowBeforeRaw,
     FlowBeforeHtml,
@@ -197,6 +253,7 @@ pub enum Name {
     GfmTaskListItemCheckAfterSpaceOrTab,
 
     GfmTableStart,
+    GfmTableHeaderlessBefore,
     GfmTableHeadRowBefore,
     GfmTableHeadRowStart,
     GfmTableHeadRowBreak,
@@ -325,6 +382,9 @@ pub enum Name {
     LabelStartImageOpen,
     LabelStartImageAfter,
 
+    LabelStartInlineFootnoteStart,
+    LabelStartInlineFootnoteOpen,
+
     LabelStartLinkStart,
 
     ListItemStart,
@@ -452,6 +512,7 @@ pub enum Name {
     TextBeforeMdxJsx,
     TextBeforeHardBreakEscape,
     TextBeforeLabelStartLink,
+    TextBeforeDirectiveText,
     TextBeforeData,
 
     ThematicBreakStart,
@@ -545,6 +606,41 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::DestinationRaw => construct::partial_destination::raw,
         Name::DestinationRawEscape => construct::partial_destination::raw_escape,
 
+        Name::DirectiveNameStart => construct::partial_directive::name_start,
+        Name::DirectiveNameInside => construct::partial_directive::name_inside,
+        Name::DirectiveLabelBefore => construct::partial_directive::label_before,
+        Name::DirectiveLabelStart => construct::partial_directive::label_start,
+        Name::DirectiveLabelInside => construct::partial_directive::label_inside,
+        Name::DirectiveLabelEscape => construct::partial_directive::label_escape,
+        Name::DirectiveLabelEnd => construct::partial_directive::label_end,
+        Name::DirectiveAttributesBefore => construct::partial_directive::attributes_before,
+        Name::DirectiveAttributesInside => construct::partial_directive::attributes_inside,
+        Name::DirectiveAttributesValueQuoted => {
+            construct::partial_directive::attributes_value_quoted
+        }
+
+        Name::DirectiveContainerStart => construct::directive_container::start,
+        Name::DirectiveContainerSequenceBefore => construct::directive_container::sequence_before,
+        Name::DirectiveContainerSequence => construct::directive_container::sequence,
+        Name::DirectiveContainerOpenAfter => construct::directive_container::open_after,
+        Name::DirectiveContainerOpenEnd => construct::directive_container::open_end,
+        Name::DirectiveContainerContStart => construct::directive_container::cont_start,
+        Name::DirectiveContainerCloseStart => construct::directive_container::close_start,
+        Name::DirectiveContainerCloseSequenceBefore => {
+            construct::directive_container::close_sequence_before
+        }
+        Name::DirectiveContainerCloseSequence => construct::directive_container::close_sequence,
+        Name::DirectiveContainerCloseAfter => construct::directive_container::close_after,
+
+        Name::DirectiveLeafStart => construct::directive_leaf::start,
+        Name::DirectiveLeafBefore => construct::directive_leaf::before,
+        Name::DirectiveLeafSequence => construct::directive_leaf::sequence,
+        Name::DirectiveLeafAfter => construct::directive_leaf::after,
+        Name::DirectiveLeafEnd => construct::directive_leaf::end,
+
+        Name::DirectiveTextStart => construct::directive_text::start,
+        Name::DirectiveTextAfter => construct::directive_text::after,
+
         Name::DocumentStart => construct::document::start,
         Name::DocumentBeforeFrontmatter => construct::document::before_frontmatter,
         Name::DocumentContainerExistingBefore => construct::document::container_existing_before,
@@ -559,13 +655,39 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::DocumentContainerNewBeforeNotGfmFootnoteDefinition => {
             construct::document::container_new_before_not_footnote_definition
         }
+        Name::DocumentContainerNewBeforeNotDirectiveContainer => {
+            construct::document::container_new_before_not_directive_container
+        }
+        Name::DocumentContainerNewBeforeNotFencedDiv => {
+            construct::document::container_new_before_not_fenced_div
+        }
         Name::DocumentContainerNewAfter => construct::document::container_new_after,
         Name::DocumentContainersAfter => construct::document::containers_after,
         Name::DocumentFlowEnd => construct::document::flow_end,
         Name::DocumentFlowInside => construct::document::flow_inside,
 
+        Name::EmojiStart => construct::emoji::start,
+        Name::EmojiValue => construct::emoji::value,
+
+        Name::FencedDivStart => construct::fenced_div::start,
+        Name::FencedDivSequenceBefore => construct::fenced_div::sequence_before,
+        Name::FencedDivSequence => construct::fenced_div::sequence,
+        Name::FencedDivAttributesBefore => construct::fenced_div::attributes_before,
+        Name::FencedDivAttributesBraced => construct::fenced_div::attributes_braced,
+        Name::FencedDivAttributesWord => construct::fenced_div::attributes_word,
+        Name::FencedDivAttributesAfter => construct::fenced_div::attributes_after,
+        Name::FencedDivTrailingSequenceBefore => construct::fenced_div::trailing_sequence_before,
+        Name::FencedDivTrailingSequence => construct::fenced_div::trailing_sequence,
+        Name::FencedDivOpenAfter => construct::fenced_div::open_after,
+        Name::FencedDivContStart => construct::fenced_div::cont_start,
+        Name::FencedDivCloseStart => construct::fenced_div::close_start,
+        Name::FencedDivCloseSequenceBefore => construct::fenced_div::close_sequence_before,
+        Name::FencedDivCloseSequence => construct::fenced_div::close_sequence,
+        Name::FencedDivCloseAfter => construct::fenced_div::close_after,
+
         Name::FlowStart => construct::flow::start,
         Name::FlowBeforeGfmTable => construct::flow::before_gfm_table,
+        Name::FlowBeforeDirectiveLeaf => construct::flow::before_directive_leaf,
         Name::FlowBeforeCodeIndented => construct::flow::before_code_indented,
         Name::FlowBeforeRaw => construct::flow::before_raw,
         Name::FlowBeforeHtml => construct::flow::before_html,
@@ -644,6 +766,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::GfmLabelStartFootnoteOpen => construct::gfm_label_start_footnote::open,
 
         Name::GfmTableStart => construct::gfm_table::start,
+        Name::GfmTableHeaderlessBefore => construct::gfm_table::headerless_before,
         Name::GfmTableHeadRowBefore => construct::gfm_table::head_row_before,
         Name::GfmTableHeadRowStart => construct::gfm_table::head_row_start,
         Name::GfmTableHeadRowBreak => construct::gfm_table::head_row_break,
@@ -810,6 +933,8 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::LabelStartImageStart => construct::label_start_image::start,
         Name::LabelStartImageOpen => construct::label_start_image::open,
         Name::LabelStartImageAfter => construct::label_start_image::after,
+        Name::LabelStartInlineFootnoteStart => construct::label_start_inline_footnote::start,
+        Name::LabelStartInlineFootnoteOpen => construct::label_start_inline_footnote::open,
         Name::LabelStartLinkStart => construct::label_start_link::start,
 
         Name::ListItemStart => construct::list_item::start,
@@ -951,6 +1076,7 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::TextBeforeMdxJsx => construct::text::before_mdx_jsx,
         Name::TextBeforeHardBreakEscape => construct::text::before_hard_break_escape,
         Name::TextBeforeLabelStartLink => construct::text::before_label_start_link,
+        Name::TextBeforeDirectiveText => construct::text::before_directive_text,
         Name::TextBeforeData => construct::text::before_data,
 
         Name::ThematicBreakStart => construct::thematic_break::start,
@@ -969,3 +1095,31 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
 
     func(tokenizer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_result() {
+        assert_eq!(State::Ok.to_result(), Ok(()), "should support `Ok`");
+
+        assert_eq!(
+            State::Error("1:1: a".into()).to_result(),
+            Err("1:1: a".into()),
+            "should turn `Error` into an error"
+        );
+
+        assert_eq!(
+            State::Nok.to_result(),
+            Err("Unexpected unfinished state `Nok` when done tokenizing, please report this as a bug in `markdown-rs`".into()),
+            "should turn `Nok` into an error instead of panicking"
+        );
+
+        assert_eq!(
+            State::Next(Name::DocumentStart).to_result(),
+            Err("Unexpected unfinished state `Next(DocumentStart)` when done tokenizing, please report this as a bug in `markdown-rs`".into()),
+            "should turn future states into an error instead of panicking"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Turn markdown that comes in chunks into HTML.
+
+use crate::configuration::Options;
+use crate::to_html_with_options;
+use alloc::{string::String, vec::Vec};
+use core::str;
+
+/// Parser that takes markdown in chunks, and turns it into HTML at the end.
+///
+/// Markdown cannot be turned into HTML before the whole document is known:
+/// a definition at the end can turn text at the start into a link.
+/// So chunks are collected, and the document is parsed once when
+/// [`finish()`][Parser::finish] is called.
+/// The result is the same as calling
+/// [`to_html_with_options()`][crate::to_html_with_options] with the chunks
+/// joined together, wherever they are split: chunks can end in the middle of
+/// a line, between the carriage return and line feed of a CRLF, or, when
+/// pushing bytes, in the middle of a UTF-8 sequence.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{Options, Parser};
+/// # fn main() -> Result<(), String> {
+///
+/// let options = Options::default();
+/// let mut parser = Parser::new(&options);
+///
+/// parser.push("# Hello, *wor");
+/// parser.push("ld*!\r");
+/// parser.push(b"\n[a] \xF0\x9F");
+/// parser.push(b"\x91\x8D\r\n\r\n[a]: b");
+///
+/// assert_eq!(
+///     parser.finish()?,
+///     "<h1>Hello, <em>world</em>!</h1>\r\n<p><a href=\"b\">a</a> 👍</p>\r\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Parser<'a> {
+    /// Configuration.
+    options: &'a Options,
+    /// Markdown pushed so far.
+    value: String,
+    /// Bytes at the end of the last chunk that start, but do not complete, a
+    /// UTF-8 sequence.
+    incomplete: Vec<u8>,
+}
+
+impl<'a> Parser<'a> {
+    /// Create a parser, with configuration.
+    #[must_use]
+    pub fn new(options: &'a Options) -> Self {
+        Self {
+            options,
+            value: String::new(),
+            incomplete: Vec::new(),
+        }
+    }
+
+    /// Add a chunk of markdown.
+    ///
+    /// Chunks can be strings or bytes.
+    /// Bytes are decoded as UTF-8: a sequence split over several chunks is
+    /// joined, and invalid bytes are replaced with the replacement character
+    /// (`U+FFFD`), like [`String::from_utf8_lossy`][] does.
+    pub fn push<T: AsRef<[u8]>>(&mut self, chunk: T) {
+        let chunk = chunk.as_ref();
+        let mut joined;
+        let mut bytes = chunk;
+
+        if !self.incomplete.is_empty() {
+            joined = core::mem::take(&mut self.incomplete);
+            joined.extend_from_slice(chunk);
+            bytes = &joined;
+        }
+
+        loop {
+            match str::from_utf8(bytes) {
+                Ok(valid) => {
+                    self.value.push_str(valid);
+                    break;
+                }
+                Err(error) => {
+                    let (valid, rest) = bytes.split_at(error.valid_up_to());
+                    self.value.push_str(str::from_utf8(valid).unwrap());
+
+                    if let Some(size) = error.error_len() {
+                        self.value.push(char::REPLACEMENT_CHARACTER);
+                        bytes = &rest[size..];
+                    } else {
+                        // Incomplete sequence at the end: wait for more.
+                        self.incomplete.extend_from_slice(rest);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Turn the markdown pushed so far into HTML.
+    ///
+    /// ## Errors
+    ///
+    /// `finish()` never errors with normal markdown because markdown does not
+    /// have syntax errors, so feel free to `unwrap()`.
+    /// However, MDX does have syntax errors.
+    /// When MDX is turned on, there are several errors that can occur with how
+    /// expressions, ESM, and JSX are written.
+    pub fn finish(mut self) -> Result<String, String> {
+        if !self.incomplete.is_empty() {
+            self.value.push(char::REPLACEMENT_CHARACTER);
+        }
+
+        to_html_with_options(&self.value, self.options)
+    }
+}
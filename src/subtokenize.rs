@@ -17,18 +17,20 @@
 //! whole document needs to be parsed up to the level of definitions, before
 //! any level that can include references can be parsed.
 
+use crate::diagnostic::Diagnostic;
 use crate::event::{Content, Event, Kind, Name, VOID_EVENTS};
 use crate::parser::ParseState;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 use crate::util::{edit_map::EditMap, skip};
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{fmt, string::String, vec, vec::Vec};
 
 #[derive(Debug)]
 pub struct Subresult {
     pub done: bool,
     pub gfm_footnote_definitions: Vec<String>,
     pub definitions: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 /// Link two [`Event`][]s.
@@ -36,39 +38,194 @@ pub struct Subresult {
 /// Arbitrary (void) events can be linked together.
 /// This optimizes for the common case where the event at `index` is connected
 /// to the previous void event.
+///
+/// The fast path is taken when the two events before `index` are the enter
+/// and exit of that previous void event, which is the case when a construct
+/// enters, exits, and then enters a linked event again, with nothing in
+/// between (such as in paragraphs, labels, and titles).
+/// Otherwise, this falls back to [`link_to_previous`][].
 pub fn link(events: &mut [Event], index: usize) {
-    link_to(events, index - 2, index);
+    if index > 1
+        && events[index - 2].kind == Kind::Enter
+        && events[index - 1].kind == Kind::Exit
+        && events[index - 2].name == events[index - 1].name
+        && events[index - 2]
+            .link
+            .as_ref()
+            .map_or(false, |link| link.next.is_none())
+    {
+        link_to(events, index - 2, index);
+    } else {
+        let found = link_to_previous(events, index);
+        debug_assert!(found, "expected previous void event to link to");
+    }
+}
+
+/// Link the [`Event`][] at `index` to the nearest previous, unlinked, void
+/// event with the same content type.
+///
+/// Unlike [`link`][], this does not assume that the previous void event is
+/// right before `index`: other events can be between them.
+/// Returns whether such an event was found and linked.
+pub fn link_to_previous(events: &mut [Event], index: usize) -> bool {
+    let content = &events[index]
+        .link
+        .as_ref()
+        .expect("expected `link` on next")
+        .content;
+    let mut previous = index;
+
+    while previous > 0 {
+        previous -= 1;
+        let event = &events[previous];
+
+        if let Some(link) = &event.link {
+            if event.kind == Kind::Enter
+                && link.next.is_none()
+                && &link.content == content
+                && previous + 1 < index
+                && events[previous + 1].kind == Kind::Exit
+                && events[previous + 1].name == event.name
+            {
+                link_to(events, previous, index);
+                return true;
+            }
+        }
+    }
+
+    false
 }
 
 /// Link two arbitrary [`Event`][]s together.
+///
+/// This is checked with [`try_link_to`][] in debug builds, which panics with
+/// the [`LinkError`][] on misuse.
 pub fn link_to(events: &mut [Event], previous: usize, next: usize) {
-    debug_assert_eq!(events[previous].kind, Kind::Enter);
-    debug_assert!(
-        VOID_EVENTS.iter().any(|d| d == &events[previous].name),
-        "expected event to be void"
-    );
-    debug_assert_eq!(events[previous + 1].kind, Kind::Exit);
-    debug_assert_eq!(events[previous].name, events[previous + 1].name);
-    debug_assert_eq!(events[next].kind, Kind::Enter);
-    debug_assert!(
-        VOID_EVENTS.iter().any(|d| d == &events[next].name),
-        "expected event to be void"
-    );
-    // Note: the exit of this event may not exist, so don’t check for that.
-
-    let link_previous = events[previous]
+    if cfg!(debug_assertions) {
+        if let Err(error) = try_link_to(events, previous, next) {
+            panic!("cannot link events: {}", error);
+        }
+    } else {
+        events[previous]
+            .link
+            .as_mut()
+            .expect("expected `link` on previous")
+            .next = Some(next);
+        events[next]
+            .link
+            .as_mut()
+            .expect("expected `link` on next")
+            .previous = Some(previous);
+    }
+}
+
+/// Problem linking two [`Event`][]s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LinkError {
+    /// Event at `index` is not the enter of a void event.
+    NotVoid(usize),
+    /// Event at `index` does not have a `link`.
+    MissingLink(usize),
+    /// Events have different content types.
+    ContentMismatch {
+        /// Index of previous event.
+        previous: usize,
+        /// Content type of previous event.
+        previous_content: Content,
+        /// Index of next event.
+        next: usize,
+        /// Content type of next event.
+        next_content: Content,
+    },
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkError::NotVoid(index) => {
+                write!(f, "expected event at {} to enter a void event", index)
+            }
+            LinkError::MissingLink(index) => {
+                write!(f, "expected `link` on event at {}", index)
+            }
+            LinkError::ContentMismatch {
+                previous,
+                previous_content,
+                next,
+                next_content,
+            } => write!(
+                f,
+                "expected `content` to match, not `{:?}` (event at {}) and `{:?}` (event at {})",
+                previous_content, previous, next_content, next
+            ),
+        }
+    }
+}
+
+/// Link two arbitrary [`Event`][]s together, if they can be linked.
+///
+/// Both events must be enters of void events with a `link` of the same
+/// content type, and the previous event must be followed by its exit.
+/// The exit of the next event may not exist yet, so that is not checked.
+/// Nothing is changed if they cannot be linked.
+pub fn try_link_to(events: &mut [Event], previous: usize, next: usize) -> Result<(), LinkError> {
+    let is_void_enter = |index: usize| {
+        events.get(index).map_or(false, |event| {
+            event.kind == Kind::Enter && VOID_EVENTS.contains(&event.name)
+        })
+    };
+
+    if !is_void_enter(previous)
+        || events.get(previous + 1).map_or(true, |exit| {
+            exit.kind != Kind::Exit || exit.name != events[previous].name
+        })
+    {
+        return Err(LinkError::NotVoid(previous));
+    }
+
+    if !is_void_enter(next) {
+        return Err(LinkError::NotVoid(next));
+    }
+
+    let previous_content = &events[previous]
+        .link
+        .as_ref()
+        .ok_or(LinkError::MissingLink(previous))?
+        .content;
+    let next_content = &events[next]
         .link
-        .as_mut()
-        .expect("expected `link` on previous");
-    link_previous.next = Some(next);
-    let link_next = events[next].link.as_mut().expect("expected `link` on next");
-    link_next.previous = Some(previous);
-
-    debug_assert_eq!(
-        events[previous].link.as_ref().unwrap().content,
-        events[next].link.as_ref().unwrap().content,
-        "expected `content` to match"
-    );
+        .as_ref()
+        .ok_or(LinkError::MissingLink(next))?
+        .content;
+
+    if previous_content != next_content {
+        return Err(LinkError::ContentMismatch {
+            previous,
+            previous_content: previous_content.clone(),
+            next,
+            next_content: next_content.clone(),
+        });
+    }
+
+    events[previous].link.as_mut().unwrap().next = Some(next);
+    events[next].link.as_mut().unwrap().previous = Some(previous);
+    Ok(())
+}
+
+/// Get the state to start parsing linked events of a content type in.
+///
+/// To add a content type, add it to [`Content`][], and map it to the state
+/// that its tokenizer starts in here.
+///
+/// Returns `None` for content types that cannot be used as subcontent, which
+/// is currently only flow.
+pub fn start(content: &Content) -> Option<StateName> {
+    match content {
+        Content::Content => Some(StateName::ContentDefinitionBefore),
+        Content::String => Some(StateName::StringStart),
+        Content::Text => Some(StateName::TextStart),
+        Content::Flow => None,
+    }
 }
 
 /// Parse linked events.
@@ -85,6 +242,7 @@ pub fn subtokenize(
         done: true,
         gfm_footnote_definitions: vec![],
         definitions: vec![],
+        diagnostics: vec![],
     };
     let mut acc = (0, 0);
 
@@ -103,16 +261,12 @@ pub fn subtokenize(
                 let mut link_index = Some(index);
                 // Subtokenizer.
                 let mut tokenizer = Tokenizer::new(event.point.clone(), parse_state);
-                debug_assert!(
-                    !matches!(link.content, Content::Flow),
-                    "cannot use flow as subcontent yet"
-                );
                 // Substate.
-                let mut state = State::Next(match link.content {
-                    Content::Content => StateName::ContentDefinitionBefore,
-                    Content::String => StateName::StringStart,
-                    _ => StateName::TextStart,
-                });
+                let mut state = if let Some(name) = start(&link.content) {
+                    State::Next(name)
+                } else {
+                    return Err("Cannot use flow as subcontent yet".into());
+                };
 
                 // Check if this is the first paragraph, after zero or more
                 // definitions (or a blank line), in a list item.
@@ -168,6 +322,7 @@ pub fn subtokenize(
                     .gfm_footnote_definitions
                     .append(&mut result.gfm_footnote_definitions);
                 value.definitions.append(&mut result.definitions);
+                value.diagnostics.append(&mut result.diagnostics);
                 value.done = false;
 
                 acc = divide_events(&mut map, events, index, &mut tokenizer.events, acc);
@@ -272,3 +427,303 @@ pub fn divide_events(
 
     (acc_before.0 + (slices.len() * 2), acc_before.1 + len)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Link, Point};
+    use crate::parser::{parse, parse_blocks};
+    use crate::ParseOptions;
+    use alloc::string::ToString;
+
+    fn point() -> Point {
+        Point {
+            line: 1,
+            column: 1,
+            index: 0,
+            vs: 0,
+        }
+    }
+
+    fn event(kind: Kind, name: Name, content: Option<Content>) -> Event {
+        Event {
+            kind,
+            name,
+            point: point(),
+            link: content.map(|content| Link {
+                previous: None,
+                next: None,
+                content,
+            }),
+        }
+    }
+
+    fn links(events: &[Event]) -> Vec<(usize, Option<usize>, Option<usize>)> {
+        events
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| {
+                event
+                    .link
+                    .as_ref()
+                    .map(|link| (index, link.previous, link.next))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_link() {
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        link(&mut events, 2);
+        assert_eq!(
+            links(&events),
+            vec![(0, None, Some(2)), (2, Some(0), None)],
+            "should link to the void event right before"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::SpaceOrTab, None),
+            event(Kind::Exit, Name::SpaceOrTab, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        link(&mut events, 4);
+        assert_eq!(
+            links(&events),
+            vec![(0, None, Some(4)), (4, Some(0), None)],
+            "should fall back to searching if the event right before is not linked"
+        );
+    }
+
+    #[test]
+    fn test_link_to_previous() {
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::LineEnding, None),
+            event(Kind::Exit, Name::LineEnding, None),
+            event(Kind::Enter, Name::SpaceOrTab, None),
+            event(Kind::Exit, Name::SpaceOrTab, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        assert!(
+            link_to_previous(&mut events, 6),
+            "should find a void event w/ other events in between"
+        );
+        assert_eq!(
+            links(&events),
+            vec![(0, None, Some(6)), (6, Some(0), None)],
+            "should link to a void event w/ other events in between"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::String)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        assert!(
+            link_to_previous(&mut events, 4),
+            "should find a void event w/ the same content type"
+        );
+        assert_eq!(
+            links(&events),
+            vec![(0, None, Some(4)), (2, None, None), (4, Some(0), None)],
+            "should skip void events w/ another content type"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        link(&mut events, 2);
+        assert!(
+            link_to_previous(&mut events, 4),
+            "should find the last event in a chain"
+        );
+        assert_eq!(
+            links(&events),
+            vec![
+                (0, None, Some(2)),
+                (2, Some(0), Some(4)),
+                (4, Some(2), None)
+            ],
+            "should continue a chain"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::String)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        assert!(
+            !link_to_previous(&mut events, 2),
+            "should not link w/o a void event w/ the same content type"
+        );
+        assert_eq!(
+            links(&events),
+            vec![(0, None, None), (2, None, None)],
+            "should not change events if nothing is found"
+        );
+    }
+
+    #[test]
+    fn test_try_link_to() {
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::String)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        assert_eq!(
+            try_link_to(&mut events, 0, 2),
+            Err(LinkError::ContentMismatch {
+                previous: 0,
+                previous_content: Content::String,
+                next: 2,
+                next_content: Content::Text
+            }),
+            "should not link events w/ different content types"
+        );
+        assert_eq!(
+            try_link_to(&mut events, 0, 2).unwrap_err().to_string(),
+            "expected `content` to match, not `String` (event at 0) and `Text` (event at 2)",
+            "should describe the problem"
+        );
+        assert_eq!(
+            links(&events),
+            vec![(0, None, None), (2, None, None)],
+            "should not change events if they cannot be linked"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+        ];
+        assert_eq!(
+            try_link_to(&mut events, 0, 2),
+            Ok(()),
+            "should link events w/ the same content type, w/o exit for next"
+        );
+        assert_eq!(
+            links(&events),
+            vec![(0, None, Some(2)), (2, Some(0), None)],
+            "should link events"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, None),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        assert_eq!(
+            try_link_to(&mut events, 0, 2),
+            Err(LinkError::MissingLink(2)),
+            "should not link events w/o `link`"
+        );
+        assert_eq!(
+            try_link_to(&mut events, 1, 2),
+            Err(LinkError::NotVoid(1)),
+            "should not link from an exit"
+        );
+        assert_eq!(
+            try_link_to(&mut events, 2, 4),
+            Err(LinkError::NotVoid(4)),
+            "should not link to an event that does not exist"
+        );
+
+        let mut events = vec![
+            event(Kind::Enter, Name::Paragraph, Some(Content::Text)),
+            event(Kind::Exit, Name::Paragraph, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        assert_eq!(
+            try_link_to(&mut events, 0, 2),
+            Err(LinkError::NotVoid(0)),
+            "should not link events that are not void"
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "cannot link events: expected `content` to match, not `String` (event at 0) and `Text` (event at 2)"
+    )]
+    #[cfg(debug_assertions)]
+    fn test_link_to_mismatch() {
+        let mut events = vec![
+            event(Kind::Enter, Name::Data, Some(Content::String)),
+            event(Kind::Exit, Name::Data, None),
+            event(Kind::Enter, Name::Data, Some(Content::Text)),
+            event(Kind::Exit, Name::Data, None),
+        ];
+        link_to(&mut events, 0, 2);
+    }
+
+    #[test]
+    fn test_start() {
+        assert_eq!(
+            start(&Content::Text),
+            Some(StateName::TextStart),
+            "should start text in text"
+        );
+        assert_eq!(
+            start(&Content::Flow),
+            None,
+            "should not support flow as subcontent"
+        );
+    }
+
+    #[test]
+    fn test_subtokenize() {
+        let options = ParseOptions::default();
+
+        for value in [
+            "[a]: b&amp;c\n\n*[a]*",
+            "> a *b\n> c* `d`\n\n- [e][]\n\n  [e]: <f> 'g&amp;'",
+            "# h\n\ni\n===\n\n    j",
+        ] {
+            let (expected, _) = parse(value, &options).unwrap();
+            // Only content is subtokenized here: string and text are left.
+            let (mut events, mut parse_state) = parse_blocks(value, &options).unwrap();
+
+            loop {
+                let mut result = subtokenize(&mut events, &parse_state, &None).unwrap();
+                parse_state.definitions.append(&mut result.definitions);
+                if result.done {
+                    break;
+                }
+            }
+
+            assert_eq!(
+                events
+                    .iter()
+                    .map(|event| (&event.kind, &event.name, event.point.index))
+                    .collect::<Vec<_>>(),
+                expected
+                    .iter()
+                    .map(|event| (&event.kind, &event.name, event.point.index))
+                    .collect::<Vec<_>>(),
+                "should subtokenize string and text in documents ({:?})",
+                value
+            );
+        }
+    }
+}
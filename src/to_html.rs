@@ -2,18 +2,27 @@
 use crate::event::{Event, Kind, Name};
 use crate::mdast::AlignKind;
 use crate::util::{
+    attributes,
+    bidi::first_strong,
     character_reference::decode as decode_character_reference,
     constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
-    encode::encode,
+    emoji::decode_emoji,
+    encode::{control_characters, encode},
     gfm_tagfilter::gfm_tagfilter,
     infer::{gfm_table_align, list_loose},
+    line_ending::detect_line_ending,
     normalize_identifier::normalize_identifier,
-    sanitize_uri::{sanitize, sanitize_with_protocols},
+    ordered_list,
+    sanitize_uri::{is_allowed_protocol, sanitize},
     skip,
     slice::{Position, Slice},
 };
-use crate::{CompileOptions, LineEnding};
+use crate::{
+    frontmatter, headings, to_mdast, CompileOptions, ElementKind, LineEnding, Options,
+    ParseOptions, UrlKind,
+};
 use alloc::{
+    collections::BTreeSet,
     format,
     string::{String, ToString},
     vec,
@@ -82,24 +91,38 @@ struct CompileContext<'a> {
     bytes: &'a [u8],
     /// Configuration.
     options: &'a CompileOptions,
+    /// Extra emoji shortcodes.
+    emoji_shortcodes: &'a [(String, String)],
+    /// Configuration used to parse, to make syntax trees of headings.
+    parse_options: &'a ParseOptions,
     // Fields used by handlers to track the things they need to track to
     // compile markdown.
     /// Rank of heading (atx).
     heading_atx_rank: Option<usize>,
     /// Buffer of heading (setext) text.
     heading_setext_buffer: Option<String>,
+    /// Slugs of headings so far, if `heading_ids` is on.
+    heading_slugs: BTreeSet<String>,
     /// Whether raw (flow) (code (fenced), math (flow)) or code (indented) contains data.
     raw_flow_seen_data: Option<bool>,
     /// Number of raw (flow) fences.
     raw_flow_fences_count: Option<usize>,
     /// Whether we are in code (text).
     raw_text_inside: bool,
+    /// Whether we are in string content (destinations, titles, labels, info
+    /// and meta of raw (flow)).
+    string_inside: bool,
     /// Whether we are in image text.
     image_alt_inside: bool,
+    /// Whether we are in the text of a paragraph or heading (setext), where
+    /// line endings are soft breaks.
+    text_inside: bool,
     /// Marker of character reference.
     character_reference_marker: Option<u8>,
     /// Whether we are expecting the first list item marker.
     list_expect_first_marker: Option<bool>,
+    /// Whether the current list item is compact.
+    list_item_compact: bool,
     /// Stack of media (link, image).
     media_stack: Vec<Media>,
     /// Stack of containers.
@@ -110,6 +133,11 @@ struct CompileContext<'a> {
     gfm_footnote_definitions: Vec<(String, String)>,
     gfm_footnote_definition_calls: Vec<(String, usize)>,
     gfm_footnote_definition_stack: Vec<(usize, usize)>,
+    /// Identifiers of GFM footnote definitions, as used in HTML, which inline
+    /// footnotes must not use.
+    gfm_footnote_definition_ids: Vec<String>,
+    /// Stack of indices of inline footnotes into the calls.
+    inline_footnote_stack: Vec<usize>,
     /// Whether we are in a GFM table head.
     gfm_table_in_head: bool,
     /// Current GFM table alignment.
@@ -119,6 +147,11 @@ struct CompileContext<'a> {
     // Fields used to influance the current compilation.
     /// Ignore the next line ending.
     slurp_one_line_ending: bool,
+    /// Index of the event where the attribution in a block quote starts:
+    /// either the exit of a line ending, or the enter of a paragraph.
+    block_quote_citation: Option<usize>,
+    /// Whether we are in the attribution of a block quote.
+    block_quote_citation_inside: bool,
     /// Whether to encode HTML.
     encode_html: bool,
     // Configuration
@@ -136,35 +169,48 @@ impl<'a> CompileContext<'a> {
     fn new(
         events: &'a [Event],
         bytes: &'a [u8],
-        options: &'a CompileOptions,
+        options: &'a Options,
         line_ending: LineEnding,
+        mut buffer: String,
     ) -> CompileContext<'a> {
+        buffer.clear();
+
         CompileContext {
             events,
             bytes,
+            options: &options.compile,
+            emoji_shortcodes: &options.parse.emoji_shortcodes,
+            parse_options: &options.parse,
             heading_atx_rank: None,
             heading_setext_buffer: None,
+            heading_slugs: BTreeSet::new(),
             raw_flow_seen_data: None,
             raw_flow_fences_count: None,
             raw_text_inside: false,
+            string_inside: false,
             character_reference_marker: None,
             list_expect_first_marker: None,
+            list_item_compact: false,
             media_stack: vec![],
             definitions: vec![],
             gfm_footnote_definitions: vec![],
             gfm_footnote_definition_calls: vec![],
+            gfm_footnote_definition_ids: vec![],
             gfm_footnote_definition_stack: vec![],
+            inline_footnote_stack: vec![],
             gfm_table_in_head: false,
             gfm_table_align: None,
             gfm_table_column: 0,
             tight_stack: vec![],
             slurp_one_line_ending: false,
+            block_quote_citation: None,
+            block_quote_citation_inside: false,
             image_alt_inside: false,
+            text_inside: false,
             encode_html: true,
             line_ending_default: line_ending,
-            buffers: vec![String::new()],
+            buffers: vec![buffer],
             index: 0,
-            options,
         }
     }
 
@@ -197,6 +243,11 @@ impl<'a> CompileContext<'a> {
         let last_buf = last_buf_opt.expect("at least one buffer should exist");
         let last_byte = last_buf.as_bytes().last();
 
+        // No line ending directly after `<li>` in compact list items.
+        if self.list_item_compact && last_buf.ends_with("<li>") {
+            return;
+        }
+
         if !matches!(last_byte, None | Some(b'\n' | b'\r')) {
             self.line_ending();
         }
@@ -204,31 +255,22 @@ impl<'a> CompileContext<'a> {
 }
 
 /// Turn events and bytes into a string of HTML.
-pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> String {
-    let mut index = 0;
-    let mut line_ending_inferred = None;
+pub fn compile(events: &[Event], bytes: &[u8], options: &Options) -> String {
+    compile_into(events, bytes, options, String::new())
+}
 
+/// Turn events and bytes into a string of HTML, reusing the memory of
+/// `buffer` (which is cleared first).
+pub fn compile_into(events: &[Event], bytes: &[u8], options: &Options, buffer: String) -> String {
     // First, we figure out what the used line ending style is.
-    // Stop when we find a line ending.
-    while index < events.len() {
-        let event = &events[index];
-
-        if event.kind == Kind::Exit
-            && (event.name == Name::BlankLineEnding || event.name == Name::LineEnding)
-        {
-            let slice = Slice::from_position(bytes, &Position::from_exit_event(events, index));
-            line_ending_inferred = Some(slice.as_str().parse().unwrap());
-            break;
-        }
-
-        index += 1;
-    }
+    let line_ending_inferred = detect_line_ending(bytes);
 
     // Figure out which line ending style we’ll use.
     let line_ending_default =
-        line_ending_inferred.unwrap_or_else(|| options.default_line_ending.clone());
+        line_ending_inferred.unwrap_or_else(|| options.compile.default_line_ending.clone());
+
+    let mut context = CompileContext::new(events, bytes, options, line_ending_default, buffer);
 
-    let mut context = CompileContext::new(events, bytes, options, line_ending_default);
     let mut definition_indices = vec![];
     let mut index = 0;
     let mut definition_inside = false;
@@ -260,6 +302,11 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         } else if event.name == Name::Definition {
             definition_inside = false;
             definition_indices.last_mut().unwrap().1 = index;
+        } else if event.name == Name::GfmFootnoteDefinitionLabelString {
+            let id = normalize_identifier(
+                Slice::from_position(bytes, &Position::from_exit_event(events, index)).as_str(),
+            );
+            context.gfm_footnote_definition_ids.push(id.to_lowercase());
         }
 
         index += 1;
@@ -291,17 +338,32 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
     }
 
     debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
-    context
-        .buffers
-        .get(0)
-        .expect("expected 1 final buffer")
-        .into()
+    control_characters(
+        context.buffers.pop().expect("expected 1 final buffer"),
+        &context.options.control_character_policy,
+    )
 }
 
 /// Handle the event at `index`.
 fn handle(context: &mut CompileContext, index: usize) {
     context.index = index;
 
+    if matches!(
+        context.events[index].name,
+        Name::CodeFencedFenceInfo
+            | Name::CodeFencedFenceMeta
+            | Name::DefinitionDestinationString
+            | Name::DefinitionLabelString
+            | Name::DefinitionTitleString
+            | Name::GfmFootnoteDefinitionLabelString
+            | Name::MathFlowFenceMeta
+            | Name::ReferenceString
+            | Name::ResourceDestinationString
+            | Name::ResourceTitleString
+    ) {
+        context.string_inside = context.events[index].kind == Kind::Enter;
+    }
+
     if context.events[index].kind == Kind::Enter {
         enter(context);
     } else {
@@ -317,9 +379,11 @@ fn enter(context: &mut CompileContext) {
         | Name::MathFlowFenceMeta
         | Name::DefinitionLabelString
         | Name::DefinitionTitleString
+        | Name::DirectiveContainer
+        | Name::DirectiveLeaf
+        | Name::DirectiveText
         | Name::GfmFootnoteDefinitionPrefix
         | Name::HeadingAtxText
-        | Name::HeadingSetextText
         | Name::Label
         | Name::MdxEsm
         | Name::MdxFlowExpression
@@ -336,6 +400,7 @@ fn enter(context: &mut CompileContext) {
         Name::Definition => on_enter_definition(context),
         Name::DefinitionDestinationString => on_enter_definition_destination_string(context),
         Name::Emphasis => on_enter_emphasis(context),
+        Name::FencedDiv => on_enter_fenced_div(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmFootnoteDefinition => on_enter_gfm_footnote_definition(context),
         Name::GfmFootnoteCall => on_enter_gfm_footnote_call(context),
@@ -349,9 +414,11 @@ fn enter(context: &mut CompileContext) {
         Name::HtmlFlow => on_enter_html_flow(context),
         Name::HtmlText => on_enter_html_text(context),
         Name::Image => on_enter_image(context),
+        Name::InlineFootnote => on_enter_inline_footnote(context),
         Name::Link => on_enter_link(context),
         Name::ListItemMarker => on_enter_list_item_marker(context),
         Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
+        Name::HeadingSetextText => on_enter_heading_setext_text(context),
         Name::Paragraph => on_enter_paragraph(context),
         Name::Resource => on_enter_resource(context),
         Name::ResourceDestinationString => on_enter_resource_destination_string(context),
@@ -364,13 +431,17 @@ fn enter(context: &mut CompileContext) {
 fn exit(context: &mut CompileContext) {
     match context.events[context.index].name {
         Name::CodeFencedFenceMeta
+        | Name::DirectiveContainer
+        | Name::DirectiveText
         | Name::MathFlowFenceMeta
         | Name::MdxJsxTextTag
         | Name::MdxTextExpression
         | Name::Resource => {
             on_exit_drop(context);
         }
-        Name::MdxEsm | Name::MdxFlowExpression | Name::MdxJsxFlowTag => on_exit_drop_slurp(context),
+        Name::DirectiveLeaf | Name::MdxEsm | Name::MdxFlowExpression | Name::MdxJsxFlowTag => {
+            on_exit_drop_slurp(context);
+        }
         Name::CharacterEscapeValue | Name::CodeTextData | Name::Data | Name::MathTextData => {
             on_exit_data(context);
         }
@@ -386,6 +457,7 @@ fn exit(context: &mut CompileContext) {
             on_exit_character_reference_marker_hexadecimal(context);
         }
         Name::CharacterReferenceValue => on_exit_character_reference_value(context),
+        Name::EmojiValue => on_exit_emoji_value(context),
         Name::CodeFenced | Name::CodeIndented | Name::MathFlow => on_exit_raw_flow(context),
         Name::CodeFencedFence | Name::MathFlowFence => on_exit_raw_flow_fence(context),
         Name::CodeFencedFenceInfo => on_exit_raw_flow_fence_info(context),
@@ -396,6 +468,7 @@ fn exit(context: &mut CompileContext) {
         Name::DefinitionLabelString => on_exit_definition_label_string(context),
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
         Name::Emphasis => on_exit_emphasis(context),
+        Name::FencedDiv => on_exit_fenced_div(context),
         Name::Frontmatter => on_exit_frontmatter(context),
         Name::GfmAutolinkLiteralEmail => on_exit_gfm_autolink_literal_email(context),
         Name::GfmAutolinkLiteralMailto => on_exit_gfm_autolink_literal_mailto(context),
@@ -425,6 +498,7 @@ fn exit(context: &mut CompileContext) {
         Name::HtmlFlow | Name::HtmlText => on_exit_html(context),
         Name::HtmlFlowData | Name::HtmlTextData => on_exit_html_data(context),
         Name::Image | Name::Link => on_exit_media(context),
+        Name::InlineFootnote => on_exit_inline_footnote(context),
         Name::Label => on_exit_label(context),
         Name::LabelText => on_exit_label_text(context),
         Name::LineEnding => on_exit_line_ending(context),
@@ -452,25 +526,49 @@ fn on_enter_buffer(context: &mut CompileContext) {
 fn on_enter_block_quote(context: &mut CompileContext) {
     context.tight_stack.push(false);
     context.line_ending_if_needed();
-    context.push("<blockquote>");
+    context.push("<blockquote");
+    push_element_attributes(context, ElementKind::BlockQuote, context.index);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeIndented`][Name::CodeIndented].
 fn on_enter_code_indented(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
-    context.push("<pre><code>");
+    context.push("<pre");
+    push_element_attributes(context, ElementKind::Code, context.index);
+    context.push("><code>");
 }
 
 /// Handle [`Enter`][Kind::Enter]:{[`CodeFenced`][Name::CodeFenced],[`MathFlow`][Name::MathFlow]}.
 fn on_enter_raw_flow(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
+    let math = context.events[context.index].name == Name::MathFlow;
+
+    // Rendered math is not compiled as code: ignore what is compiled, and get
+    // the value from events when exiting.
+    if math && context.options.math_renderer.is_some() {
+        context.raw_flow_fences_count = Some(0);
+        context.buffer();
+        return;
+    }
+
+    context.push("<pre");
+    push_element_attributes(
+        context,
+        if math {
+            ElementKind::Math
+        } else {
+            ElementKind::Code
+        },
+        context.index,
+    );
     // Note that no `>` is used, which is added later (due to info)
-    context.push("<pre><code");
+    context.push("><code");
     context.raw_flow_fences_count = Some(0);
 
-    if context.events[context.index].name == Name::MathFlow {
+    if math {
         context.push(" class=\"language-math math-display\"");
     }
 }
@@ -478,7 +576,14 @@ fn on_enter_raw_flow(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
 fn on_enter_raw_text(context: &mut CompileContext) {
     context.raw_text_inside = true;
-    if !context.image_alt_inside {
+
+    // Rendered math gets its value unencoded.
+    if context.events[context.index].name == Name::MathText
+        && context.options.math_renderer.is_some()
+        && !context.image_alt_inside
+    {
+        context.encode_html = false;
+    } else if !context.image_alt_inside {
         context.push("<code");
         if context.events[context.index].name == Name::MathText {
             context.push(" class=\"language-math math-inline\"");
@@ -514,6 +619,72 @@ fn on_enter_emphasis(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`FencedDiv`][Name::FencedDiv].
+fn on_enter_fenced_div(context: &mut CompileContext) {
+    let mut index = context.index + 1;
+    let mut value = None;
+
+    // Find the attributes in the opening fence.
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == Name::FencedDivFence {
+                break;
+            }
+
+            if event.name == Name::FencedDivAttributes {
+                value = Some(
+                    Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, index),
+                    )
+                    .serialize(),
+                );
+            }
+        }
+
+        index += 1;
+    }
+
+    let attributes = attributes::parse(value.as_deref().unwrap_or_default());
+
+    context.tight_stack.push(false);
+    context.line_ending_if_needed();
+    context.push("<div");
+
+    if let Some(id) = attributes.id {
+        context.push(" id=\"");
+        context.push(&encode(&id, context.encode_html));
+        context.push("\"");
+    }
+
+    if !attributes.classes.is_empty() {
+        context.push(" class=\"");
+        context.push(&encode(&attributes.classes.join(" "), context.encode_html));
+        context.push("\"");
+    }
+
+    for (name, value) in attributes.other {
+        // Event handlers are dangerous.
+        if !context.options.allow_dangerous_html
+            && name.len() > 2
+            && name.as_bytes()[..2].eq_ignore_ascii_case(b"on")
+        {
+            continue;
+        }
+
+        context.push(" ");
+        context.push(&name);
+        context.push("=\"");
+        context.push(&encode(&value, context.encode_html));
+        context.push("\"");
+    }
+
+    push_element_attributes(context, ElementKind::FencedDiv, context.index);
+    context.push(">");
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Frontmatter`][Name::Frontmatter].
 fn on_enter_frontmatter(context: &mut CompileContext) {
     context.buffer();
@@ -548,7 +719,9 @@ fn on_enter_gfm_table(context: &mut CompileContext) {
     let align = gfm_table_align(context.events, context.index);
     context.gfm_table_align = Some(align);
     context.line_ending_if_needed();
-    context.push("<table>");
+    context.push("<table");
+    push_element_attributes(context, ElementKind::Table, context.index);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmTableBody`][Name::GfmTableBody].
@@ -605,6 +778,12 @@ fn on_enter_gfm_task_list_item_check(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`HeadingSetextText`][Name::HeadingSetextText].
+fn on_enter_heading_setext_text(context: &mut CompileContext) {
+    context.buffer();
+    context.text_inside = true;
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`HtmlFlow`][Name::HtmlFlow].
 fn on_enter_html_flow(context: &mut CompileContext) {
     context.line_ending_if_needed();
@@ -633,6 +812,29 @@ fn on_enter_image(context: &mut CompileContext) {
     context.image_alt_inside = true; // Disallow tags.
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_enter_inline_footnote(context: &mut CompileContext) {
+    context.media_stack.push(Media {
+        image: false,
+        label_id: None,
+        label: None,
+        reference_id: None,
+        destination: None,
+        title: None,
+    });
+    // Add the call now, so that it is numbered before calls in its content.
+    let call_index = context.gfm_footnote_definition_calls.len();
+    let mut id = format!("inline-{}", call_index + 1);
+
+    // Do not clash with the `id`s of footnote definitions (or their calls).
+    while inline_footnote_id_taken(&context.gfm_footnote_definition_ids, &id) {
+        id.insert(0, '_');
+    }
+
+    context.gfm_footnote_definition_calls.push((id, 1));
+    context.inline_footnote_stack.push(call_index);
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
     context.media_stack.push(Media {
@@ -652,11 +854,13 @@ fn on_enter_list(context: &mut CompileContext) {
     context.line_ending_if_needed();
 
     // Note: no `>`.
-    context.push(if context.events[context.index].name == Name::ListOrdered {
-        "<ol"
+    if context.events[context.index].name == Name::ListOrdered {
+        context.push("<ol");
+        push_element_attributes(context, ElementKind::OrderedList, context.index);
     } else {
-        "<ul"
-    });
+        context.push("<ul");
+        push_element_attributes(context, ElementKind::UnorderedList, context.index);
+    }
     context.list_expect_first_marker = Some(true);
 }
 
@@ -668,17 +872,66 @@ fn on_enter_list_item_marker(context: &mut CompileContext) {
 
     context.line_ending_if_needed();
 
-    context.push("<li>");
+    context.push("<li");
+    push_dir(
+        context,
+        skip::to_back(context.events, context.index, &[Name::ListItem]),
+    );
+    push_element_attributes(
+        context,
+        ElementKind::ListItem,
+        skip::to_back(context.events, context.index, &[Name::ListItem]),
+    );
+    context.push(">");
     context.list_expect_first_marker = Some(false);
+    context.list_item_compact =
+        context.options.compact_list_items && *context.tight_stack.last().unwrap_or(&false);
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`Paragraph`][Name::Paragraph].
 fn on_enter_paragraph(context: &mut CompileContext) {
+    context.text_inside = true;
     let tight = context.tight_stack.last().unwrap_or(&false);
 
-    if !tight {
+    if *tight {
+        // The rest of the line of the closing fence of a fenced div is a blank
+        // line ending, which is not compiled, so add a line ending.
+        let before = skip::opt_back(
+            context.events,
+            context.index - 1,
+            &[Name::BlankLineEnding, Name::SpaceOrTab],
+        );
+
+        if context.events[before].name == Name::FencedDiv {
+            context.line_ending_if_needed();
+        }
+    } else {
         context.line_ending_if_needed();
-        context.push("<p>");
+        context.block_quote_citation = block_quote_citation(context, context.index);
+
+        if context.block_quote_citation == Some(context.index) {
+            context.block_quote_citation_inside = true;
+            context.push("<footer");
+            push_source_position(
+                context,
+                context.index,
+                skip::counterpart(context.events, context.index),
+            );
+            context.push(">");
+        } else {
+            context.push("<p");
+            push_dir(context, context.index);
+
+            // Without the attribution, which is compiled separately.
+            if let Some(line_ending) = context.block_quote_citation {
+                push_source_position(context, context.index, line_ending - 1);
+                push_function_attributes(context, ElementKind::Paragraph);
+            } else {
+                push_element_attributes(context, ElementKind::Paragraph, context.index);
+            }
+
+            context.push(">");
+        }
     }
 }
 
@@ -734,7 +987,11 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:{[`HardBreakEscape`][Name::HardBreakEscape],[`HardBreakTrailing`][Name::HardBreakTrailing]}.
 fn on_exit_break(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("<br />");
+        if let Some(ref value) = context.options.hard_break_markup {
+            context.push(value);
+        } else {
+            context.push("<br />");
+        }
     }
 }
 
@@ -784,6 +1041,18 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     context.push(&encode(&value, context.encode_html));
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`EmojiValue`][Name::EmojiValue].
+fn on_exit_emoji_value(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+    );
+    let value = decode_emoji(slice.as_str(), context.emoji_shortcodes)
+        .expect("expected to parse only known emoji");
+
+    context.push(&encode(value, context.encode_html));
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFlowChunk`][Name::CodeFlowChunk],[`MathFlowChunk`][Name::MathFlowChunk]}.
 fn on_exit_raw_flow_chunk(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(true);
@@ -824,6 +1093,24 @@ fn on_exit_raw_flow_fence_info(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
 fn on_exit_raw_flow(context: &mut CompileContext) {
+    if let Some(math_renderer) = &context.options.math_renderer {
+        if context.events[context.index].name == Name::MathFlow {
+            context.resume();
+            let html = math_renderer(&math_flow_value(context), true);
+            context.push(&html);
+
+            if let Some(count) = context.raw_flow_fences_count.take() {
+                if count < 2 {
+                    context.line_ending_if_needed();
+                }
+            }
+
+            context.raw_flow_seen_data = None;
+            context.slurp_one_line_ending = false;
+            return;
+        }
+    }
+
     // One special case is if we are inside a container, and the raw (flow) was
     // not closed (meaning it runs to the end).
     // In that case, the following line ending, is considered *outside* the
@@ -843,11 +1130,26 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
 
     // But in most cases, it’s simpler: when we’ve seen some data, emit an extra
     // line ending when needed.
-    if context
+    let seen_data = context
         .raw_flow_seen_data
         .take()
-        .expect("`raw_flow_seen_data` must be defined")
-    {
+        .expect("`raw_flow_seen_data` must be defined");
+
+    // Unless the final line ending is removed.
+    if context.options.trim_code_trailing_newline {
+        let buf = context
+            .buffers
+            .last_mut()
+            .expect("at least one buffer should exist");
+
+        if buf.ends_with('\n') {
+            buf.pop();
+        }
+
+        if buf.ends_with('\r') {
+            buf.pop();
+        }
+    } else if seen_data {
         context.line_ending_if_needed();
     }
 
@@ -906,6 +1208,16 @@ fn on_exit_raw_text(context: &mut CompileContext) {
     }
 
     context.raw_text_inside = false;
+
+    if !context.encode_html {
+        if let Some(math_renderer) = &context.options.math_renderer {
+            context.encode_html = true;
+            let html = math_renderer(str::from_utf8(&bytes).unwrap(), false);
+            context.push(&html);
+            return;
+        }
+    }
+
     context.push(str::from_utf8(&bytes).unwrap());
 
     if !context.image_alt_inside {
@@ -913,6 +1225,59 @@ fn on_exit_raw_text(context: &mut CompileContext) {
     }
 }
 
+/// Get the value of the math (flow) exited at the current event.
+///
+/// Like in mdast, this is the content without the line endings after the
+/// opening fence and before the closing fence.
+fn math_flow_value(context: &CompileContext) -> String {
+    let mut index = skip::to_back(context.events, context.index - 1, &[Name::MathFlow]);
+    let mut value = String::new();
+    let mut fence_seen = false;
+
+    while index < context.index {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            match event.name {
+                Name::MathFlowFence => fence_seen = true,
+                Name::MathFlowChunk => value.push_str(
+                    // Must serialize to get virtual spaces.
+                    &Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, index),
+                    )
+                    .serialize(),
+                ),
+                Name::LineEnding if fence_seen => value.push_str(
+                    Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, index),
+                    )
+                    .as_str(),
+                ),
+                _ => {}
+            }
+        }
+
+        index += 1;
+    }
+
+    trim_eol(&value)
+}
+
+/// Remove one line ending from the start and from the end of `value`.
+fn trim_eol(value: &str) -> String {
+    let value = value
+        .strip_prefix("\r\n")
+        .or_else(|| value.strip_prefix(['\r', '\n']))
+        .unwrap_or(value);
+    let value = value
+        .strip_suffix("\r\n")
+        .or_else(|| value.strip_suffix(['\r', '\n']))
+        .unwrap_or(value);
+    value.into()
+}
+
 /// Handle [`Exit`][Kind::Exit]:*.
 ///
 /// Resumes, and ignores what was resumed.
@@ -930,14 +1295,20 @@ fn on_exit_drop_slurp(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeTextData`][Name::CodeTextData],[`Data`][Name::Data],[`CharacterEscapeValue`][Name::CharacterEscapeValue]}.
 fn on_exit_data(context: &mut CompileContext) {
-    context.push(&encode(
-        Slice::from_position(
-            context.bytes,
-            &Position::from_exit_event(context.events, context.index),
-        )
-        .as_str(),
-        context.encode_html,
-    ));
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+    );
+
+    if let (Name::Data, false, Some(transform)) = (
+        &context.events[context.index].name,
+        context.string_inside,
+        &context.options.text_transform,
+    ) {
+        context.push(&encode(&transform(slice.as_str()), context.encode_html));
+    } else {
+        context.push(&encode(slice.as_str(), context.encode_html));
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`Definition`][Name::Definition].
@@ -983,10 +1354,34 @@ fn on_exit_emphasis(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`FencedDiv`][Name::FencedDiv].
+fn on_exit_fenced_div(context: &mut CompileContext) {
+    context.tight_stack.pop();
+    context.line_ending_if_needed();
+    context.slurp_one_line_ending = false;
+    context.push("</div>");
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) {
     context.resume();
-    context.slurp_one_line_ending = true;
+
+    let position = Position::from_exit_event(context.events, context.index);
+    // YAML frontmatter starts with `-`, TOML with `+`.
+    let title = if context.options.frontmatter_title && context.bytes[position.start.index] == b'-'
+    {
+        frontmatter::title(Slice::from_position(context.bytes, &position).as_str())
+    } else {
+        None
+    };
+
+    if let Some(title) = title {
+        context.push("<h1>");
+        context.push(&encode(&title, context.encode_html));
+        context.push("</h1>");
+    } else {
+        context.slurp_one_line_ending = true;
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmAutolinkLiteralEmail`][Name::GfmAutolinkLiteralEmail].
@@ -1064,7 +1459,6 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
     let indices = context.media_stack.pop().unwrap().label_id.unwrap();
     let id =
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str());
-    let safe_id = sanitize(&id.to_lowercase());
     let mut call_index = 0;
 
     // See if this has been called before.
@@ -1085,10 +1479,45 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
 
     // No call is output in an image alt, though the definition and
     // backreferences are generated as if it was the case.
-    if context.image_alt_inside {
-        return;
+    if !context.image_alt_inside {
+        generate_footnote_call(context, call_index);
     }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_exit_inline_footnote(context: &mut CompileContext) {
+    let label = context.media_stack.pop().unwrap().label.unwrap();
+    let call_index = context.inline_footnote_stack.pop().unwrap();
+    context.gfm_footnote_definitions.push((
+        context.gfm_footnote_definition_calls[call_index].0.clone(),
+        format!("<p>{}</p>", label),
+    ));
+
+    if !context.image_alt_inside {
+        generate_footnote_call(context, call_index);
+    }
+}
+
+/// Check whether the `id` of an inline footnote is used by the footnote
+/// definitions with `ids`, or by a second or later call to them (which have
+/// `-2`, `-3`, and so on after the `id` of their definition).
+fn inline_footnote_id_taken(ids: &[String], id: &str) -> bool {
+    ids.iter().any(|d| {
+        d == id
+            || matches!(
+                id.strip_prefix(d.as_str()).and_then(|rest| rest.strip_prefix('-')),
+                Some(count) if count.parse::<usize>().map_or(false, |count| count > 1)
+            )
+    })
+}
 
+/// Generate a footnote call.
+fn generate_footnote_call(context: &mut CompileContext, call_index: usize) {
+    let safe_id = sanitize(
+        &context.gfm_footnote_definition_calls[call_index]
+            .0
+            .to_lowercase(),
+    );
     context.push("<sup><a href=\"#");
     if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
         context.push(&encode(value, context.encode_html));
@@ -1113,7 +1542,11 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
                 .to_string(),
         );
     }
-    context.push("\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">");
+    context.push("\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\"");
+    if context.options.gfm_footnote_roles {
+        context.push(" role=\"doc-noteref\"");
+    }
+    context.push(">");
 
     context.push(&(call_index + 1).to_string());
     context.push("</a></sup>");
@@ -1247,6 +1680,12 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
         context.heading_atx_rank = Some(rank);
         context.push("<h");
         context.push(&rank.to_string());
+        let enter = skip::to_back(context.events, context.index, &[Name::HeadingAtx]);
+        push_heading_id(context, enter);
+        push_dir(context, enter);
+        // Rank is at most `6`.
+        #[allow(clippy::cast_possible_truncation)]
+        push_element_attributes(context, ElementKind::Heading(rank as u8), enter);
         context.push(">");
     }
 }
@@ -1259,6 +1698,7 @@ fn on_exit_heading_atx_text(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`HeadingSetextText`][Name::HeadingSetextText].
 fn on_exit_heading_setext_text(context: &mut CompileContext) {
+    context.text_inside = false;
     let buf = context.resume();
     context.heading_setext_buffer = Some(buf);
     context.slurp_one_line_ending = true;
@@ -1272,15 +1712,19 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
         .expect("`heading_atx_rank` must be set in headings");
     let position = Position::from_exit_event(context.events, context.index);
     let head = context.bytes[position.start.index];
-    let rank = if head == b'-' { "2" } else { "1" };
+    let rank = if head == b'-' { 2 } else { 1 };
 
     context.line_ending_if_needed();
     context.push("<h");
-    context.push(rank);
+    context.push(&rank.to_string());
+    let enter = skip::to_back(context.events, context.index, &[Name::HeadingSetext]);
+    push_heading_id(context, enter);
+    push_dir(context, enter);
+    push_element_attributes(context, ElementKind::Heading(rank), enter);
     context.push(">");
     context.push(&text);
     context.push("</h");
-    context.push(rank);
+    context.push(&rank.to_string());
     context.push(">");
 }
 
@@ -1330,6 +1774,26 @@ fn on_exit_line_ending(context: &mut CompileContext) {
     {
         context.slurp_one_line_ending = false;
     } else {
+        let citation = context.block_quote_citation == Some(context.index);
+
+        if citation {
+            context.push("</p>");
+        } else if context.options.breaks
+            && context.text_inside
+            && !context.string_inside
+            && !context.image_alt_inside
+            && !matches!(
+                context.events[context.index - 2].name,
+                Name::HardBreakEscape | Name::HardBreakTrailing | Name::HtmlTextData
+            )
+        {
+            if let Some(ref value) = context.options.hard_break_markup {
+                context.push(value);
+            } else {
+                context.push("<br />");
+            }
+        }
+
         context.push(&encode(
             Slice::from_position(
                 context.bytes,
@@ -1338,6 +1802,17 @@ fn on_exit_line_ending(context: &mut CompileContext) {
             .as_str(),
             context.encode_html,
         ));
+
+        if citation {
+            context.block_quote_citation_inside = true;
+            context.push("<footer");
+            push_source_position(
+                context,
+                skip_citation_whitespace(context.events, context.index + 1, false),
+                paragraph_exit(context.events, context.index),
+            );
+            context.push(">");
+        }
     }
 }
 
@@ -1365,16 +1840,19 @@ fn on_exit_list_item(context: &mut CompileContext) {
             Name::SpaceOrTab,
             // Also ignore things that don’t contribute to the document.
             Name::Definition,
+            Name::DirectiveContainer,
+            Name::DirectiveLeaf,
             Name::GfmFootnoteDefinition,
         ],
     );
     let previous = &context.events[before_item];
     let tight_paragraph = *tight && previous.name == Name::Paragraph;
     let empty_item = previous.name == Name::ListItemPrefix;
+    let compact_item = *tight && context.options.compact_list_items;
 
     context.slurp_one_line_ending = false;
 
-    if !tight_paragraph && !empty_item {
+    if !tight_paragraph && !empty_item && !compact_item {
         context.line_ending_if_needed();
     }
 
@@ -1388,7 +1866,14 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
         );
-        let value = slice.as_str().parse::<u32>().ok().unwrap();
+        let kind = ordered_list::kind(slice.bytes);
+        let value = ordered_list::number(slice.bytes, kind);
+
+        if kind != ordered_list::Kind::Decimal {
+            context.push(" type=\"");
+            context.push(kind.as_str());
+            context.push("\"");
+        }
 
         if value != 1 {
             context.push(" start=\"");
@@ -1439,32 +1924,36 @@ fn on_exit_media(context: &mut CompileContext) {
         None
     };
 
-    if !is_in_image {
+    let destination = if let Some(index) = definition_index {
+        context.definitions[index].destination.clone()
+    } else {
+        media.destination
+    };
+
+    // Links to protocols that are not allowed are turned into their text.
+    let as_text = !media.image
+        && destination.as_ref().map_or(false, |destination| {
+            !is_link_protocol_allowed(context, destination)
+        });
+    let as_element = !is_in_image && !as_text;
+
+    if as_element {
         if media.image {
             context.push("<img src=\"");
         } else {
             context.push("<a href=\"");
         };
 
-        let destination = if let Some(index) = definition_index {
-            context.definitions[index].destination.as_ref()
-        } else {
-            media.destination.as_ref()
-        };
-
         if let Some(destination) = destination {
-            let url = if context.options.allow_dangerous_protocol {
-                sanitize(destination)
-            } else {
-                sanitize_with_protocols(
-                    destination,
-                    if media.image {
-                        &SAFE_PROTOCOL_SRC
-                    } else {
-                        &SAFE_PROTOCOL_HREF
-                    },
-                )
-            };
+            let url = sanitize_url(
+                context,
+                &destination,
+                if media.image {
+                    UrlKind::Image
+                } else {
+                    UrlKind::Link
+                },
+            );
             context.push(&url);
         }
 
@@ -1477,7 +1966,7 @@ fn on_exit_media(context: &mut CompileContext) {
         context.push(&label);
     }
 
-    if !is_in_image {
+    if as_element {
         context.push("\"");
 
         let title = if let Some(index) = definition_index {
@@ -1502,7 +1991,7 @@ fn on_exit_media(context: &mut CompileContext) {
     if !media.image {
         context.push(&label);
 
-        if !is_in_image {
+        if as_element {
             context.push("</a>");
         }
     }
@@ -1510,11 +1999,17 @@ fn on_exit_media(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`Paragraph`][Name::Paragraph].
 fn on_exit_paragraph(context: &mut CompileContext) {
+    context.text_inside = false;
     let tight = context.tight_stack.last().unwrap_or(&false);
 
     if *tight {
         context.slurp_one_line_ending = true;
+    } else if context.block_quote_citation_inside {
+        context.block_quote_citation = None;
+        context.block_quote_citation_inside = false;
+        context.push("</footer>");
     } else {
+        context.block_quote_citation = None;
         context.push("</p>");
     }
 }
@@ -1551,13 +2046,19 @@ fn on_exit_strong(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`ThematicBreak`][Name::ThematicBreak].
 fn on_exit_thematic_break(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<hr />");
+    context.push("<hr");
+    push_element_attributes(context, ElementKind::ThematicBreak, context.index);
+    context.push(" />");
 }
 
 /// Generate a footnote section.
 fn generate_footnote_section(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<section data-footnotes=\"\" class=\"footnotes\"><");
+    context.push("<section data-footnotes=\"\" class=\"footnotes\"");
+    if context.options.gfm_footnote_roles {
+        context.push(" role=\"doc-endnotes\"");
+    }
+    context.push("><");
     if let Some(ref value) = context.options.gfm_footnote_label_tag_name {
         context.push(&encode(value, context.encode_html));
     } else {
@@ -1655,7 +2156,11 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         } else {
             backreferences.push_str("Back to content");
         }
-        backreferences.push_str("\" class=\"data-footnote-backref\">↩");
+        backreferences.push('"');
+        if context.options.gfm_footnote_roles {
+            backreferences.push_str(" role=\"doc-backlink\"");
+        }
+        backreferences.push_str(" class=\"data-footnote-backref\">↩");
         if reference_index != 0 {
             backreferences.push_str("<sup>");
             backreferences.push_str(&(reference_index + 1).to_string());
@@ -1718,19 +2223,19 @@ fn generate_autolink(
         index += 1;
     }
 
-    if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
+    let url = if let Some(protocol) = protocol {
+        format!("{}{}", protocol, value)
+    } else {
+        value.into()
+    };
+    let as_element = !context.image_alt_inside
+        && (!is_in_link || !is_gfm_literal)
+        && is_link_protocol_allowed(context, &url);
+
+    if as_element {
         context.push("<a href=\"");
-        let url = if let Some(protocol) = protocol {
-            format!("{}{}", protocol, value)
-        } else {
-            value.into()
-        };
 
-        let url = if context.options.allow_dangerous_protocol {
-            sanitize(&url)
-        } else {
-            sanitize_with_protocols(&url, &SAFE_PROTOCOL_HREF)
-        };
+        let url = sanitize_url(context, &url, UrlKind::Link);
 
         context.push(&url);
         context.push("\">");
@@ -1738,7 +2243,327 @@ fn generate_autolink(
 
     context.push(&encode(value, context.encode_html));
 
-    if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
+    if as_element {
         context.push("</a>");
     }
 }
+
+/// Add an `id` attribute for the heading entered at `enter`, if
+/// [`heading_ids`][crate::CompileOptions::heading_ids] is on.
+///
+/// Headings are compiled in document order, so slugs are made here, one
+/// heading at a time, from the syntax tree of just that heading, which gives
+/// the same slugs as `to_headings`.
+/// With MDX, making the tree can fail, in which case no `id` is added.
+fn push_heading_id(context: &mut CompileContext, enter: usize) {
+    if !context.options.heading_ids {
+        return;
+    }
+
+    let exit = skip::counterpart(context.events, enter);
+
+    if let Ok(tree) = to_mdast::compile(
+        &context.events[enter..=exit],
+        context.bytes,
+        context.parse_options,
+    ) {
+        if let Some(heading) = tree.children().and_then(|children| children.first()) {
+            let slug = headings::slug_heading(
+                heading,
+                &context.options.slug_policy,
+                &mut context.heading_slugs,
+            );
+            let value = encode(&slug, context.encode_html);
+            context.push(" id=\"");
+            context.push(&value);
+            context.push("\"");
+        }
+    }
+}
+
+/// Add the attributes that
+/// [`element_attributes`][crate::CompileOptions::element_attributes] gives
+/// for an element of `kind`, if it is passed, and where the element is, if
+/// [`source_positions`][crate::CompileOptions::source_positions] is on.
+///
+/// `index` is the enter or exit event of the element.
+fn push_element_attributes(context: &mut CompileContext, kind: ElementKind, index: usize) {
+    if context.options.source_positions {
+        let other = skip::counterpart(context.events, index);
+
+        if index < other {
+            push_source_position(context, index, other);
+        } else {
+            push_source_position(context, other, index);
+        }
+    }
+
+    push_function_attributes(context, kind);
+}
+
+/// Add the attributes that
+/// [`element_attributes`][crate::CompileOptions::element_attributes] gives
+/// for an element of `kind`, if it is passed.
+fn push_function_attributes(context: &mut CompileContext, kind: ElementKind) {
+    if let Some(element_attributes) = &context.options.element_attributes {
+        for (name, value) in element_attributes(kind) {
+            context.push(" ");
+            context.push(&name);
+            context.push("=\"");
+            context.push(&encode(&value, context.encode_html));
+            context.push("\"");
+        }
+    }
+}
+
+/// Add where the source from the event at `enter` to the event at `exit` is,
+/// if [`source_positions`][crate::CompileOptions::source_positions] is on.
+fn push_source_position(context: &mut CompileContext, enter: usize, mut exit: usize) {
+    if !context.options.source_positions {
+        return;
+    }
+
+    // Trailing line endings and whitespace (such as blank lines at the end
+    // of list items) are not part of where the element is.
+    // Exits of children at the same place are skipped to find them.
+    while exit - 1 > enter && {
+        let previous = &context.events[exit - 1];
+        matches!(
+            previous.name,
+            Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+        ) || (previous.kind == Kind::Exit
+            && previous.point.index == context.events[exit].point.index
+            && previous.point.vs == context.events[exit].point.vs)
+    } {
+        exit -= 1;
+    }
+
+    let start = &context.events[enter].point;
+    let end = &context.events[exit].point;
+    // The end is inclusive, so it is the character before the exit.
+    context.push(&format!(
+        " data-sourcepos=\"{}:{}-{}:{}\"",
+        start.line,
+        start.column,
+        end.line,
+        end.column.saturating_sub(1).max(1)
+    ));
+}
+
+/// Add a `dir` attribute for the text in the element entered at `enter`, if
+/// [`auto_dir`][crate::CompileOptions::auto_dir] is on.
+///
+/// Destinations, titles, and labels of references are not text, and are
+/// ignored.
+fn push_dir(context: &mut CompileContext, enter: usize) {
+    if !context.options.auto_dir {
+        return;
+    }
+
+    let mut index = enter;
+    let mut balance = 0;
+    let mut ignore = 0;
+
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Enter {
+            balance += 1;
+
+            if matches!(event.name, Name::Reference | Name::Resource) {
+                ignore += 1;
+            }
+        } else {
+            balance -= 1;
+
+            if balance == 0 {
+                break;
+            }
+
+            if matches!(event.name, Name::Reference | Name::Resource) {
+                ignore -= 1;
+            } else if ignore == 0 && matches!(event.name, Name::CodeTextData | Name::Data) {
+                let slice = Slice::from_position(
+                    context.bytes,
+                    &Position::from_exit_event(context.events, index),
+                );
+
+                if let Some(direction) = first_strong(slice.as_str()) {
+                    context.push(" dir=\"");
+                    context.push(direction.as_str());
+                    context.push("\"");
+                    break;
+                }
+            }
+        }
+
+        index += 1;
+    }
+}
+
+/// Find where the attribution starts in the paragraph entered at `enter`, if
+/// [`block_quote_citation`][crate::CompileOptions::block_quote_citation] is
+/// on.
+///
+/// The attribution is the last line of the paragraph, when it starts with
+/// `—` or `--`, and only when the paragraph is the last thing in a block
+/// quote.
+/// Returns the index of the exit of the line ending before that line, or
+/// `enter` if the whole paragraph is the attribution (which is only allowed
+/// when there is something before it in the block quote).
+fn block_quote_citation(context: &CompileContext, enter: usize) -> Option<usize> {
+    if !context.options.block_quote_citation {
+        return None;
+    }
+
+    let events = context.events;
+    let mut index = enter + 1;
+    let mut balance = 0;
+    let mut last_line_ending = None;
+
+    // Find the exit of the paragraph, and the last line ending directly in it.
+    while events[index].kind == Kind::Enter || balance > 0 {
+        if events[index].kind == Kind::Enter {
+            balance += 1;
+        } else {
+            balance -= 1;
+
+            if balance == 0 && events[index].name == Name::LineEnding {
+                last_line_ending = Some(index);
+            }
+        }
+
+        index += 1;
+    }
+
+    // The paragraph must be the last thing in a block quote.
+    let after = skip_citation_whitespace(events, index + 1, true);
+
+    if after >= events.len()
+        || events[after].kind != Kind::Exit
+        || events[after].name != Name::BlockQuote
+    {
+        return None;
+    }
+
+    let start = if let Some(line_ending) = last_line_ending {
+        line_ending
+    } else {
+        // There must be something before the paragraph in the block quote.
+        let mut before = enter;
+
+        while before > 0
+            && matches!(
+                events[before - 1].name,
+                Name::BlankLineEnding
+                    | Name::BlockQuoteMarker
+                    | Name::BlockQuotePrefix
+                    | Name::LineEnding
+                    | Name::SpaceOrTab
+            )
+        {
+            before -= 1;
+        }
+
+        if before == 0
+            || (events[before - 1].kind == Kind::Enter
+                && events[before - 1].name == Name::BlockQuote)
+        {
+            return None;
+        }
+
+        enter
+    };
+
+    let data = skip_citation_whitespace(events, start + 1, false);
+
+    if events[data].kind == Kind::Enter && events[data].name == Name::Data {
+        let value =
+            Slice::from_position(context.bytes, &Position::from_exit_event(events, data + 1));
+        let value = value.as_str().trim_start();
+
+        if value.starts_with('—') || value.starts_with("--") {
+            return Some(start);
+        }
+    }
+
+    None
+}
+
+/// Find the exit of the paragraph that the line ending, whose exit is at
+/// `index`, is directly in.
+fn paragraph_exit(events: &[Event], mut index: usize) -> usize {
+    let mut balance = 0;
+
+    loop {
+        index += 1;
+
+        if events[index].kind == Kind::Enter {
+            balance += 1;
+        } else if balance == 0 {
+            return index;
+        } else {
+            balance -= 1;
+        }
+    }
+}
+
+/// Move past whitespace and block quote prefixes, and optionally line
+/// endings, from `index`.
+fn skip_citation_whitespace(events: &[Event], mut index: usize, line_endings: bool) -> usize {
+    while index < events.len()
+        && (matches!(
+            events[index].name,
+            Name::BlockQuoteMarker | Name::BlockQuotePrefix | Name::SpaceOrTab
+        ) || (line_endings
+            && matches!(events[index].name, Name::BlankLineEnding | Name::LineEnding)))
+    {
+        index += 1;
+    }
+
+    index
+}
+
+/// Make a URL safe for use in `src` (for images) or `href`, dropping it if
+/// its protocol is not allowed, and rewriting it with
+/// [`url_rewriter`][crate::CompileOptions::url_rewriter] otherwise.
+fn sanitize_url(context: &CompileContext, url: &str, kind: UrlKind) -> String {
+    let options = context.options;
+    let allowed = options.allow_dangerous_protocol
+        || match (
+            kind,
+            &options.allowed_image_protocols,
+            &options.allowed_link_protocols,
+        ) {
+            (UrlKind::Image, Some(protocols), _) | (UrlKind::Link, _, Some(protocols)) => {
+                is_allowed_protocol(url, protocols)
+            }
+            (UrlKind::Image, None, _) => is_allowed_protocol(url, &SAFE_PROTOCOL_SRC),
+            (UrlKind::Link, _, None) => is_allowed_protocol(url, &SAFE_PROTOCOL_HREF),
+        };
+
+    if !allowed {
+        String::new()
+    } else if let Some(rewrite) = &options.url_rewriter {
+        sanitize(&rewrite(url, kind))
+    } else {
+        sanitize(url)
+    }
+}
+
+/// Check whether a link to `url` is allowed by
+/// [`allowed_link_schemes`][crate::CompileOptions::allowed_link_schemes].
+///
+/// That deprecated option is ignored when
+/// [`allowed_link_protocols`][crate::CompileOptions::allowed_link_protocols]
+/// is set, which instead drops the URL in `sanitize_url`.
+#[allow(deprecated)]
+fn is_link_protocol_allowed(context: &CompileContext, url: &str) -> bool {
+    match (
+        &context.options.allowed_link_protocols,
+        &context.options.allowed_link_schemes,
+    ) {
+        (None, Some(protocols)) => is_allowed_protocol(url, protocols),
+        _ => true,
+    }
+}
@@ -0,0 +1,1046 @@
+//! Turn a syntax tree into markdown.
+
+use crate::configuration::MarkdownOptions;
+use crate::mdast::{
+    AlignKind, AttributeContent, AttributeValue, Code, Heading, List, ListItem, Node,
+    ReferenceKind, Table,
+};
+use crate::util::{
+    char::{classify, classify_opt, Kind as CharacterKind},
+    normalize_identifier::normalize_identifier,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Context used to serialize a tree.
+struct State<'a> {
+    /// Configuration.
+    options: &'a MarkdownOptions,
+    /// Whether we are in a table cell, where `|` has to be escaped and line
+    /// endings cannot occur.
+    in_table: bool,
+    /// First character after the current phrasing node, if it is text.
+    after: Option<char>,
+}
+
+/// Turn a syntax tree into markdown.
+pub fn compile(tree: &Node, options: &MarkdownOptions) -> Result<String, String> {
+    if !matches!(options.bullet, '*' | '+' | '-') {
+        return Err(format!(
+            "Cannot serialize with `{}` as bullet, expected `*`, `+`, or `-`",
+            options.bullet
+        ));
+    }
+
+    if !matches!(options.emphasis, '*' | '_') {
+        return Err(format!(
+            "Cannot serialize with `{}` as emphasis marker, expected `*` or `_`",
+            options.emphasis
+        ));
+    }
+
+    if !matches!(options.fence, '`' | '~') {
+        return Err(format!(
+            "Cannot serialize with `{}` as fence marker, expected `` ` `` or `~`",
+            options.fence
+        ));
+    }
+
+    let mut state = State {
+        options,
+        in_table: false,
+        after: None,
+    };
+    let mut value = block(&mut state, tree)?;
+
+    if !value.is_empty() {
+        value.push('\n');
+    }
+
+    Ok(value)
+}
+
+/// Serialize flow content: blocks, separated by blank lines.
+///
+/// When `tight`, blocks are separated by a line ending instead, where that
+/// does not join them.
+fn flow(state: &mut State, children: &[Node], tight: bool) -> Result<String, String> {
+    let mut result = String::new();
+    let mut previous: Option<&Node> = None;
+    let mut alternate = false;
+
+    for child in children {
+        let value = match child {
+            Node::List(list) => {
+                // Two lists of the same kind next to each other would be
+                // seen as one list: use another marker.
+                alternate = matches!(previous, Some(Node::List(before)) if before.ordered == list.ordered)
+                    && !alternate;
+                list_to_markdown(state, list, alternate)?
+            }
+            _ => block(state, child)?,
+        };
+
+        if previous.is_some() {
+            let interrupts = matches!(
+                child,
+                Node::BlockQuote(_)
+                    | Node::Code(_)
+                    | Node::Heading(_)
+                    | Node::List(_)
+                    | Node::Math(_)
+                    | Node::ThematicBreak(_)
+            );
+            // Paragraphs after these would be seen as a continuation.
+            let lazy = !interrupts
+                && matches!(
+                    previous,
+                    Some(
+                        Node::BlockQuote(_)
+                            | Node::FootnoteDefinition(_)
+                            | Node::Html(_)
+                            | Node::List(_)
+                            | Node::Paragraph(_)
+                    )
+                );
+            result.push_str(if tight && !lazy { "\n" } else { "\n\n" });
+
+            // A paragraph directly after a definition w/o title would be seen
+            // as its title if it starts like one.
+            if tight
+                && matches!(previous, Some(Node::Definition(definition)) if definition.title.is_none())
+                && matches!(child, Node::Paragraph(_))
+                && value.starts_with(|char| matches!(char, '"' | '\'' | '('))
+            {
+                result.push('\\');
+            }
+        }
+
+        result.push_str(&value);
+        previous = Some(child);
+    }
+
+    Ok(result)
+}
+
+/// Serialize a node as a block.
+fn block(state: &mut State, node: &Node) -> Result<String, String> {
+    let value = match node {
+        Node::Root(root) => flow(state, &root.children, false)?,
+        Node::BlockQuote(quote) => prefix(&flow(state, &quote.children, false)?, ">", "> "),
+        Node::Code(code) => code_to_markdown(state, code),
+        Node::ContainerDirective(directive) => {
+            let sequence = ":".repeat(3 + fence_depth(&directive.children));
+            let mut value = format!("{}{}", sequence, directive.name);
+
+            if !directive.label.is_empty() {
+                value.push('[');
+                phrasing(state, &directive.label, &mut value)?;
+                value.push(']');
+            }
+
+            value.push_str(&attributes(
+                directive.id.as_ref(),
+                &directive.classes,
+                &directive.attributes,
+                false,
+            ));
+            value.push('\n');
+            let content = flow(state, &directive.children, false)?;
+            if !content.is_empty() {
+                value.push_str(&content);
+                value.push('\n');
+            }
+            value.push_str(&sequence);
+            value
+        }
+        Node::Definition(definition) => {
+            let mut value = format!(
+                "[{}]: {}",
+                label(definition.label.as_ref(), &definition.identifier),
+                destination(&definition.url)
+            );
+            if let Some(title) = &definition.title {
+                value.push(' ');
+                value.push_str(&quote_title(title));
+            }
+            value
+        }
+        Node::FencedDiv(div) => {
+            let sequence = ":".repeat(3 + fence_depth(&div.children));
+            let mut value = format!(
+                "{} {}\n",
+                sequence,
+                attributes(div.id.as_ref(), &div.classes, &div.attributes, true)
+            );
+            let content = flow(state, &div.children, false)?;
+            if !content.is_empty() {
+                value.push_str(&content);
+                value.push('\n');
+            }
+            value.push_str(&sequence);
+            value
+        }
+        Node::FootnoteDefinition(definition) => {
+            format!(
+                "[^{}]: {}",
+                label(definition.label.as_ref(), &definition.identifier),
+                indent_rest(&flow(state, &definition.children, false)?, "    ")
+            )
+        }
+        Node::Heading(heading) => heading_to_markdown(state, heading)?,
+        Node::Html(html) => html.value.trim_end_matches('\n').into(),
+        Node::LeafDirective(directive) => {
+            let mut value = format!("::{}", directive.name);
+
+            if !directive.children.is_empty() {
+                value.push('[');
+                phrasing(state, &directive.children, &mut value)?;
+                value.push(']');
+            }
+
+            value.push_str(&attributes(
+                directive.id.as_ref(),
+                &directive.classes,
+                &directive.attributes,
+                false,
+            ));
+            value
+        }
+        Node::List(list) => list_to_markdown(state, list, false)?,
+        Node::Math(math) => {
+            let size = longest_run(&math.value, '$').max(1) + 1;
+            let sequence = "$".repeat(size);
+            let mut value = sequence.clone();
+            if let Some(meta) = &math.meta {
+                value.push_str(meta);
+            }
+            value.push('\n');
+            if !math.value.is_empty() {
+                value.push_str(&math.value);
+                value.push('\n');
+            }
+            value.push_str(&sequence);
+            value
+        }
+        Node::MdxFlowExpression(expression) => format!("{{{}}}", expression.value),
+        Node::MdxJsxFlowElement(element) => {
+            let open = jsx_open(element.name.as_ref(), &element.attributes);
+
+            if element.children.is_empty() {
+                format!("{} />", open)
+            } else {
+                format!(
+                    "{}>\n{}\n</{}>",
+                    open,
+                    flow(state, &element.children, false)?,
+                    element.name.as_deref().unwrap_or("")
+                )
+            }
+        }
+        Node::MdxjsEsm(esm) => esm.value.clone(),
+        Node::Paragraph(paragraph) => {
+            let mut value = String::new();
+            phrasing(state, &paragraph.children, &mut value)?;
+            value
+        }
+        Node::Table(table) => table_to_markdown(state, table)?,
+        Node::ThematicBreak(_) => "***".into(),
+        Node::Toml(toml) => format!("+++\n{}\n+++", toml.value),
+        Node::Yaml(yaml) => format!("---\n{}\n---", yaml.value),
+        Node::ListItem(_) => return Err("Cannot serialize `ListItem` outside of `List`".into()),
+        Node::TableRow(_) | Node::TableCell(_) => {
+            return Err("Cannot serialize `TableRow` or `TableCell` outside of `Table`".into())
+        }
+        _ => {
+            let mut value = String::new();
+            phrasing_node(state, node, &mut value)?;
+            value
+        }
+    };
+
+    Ok(value)
+}
+
+/// Serialize a heading, as ATX, unless it has line endings, which only
+/// setext headings (rank 1 and 2) can have.
+fn heading_to_markdown(state: &mut State, heading: &Heading) -> Result<String, String> {
+    let mut content = String::new();
+    phrasing(state, &heading.children, &mut content)?;
+
+    if content.contains('\n') && matches!(heading.depth, 1 | 2) {
+        let underline = if heading.depth == 1 { "===" } else { "---" };
+        return Ok(format!("{}\n{}", content, underline));
+    }
+
+    let mut content = content.replace('\n', " ");
+
+    // A final `#` would be seen as part of a closing sequence.
+    if content.ends_with('#') && !content.ends_with("\\#") {
+        content.pop();
+        content.push_str("\\#");
+    }
+
+    let mut value = "#".repeat(heading.depth.clamp(1, 6).into());
+
+    if !content.is_empty() {
+        value.push(' ');
+        value.push_str(&content);
+    }
+
+    Ok(value)
+}
+
+/// Serialize a list.
+///
+/// When `alternate`, another bullet or delimiter is used, so that the list is
+/// not joined with a list before it.
+fn list_to_markdown(state: &mut State, list: &List, alternate: bool) -> Result<String, String> {
+    let bullet = if alternate {
+        if state.options.bullet == '-' {
+            '*'
+        } else {
+            '-'
+        }
+    } else {
+        state.options.bullet
+    };
+    let delimiter = if alternate { ')' } else { '.' };
+    let start = list.start.unwrap_or(1);
+    let mut result = String::new();
+
+    for (index, child) in list.children.iter().enumerate() {
+        let marker = if list.ordered {
+            format!("{}{}", start as usize + index, delimiter)
+        } else {
+            bullet.to_string()
+        };
+
+        if index > 0 {
+            result.push_str(if list.spread { "\n\n" } else { "\n" });
+        }
+
+        if let Node::ListItem(item) = child {
+            result.push_str(&list_item_to_markdown(state, item, &marker)?);
+        } else {
+            return Err("Cannot serialize non-`ListItem` in `List`".into());
+        }
+    }
+
+    Ok(result)
+}
+
+/// Serialize a list item, with `marker`.
+fn list_item_to_markdown(
+    state: &mut State,
+    item: &ListItem,
+    marker: &str,
+) -> Result<String, String> {
+    let mut content = flow(state, &item.children, !item.spread)?;
+
+    if let Some(checked) = item.checked {
+        content.insert_str(0, if checked { "[x] " } else { "[ ] " });
+    }
+
+    if content.is_empty() {
+        return Ok(marker.into());
+    }
+
+    Ok(format!(
+        "{} {}",
+        marker,
+        indent_rest(&content, &" ".repeat(marker.len() + 1))
+    ))
+}
+
+/// Serialize code (flow), always fenced.
+fn code_to_markdown(state: &State, code: &Code) -> String {
+    let mut info = code.lang.as_deref().map(escape_info).unwrap_or_default();
+
+    if let Some(meta) = &code.meta {
+        info.push(' ');
+        info.push_str(&escape_info(meta));
+    }
+
+    // Info strings of code fenced with grave accents cannot contain them.
+    let marker = if state.options.fence == '`' && info.contains('`') {
+        '~'
+    } else {
+        state.options.fence
+    };
+    let sequence = marker
+        .to_string()
+        .repeat((longest_run(&code.value, marker) + 1).max(3));
+    let mut value = format!("{}{}\n", sequence, info);
+
+    if !code.value.is_empty() {
+        value.push_str(&code.value);
+        value.push('\n');
+    }
+
+    value.push_str(&sequence);
+    value
+}
+
+/// Escape the language or meta of code, where character escapes and
+/// references work, like in text.
+fn escape_info(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut result = String::new();
+
+    for (index, char) in chars.iter().enumerate() {
+        let after = chars.get(index + 1).copied();
+        let escape = match char {
+            '\\' => after.map_or(true, |after| after.is_ascii_punctuation()),
+            '&' => after.map_or(false, |after| after.is_ascii_alphanumeric() || after == '#'),
+            _ => false,
+        };
+
+        if escape {
+            result.push('\\');
+        }
+
+        result.push(*char);
+    }
+
+    result
+}
+
+/// Serialize a table (GFM).
+fn table_to_markdown(state: &mut State, table: &Table) -> Result<String, String> {
+    let mut rows = Vec::new();
+    state.in_table = true;
+
+    for row in &table.children {
+        if let Node::TableRow(row) = row {
+            let mut cells = Vec::new();
+
+            for cell in &row.children {
+                if let Node::TableCell(cell) = cell {
+                    let mut value = String::new();
+                    phrasing(state, &cell.children, &mut value)?;
+                    cells.push(value);
+                } else {
+                    state.in_table = false;
+                    return Err("Cannot serialize non-`TableCell` in `TableRow`".into());
+                }
+            }
+
+            rows.push(cells);
+        } else {
+            state.in_table = false;
+            return Err("Cannot serialize non-`TableRow` in `Table`".into());
+        }
+    }
+
+    state.in_table = false;
+
+    let mut result = String::new();
+
+    for (index, cells) in rows.iter().enumerate() {
+        if index > 0 {
+            result.push('\n');
+        }
+
+        result.push('|');
+
+        for cell in cells {
+            result.push(' ');
+            result.push_str(cell);
+            result.push_str(" |");
+        }
+
+        // The delimiter row must have as many cells as the header row.
+        if index == 0 {
+            result.push_str("\n|");
+
+            for column in 0..cells.len() {
+                result.push_str(match table.align.get(column) {
+                    Some(AlignKind::Left) => " :-- |",
+                    Some(AlignKind::Right) => " --: |",
+                    Some(AlignKind::Center) => " :-: |",
+                    _ => " --- |",
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Serialize phrasing content into `buffer`.
+fn phrasing(state: &mut State, children: &[Node], buffer: &mut String) -> Result<(), String> {
+    for (index, child) in children.iter().enumerate() {
+        state.after = if let Some(Node::Text(text)) = children.get(index + 1) {
+            text.value.chars().next()
+        } else {
+            None
+        };
+        phrasing_node(state, child, buffer)?;
+    }
+
+    Ok(())
+}
+
+/// Serialize a phrasing node into `buffer`.
+fn phrasing_node(state: &mut State, node: &Node, buffer: &mut String) -> Result<(), String> {
+    match node {
+        Node::Break(_) => buffer.push_str(if state.in_table { " " } else { "\\\n" }),
+        Node::Delete(delete) => {
+            buffer.push_str("~~");
+            phrasing(state, &delete.children, buffer)?;
+            buffer.push_str("~~");
+        }
+        Node::Emphasis(emphasis) => {
+            let marker = emphasis_marker(state, buffer, false);
+            buffer.push(marker);
+            phrasing(state, &emphasis.children, buffer)?;
+            buffer.push(marker);
+        }
+        Node::FootnoteReference(reference) => {
+            buffer.push_str("[^");
+            buffer.push_str(&label(reference.label.as_ref(), &reference.identifier));
+            buffer.push(']');
+        }
+        Node::Html(html) => {
+            let mut lines = html.value.split('\n');
+            buffer.push_str(lines.next().unwrap_or_default());
+
+            // Lines that start like a block (such as `>`) are indented, so
+            // that they are seen as a continuation of the paragraph instead.
+            for line in lines {
+                buffer.push('\n');
+                if line
+                    .starts_with(|char: char| char.is_ascii_punctuation() || char.is_ascii_digit())
+                {
+                    buffer.push_str("    ");
+                }
+                buffer.push_str(line);
+            }
+        }
+        Node::Image(image) => {
+            buffer.push_str("![");
+            text(state, &image.alt, buffer);
+            buffer.push_str("](");
+            buffer.push_str(&resource(&image.url, image.title.as_ref()));
+            buffer.push(')');
+        }
+        Node::ImageReference(reference) => {
+            buffer.push_str("![");
+            let start = buffer.len();
+            text(state, &reference.alt, buffer);
+            let content = buffer[start..].to_string();
+            buffer.push(']');
+            buffer.push_str(&reference_end(
+                reference.reference_kind,
+                &content,
+                reference.label.as_ref(),
+                &reference.identifier,
+            ));
+        }
+        Node::InlineCode(code) => {
+            buffer.push_str(&raw_text(state, &code.value, '`'));
+        }
+        Node::InlineFootnote(footnote) => {
+            buffer.push_str("^[");
+            phrasing(state, &footnote.children, buffer)?;
+            buffer.push(']');
+        }
+        Node::InlineMath(math) => buffer.push_str(&raw_text(state, &math.value, '$')),
+        Node::Link(link) => {
+            if let Some(autolink) = autolink(link) {
+                buffer.push_str(&autolink);
+            } else {
+                escape_bang(buffer);
+                buffer.push('[');
+                phrasing(state, &link.children, buffer)?;
+                buffer.push_str("](");
+                buffer.push_str(&resource(&link.url, link.title.as_ref()));
+                buffer.push(')');
+            }
+        }
+        Node::LinkReference(reference) => {
+            escape_bang(buffer);
+            buffer.push('[');
+            let start = buffer.len();
+            phrasing(state, &reference.children, buffer)?;
+            let content = buffer[start..].to_string();
+            buffer.push(']');
+            buffer.push_str(&reference_end(
+                reference.reference_kind,
+                &content,
+                reference.label.as_ref(),
+                &reference.identifier,
+            ));
+        }
+        Node::MdxJsxTextElement(element) => {
+            let open = jsx_open(element.name.as_ref(), &element.attributes);
+
+            if element.children.is_empty() {
+                buffer.push_str(&open);
+                buffer.push_str(" />");
+            } else {
+                buffer.push_str(&open);
+                buffer.push('>');
+                phrasing(state, &element.children, buffer)?;
+                buffer.push_str("</");
+                buffer.push_str(element.name.as_deref().unwrap_or(""));
+                buffer.push('>');
+            }
+        }
+        Node::MdxTextExpression(expression) => {
+            buffer.push('{');
+            buffer.push_str(&expression.value);
+            buffer.push('}');
+        }
+        Node::Strong(strong) => {
+            let marker = emphasis_marker(state, buffer, true);
+            buffer.push(marker);
+            buffer.push(marker);
+            phrasing(state, &strong.children, buffer)?;
+            buffer.push(marker);
+            buffer.push(marker);
+        }
+        Node::Text(node) => text(state, &node.value, buffer),
+        Node::TextDirective(directive) => {
+            buffer.push(':');
+            buffer.push_str(&directive.name);
+
+            if !directive.children.is_empty() {
+                buffer.push('[');
+                phrasing(state, &directive.children, buffer)?;
+                buffer.push(']');
+            }
+
+            buffer.push_str(&attributes(
+                directive.id.as_ref(),
+                &directive.classes,
+                &directive.attributes,
+                false,
+            ));
+        }
+        _ => return Err("Cannot serialize flow content in phrasing content".into()),
+    }
+
+    Ok(())
+}
+
+/// Serialize text into `buffer`, escaping what would otherwise be seen as
+/// markdown syntax.
+fn text(state: &State, value: &str, buffer: &mut String) {
+    let chars: Vec<char> = value.chars().collect();
+    let initial = !state.in_table && matches!(buffer.chars().next_back(), None | Some('\n'));
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+        let before = buffer.chars().next_back();
+        let after = chars.get(index + 1).copied();
+        let line_start = !state.in_table && matches!(before, None | Some('\n'));
+
+        if char == '\n' && state.in_table {
+            buffer.push(' ');
+        }
+        // A blank line would end the paragraph.
+        else if char == '\n' && before == Some('\n') {
+            buffer.push_str("&#10;");
+        }
+        // Initial whitespace would be dropped.
+        else if line_start && char == ' ' {
+            buffer.push_str("&#x20;");
+        } else if line_start && char == '\t' {
+            buffer.push_str("&#x9;");
+        } else {
+            let escape = match char {
+                '*' | '[' | ']' | '`' | '~' | '$' | '{' => true,
+                '\\' => after.map_or(true, |after| after.is_ascii_punctuation() || after == '\n'),
+                '_' => {
+                    !(before.map_or(false, |before| before.is_ascii_alphanumeric())
+                        && after.map_or(false, |after| after.is_ascii_alphanumeric()))
+                }
+                '&' => after.map_or(false, |after| after.is_ascii_alphanumeric() || after == '#'),
+                '<' => after.map_or(false, |after| {
+                    after.is_ascii_alphabetic() || matches!(after, '/' | '!' | '?')
+                }),
+                ':' => line_start || after.map_or(false, |after| after.is_ascii_alphabetic()),
+                '|' => state.in_table || line_start,
+                '#' | '>' | '+' | '-' | '=' => line_start,
+                // An ordered list item.
+                '.' | ')' => {
+                    let mut start = index;
+                    while start > 0 && chars[start - 1].is_ascii_digit() {
+                        start -= 1;
+                    }
+                    let line_start = if start == 0 {
+                        initial
+                    } else {
+                        chars[start - 1] == '\n' && !state.in_table
+                    };
+                    start < index && line_start
+                }
+                _ => false,
+            };
+
+            if escape {
+                buffer.push('\\');
+            }
+
+            buffer.push(char);
+        }
+
+        index += 1;
+    }
+}
+
+/// Serialize code (text) or math (text), with a sequence of `marker`s that
+/// does not occur in `value`.
+///
+/// The value in mdast is what is between the sequences, so it is kept as is,
+/// unless it starts or ends with a marker.
+fn raw_text(state: &State, value: &str, marker: char) -> String {
+    let mut size = 1;
+
+    while has_run(value, marker, size) {
+        size += 1;
+    }
+
+    let sequence = marker.to_string().repeat(size);
+    let pad = if value.starts_with(marker) || value.ends_with(marker) {
+        " "
+    } else {
+        ""
+    };
+    let value = if state.in_table {
+        value.replace('|', "\\|").replace('\n', " ")
+    } else {
+        value.into()
+    };
+
+    format!("{}{}{}{}{}", sequence, pad, value, pad, sequence)
+}
+
+/// Serialize a link as an autolink, if it can be one.
+fn autolink(link: &crate::mdast::Link) -> Option<String> {
+    if link.title.is_some() || link.children.len() != 1 {
+        return None;
+    }
+
+    let text = if let Node::Text(text) = &link.children[0] {
+        text
+    } else {
+        return None;
+    };
+
+    let url = &link.url;
+    let scheme = url.split_once(':').map_or("", |(scheme, _)| scheme);
+
+    if (text.value == *url || format!("mailto:{}", text.value) == *url)
+        && scheme.len() > 1
+        && scheme
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'))
+        && !url
+            .chars()
+            .any(|char| char.is_whitespace() || char.is_control() || matches!(char, '<' | '>'))
+    {
+        Some(format!("<{}>", text.value))
+    } else {
+        None
+    }
+}
+
+/// Serialize the destination and title of a link or image.
+fn resource(url: &str, title: Option<&String>) -> String {
+    let mut value = destination(url);
+
+    if let Some(title) = title {
+        value.push(' ');
+        value.push_str(&quote_title(title));
+    }
+
+    value
+}
+
+/// Serialize a destination.
+fn destination(url: &str) -> String {
+    if url.is_empty()
+        || url
+            .chars()
+            .any(|char| char.is_whitespace() || char.is_control())
+    {
+        let mut value = String::from("<");
+        for char in url.chars() {
+            if matches!(char, '<' | '>' | '\\') {
+                value.push('\\');
+            }
+            value.push(char);
+        }
+        value.push('>');
+        value
+    } else {
+        let chars: Vec<char> = url.chars().collect();
+        let mut value = String::new();
+        for (index, char) in chars.iter().enumerate() {
+            let escape = match char {
+                '(' | ')' | '<' | '\\' => true,
+                '&' => chars.get(index + 1).map_or(false, |after| {
+                    after.is_ascii_alphanumeric() || *after == '#'
+                }),
+                _ => false,
+            };
+            if escape {
+                value.push('\\');
+            }
+            value.push(*char);
+        }
+        value
+    }
+}
+
+/// Serialize a title, in double quotes.
+fn quote_title(title: &str) -> String {
+    format!("\"{}\"", title.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Serialize a label.
+///
+/// The `label` field is used if it matches `identifier`, which is not the
+/// case when it contained character escapes or references: then the
+/// identifier is used.
+fn label(label: Option<&String>, identifier: &str) -> String {
+    if let Some(label) = label {
+        let mut value = String::new();
+
+        for char in label.chars() {
+            if matches!(char, '[' | ']' | '\\') {
+                value.push('\\');
+            }
+            value.push(char);
+        }
+
+        if normalize_identifier(&value).to_lowercase() == identifier {
+            return value;
+        }
+    }
+
+    identifier.into()
+}
+
+/// Serialize what comes after the text of a reference.
+///
+/// Collapsed and shortcut references use their text (`content`) as label:
+/// when that does not match `identifier`, which happens when it needed
+/// escapes, a full reference is used instead.
+fn reference_end(
+    kind: ReferenceKind,
+    content: &str,
+    label_value: Option<&String>,
+    identifier: &str,
+) -> String {
+    match kind {
+        ReferenceKind::Collapsed | ReferenceKind::Shortcut
+            if normalize_identifier(content).to_lowercase() == identifier =>
+        {
+            if kind == ReferenceKind::Collapsed {
+                "[]".into()
+            } else {
+                String::new()
+            }
+        }
+        _ => format!("[{}]", label(label_value, identifier)),
+    }
+}
+
+/// Get the marker to use for emphasis or strong.
+///
+/// Directly after another marker, emphasis uses the other marker, as `**`
+/// would be seen as strong, and strong uses the same marker, as `***` is
+/// seen as emphasis and strong.
+/// Underscores do not work inside words, so then asterisks are used.
+fn emphasis_marker(state: &State, buffer: &str, strong: bool) -> char {
+    let mut marker = state.options.emphasis;
+
+    if let Some(before) = buffer.chars().next_back() {
+        if matches!(before, '*' | '_') && !buffer[..buffer.len() - 1].ends_with('\\') {
+            marker = if strong {
+                before
+            } else if before == '*' {
+                '_'
+            } else {
+                '*'
+            };
+        }
+    }
+
+    if marker == '_'
+        && (classify_opt(buffer.chars().next_back()) == CharacterKind::Other
+            || state.after.map(classify) == Some(CharacterKind::Other))
+    {
+        marker = '*';
+    }
+
+    marker
+}
+
+/// Escape an exclamation mark at the end of `buffer`, which would otherwise
+/// turn a following link into an image.
+fn escape_bang(buffer: &mut String) {
+    if buffer.ends_with('!') && !buffer.ends_with("\\!") {
+        buffer.pop();
+        buffer.push_str("\\!");
+    }
+}
+
+/// Serialize attributes of a directive or fenced div.
+///
+/// Fenced divs always need braces, directives only when there are
+/// attributes.
+fn attributes(
+    id: Option<&String>,
+    classes: &[String],
+    other: &[(String, String)],
+    braces: bool,
+) -> String {
+    let mut parts = Vec::new();
+
+    if let Some(id) = id {
+        parts.push(format!("#{}", id));
+    }
+
+    for class in classes {
+        parts.push(format!(".{}", class));
+    }
+
+    for (name, value) in other {
+        let quote = if value.contains('"') && !value.contains('\'') {
+            '\''
+        } else {
+            '"'
+        };
+        parts.push(format!("{}={}{}{}", name, quote, value, quote));
+    }
+
+    if parts.is_empty() && !braces {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(" "))
+    }
+}
+
+/// Serialize the opening tag of a JSX element, without its final `>` or
+/// `/>`.
+fn jsx_open(name: Option<&String>, attributes: &[AttributeContent]) -> String {
+    let mut value = format!("<{}", name.map_or("", String::as_str));
+
+    for attribute in attributes {
+        value.push(' ');
+
+        match attribute {
+            AttributeContent::Expression {
+                value: expression, ..
+            } => {
+                value.push('{');
+                value.push_str(expression);
+                value.push('}');
+            }
+            AttributeContent::Property(property) => {
+                value.push_str(&property.name);
+
+                match &property.value {
+                    Some(AttributeValue::Literal(literal)) => {
+                        let quote = if literal.contains('"') { '\'' } else { '"' };
+                        value.push('=');
+                        value.push(quote);
+                        value.push_str(literal);
+                        value.push(quote);
+                    }
+                    Some(AttributeValue::Expression(expression)) => {
+                        value.push_str("={");
+                        value.push_str(&expression.value);
+                        value.push('}');
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    value
+}
+
+/// Get how many directives (container) or fenced divs are nested in
+/// `children`, so that outer fences can use more colons.
+fn fence_depth(children: &[Node]) -> usize {
+    children
+        .iter()
+        .map(|child| match child {
+            Node::ContainerDirective(directive) => 1 + fence_depth(&directive.children),
+            Node::FencedDiv(div) => 1 + fence_depth(&div.children),
+            _ => child.children().map_or(0, |children| fence_depth(children)),
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Prefix each line in `value`: empty lines with `empty`, other lines with
+/// `filled`.
+fn prefix(value: &str, empty: &str, filled: &str) -> String {
+    value
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                empty.into()
+            } else {
+                format!("{}{}", filled, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Indent each line in `value` except for the first, leaving empty lines
+/// empty.
+fn indent_rest(value: &str, indent: &str) -> String {
+    match value.split_once('\n') {
+        Some((first, rest)) => format!("{}\n{}", first, prefix(rest, "", indent)),
+        None => value.into(),
+    }
+}
+
+/// Get the size of the longest run of `marker` in `value`.
+fn longest_run(value: &str, marker: char) -> usize {
+    let mut longest = 0;
+    let mut size = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            size += 1;
+            longest = longest.max(size);
+        } else {
+            size = 0;
+        }
+    }
+
+    longest
+}
+
+/// Check if there is a run of exactly `size` `marker`s in `value`.
+fn has_run(value: &str, marker: char, size: usize) -> bool {
+    let mut current = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            current += 1;
+        } else {
+            if current == size {
+                return true;
+            }
+            current = 0;
+        }
+    }
+
+    current == size
+}
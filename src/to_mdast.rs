@@ -3,22 +3,27 @@
 use crate::event::{Event, Kind, Name, Point as EventPoint};
 use crate::mdast::{
     AttributeContent, AttributeValue, AttributeValueExpression, BlockQuote, Break, Code,
-    Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Html, Image,
-    ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math,
-    MdxFlowExpression, MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression,
-    MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell, TableRow, Text,
+    ContainerDirective, Definition, Delete, Emphasis, FencedDiv, FootnoteDefinition,
+    FootnoteReference, Heading, Html, Image, ImageReference, InlineCode, InlineFootnote,
+    InlineMath, LeafDirective, Link, LinkReference, List, ListItem, Math, MdxFlowExpression,
+    MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression, MdxjsEsm, Node,
+    Paragraph, ReferenceKind, Root, Strong, Table, TableCell, TableRow, Text, TextDirective,
     ThematicBreak, Toml, Yaml,
 };
 use crate::unist::{Point, Position};
 use crate::util::{
+    attributes,
     character_reference::{
         decode as decode_character_reference, parse as parse_character_reference,
     },
+    emoji::decode_emoji,
     infer::{gfm_table_align, list_item_loose, list_loose},
     mdx_collect::{collect, Result as CollectResult},
     normalize_identifier::normalize_identifier,
+    ordered_list,
     slice::{Position as SlicePosition, Slice},
 };
+use crate::ParseOptions;
 use alloc::{
     format,
     string::{String, ToString},
@@ -87,6 +92,8 @@ struct CompileContext<'a> {
     events: &'a [Event],
     /// List of bytes.
     bytes: &'a [u8],
+    /// Extra emoji shortcodes.
+    emoji_shortcodes: &'a [(String, String)],
     // Fields used by handlers to track the things they need to track to
     // compile markdown.
     character_reference_marker: u8,
@@ -106,7 +113,7 @@ struct CompileContext<'a> {
 
 impl<'a> CompileContext<'a> {
     /// Create a new compile context.
-    fn new(events: &'a [Event], bytes: &'a [u8]) -> CompileContext<'a> {
+    fn new(events: &'a [Event], bytes: &'a [u8], options: &'a ParseOptions) -> CompileContext<'a> {
         let tree = Node::Root(Root {
             children: vec![],
             position: Some(Position {
@@ -126,6 +133,7 @@ impl<'a> CompileContext<'a> {
         CompileContext {
             events,
             bytes,
+            emoji_shortcodes: &options.emoji_shortcodes,
             character_reference_marker: 0,
             gfm_table_inside: false,
             hard_break_after: false,
@@ -222,8 +230,8 @@ impl<'a> CompileContext<'a> {
 }
 
 /// Turn events and bytes into a syntax tree.
-pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, String> {
-    let mut context = CompileContext::new(events, bytes);
+pub fn compile(events: &[Event], bytes: &[u8], options: &ParseOptions) -> Result<Node, String> {
+    let mut context = CompileContext::new(events, bytes, options);
 
     let mut index = 0;
     while index < events.len() {
@@ -265,6 +273,7 @@ fn enter(context: &mut CompileContext) -> Result<(), String> {
         | Name::CodeFlowChunk
         | Name::CodeTextData
         | Name::Data
+        | Name::Emoji
         | Name::FrontmatterChunk
         | Name::HtmlFlowData
         | Name::HtmlTextData
@@ -289,7 +298,12 @@ fn enter(context: &mut CompileContext) -> Result<(), String> {
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeText => on_enter_code_text(context),
         Name::Definition => on_enter_definition(context),
+        Name::DirectiveContainer => on_enter_directive_container(context),
+        Name::DirectiveLabel => on_enter_directive_label(context),
+        Name::DirectiveLeaf => on_enter_directive_leaf(context),
+        Name::DirectiveText => on_enter_directive_text(context),
         Name::Emphasis => on_enter_emphasis(context),
+        Name::FencedDiv => on_enter_fenced_div(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmAutolinkLiteralEmail
         | Name::GfmAutolinkLiteralMailto
@@ -306,6 +320,7 @@ fn enter(context: &mut CompileContext) -> Result<(), String> {
         Name::HeadingAtx | Name::HeadingSetext => on_enter_heading(context),
         Name::HtmlFlow | Name::HtmlText => on_enter_html(context),
         Name::Image => on_enter_image(context),
+        Name::InlineFootnote => on_enter_inline_footnote(context),
         Name::Link => on_enter_link(context),
         Name::ListItem => on_enter_list_item(context),
         Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
@@ -340,7 +355,12 @@ fn exit(context: &mut CompileContext) -> Result<(), String> {
         | Name::BlockQuote
         | Name::CharacterReference
         | Name::Definition
+        | Name::DirectiveContainer
+        | Name::DirectiveLeaf
+        | Name::DirectiveText
+        | Name::Emoji
         | Name::Emphasis
+        | Name::FencedDiv
         | Name::GfmFootnoteDefinition
         | Name::GfmStrikethrough
         | Name::GfmTableRow
@@ -378,6 +398,7 @@ fn exit(context: &mut CompileContext) -> Result<(), String> {
             on_exit_character_reference_marker_hexadecimal(context);
         }
         Name::CharacterReferenceValue => on_exit_character_reference_value(context),
+        Name::EmojiValue => on_exit_emoji_value(context),
         Name::CodeFencedFenceInfo => on_exit_code_fenced_fence_info(context),
         Name::CodeFencedFenceMeta | Name::MathFlowFenceMeta => on_exit_raw_flow_fence_meta(context),
         Name::CodeFencedFence | Name::MathFlowFence => on_exit_raw_flow_fence(context),
@@ -389,13 +410,18 @@ fn exit(context: &mut CompileContext) -> Result<(), String> {
             on_exit_definition_id(context);
         }
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
+        Name::DirectiveAttributes => on_exit_directive_attributes(context),
+        Name::DirectiveLabel => on_exit_directive_label(context),
+        Name::DirectiveName => on_exit_directive_name(context),
         Name::Frontmatter => on_exit_frontmatter(context)?,
         Name::GfmAutolinkLiteralEmail
         | Name::GfmAutolinkLiteralMailto
         | Name::GfmAutolinkLiteralProtocol
         | Name::GfmAutolinkLiteralWww
         | Name::GfmAutolinkLiteralXmpp => on_exit_gfm_autolink_literal(context)?,
-        Name::GfmFootnoteCall | Name::Image | Name::Link => on_exit_media(context)?,
+        Name::GfmFootnoteCall | Name::Image | Name::InlineFootnote | Name::Link => {
+            on_exit_media(context)?;
+        }
         Name::GfmTable => on_exit_gfm_table(context)?,
         Name::GfmTaskListItemValueUnchecked | Name::GfmTaskListItemValueChecked => {
             on_exit_gfm_task_list_item_value(context);
@@ -569,6 +595,51 @@ fn on_enter_definition(context: &mut CompileContext) {
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveContainer`][Name::DirectiveContainer].
+fn on_enter_directive_container(context: &mut CompileContext) {
+    context.tail_push(Node::ContainerDirective(ContainerDirective {
+        children: vec![],
+        position: None,
+        name: String::new(),
+        label: vec![],
+        id: None,
+        classes: vec![],
+        attributes: vec![],
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_enter_directive_label(context: &mut CompileContext) {
+    // The label of a container is not part of its content.
+    if let Node::ContainerDirective(_) = context.tail_mut() {
+        context.buffer();
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveLeaf`][Name::DirectiveLeaf].
+fn on_enter_directive_leaf(context: &mut CompileContext) {
+    context.tail_push(Node::LeafDirective(LeafDirective {
+        children: vec![],
+        position: None,
+        name: String::new(),
+        id: None,
+        classes: vec![],
+        attributes: vec![],
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveText`][Name::DirectiveText].
+fn on_enter_directive_text(context: &mut CompileContext) {
+    context.tail_push(Node::TextDirective(TextDirective {
+        children: vec![],
+        position: None,
+        name: String::new(),
+        id: None,
+        classes: vec![],
+        attributes: vec![],
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Emphasis`][Name::Emphasis].
 fn on_enter_emphasis(context: &mut CompileContext) {
     context.tail_push(Node::Emphasis(Emphasis {
@@ -643,6 +714,45 @@ fn on_enter_hard_break(context: &mut CompileContext) {
     context.tail_push(Node::Break(Break { position: None }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`FencedDiv`][Name::FencedDiv].
+fn on_enter_fenced_div(context: &mut CompileContext) {
+    let mut index = context.index + 1;
+    let mut value = None;
+
+    // Find the attributes in the opening fence.
+    while index < context.events.len() {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == Name::FencedDivFence {
+                break;
+            }
+
+            if event.name == Name::FencedDivAttributes {
+                value = Some(
+                    Slice::from_position(
+                        context.bytes,
+                        &SlicePosition::from_exit_event(context.events, index),
+                    )
+                    .serialize(),
+                );
+            }
+        }
+
+        index += 1;
+    }
+
+    let attributes = attributes::parse(value.as_deref().unwrap_or_default());
+
+    context.tail_push(Node::FencedDiv(FencedDiv {
+        children: vec![],
+        position: None,
+        id: attributes.id,
+        classes: attributes.classes,
+        attributes: attributes.other,
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Frontmatter`][Name::Frontmatter].
 fn on_enter_frontmatter(context: &mut CompileContext) {
     let index = context.events[context.index].point.index;
@@ -726,6 +836,18 @@ fn on_enter_image(context: &mut CompileContext) {
     context.media_reference_stack.push(Reference::new());
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`InlineFootnote`][Name::InlineFootnote].
+fn on_enter_inline_footnote(context: &mut CompileContext) {
+    context.tail_push(Node::InlineFootnote(InlineFootnote {
+        children: vec![],
+        position: None,
+    }));
+    let mut reference = Reference::new();
+    // It’s not a reference.
+    reference.reference_kind = None;
+    context.media_reference_stack.push(reference);
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
     context.tail_push(Node::Link(Link {
@@ -976,6 +1098,22 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     context.character_reference_marker = 0;
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`EmojiValue`][Name::EmojiValue].
+fn on_exit_emoji_value(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+    );
+    let value = decode_emoji(slice.as_str(), context.emoji_shortcodes)
+        .expect("expected to parse only known emoji");
+
+    if let Node::Text(node) = context.tail_mut() {
+        node.value.push_str(value);
+    } else {
+        unreachable!("expected text on stack");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`CodeFencedFenceInfo`][Name::CodeFencedFenceInfo].
 fn on_exit_code_fenced_fence_info(context: &mut CompileContext) {
     let value = context.resume().to_string();
@@ -1122,6 +1260,67 @@ fn on_exit_definition_id(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributes`][Name::DirectiveAttributes].
+fn on_exit_directive_attributes(context: &mut CompileContext) {
+    let value = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+    )
+    .serialize();
+    let attributes::Attributes { id, classes, other } = attributes::parse(&value);
+
+    match context.tail_mut() {
+        Node::ContainerDirective(node) => {
+            node.id = id;
+            node.classes = classes;
+            node.attributes = other;
+        }
+        Node::LeafDirective(node) => {
+            node.id = id;
+            node.classes = classes;
+            node.attributes = other;
+        }
+        Node::TextDirective(node) => {
+            node.id = id;
+            node.classes = classes;
+            node.attributes = other;
+        }
+        _ => unreachable!("expected directive on stack"),
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_exit_directive_label(context: &mut CompileContext) {
+    // Labels of leaf and text directives are their children, labels of
+    // container directives were buffered.
+    if let Node::Paragraph(_) = context.tail_mut() {
+        let label = match context.resume() {
+            Node::Paragraph(node) => node.children,
+            _ => unreachable!("expected buffer"),
+        };
+
+        if let Node::ContainerDirective(node) = context.tail_mut() {
+            node.label = label;
+        }
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveName`][Name::DirectiveName].
+fn on_exit_directive_name(context: &mut CompileContext) {
+    let value = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+    )
+    .serialize();
+
+    match context.tail_mut() {
+        Node::ContainerDirective(node) => node.name = value,
+        Node::LeafDirective(node) => node.name = value,
+        Node::TextDirective(node) => node.name = value,
+        _ => unreachable!("expected directive on stack"),
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionTitleString`][Name::DefinitionTitleString].
 fn on_exit_definition_title_string(context: &mut CompileContext) {
     let value = context.resume().to_string();
@@ -1271,8 +1470,9 @@ fn on_exit_label_text(context: &mut CompileContext) {
     match context.tail_mut() {
         Node::Link(node) => node.children = children,
         Node::Image(node) => node.alt = label,
+        Node::InlineFootnote(node) => node.children = children,
         Node::FootnoteReference(_) => {}
-        _ => unreachable!("expected footnote refereence, image, or link on stack"),
+        _ => unreachable!("expected footnote refereence, image, inline footnote, or link on stack"),
     }
 }
 
@@ -1324,7 +1524,7 @@ fn on_exit_html(context: &mut CompileContext) -> Result<(), String> {
     Ok(())
 }
 
-/// Handle [`Exit`][Kind::Exit]:{[`GfmFootnoteCall`][Name::GfmFootnoteCall],[`Image`][Name::Image],[`Link`][Name::Link]}.
+/// Handle [`Exit`][Kind::Exit]:{[`GfmFootnoteCall`][Name::GfmFootnoteCall],[`Image`][Name::Image],[`InlineFootnote`][Name::InlineFootnote],[`Link`][Name::Link]}.
 fn on_exit_media(context: &mut CompileContext) -> Result<(), String> {
     let reference = context
         .media_reference_stack
@@ -1424,13 +1624,12 @@ fn on_exit_list_item(context: &mut CompileContext) -> Result<(), String> {
 
 /// Handle [`Exit`][Kind::Exit]:[`ListItemValue`][Name::ListItemValue].
 fn on_exit_list_item_value(context: &mut CompileContext) {
-    let start = Slice::from_position(
+    let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
     )
-    .as_str()
-    .parse()
-    .expect("expected list value up to u8");
+    .bytes;
+    let start = ordered_list::number(value, ordered_list::kind(value));
 
     if let Node::List(node) = context.tail_penultimate_mut() {
         debug_assert!(node.ordered, "expected list to be ordered");
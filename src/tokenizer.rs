@@ -8,10 +8,11 @@
 //!
 //! [`attempt`]: Tokenizer::attempt
 
+use crate::diagnostic::Diagnostic;
 use crate::event::{Content, Event, Kind, Link, Name, Point, VOID_EVENTS};
 use crate::parser::ParseState;
 use crate::resolve::{call as call_resolve, Name as ResolveName};
-use crate::state::{call, State};
+use crate::state::{call, Name as StateName, State};
 use crate::subtokenize::Subresult;
 
 #[cfg(feature = "log")]
@@ -35,11 +36,16 @@ pub enum Container {
     ListItem,
     /// [GFM: Footnote definition][crate::construct::gfm_footnote_definition].
     GfmFootnoteDefinition,
+    /// [Fenced div][crate::construct::fenced_div].
+    FencedDiv,
+    /// [Directive (container)][crate::construct::directive_container].
+    Directive,
 }
 
 /// Info used to tokenize a container.
 ///
-/// Practically, these fields are only used for list items.
+/// Practically, these fields are only used for list items, fenced divs, and
+/// directives (container).
 #[derive(Debug)]
 pub struct ContainerState {
     /// Kind.
@@ -103,6 +109,15 @@ pub enum LabelKind {
     ///
     /// Construct: [Label end][crate::construct::label_end].
     GfmUndefinedFootnote,
+    /// Label (inline footnote) start.
+    ///
+    /// ```markdown
+    /// > | a ^[b] c
+    ///       ^^
+    /// ```
+    ///
+    /// Construct: [Label start (inline footnote)][crate::construct::label_start_inline_footnote].
+    InlineFootnote,
 }
 
 /// Label start, looking for an end.
@@ -153,6 +168,9 @@ struct Attempt {
     /// `nok` of [`State::Nok`][], because that means it is used in *another*
     /// attempt, which will receive that `Nok`, and has to handle it.
     progress: Option<Progress>,
+    /// Whether the attempt exceeds `max_attempts`, in which case it is
+    /// unsuccessful without trying.
+    exhausted: bool,
 }
 
 /// The internal state of a tokenizer.
@@ -200,6 +218,9 @@ pub struct TokenizeState<'a> {
     /// a list item.
     /// Used for GFM task list items.
     pub document_at_first_paragraph_of_list_item: bool,
+    /// Last whitespace seen when continuing list items: where it was seen,
+    /// and where it ends.
+    pub document_whitespace: Option<(usize, usize)>,
 
     // Couple of very frequent settings for parsing whitespace.
     pub space_or_tab_eol_content: Option<Content>,
@@ -209,6 +230,8 @@ pub struct TokenizeState<'a> {
     pub space_or_tab_content: Option<Content>,
     pub space_or_tab_min: usize,
     pub space_or_tab_max: usize,
+    pub space_or_tab_measure: bool,
+    pub space_or_tab_measured: Option<usize>,
     pub space_or_tab_size: usize,
     pub space_or_tab_token: Name,
 
@@ -226,10 +249,22 @@ pub struct TokenizeState<'a> {
     /// Used when tokenizing [text content][crate::construct::text].
     pub labels: Vec<Label>,
 
+    // Couple of directive related fields.
+    /// Sorted ranges of places where directive labels cannot be closed.
+    ///
+    /// Used when tokenizing [text content][crate::construct::text].
+    pub directive_label_unclosed: Vec<(usize, usize)>,
+    /// Sorted ranges of places where directive attributes cannot be closed.
+    ///
+    /// Used when tokenizing [text content][crate::construct::text].
+    pub directive_attributes_unclosed: Vec<(usize, usize)>,
+
     /// List of defined definition identifiers.
     pub definitions: Vec<String>,
     /// List of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// List of warnings.
+    pub diagnostics: Vec<Diagnostic>,
 
     // Last error message provided at an EOF of an expression.
     pub mdx_last_parse_error: Option<String>,
@@ -348,8 +383,12 @@ impl<'a> Tokenizer<'a> {
                 document_child_state: None,
                 document_child: None,
                 document_at_first_paragraph_of_list_item: false,
+                document_whitespace: None,
+                directive_label_unclosed: vec![],
+                directive_attributes_unclosed: vec![],
                 definitions: vec![],
                 gfm_footnote_definitions: vec![],
+                diagnostics: vec![],
                 mdx_last_parse_error: None,
                 end: 0,
                 label_starts: vec![],
@@ -369,6 +408,8 @@ impl<'a> Tokenizer<'a> {
                 space_or_tab_content: None,
                 space_or_tab_min: 0,
                 space_or_tab_max: 0,
+                space_or_tab_measure: false,
+                space_or_tab_measured: None,
                 space_or_tab_size: 0,
                 space_or_tab_token: Name::SpaceOrTab,
                 start: 0,
@@ -591,6 +632,7 @@ impl<'a> Tokenizer<'a> {
         let progress = Some(self.capture());
         let attempt = Attempt {
             kind: AttemptKind::Check,
+            exhausted: self.exhausted(),
             progress,
             ok,
             nok,
@@ -612,6 +654,9 @@ impl<'a> Tokenizer<'a> {
 
         let attempt = Attempt {
             kind: AttemptKind::Attempt,
+            // Only attempts that can fall back to something else can be
+            // exhausted.
+            exhausted: progress.is_some() && self.exhausted(),
             progress,
             ok,
             nok,
@@ -619,6 +664,22 @@ impl<'a> Tokenizer<'a> {
         self.attempts.push(attempt);
     }
 
+    /// Count an attempt, and check whether there are too many.
+    fn exhausted(&self) -> bool {
+        let attempts = self.parse_state.attempts.get() + 1;
+        self.parse_state.attempts.set(attempts);
+        matches!(self.parse_state.options.max_attempts, Some(max) if attempts > max)
+    }
+
+    /// Check whether another label start would nest labels deeper than
+    /// `max_nesting`.
+    pub fn label_starts_full(&self) -> bool {
+        matches!(
+            self.parse_state.options.max_nesting,
+            Some(max) if self.tokenize_state.label_starts.len() >= max
+        )
+    }
+
     /// Tokenize.
     pub fn push(&mut self, from: (usize, usize), to: (usize, usize), state: State) -> State {
         push_impl(self, from, to, state, false)
@@ -635,6 +696,7 @@ impl<'a> Tokenizer<'a> {
             done: false,
             gfm_footnote_definitions: self.tokenize_state.gfm_footnote_definitions.split_off(0),
             definitions: self.tokenize_state.definitions.split_off(0),
+            diagnostics: vec![],
         };
 
         if resolve {
@@ -653,6 +715,11 @@ impl<'a> Tokenizer<'a> {
             self.map.consume(&mut self.events);
         }
 
+        // Resolvers can add diagnostics too.
+        value
+            .diagnostics
+            .append(&mut self.tokenize_state.diagnostics);
+
         Ok(value)
     }
 }
@@ -703,6 +770,19 @@ fn push_impl(
     tokenizer.move_to(from);
 
     loop {
+        // Fail exhausted attempts before they start.
+        if let Some(attempt) = tokenizer.attempts.last_mut() {
+            if attempt.exhausted {
+                // Blank lines are always checked, as what comes after
+                // expects it.
+                if matches!(state, State::Retry(StateName::BlankLineStart)) {
+                    attempt.exhausted = false;
+                } else if matches!(state, State::Next(_) | State::Retry(_)) {
+                    state = State::Nok;
+                }
+            }
+        }
+
         match state {
             State::Error(_) => break,
             State::Ok | State::Nok => {
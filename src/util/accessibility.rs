@@ -0,0 +1,156 @@
+//! Check events for accessibility problems.
+
+use crate::diagnostic::Diagnostic;
+use crate::event::{Event, Kind, Name};
+use crate::unist::{Point, Position};
+use crate::util::{
+    character_reference::decode as decode_character_reference,
+    slice::{Position as SlicePosition, Slice},
+};
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// Link texts that do not describe where a link goes.
+static NON_DESCRIPTIVE_LINK_TEXTS: [&str; 9] = [
+    "click",
+    "click here",
+    "here",
+    "learn more",
+    "link",
+    "more",
+    "read more",
+    "this",
+    "this link",
+];
+
+/// Link or image that is open.
+struct Open {
+    /// Index of its enter event.
+    index: usize,
+    /// Text content of its label.
+    text: String,
+    /// Whether its label is still open.
+    in_label: bool,
+}
+
+/// Find images without alt text and links with non-descriptive text.
+pub fn lint(events: &[Event], bytes: &[u8]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut stack: Vec<Open> = vec![];
+    let mut character_reference_marker = 0;
+    // Depth of resources and references, which are not part of text.
+    let mut destination_depth = 0;
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        match (&event.kind, &event.name) {
+            (Kind::Enter, Name::Image | Name::Link) => stack.push(Open {
+                index,
+                text: String::new(),
+                in_label: true,
+            }),
+            (Kind::Enter, Name::Reference | Name::Resource) => destination_depth += 1,
+            (Kind::Exit, Name::Reference | Name::Resource) => destination_depth -= 1,
+            (Kind::Exit, Name::Label) => {
+                if let Some(open) = stack.last_mut() {
+                    open.in_label = false;
+                }
+            }
+            (Kind::Exit, Name::CharacterReferenceMarker) => character_reference_marker = b'&',
+            (Kind::Exit, Name::CharacterReferenceMarkerNumeric) => {
+                character_reference_marker = b'#';
+            }
+            (Kind::Exit, Name::CharacterReferenceMarkerHexadecimal) => {
+                character_reference_marker = b'x';
+            }
+            (Kind::Exit, Name::LineEnding) if destination_depth == 0 => {
+                for open in &mut stack {
+                    if open.in_label {
+                        open.text.push(' ');
+                    }
+                }
+            }
+            (
+                Kind::Exit,
+                Name::CharacterEscapeValue
+                | Name::CharacterReferenceValue
+                | Name::CodeTextData
+                | Name::Data,
+            ) if destination_depth == 0 => {
+                let slice =
+                    Slice::from_position(bytes, &SlicePosition::from_exit_event(events, index));
+                let mut value = slice.serialize();
+
+                if event.name == Name::CharacterReferenceValue {
+                    value = decode_character_reference(&value, character_reference_marker, true)
+                        .expect("expected to parse only valid named references");
+                    character_reference_marker = 0;
+                }
+
+                for open in &mut stack {
+                    if open.in_label {
+                        open.text.push_str(&value);
+                    }
+                }
+            }
+            (Kind::Exit, Name::Image | Name::Link) => {
+                let open = stack.pop().expect("expected open link or image");
+                let position = Position {
+                    start: to_point(&events[open.index]),
+                    end: to_point(event),
+                };
+                let text = normalize(&open.text);
+
+                if event.name == Name::Image {
+                    if text.is_empty() {
+                        diagnostics.push(Diagnostic {
+                            position,
+                            reason: "Unexpected image without alt text".into(),
+                            rule_id: "missing-image-alt".into(),
+                        });
+                    }
+                } else if text.is_empty() {
+                    diagnostics.push(Diagnostic {
+                        position,
+                        reason: "Unexpected link without text".into(),
+                        rule_id: "non-descriptive-link-text".into(),
+                    });
+                } else if NON_DESCRIPTIVE_LINK_TEXTS.contains(&text.as_str()) {
+                    diagnostics.push(Diagnostic {
+                        position,
+                        reason: format!("Unexpected non-descriptive link text `{}`", text),
+                        rule_id: "non-descriptive-link-text".into(),
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    diagnostics
+}
+
+/// Get a unist point from an event.
+fn to_point(event: &Event) -> Point {
+    Point::new(event.point.line, event.point.column, event.point.index)
+}
+
+/// Lowercase `value`, collapse whitespace, and remove trailing punctuation.
+fn normalize(value: &str) -> String {
+    let mut result = String::new();
+
+    for word in value.split_whitespace() {
+        if !result.is_empty() {
+            result.push(' ');
+        }
+
+        result.push_str(word);
+    }
+
+    result
+        .trim_end_matches(|char: char| char.is_ascii_punctuation())
+        .to_lowercase()
+}
@@ -0,0 +1,119 @@
+//! Parse attributes, such as in `{.a #b c=d}`.
+
+use alloc::{string::String, vec::Vec};
+
+/// Attributes, parsed.
+#[derive(Debug, Default)]
+pub struct Attributes {
+    /// ID (`#a`, `id=a`).
+    ///
+    /// When there are several, the last one is used.
+    pub id: Option<String>,
+    /// Classes (`.a`, `class=a`).
+    pub classes: Vec<String>,
+    /// Other attributes (`a=b`, `a="b c"`), in order.
+    pub other: Vec<(String, String)>,
+}
+
+/// Parse attributes in `value`.
+///
+/// `value` is either a word, which is a class (`a`), or attributes in braces
+/// (`{.a #b c=d e="f g"}`).
+/// In braces, attributes are separated by whitespace, and values can be
+/// quoted with `"` or `'`.
+/// Attributes that cannot be parsed, and names that are not valid HTML
+/// attribute names, are ignored.
+pub fn parse(value: &str) -> Attributes {
+    let mut result = Attributes::default();
+
+    let inner = if let Some(inner) = value.strip_prefix('{') {
+        inner.strip_suffix('}').unwrap_or(inner)
+    } else {
+        if !value.is_empty() {
+            result.classes.push(value.into());
+        }
+
+        return result;
+    };
+
+    let bytes = inner.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index].is_ascii_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+
+        while index < bytes.len() && !bytes[index].is_ascii_whitespace() && bytes[index] != b'=' {
+            index += 1;
+        }
+
+        let name = &inner[start..index];
+
+        if index < bytes.len() && bytes[index] == b'=' {
+            index += 1;
+            let quote = bytes
+                .get(index)
+                .copied()
+                .filter(|byte| matches!(byte, b'"' | b'\''));
+
+            let value = if let Some(quote) = quote {
+                index += 1;
+                let start = index;
+
+                while index < bytes.len() && bytes[index] != quote {
+                    index += 1;
+                }
+
+                let value = &inner[start..index];
+                // Skip the closing quote.
+                index += 1;
+                value
+            } else {
+                let start = index;
+
+                while index < bytes.len() && !bytes[index].is_ascii_whitespace() {
+                    index += 1;
+                }
+
+                &inner[start..index]
+            };
+
+            if name == "id" {
+                result.id = Some(value.into());
+            } else if name == "class" {
+                result
+                    .classes
+                    .extend(value.split_ascii_whitespace().map(Into::into));
+            } else if is_name(name) {
+                result.other.push((name.into(), value.into()));
+            }
+        } else if let Some(class) = name.strip_prefix('.') {
+            if !class.is_empty() {
+                result.classes.push(class.into());
+            }
+        } else if let Some(id) = name.strip_prefix('#') {
+            if !id.is_empty() {
+                result.id = Some(id.into());
+            }
+        }
+    }
+
+    result
+}
+
+/// Check whether `name` can be used as an attribute name in HTML.
+///
+/// This is stricter than HTML, and matches the attribute names allowed in
+/// [HTML (text)][crate::construct::html_text].
+fn is_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+
+    matches!(bytes.first(), Some(b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z'))
+        && bytes
+            .iter()
+            .all(|byte| matches!(byte, b'-' | b'.' | b'0'..=b'9' | b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z'))
+}
@@ -0,0 +1,54 @@
+//! Info on the direction of text.
+
+/// Direction of text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// Left-to-right, such as Latin.
+    Ltr,
+    /// Right-to-left, such as Arabic and Hebrew.
+    Rtl,
+}
+
+impl Direction {
+    /// Turn the direction into a value for the HTML `dir` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// Blocks of characters that are written right-to-left.
+///
+/// Based on the bidi classes `R` and `AL` in Unicode.
+/// Includes Hebrew, Arabic, Syriac, Thaana, N’Ko, Samaritan, Mandaic, their
+/// supplements and presentation forms, and historic scripts in the
+/// supplementary planes.
+static RTL: [(char, char); 5] = [
+    ('\u{0590}', '\u{08FF}'),
+    ('\u{FB1D}', '\u{FDFF}'),
+    ('\u{FE70}', '\u{FEFF}'),
+    ('\u{10800}', '\u{10FFF}'),
+    ('\u{1E800}', '\u{1EFFF}'),
+];
+
+/// Get the direction of the first character with a strong direction in
+/// `value`.
+///
+/// This is the “first strong” heuristic from the Unicode bidi algorithm (and
+/// `dir="auto"` in HTML), simplified: letters in right-to-left blocks are
+/// right-to-left, other letters are left-to-right, and everything else (such
+/// as digits, punctuation, and whitespace) is neutral.
+pub fn first_strong(value: &str) -> Option<Direction> {
+    value.chars().find(|char| char.is_alphabetic()).map(|char| {
+        if RTL
+            .iter()
+            .any(|(start, end)| *start <= char && char <= *end)
+        {
+            Direction::Rtl
+        } else {
+            Direction::Ltr
+        }
+    })
+}
@@ -8,8 +8,9 @@
 //!
 //! Some constants are instead lists of things, such as the list of tag names
 //! considered in the **raw** production of [HTML (flow)][html_flow]
-//! ([`HTML_RAW_NAMES`][]), or the list of named character references
-//! ([`CHARACTER_REFERENCES`][]).
+//! ([`HTML_RAW_NAMES`][]), the list of named character references
+//! ([`CHARACTER_REFERENCES`][]), or the list of emoji shortcodes
+//! ([`EMOJI`][]).
 //!
 //! [raw_flow]: crate::construct::raw_flow
 //! [heading_atx]: crate::construct::heading_atx
@@ -67,6 +68,21 @@ pub const CHARACTER_REFERENCE_NAMED_SIZE_MAX: usize = 31;
 /// [raw_flow]: crate::construct::raw_flow
 pub const CODE_FENCED_SEQUENCE_SIZE_MIN: usize = 3;
 
+/// The number of markers needed for a [directive (container)][directive_container]
+/// to form.
+///
+/// Like code (fenced), the number is `3`.
+///
+/// [directive_container]: crate::construct::directive_container
+pub const DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN: usize = 3;
+
+/// The number of markers needed for a [fenced div][fenced_div] to form.
+///
+/// Like code (fenced), the number is `3`.
+///
+/// [fenced_div]: crate::construct::fenced_div
+pub const FENCED_DIV_SEQUENCE_SIZE_MIN: usize = 3;
+
 /// The number of markers needed for [frontmatter][] to form.
 ///
 /// Like many things in markdown, the number is `3`.
@@ -2701,6 +2717,574 @@ pub const CHARACTER_REFERENCES_HTML_4: [(&str, &str); 252] = [
     ("zwnj", "‌"),
 ];
 
+// Important: please touch the below list as few times as possible to keep Git small.
+
+/// List of names and values that form [emoji][emoji] shortcodes.
+///
+/// This list is sensitive to casing, and sorted by name, so that it can be
+/// searched.
+///
+/// It contains common shortcodes supported on GitHub.
+///
+/// ## References
+///
+/// *   [`github/gemoji`](https://github.com/github/gemoji)
+///
+/// [emoji]: crate::construct::emoji
+pub static EMOJI: [(&str, &str); 551] = [
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("1234", "🔢"),
+    ("8ball", "🎱"),
+    ("a", "🅰️"),
+    ("ab", "🆎"),
+    ("abc", "🔤"),
+    ("airplane", "✈️"),
+    ("alarm_clock", "⏰"),
+    ("alien", "👽"),
+    ("ambulance", "🚑"),
+    ("anchor", "⚓"),
+    ("angel", "👼"),
+    ("anger", "💢"),
+    ("angry", "😠"),
+    ("anguished", "😧"),
+    ("ant", "🐜"),
+    ("apple", "🍎"),
+    ("arrow_down", "⬇️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_right", "➡️"),
+    ("arrow_up", "⬆️"),
+    ("art", "🎨"),
+    ("astonished", "😲"),
+    ("athletic_shoe", "👟"),
+    ("avocado", "🥑"),
+    ("baby", "👶"),
+    ("baby_bottle", "🍼"),
+    ("balloon", "🎈"),
+    ("banana", "🍌"),
+    ("bank", "🏦"),
+    ("bar_chart", "📊"),
+    ("baseball", "⚾"),
+    ("basketball", "🏀"),
+    ("bat", "🦇"),
+    ("bath", "🛀"),
+    ("battery", "🔋"),
+    ("bear", "🐻"),
+    ("bee", "🐝"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("bell", "🔔"),
+    ("bento", "🍱"),
+    ("bicyclist", "🚴"),
+    ("bike", "🚲"),
+    ("bird", "🐦"),
+    ("birthday", "🎂"),
+    ("black_heart", "🖤"),
+    ("blossom", "🌼"),
+    ("blue_heart", "💙"),
+    ("blush", "😊"),
+    ("boar", "🐗"),
+    ("bomb", "💣"),
+    ("book", "📖"),
+    ("bookmark", "🔖"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("boot", "👢"),
+    ("bouquet", "💐"),
+    ("bow", "🙇"),
+    ("bowling", "🎳"),
+    ("boy", "👦"),
+    ("bread", "🍞"),
+    ("bride_with_veil", "👰"),
+    ("bridge_at_night", "🌉"),
+    ("briefcase", "💼"),
+    ("broken_heart", "💔"),
+    ("brown_heart", "🤎"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("bus", "🚌"),
+    ("butterfly", "🦋"),
+    ("cactus", "🌵"),
+    ("cake", "🍰"),
+    ("calendar", "📆"),
+    ("calling", "📲"),
+    ("camel", "🐫"),
+    ("camera", "📷"),
+    ("candy", "🍬"),
+    ("car", "🚗"),
+    ("carrot", "🥕"),
+    ("cat", "🐱"),
+    ("cat2", "🐈"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("cheese", "🧀"),
+    ("cherries", "🍒"),
+    ("cherry_blossom", "🌸"),
+    ("chestnut", "🌰"),
+    ("chicken", "🐔"),
+    ("children_crossing", "🚸"),
+    ("chocolate_bar", "🍫"),
+    ("christmas_tree", "🎄"),
+    ("clap", "👏"),
+    ("clapper", "🎬"),
+    ("clipboard", "📋"),
+    ("clock1", "🕐"),
+    ("closed_book", "📕"),
+    ("cloud", "☁️"),
+    ("clown_face", "🤡"),
+    ("cocktail", "🍸"),
+    ("coffee", "☕"),
+    ("cold_sweat", "😰"),
+    ("collision", "💥"),
+    ("computer", "💻"),
+    ("confetti_ball", "🎊"),
+    ("confounded", "😖"),
+    ("confused", "😕"),
+    ("construction", "🚧"),
+    ("cookie", "🍪"),
+    ("cool", "🆒"),
+    ("cop", "👮"),
+    ("copyright", "©️"),
+    ("corn", "🌽"),
+    ("couch_and_lamp", "🛋️"),
+    ("cow", "🐮"),
+    ("cow2", "🐄"),
+    ("crab", "🦀"),
+    ("crayon", "🖍️"),
+    ("credit_card", "💳"),
+    ("crescent_moon", "🌙"),
+    ("crocodile", "🐊"),
+    ("croissant", "🥐"),
+    ("crossed_fingers", "🤞"),
+    ("crown", "👑"),
+    ("cry", "😢"),
+    ("crying_cat_face", "😿"),
+    ("crystal_ball", "🔮"),
+    ("cupid", "💘"),
+    ("curly_loop", "➰"),
+    ("cyclone", "🌀"),
+    ("dancer", "💃"),
+    ("dart", "🎯"),
+    ("dash", "💨"),
+    ("date", "📅"),
+    ("deer", "🦌"),
+    ("desert", "🏜️"),
+    ("disappointed", "😞"),
+    ("disappointed_relieved", "😥"),
+    ("dizzy", "💫"),
+    ("dizzy_face", "😵"),
+    ("dog", "🐶"),
+    ("dog2", "🐕"),
+    ("dollar", "💵"),
+    ("dolphin", "🐬"),
+    ("door", "🚪"),
+    ("doughnut", "🍩"),
+    ("dove", "🕊️"),
+    ("dragon", "🐉"),
+    ("dragon_face", "🐲"),
+    ("dress", "👗"),
+    ("drooling_face", "🤤"),
+    ("droplet", "💧"),
+    ("duck", "🦆"),
+    ("eagle", "🦅"),
+    ("ear", "👂"),
+    ("earth_africa", "🌍"),
+    ("earth_americas", "🌎"),
+    ("earth_asia", "🌏"),
+    ("egg", "🥚"),
+    ("eggplant", "🍆"),
+    ("elephant", "🐘"),
+    ("email", "📧"),
+    ("envelope", "✉️"),
+    ("euro", "💶"),
+    ("evergreen_tree", "🌲"),
+    ("exclamation", "❗"),
+    ("expressionless", "😑"),
+    ("eyeglasses", "👓"),
+    ("eyes", "👀"),
+    ("face_with_head_bandage", "🤕"),
+    ("face_with_thermometer", "🤒"),
+    ("facepunch", "👊"),
+    ("factory", "🏭"),
+    ("fallen_leaf", "🍂"),
+    ("family", "👪"),
+    ("fearful", "😨"),
+    ("feet", "🐾"),
+    ("ferris_wheel", "🎡"),
+    ("file_folder", "📁"),
+    ("fire", "🔥"),
+    ("fire_engine", "🚒"),
+    ("fireworks", "🎆"),
+    ("first_quarter_moon", "🌓"),
+    ("fish", "🐟"),
+    ("fishing_pole_and_fish", "🎣"),
+    ("fist", "✊"),
+    ("flashlight", "🔦"),
+    ("floppy_disk", "💾"),
+    ("flushed", "😳"),
+    ("football", "🏈"),
+    ("fork_and_knife", "🍴"),
+    ("fountain", "⛲"),
+    ("four_leaf_clover", "🍀"),
+    ("fox_face", "🦊"),
+    ("fries", "🍟"),
+    ("frog", "🐸"),
+    ("frowning", "😦"),
+    ("fuelpump", "⛽"),
+    ("full_moon", "🌕"),
+    ("game_die", "🎲"),
+    ("gem", "💎"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("gift_heart", "💝"),
+    ("giraffe", "🦒"),
+    ("girl", "👧"),
+    ("globe_with_meridians", "🌐"),
+    ("goat", "🐐"),
+    ("golf", "⛳"),
+    ("gorilla", "🦍"),
+    ("grapes", "🍇"),
+    ("green_apple", "🍏"),
+    ("green_book", "📗"),
+    ("green_heart", "💚"),
+    ("grey_exclamation", "❕"),
+    ("grey_question", "❔"),
+    ("grimacing", "😬"),
+    ("grin", "😁"),
+    ("grinning", "😀"),
+    ("guitar", "🎸"),
+    ("gun", "🔫"),
+    ("hamburger", "🍔"),
+    ("hammer", "🔨"),
+    ("hamster", "🐹"),
+    ("hand", "✋"),
+    ("handbag", "👜"),
+    ("hankey", "💩"),
+    ("hatching_chick", "🐣"),
+    ("headphones", "🎧"),
+    ("hear_no_evil", "🙉"),
+    ("heart", "❤️"),
+    ("heart_eyes", "😍"),
+    ("heart_eyes_cat", "😻"),
+    ("heartbeat", "💓"),
+    ("heartpulse", "💗"),
+    ("hearts", "♥️"),
+    ("heavy_check_mark", "✔️"),
+    ("heavy_multiplication_x", "✖️"),
+    ("hedgehog", "🦔"),
+    ("helicopter", "🚁"),
+    ("herb", "🌿"),
+    ("hibiscus", "🌺"),
+    ("high_brightness", "🔆"),
+    ("hocho", "🔪"),
+    ("honey_pot", "🍯"),
+    ("horse", "🐴"),
+    ("hospital", "🏥"),
+    ("hot_pepper", "🌶️"),
+    ("hotdog", "🌭"),
+    ("hotel", "🏨"),
+    ("hourglass", "⌛"),
+    ("house", "🏠"),
+    ("hugs", "🤗"),
+    ("hushed", "😯"),
+    ("ice_cream", "🍨"),
+    ("icecream", "🍦"),
+    ("imp", "👿"),
+    ("inbox_tray", "📥"),
+    ("information_source", "ℹ️"),
+    ("innocent", "😇"),
+    ("iphone", "📱"),
+    ("jack_o_lantern", "🎃"),
+    ("jeans", "👖"),
+    ("joy", "😂"),
+    ("joy_cat", "😹"),
+    ("key", "🔑"),
+    ("keyboard", "⌨️"),
+    ("kiss", "💋"),
+    ("kissing", "😗"),
+    ("kissing_heart", "😘"),
+    ("kiwi_fruit", "🥝"),
+    ("knife", "🔪"),
+    ("koala", "🐨"),
+    ("label", "🏷️"),
+    ("ladybug", "🐞"),
+    ("laughing", "😆"),
+    ("leaves", "🍃"),
+    ("ledger", "📒"),
+    ("lemon", "🍋"),
+    ("leopard", "🐆"),
+    ("link", "🔗"),
+    ("lion", "🦁"),
+    ("lips", "👄"),
+    ("lipstick", "💄"),
+    ("lock", "🔒"),
+    ("lollipop", "🍭"),
+    ("loudspeaker", "📢"),
+    ("love_letter", "💌"),
+    ("mag", "🔍"),
+    ("mailbox", "📫"),
+    ("man", "👨"),
+    ("mango", "🥭"),
+    ("maple_leaf", "🍁"),
+    ("mask", "😷"),
+    ("meat_on_bone", "🍖"),
+    ("medal_sports", "🏅"),
+    ("mega", "📣"),
+    ("memo", "📝"),
+    ("microphone", "🎤"),
+    ("microscope", "🔬"),
+    ("milky_way", "🌌"),
+    ("money_with_wings", "💸"),
+    ("moneybag", "💰"),
+    ("monkey", "🐒"),
+    ("monkey_face", "🐵"),
+    ("moon", "🌔"),
+    ("mortar_board", "🎓"),
+    ("mosquito", "🦟"),
+    ("motorcycle", "🏍️"),
+    ("mount_fuji", "🗻"),
+    ("mountain", "⛰️"),
+    ("mouse", "🐭"),
+    ("mouse2", "🐁"),
+    ("muscle", "💪"),
+    ("mushroom", "🍄"),
+    ("musical_note", "🎵"),
+    ("nail_care", "💅"),
+    ("nerd_face", "🤓"),
+    ("neutral_face", "😐"),
+    ("new", "🆕"),
+    ("new_moon", "🌑"),
+    ("newspaper", "📰"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("no_mouth", "😶"),
+    ("nose", "👃"),
+    ("notebook", "📓"),
+    ("notes", "🎶"),
+    ("nut_and_bolt", "🔩"),
+    ("o", "⭕"),
+    ("ocean", "🌊"),
+    ("octopus", "🐙"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("old_key", "🗝️"),
+    ("older_man", "👴"),
+    ("older_woman", "👵"),
+    ("open_book", "📖"),
+    ("open_hands", "👐"),
+    ("open_mouth", "😮"),
+    ("orange", "🍊"),
+    ("orange_book", "📙"),
+    ("orange_heart", "🧡"),
+    ("owl", "🦉"),
+    ("ox", "🐂"),
+    ("package", "📦"),
+    ("palm_tree", "🌴"),
+    ("panda_face", "🐼"),
+    ("paperclip", "📎"),
+    ("parrot", "🦜"),
+    ("partying_face", "🥳"),
+    ("peach", "🍑"),
+    ("peanuts", "🥜"),
+    ("pear", "🍐"),
+    ("pencil", "📝"),
+    ("pencil2", "✏️"),
+    ("penguin", "🐧"),
+    ("pensive", "😔"),
+    ("persevere", "😣"),
+    ("phone", "☎️"),
+    ("pig", "🐷"),
+    ("pig2", "🐖"),
+    ("pill", "💊"),
+    ("pineapple", "🍍"),
+    ("pizza", "🍕"),
+    ("point_down", "👇"),
+    ("point_left", "👈"),
+    ("point_right", "👉"),
+    ("point_up", "☝️"),
+    ("point_up_2", "👆"),
+    ("poop", "💩"),
+    ("popcorn", "🍿"),
+    ("postbox", "📮"),
+    ("potato", "🥔"),
+    ("pouting_cat", "😾"),
+    ("pray", "🙏"),
+    ("pretzel", "🥨"),
+    ("purple_heart", "💜"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rabbit", "🐰"),
+    ("rabbit2", "🐇"),
+    ("racehorse", "🐎"),
+    ("radio", "📻"),
+    ("rage", "😡"),
+    ("rainbow", "🌈"),
+    ("raised_hand", "✋"),
+    ("raised_hands", "🙌"),
+    ("ram", "🐏"),
+    ("ramen", "🍜"),
+    ("rat", "🐀"),
+    ("recycle", "♻️"),
+    ("red_circle", "🔴"),
+    ("registered", "®️"),
+    ("relaxed", "☺️"),
+    ("relieved", "😌"),
+    ("rescue_worker_helmet", "⛑️"),
+    ("rice", "🍚"),
+    ("ring", "💍"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rofl", "🤣"),
+    ("roll_eyes", "🙄"),
+    ("rooster", "🐓"),
+    ("rose", "🌹"),
+    ("rotating_light", "🚨"),
+    ("runner", "🏃"),
+    ("running", "🏃"),
+    ("sailboat", "⛵"),
+    ("sake", "🍶"),
+    ("sandwich", "🥪"),
+    ("santa", "🎅"),
+    ("satellite", "📡"),
+    ("satisfied", "😆"),
+    ("sauropod", "🦕"),
+    ("saxophone", "🎷"),
+    ("school", "🏫"),
+    ("scissors", "✂️"),
+    ("scream", "😱"),
+    ("scream_cat", "🙀"),
+    ("scroll", "📜"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("shark", "🦈"),
+    ("sheep", "🐑"),
+    ("shell", "🐚"),
+    ("ship", "🚢"),
+    ("shirt", "👕"),
+    ("shrimp", "🦐"),
+    ("shrug", "🤷"),
+    ("skull", "💀"),
+    ("sleeping", "😴"),
+    ("sleepy", "😪"),
+    ("slightly_frowning_face", "🙁"),
+    ("slightly_smiling_face", "🙂"),
+    ("smile", "😄"),
+    ("smile_cat", "😸"),
+    ("smiley", "😃"),
+    ("smiley_cat", "😺"),
+    ("smiling_imp", "😈"),
+    ("smirk", "😏"),
+    ("smirk_cat", "😼"),
+    ("snail", "🐌"),
+    ("snake", "🐍"),
+    ("sneezing_face", "🤧"),
+    ("snowflake", "❄️"),
+    ("snowman", "⛄"),
+    ("sob", "😭"),
+    ("soccer", "⚽"),
+    ("sparkles", "✨"),
+    ("sparkling_heart", "💖"),
+    ("speak_no_evil", "🙊"),
+    ("speech_balloon", "💬"),
+    ("spider", "🕷️"),
+    ("squid", "🦑"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("stars", "🌠"),
+    ("stethoscope", "🩺"),
+    ("stop_sign", "🛑"),
+    ("strawberry", "🍓"),
+    ("stuck_out_tongue", "😛"),
+    ("stuck_out_tongue_closed_eyes", "😝"),
+    ("stuck_out_tongue_winking_eye", "😜"),
+    ("sun_with_face", "🌞"),
+    ("sunflower", "🌻"),
+    ("sunglasses", "😎"),
+    ("sunny", "☀️"),
+    ("sunrise", "🌅"),
+    ("sushi", "🍣"),
+    ("swan", "🦢"),
+    ("sweat", "😓"),
+    ("sweat_drops", "💦"),
+    ("sweat_smile", "😅"),
+    ("sweet_potato", "🍠"),
+    ("t-rex", "🦖"),
+    ("taco", "🌮"),
+    ("tada", "🎉"),
+    ("tangerine", "🍊"),
+    ("taxi", "🚕"),
+    ("tea", "🍵"),
+    ("telephone", "☎️"),
+    ("telescope", "🔭"),
+    ("tennis", "🎾"),
+    ("tent", "⛺"),
+    ("thinking", "🤔"),
+    ("thought_balloon", "💭"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("ticket", "🎫"),
+    ("tiger", "🐯"),
+    ("tiger2", "🐅"),
+    ("tired_face", "😫"),
+    ("tm", "™️"),
+    ("toilet", "🚽"),
+    ("tomato", "🍅"),
+    ("tongue", "👅"),
+    ("tophat", "🎩"),
+    ("tractor", "🚜"),
+    ("traffic_light", "🚥"),
+    ("train", "🚋"),
+    ("triangular_ruler", "📐"),
+    ("trident", "🔱"),
+    ("triumph", "😤"),
+    ("trophy", "🏆"),
+    ("tropical_fish", "🐠"),
+    ("truck", "🚚"),
+    ("trumpet", "🎺"),
+    ("tulip", "🌷"),
+    ("turkey", "🦃"),
+    ("turtle", "🐢"),
+    ("tv", "📺"),
+    ("two_hearts", "💕"),
+    ("umbrella", "☔"),
+    ("unamused", "😒"),
+    ("unicorn", "🦄"),
+    ("unlock", "🔓"),
+    ("upside_down_face", "🙃"),
+    ("v", "✌️"),
+    ("vampire", "🧛"),
+    ("vertical_traffic_light", "🚦"),
+    ("violin", "🎻"),
+    ("volcano", "🌋"),
+    ("warning", "⚠️"),
+    ("watch", "⌚"),
+    ("watermelon", "🍉"),
+    ("wave", "👋"),
+    ("weary", "😩"),
+    ("whale", "🐳"),
+    ("whale2", "🐋"),
+    ("wheelchair", "♿"),
+    ("white_check_mark", "✅"),
+    ("white_flower", "💮"),
+    ("wilted_flower", "🥀"),
+    ("wind_chime", "🎐"),
+    ("wine_glass", "🍷"),
+    ("wink", "😉"),
+    ("wolf", "🐺"),
+    ("woman", "👩"),
+    ("worried", "😟"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("yellow_heart", "💛"),
+    ("yen", "💴"),
+    ("yum", "😋"),
+    ("zap", "⚡"),
+    ("zebra", "🦓"),
+    ("zipper_mouth_face", "🤐"),
+    ("zzz", "💤"),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2726,6 +3310,11 @@ mod tests {
             "`CHARACTER_REFERENCE_NAMED_SIZE_MAX`"
         );
 
+        assert!(
+            EMOJI.windows(2).all(|d| d[0].0 < d[1].0),
+            "`EMOJI` should be sorted"
+        );
+
         assert_eq!(
             GFM_HTML_TAGFILTER_SIZE_MAX,
             longest(&GFM_HTML_TAGFILTER_NAMES).unwrap().len(),
@@ -9,7 +9,7 @@
 //! through another tokenizer and inject the result.
 
 use crate::event::Event;
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, vec::Vec};
 
 /// Shift `previous` and `next` links according to `jumps`.
 ///
@@ -57,14 +57,19 @@ fn shift_links(events: &mut [Event], jumps: &[(usize, usize, usize)]) {
 /// Tracks a bunch of edits.
 #[derive(Debug)]
 pub struct EditMap {
-    /// Record of changes.
-    map: Vec<(usize, usize, Vec<Event>)>,
+    /// Record of changes, by the index they happen at.
+    ///
+    /// A map, instead of a list, so that adding many edits (such as when
+    /// resolving lots of attention in a big paragraph) stays fast.
+    map: BTreeMap<usize, (usize, Vec<Event>)>,
 }
 
 impl EditMap {
     /// Create a new edit map.
     pub fn new() -> EditMap {
-        EditMap { map: vec![] }
+        EditMap {
+            map: BTreeMap::new(),
+        }
     }
     /// Create an edit: a remove and/or add at a certain place.
     pub fn add(&mut self, index: usize, remove: usize, add: Vec<Event>) {
@@ -76,20 +81,23 @@ impl EditMap {
     }
     /// Done, change the events.
     pub fn consume(&mut self, events: &mut Vec<Event>) {
-        self.map
-            .sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
         if self.map.is_empty() {
             return;
         }
 
+        // Sorted by index, as it’s a B-tree.
+        let map: Vec<(usize, usize, Vec<Event>)> = core::mem::take(&mut self.map)
+            .into_iter()
+            .map(|(at, (remove, add))| (at, remove, add))
+            .collect();
+
         // Calculate jumps: where items in the current list move to.
-        let mut jumps = Vec::with_capacity(self.map.len());
+        let mut jumps = Vec::with_capacity(map.len());
         let mut index = 0;
         let mut add_acc = 0;
         let mut remove_acc = 0;
-        while index < self.map.len() {
-            let (at, remove, add) = &self.map[index];
+        while index < map.len() {
+            let (at, remove, add) = &map[index];
             remove_acc += remove;
             add_acc += add.len();
             jumps.push((*at, remove_acc, add_acc));
@@ -99,13 +107,11 @@ impl EditMap {
         shift_links(events, &jumps);
 
         let len_before = events.len();
-        let mut index = self.map.len();
-        let mut vecs = Vec::with_capacity(index * 2 + 1);
-        while index > 0 {
-            index -= 1;
-            vecs.push(events.split_off(self.map[index].0 + self.map[index].1));
-            vecs.push(self.map[index].2.split_off(0));
-            events.truncate(self.map[index].0);
+        let mut vecs = Vec::with_capacity(map.len() * 2 + 1);
+        for (at, remove, add) in map.into_iter().rev() {
+            vecs.push(events.split_off(at + remove));
+            vecs.push(add);
+            events.truncate(at);
         }
         vecs.push(events.split_off(0));
 
@@ -114,35 +120,128 @@ impl EditMap {
         while let Some(mut slice) = vecs.pop() {
             events.append(&mut slice);
         }
-
-        self.map.truncate(0);
     }
 }
 
 /// Create an edit.
 fn add_impl(edit_map: &mut EditMap, at: usize, remove: usize, mut add: Vec<Event>, before: bool) {
-    let mut index = 0;
-
     if remove == 0 && add.is_empty() {
         return;
     }
 
-    while index < edit_map.map.len() {
-        if edit_map.map[index].0 == at {
-            edit_map.map[index].1 += remove;
+    if let Some(edit) = edit_map.map.get_mut(&at) {
+        edit.0 += remove;
 
-            if before {
-                add.append(&mut edit_map.map[index].2);
-                edit_map.map[index].2 = add;
-            } else {
-                edit_map.map[index].2.append(&mut add);
-            }
+        if before {
+            add.append(&mut edit.1);
+            edit.1 = add;
+        } else {
+            edit.1.append(&mut add);
+        }
+    } else {
+        edit_map.map.insert(at, (remove, add));
+    }
+}
 
-            return;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Content, Kind, Link, Name, Point};
+    use alloc::vec;
+
+    fn event(index: usize) -> Event {
+        Event {
+            kind: Kind::Enter,
+            name: Name::Data,
+            point: Point {
+                line: 1,
+                column: index + 1,
+                index,
+                vs: 0,
+            },
+            link: None,
         }
+    }
 
-        index += 1;
+    fn indices(events: &[Event]) -> Vec<usize> {
+        events.iter().map(|d| d.point.index).collect()
     }
 
-    edit_map.map.push((at, remove, add));
+    #[test]
+    fn consume() {
+        let mut events: Vec<Event> = (0..6).map(event).collect();
+        let mut map = EditMap::new();
+        map.add(4, 1, vec![event(40)]);
+        map.add(1, 2, vec![]);
+        map.add(6, 0, vec![event(60), event(61)]);
+        map.consume(&mut events);
+        assert_eq!(
+            indices(&events),
+            vec![0, 3, 40, 5, 60, 61],
+            "should apply edits, in any order, in one go"
+        );
+
+        let mut map = EditMap::new();
+        map.consume(&mut events);
+        assert_eq!(
+            indices(&events),
+            vec![0, 3, 40, 5, 60, 61],
+            "should support no edits"
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let mut events: Vec<Event> = (0..4).map(event).collect();
+        let mut map = EditMap::new();
+        map.add(1, 1, vec![event(10)]);
+        map.add(1, 1, vec![event(11)]);
+        map.add_before(1, 0, vec![event(12)]);
+        map.add(3, 0, vec![]);
+        map.consume(&mut events);
+        assert_eq!(
+            indices(&events),
+            vec![0, 12, 10, 11, 3],
+            "should merge edits at the same index, summing removals"
+        );
+
+        let mut events: Vec<Event> = (0..4).map(event).collect();
+        let mut map = EditMap::new();
+        map.add(1, 1, vec![event(10)]);
+        map.add(2, 1, vec![event(20)]);
+        map.consume(&mut events);
+        assert_eq!(
+            indices(&events),
+            vec![0, 10, 20, 3],
+            "should support adjacent edits"
+        );
+    }
+
+    #[test]
+    fn links() {
+        let mut events: Vec<Event> = (0..5).map(event).collect();
+        events[1].link = Some(Link {
+            previous: None,
+            next: Some(3),
+            content: Content::Text,
+        });
+        events[3].link = Some(Link {
+            previous: Some(1),
+            next: None,
+            content: Content::Text,
+        });
+        let mut map = EditMap::new();
+        map.add(0, 1, vec![]);
+        map.add(2, 0, vec![event(20), event(21)]);
+        map.consume(&mut events);
+        assert_eq!(indices(&events), vec![1, 20, 21, 2, 3, 4]);
+        assert_eq!(
+            (
+                events[0].link.as_ref().unwrap().next,
+                events[4].link.as_ref().unwrap().previous
+            ),
+            (Some(4), Some(0)),
+            "should shift links to where events move"
+        );
+    }
 }
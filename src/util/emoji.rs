@@ -0,0 +1,35 @@
+//! Helpers for emoji.
+
+use crate::util::constant::EMOJI;
+use alloc::string::String;
+
+/// Decode emoji shortcodes.
+///
+/// Turn the name coming from an emoji shortcode (without the `:`s) into an
+/// emoji.
+/// This looks the given name up at `0` in the tuples of `custom` first, and
+/// then in [`EMOJI`][], and takes the corresponding value from `1`.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::emoji::decode_emoji;
+///
+/// assert_eq!(decode_emoji("smile", &[]), Some("😄"));
+/// assert_eq!(decode_emoji("ferris", &[("ferris".into(), "🦀".into())]), Some("🦀"));
+/// assert_eq!(decode_emoji("ferris", &[]), None);
+/// ```
+///
+/// ## References
+///
+/// *   [`github/gemoji`](https://github.com/github/gemoji)
+pub fn decode_emoji<'a>(name: &str, custom: &'a [(String, String)]) -> Option<&'a str> {
+    if let Some((_, value)) = custom.iter().find(|d| d.0 == name) {
+        Some(value)
+    } else {
+        EMOJI
+            .binary_search_by(|d| d.0.cmp(name))
+            .ok()
+            .map(|index| EMOJI[index].1)
+    }
+}
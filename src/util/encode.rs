@@ -53,3 +53,123 @@ pub fn encode(value: &str, encode_html: bool) -> String {
 
     result
 }
+
+/// Encode characters that have special meaning in HTML.
+///
+/// This is the same encoding that the HTML compiler uses for text and for
+/// attribute values (which it wraps in double quotes), so it can be used to
+/// generate markup around the HTML that this crate generates.
+/// `&`, `"`, `<`, and `>` are turned into character references, and NUL is
+/// replaced by the replacement character (`U+FFFD`).
+///
+/// Note that this does not make URLs safe: use [`sanitize`][crate::sanitize]
+/// for that.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::escape_html;
+///
+/// assert_eq!(escape_html("I <3 🦀 & \"you\""), "I &lt;3 🦀 &amp; &quot;you&quot;");
+/// ```
+#[must_use]
+pub fn escape_html(value: &str) -> String {
+    encode(value, true)
+}
+
+/// How to handle control characters that are not allowed in HTML.
+///
+/// These are the C0 controls (other than tab, line feed, form feed, and
+/// carriage return), delete, and the C1 controls: `U+0001` through `U+0008`,
+/// `U+000B`, `U+000E` through `U+001F`, and `U+007F` through `U+009F`.
+/// They can occur in markdown, and are passed through by default, but HTML
+/// parsers report them as errors.
+/// NUL (`U+0000`) is always replaced by the replacement character (`U+FFFD`).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_with_options, CompileOptions, ControlCharacterPolicy, Options};
+/// # fn main() -> Result<(), String> {
+///
+/// assert_eq!(
+///     to_html_with_options(
+///         "a\u{1}b",
+///         &Options {
+///             compile: CompileOptions {
+///               control_character_policy: ControlCharacterPolicy::Replace,
+///               ..CompileOptions::default()
+///             },
+///             ..Options::default()
+///         }
+///     )?,
+///     "<p>a\u{FFFD}b</p>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum ControlCharacterPolicy {
+    /// Keep them.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// a␁b
+    /// ```
+    ///
+    /// Yields `<p>a␁b</p>` (with the control character `U+0001`).
+    #[default]
+    Keep,
+    /// Replace them with the replacement character (`U+FFFD`).
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// a␁b
+    /// ```
+    ///
+    /// Yields `<p>a�b</p>`.
+    Replace,
+    /// Remove them.
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// a␁b
+    /// ```
+    ///
+    /// Yields `<p>ab</p>`.
+    Remove,
+}
+
+/// Handle control characters that are not allowed in HTML in `value`,
+/// according to `policy`.
+pub fn control_characters(value: String, policy: &ControlCharacterPolicy) -> String {
+    let replacement = match policy {
+        ControlCharacterPolicy::Keep => return value,
+        ControlCharacterPolicy::Replace => Some(char::REPLACEMENT_CHARACTER),
+        ControlCharacterPolicy::Remove => None,
+    };
+
+    if !value.chars().any(is_invalid_control) {
+        return value;
+    }
+
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        if !is_invalid_control(char) {
+            result.push(char);
+        } else if let Some(replacement) = replacement {
+            result.push(replacement);
+        }
+    }
+
+    result
+}
+
+/// Check whether `char` is a control character that is not allowed in HTML.
+fn is_invalid_control(char: char) -> bool {
+    matches!(char, '\u{1}'..='\u{8}' | '\u{B}' | '\u{E}'..='\u{1F}' | '\u{7F}'..='\u{9F}')
+}
@@ -52,7 +52,8 @@ pub fn gfm_tagfilter(value: &str) -> String {
             // Non-empty.
             if name_end != name_start &&
                 // HTML whitespace, closing slash, or closing angle bracket.
-                matches!(bytes[name_end], b'\t' | b'\n' | 12 /* `\f` */ | b'\r' | b' ' | b'/' | b'>') &&
+                // HTML data is split at line endings, so the end is one too.
+                (name_end == len || matches!(bytes[name_end], b'\t' | b'\n' | 12 /* `\f` */ | b'\r' | b' ' | b'/' | b'>')) &&
                 // Known name.
                 GFM_HTML_TAGFILTER_NAMES.contains(&str::from_utf8(&bytes[name_start..name_end])
                 .unwrap()
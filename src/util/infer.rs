@@ -51,6 +51,15 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
                 //   | * a
                 // ```
                 let mut at_empty_block_quote = false;
+                // Blank line after the closing fence of a fenced div or
+                // directive (container):
+                //
+                // ```markdown
+                // > | * :::␊
+                //         ^
+                //   | * a
+                // ```
+                let at_container_fence = after_container_fence(events, index);
 
                 // List.
                 let mut before = index - 2;
@@ -71,7 +80,7 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
                     }
                 }
 
-                if !at_empty_list_item && !at_empty_block_quote {
+                if !at_empty_list_item && !at_empty_block_quote && !at_container_fence {
                     return true;
                 }
             }
@@ -95,12 +104,33 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
         "expected list item"
     );
     let mut balance = 0;
+    // Whether a blank line was seen, which only makes the item loose if
+    // more content follows it.
+    let mut blank = false;
 
     while index < events.len() {
         let event = &events[index];
 
         if event.kind == Kind::Enter {
             balance += 1;
+
+            // Content after a blank line:
+            //
+            // ```markdown
+            //   | - a
+            //   |
+            // > |   b
+            //       ^
+            // ```
+            if blank
+                && balance == 2
+                && !matches!(
+                    event.name,
+                    Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+                )
+            {
+                return true;
+            }
         } else {
             balance -= 1;
 
@@ -125,8 +155,8 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
                     at_prefix = true;
                 }
 
-                if !at_prefix {
-                    return true;
+                if !at_prefix && !after_container_fence(events, index) {
+                    blank = true;
                 }
             }
 
@@ -142,6 +172,23 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
     false
 }
 
+/// Check if the blank line ending exited at `index` is the rest of the line
+/// of the closing fence of a fenced div or directive (container).
+///
+/// The container, and the list items closed along with it, are exited
+/// before that line ending, but it is not a blank line.
+fn after_container_fence(events: &[Event], index: usize) -> bool {
+    let mut before = index - 2;
+
+    while events[before].name == Name::ListItem {
+        before -= 1;
+    }
+
+    (events[before].name == Name::FencedDiv && events[before - 1].name == Name::FencedDivFence)
+        || (events[before].name == Name::DirectiveContainer
+            && events[before - 1].name == Name::DirectiveContainerFence)
+}
+
 /// Figure out the alignment of a GFM table.
 pub fn gfm_table_align(events: &[Event], mut index: usize) -> Vec<AlignKind> {
     debug_assert!(
@@ -60,6 +60,38 @@ impl LineEnding {
     }
 }
 
+/// Detect the line ending used in `value` (a string or bytes).
+///
+/// The first line ending in `value` is used.
+/// Returns `None` if there are no line endings.
+///
+/// This is also how the HTML compiler picks the line ending to use: only when
+/// there are no line endings, it uses
+/// [`default_line_ending`][crate::CompileOptions::default_line_ending].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{detect_line_ending, LineEnding};
+///
+/// assert_eq!(detect_line_ending("a\r\nb\nc"), Some(LineEnding::CarriageReturnLineFeed));
+/// assert_eq!(detect_line_ending("a"), None);
+/// assert_eq!(detect_line_ending(b"a\rb"), Some(LineEnding::CarriageReturn));
+/// ```
+#[must_use]
+pub fn detect_line_ending<T: AsRef<[u8]> + ?Sized>(value: &T) -> Option<LineEnding> {
+    let bytes = value.as_ref();
+    let index = bytes.iter().position(|d| matches!(d, b'\n' | b'\r'))?;
+
+    Some(if bytes[index] == b'\n' {
+        LineEnding::LineFeed
+    } else if bytes.get(index + 1) == Some(&b'\n') {
+        LineEnding::CarriageReturnLineFeed
+    } else {
+        LineEnding::CarriageReturn
+    })
+}
+
 impl FromStr for LineEnding {
     type Err = String;
 
@@ -1,7 +1,7 @@
 //! Collect info for MDX.
 
 use crate::event::{Event, Kind, Name};
-use crate::util::slice::{Position, Slice};
+use crate::util::slice::Position;
 use alloc::{string::String, vec, vec::Vec};
 
 pub type Stop = (usize, usize);
@@ -28,15 +28,13 @@ pub fn collect(
     while index < events.len() {
         if events[index].kind == Kind::Enter {
             if names.contains(&events[index].name) {
-                // Include virtual spaces, and assume void.
-                let value = Slice::from_position(
-                    bytes,
-                    &Position {
-                        start: &events[index].point,
-                        end: &events[index + 1].point,
-                    },
-                )
-                .serialize();
+                // Collapse virtual spaces into their tab, so that the value
+                // maps to the source byte for byte, and assume void.
+                let value = Position {
+                    start: &events[index].point,
+                    end: &events[index + 1].point,
+                }
+                .to_source_string(bytes);
                 result
                     .stops
                     .push((result.value.len(), events[index].point.index));
@@ -1,9 +1,13 @@
 //! Utilities used when processing markdown.
 
+pub mod accessibility;
+pub mod attributes;
+pub mod bidi;
 pub mod char;
 pub mod character_reference;
 pub mod constant;
 pub mod edit_map;
+pub mod emoji;
 pub mod encode;
 pub mod gfm_tagfilter;
 pub mod identifier;
@@ -12,7 +16,9 @@ pub mod line_ending;
 pub mod location;
 pub mod mdx;
 pub mod mdx_collect;
+pub mod multiblock;
 pub mod normalize_identifier;
+pub mod ordered_list;
 pub mod sanitize_uri;
 pub mod skip;
 pub mod slice;
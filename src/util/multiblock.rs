@@ -0,0 +1,137 @@
+//! Cheaply check whether markdown is more than a paragraph.
+
+use crate::util::{constant::TAB_SIZE, ordered_list::is_roman};
+
+/// Check whether `value` might contain more than one block, or a block other
+/// than a paragraph.
+///
+/// This does not parse `value`: it only looks at how lines start, for things
+/// that can start blocks (such as `#`, `>`, `-`, `1.`, and `` ``` ``), and at
+/// blank lines between lines.
+/// It errs on the side of caution: `true` means `value` *might* contain
+/// blocks, `false` means `value` is at most a single paragraph, so it can be
+/// compiled as text (phrasing) only.
+///
+/// Block constructs that are off by default (such as GFM, MDX, frontmatter,
+/// and math) are checked too, as are list items with letters (`a.`).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::is_multiblock;
+///
+/// assert!(!is_multiblock("Hi, *there*!\nHow are you?"));
+/// assert!(is_multiblock("Hi!\n\nHow are you?"));
+/// assert!(is_multiblock("- Hi!"));
+/// ```
+pub fn is_multiblock(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+    let mut first = true;
+    let mut blank = false;
+
+    while index < bytes.len() {
+        let mut end = index;
+        while end < bytes.len() && !matches!(bytes[end], b'\n' | b'\r') {
+            end += 1;
+        }
+
+        let line = &bytes[index..end];
+        let (column, rest) = indent(line);
+
+        if rest.is_empty() {
+            blank = !first;
+        } else {
+            // Content after a blank line.
+            if blank
+                // Indented code.
+                || (first && column >= TAB_SIZE)
+                || (column < TAB_SIZE && starts_block(rest, &bytes[index..]))
+            {
+                return true;
+            }
+
+            first = false;
+        }
+
+        // Skip the line ending.
+        if end + 1 < bytes.len() && bytes[end] == b'\r' && bytes[end + 1] == b'\n' {
+            end += 1;
+        }
+
+        index = end + 1;
+    }
+
+    false
+}
+
+/// Get the size (in columns) of the indent of `line`, and what comes after.
+fn indent(line: &[u8]) -> (usize, &[u8]) {
+    let mut column = 0;
+    let mut index = 0;
+
+    while index < line.len() {
+        match line[index] {
+            b'\t' => column += TAB_SIZE - column % TAB_SIZE,
+            b' ' => column += 1,
+            _ => break,
+        }
+
+        index += 1;
+    }
+
+    (column, &line[index..])
+}
+
+/// Check whether `line` (without indent) might start a block.
+///
+/// `after` is the rest of the document, from the start of the line.
+fn starts_block(line: &[u8], after: &[u8]) -> bool {
+    match line[0] {
+        // Heading (ATX), block quote, HTML (flow), MDX (JSX, expression).
+        b'#' | b'>' | b'<' | b'{' => true,
+        // Code (fenced), math (flow).
+        b'`' | b'~' | b'$' => {
+            let size = line.iter().take_while(|byte| **byte == line[0]).count();
+            size >= if line[0] == b'$' { 2 } else { 3 }
+        }
+        // Thematic break, list item, heading (setext) underline, table
+        // delimiter row, fenced div.
+        b'*' | b'+' | b'-' | b'=' | b'_' | b'|' | b':' => {
+            matches!(line.get(1), None | Some(b'\t' | b' '))
+                || line.starts_with(b":::")
+                || line.iter().all(|byte| {
+                    matches!(byte, b'\t' | b' ' | b'*' | b'-' | b'=' | b'_' | b'|' | b':')
+                })
+        }
+        // List item (ordered).
+        b'0'..=b'9' => {
+            let mut index = 1;
+            while index < line.len() && line[index].is_ascii_digit() {
+                index += 1;
+            }
+            matches!(line.get(index), Some(b'.' | b')'))
+                && matches!(line.get(index + 1), None | Some(b'\t' | b' '))
+        }
+        // Definition, footnote definition.
+        b'[' => after.windows(2).any(|pair| pair == b"]:"),
+        // MDX (ESM), list item (ordered, letters or roman numerals).
+        b'e' => line.starts_with(b"export ") || is_alpha_list_item(line),
+        b'i' => line.starts_with(b"import ") || is_alpha_list_item(line),
+        b'A'..=b'Z' | b'a'..=b'z' => is_alpha_list_item(line),
+        _ => false,
+    }
+}
+
+/// Check whether `line` (without indent) might start an ordered list item
+/// whose value is a letter or roman numerals.
+fn is_alpha_list_item(line: &[u8]) -> bool {
+    let size = line
+        .iter()
+        .take_while(|byte| byte.is_ascii_alphabetic())
+        .count();
+
+    (size == 1 || line[..size].iter().all(|byte| is_roman(*byte)))
+        && matches!(line.get(size), Some(b'.' | b')'))
+        && matches!(line.get(size + 1), None | Some(b'\t' | b' '))
+}
@@ -0,0 +1,122 @@
+//! Info on the values of ordered list items.
+//!
+//! Values are decimal (`1.`) by default, or letters (`a.`) and roman
+//! numerals (`iv.`) with
+//! [`alpha_ordered_lists`][crate::ParseOptions::alpha_ordered_lists].
+
+/// Kind of value of an ordered list item.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// Decimal numbers, such as `1`.
+    Decimal,
+    /// Lowercase letters, such as `a`.
+    LowerAlpha,
+    /// Uppercase letters, such as `A`.
+    UpperAlpha,
+    /// Lowercase roman numerals, such as `i`.
+    LowerRoman,
+    /// Uppercase roman numerals, such as `I`.
+    UpperRoman,
+}
+
+impl Kind {
+    /// Turn the kind into a value for the HTML `type` attribute of `<ol>`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Kind::Decimal => "1",
+            Kind::LowerAlpha => "a",
+            Kind::UpperAlpha => "A",
+            Kind::LowerRoman => "i",
+            Kind::UpperRoman => "I",
+        }
+    }
+}
+
+/// Check whether `byte` is a roman numeral (either case).
+pub fn is_roman(byte: u8) -> bool {
+    matches!(
+        byte.to_ascii_lowercase(),
+        b'c' | b'd' | b'i' | b'l' | b'm' | b'v' | b'x'
+    )
+}
+
+/// Get the kind of `value`.
+///
+/// A single letter is a letter, except for `i` and `I`, which are roman
+/// numerals.
+/// Several letters are roman numerals.
+pub fn kind(value: &[u8]) -> Kind {
+    match value {
+        [b'i'] => Kind::LowerRoman,
+        [b'I'] => Kind::UpperRoman,
+        [byte] if byte.is_ascii_lowercase() => Kind::LowerAlpha,
+        [byte] if byte.is_ascii_uppercase() => Kind::UpperAlpha,
+        [byte, ..] if byte.is_ascii_lowercase() => Kind::LowerRoman,
+        [byte, ..] if byte.is_ascii_uppercase() => Kind::UpperRoman,
+        _ => Kind::Decimal,
+    }
+}
+
+/// Check whether `value`, which is a single letter that is also a roman
+/// numeral (such as `i` or `v`), can continue a list of `kind`.
+///
+/// Such values are ambiguous: `i` comes after `h` in a list of letters, and
+/// `v` comes after `iv` in a list of roman numerals.
+pub fn is_ambiguous(value: &[u8], kind: Kind) -> bool {
+    match value {
+        [byte] if is_roman(*byte) => {
+            if byte.is_ascii_lowercase() {
+                matches!(kind, Kind::LowerAlpha | Kind::LowerRoman)
+            } else {
+                matches!(kind, Kind::UpperAlpha | Kind::UpperRoman)
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Get the number that `value`, of `kind`, represents.
+///
+/// Letters count from `a` (`1`) to `z` (`26`).
+/// Roman numerals support subtraction (`iv` is `4`) but are otherwise not
+/// validated (`iiiii` is `5`).
+pub fn number(value: &[u8], kind: Kind) -> u32 {
+    match kind {
+        Kind::Decimal => value
+            .iter()
+            .fold(0, |acc, byte| acc * 10 + u32::from(byte - b'0')),
+        Kind::LowerAlpha | Kind::UpperAlpha => u32::from(value[0].to_ascii_lowercase() - b'a') + 1,
+        Kind::LowerRoman | Kind::UpperRoman => {
+            let mut total = 0;
+            let mut index = 0;
+
+            while index < value.len() {
+                let current = roman(value[index]);
+
+                if index + 1 < value.len() && current < roman(value[index + 1]) {
+                    total -= current;
+                } else {
+                    total += current;
+                }
+
+                index += 1;
+            }
+
+            total.max(0).unsigned_abs()
+        }
+    }
+}
+
+/// Get the number that a roman numeral represents.
+fn roman(byte: u8) -> i32 {
+    match byte.to_ascii_lowercase() {
+        b'i' => 1,
+        b'v' => 5,
+        b'x' => 10,
+        b'l' => 50,
+        b'c' => 100,
+        b'd' => 500,
+        b'm' => 1000,
+        _ => 0,
+    }
+}
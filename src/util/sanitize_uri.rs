@@ -27,55 +27,43 @@ pub fn sanitize(value: &str) -> String {
     encode(&normalize(value), true)
 }
 
-/// Make a value safe for injection as a URL, and check protocols.
+/// Check whether the protocol of a URL is one of `protocols`.
 ///
-/// This first uses [`sanitize`][sanitize].
-/// Then, a vec of (lowercase) allowed protocols can be given, in which case
-/// the URL is ignored or kept.
-///
-/// For example, `&["http", "https", "irc", "ircs", "mailto", "xmpp"]`
-/// can be used for `a[href]`, or `&["http", "https"]` for `img[src]`.
-/// If the URL includes an unknown protocol (one not matched by `protocol`, such
-/// as a dangerous example, `javascript:`), the value is ignored.
+/// Protocols are compared case-insensitively.
+/// URLs without protocol are relative, and always allowed.
 ///
 /// ## Examples
 ///
 /// ```rust ignore
-/// use markdown::util::sanitize_uri::sanitize_with_protocols;
+/// use markdown::util::sanitize_uri::is_allowed_protocol;
 ///
-/// assert_eq!(sanitize_with_protocols("javascript:alert(1)", &["http", "https"]), "");
-/// assert_eq!(sanitize_with_protocols("https://example.com", &["http", "https"]), "https://example.com");
-/// assert_eq!(sanitize_with_protocols("https://a👍b.c/%20/%", &["http", "https"]), "https://a%F0%9F%91%8Db.c/%20/%25");
+/// assert!(is_allowed_protocol("HTTPS://example.com", &["https"]));
+/// assert!(is_allowed_protocol("./a/b.html", &["https"]));
+/// assert!(!is_allowed_protocol("ftp://example.com", &["https"]));
 /// ```
-///
-/// ## References
-///
-/// *   [`micromark-util-sanitize-uri` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-sanitize-uri)
-pub fn sanitize_with_protocols(value: &str, protocols: &[&str]) -> String {
-    let value = sanitize(value);
+pub fn is_allowed_protocol<T: AsRef<str>>(value: &str, protocols: &[T]) -> bool {
+    if let Some(protocol) = protocol(value) {
+        protocols
+            .iter()
+            .any(|d| d.as_ref().eq_ignore_ascii_case(protocol))
+    } else {
+        true
+    }
+}
 
+/// Get the protocol of a URL, if there is one.
+fn protocol(value: &str) -> Option<&str> {
     let end = value.find(|c| matches!(c, '?' | '#' | '/'));
-    let mut colon = value.find(|c| matches!(c, ':'));
+    let colon = value.find(|c| matches!(c, ':'))?;
 
     // If the first colon is after `?`, `#`, or `/`, it’s not a protocol.
     if let Some(end) = end {
-        if let Some(index) = colon {
-            if index > end {
-                colon = None;
-            }
-        }
-    }
-
-    // If there is no protocol, it’s relative, and fine.
-    if let Some(colon) = colon {
-        // If it is a protocol, it should be allowed.
-        let protocol = value[0..colon].to_lowercase();
-        if !protocols.contains(&protocol.as_str()) {
-            return String::new();
+        if colon > end {
+            return None;
         }
     }
 
-    value
+    Some(&value[0..colon])
 }
 
 /// Normalize a URL (such as used in [definitions][definition],
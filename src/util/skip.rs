@@ -22,6 +22,35 @@ pub fn to_back(events: &[Event], index: usize, names: &[Name]) -> usize {
     to_impl(events, index, names, false)
 }
 
+/// Get the index of the exit of the enter event at `index`, or of the enter
+/// of the exit event at `index`.
+///
+/// Nested events with the same name (such as lists in lists) are skipped.
+pub fn counterpart(events: &[Event], index: usize) -> usize {
+    let name = &events[index].name;
+    let kind = &events[index].kind;
+    let forward = *kind == Kind::Enter;
+    let mut other = index;
+    let mut depth = 0;
+
+    loop {
+        other = if forward { other + 1 } else { other - 1 };
+        let event = &events[other];
+
+        if event.name == *name {
+            if event.kind == *kind {
+                depth += 1;
+            } else if depth == 0 {
+                break;
+            } else {
+                depth -= 1;
+            }
+        }
+    }
+
+    other
+}
+
 /// Skip to something.
 fn to_impl(events: &[Event], mut index: usize, names: &[Name], forward: bool) -> usize {
     while index < events.len() {
@@ -47,6 +47,21 @@ impl<'a> Position<'a> {
     pub fn to_indices(&self) -> (usize, usize) {
         (self.start.index, self.end.index)
     }
+
+    /// Turn a position into the source it came from.
+    ///
+    /// Unlike [`Slice::serialize`][], which expands virtual spaces, this
+    /// collapses them back into the tab they came from: a tab that is
+    /// partially in this position is included as a whole.
+    pub fn to_source_string(&self, bytes: &[u8]) -> String {
+        let end = if self.end.vs > 0 {
+            self.end.index + 1
+        } else {
+            self.end.index
+        };
+
+        String::from_utf8(bytes[self.start.index..end].to_vec()).unwrap()
+    }
 }
 
 /// Bytes belonging to a range.
@@ -72,8 +87,10 @@ impl<'a> Slice<'a> {
 
         // If we have virtual spaces before, it means we are past the actual
         // character at that index, and those virtual spaces.
+        // The tab does not have to start at a tab stop, so the number of
+        // spaces left is based on the column.
         if before > 0 {
-            before = TAB_SIZE - before;
+            before = TAB_SIZE - (position.start.column - 1) % TAB_SIZE;
             start += 1;
         };
 
@@ -123,3 +140,63 @@ impl<'a> Slice<'a> {
         format!("{}{}{}", prefix, self.as_str(), suffix)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Name;
+    use crate::parser::parse;
+    use crate::ParseOptions;
+    use alloc::vec::Vec;
+
+    fn sources(value: &str, name: &Name) -> Vec<(String, String)> {
+        let options = ParseOptions::default();
+        let (events, parse_state) = parse(value, &options).unwrap();
+        let mut result = vec![];
+
+        for index in 0..events.len() {
+            if events[index].kind == Kind::Exit && events[index].name == *name {
+                let position = Position::from_exit_event(&events, index);
+                result.push((
+                    position.to_source_string(parse_state.bytes),
+                    Slice::from_position(parse_state.bytes, &position).serialize(),
+                ));
+            }
+        }
+
+        result
+    }
+
+    #[test]
+    fn to_source_string() {
+        assert_eq!(
+            sources("\ta\tb", &Name::CodeFlowChunk),
+            vec![("a\tb".into(), "a\tb".into())],
+            "should keep tabs inside a position"
+        );
+
+        assert_eq!(
+            sources("\t\ta", &Name::CodeFlowChunk),
+            vec![("\ta".into(), "\ta".into())],
+            "should keep tabs after the indent of code (indented)"
+        );
+
+        assert_eq!(
+            sources(">\t\ta", &Name::CodeFlowChunk),
+            vec![("\ta".into(), "  a".into())],
+            "should collapse virtual spaces at the start back into their tab"
+        );
+
+        assert_eq!(
+            sources("-\t\t\ta", &Name::CodeFlowChunk),
+            vec![("\t\ta".into(), "  \ta".into())],
+            "should collapse virtual spaces after a list item marker"
+        );
+
+        assert_eq!(
+            sources("a\tb", &Name::Data),
+            vec![("a\tb".into(), "a\tb".into())],
+            "should match `serialize` w/o virtual spaces"
+        );
+    }
+}
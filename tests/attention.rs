@@ -819,6 +819,60 @@ fn attention() -> Result<(), String> {
         "should not end strong emphasis inside autolinks (2)"
     );
 
+    assert_eq!(
+        to_html("*a`code`b*"),
+        "<p><em>a<code>code</code>b</em></p>",
+        "should support code in emphasis"
+    );
+
+    assert_eq!(
+        to_html("*a `b*` c*"),
+        "<p><em>a <code>b*</code> c</em></p>",
+        "should match emphasis around code, not inside it"
+    );
+
+    assert_eq!(
+        to_html("*`a*`"),
+        "<p>*<code>a*</code></p>",
+        "should not end emphasis inside code (3)"
+    );
+
+    assert_eq!(
+        to_html("`*a`*"),
+        "<p><code>*a</code>*</p>",
+        "should not start emphasis inside code"
+    );
+
+    assert_eq!(
+        to_html("**a`**`**"),
+        "<p><strong>a<code>**</code></strong></p>",
+        "should not end strong inside code"
+    );
+
+    assert_eq!(
+        to_html("*a ``b*` c*"),
+        "<p><em>a ``b</em>` c*</p>",
+        "should end emphasis inside unmatched code markers"
+    );
+
+    assert_eq!(
+        to_html("*a <https://b*c> d*"),
+        "<p><em>a <a href=\"https://b*c\">https://b*c</a> d</em></p>",
+        "should match emphasis around autolinks, not inside them"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a <b c=\"*\"> d*", &danger)?,
+        "<p><em>a <b c=\"*\"> d</em></p>",
+        "should match emphasis around HTML, not inside it"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a <!--*--> b*", &danger)?,
+        "<p><em>a <!--*--> b</em></p>",
+        "should match emphasis around HTML comments, not inside them"
+    );
+
     assert_eq!(
         to_html_with_options(
             "*a*",
@@ -837,6 +891,68 @@ fn attention() -> Result<(), String> {
         "should support turning off attention"
     );
 
+    let no_attention = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attention: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("**a** and __b__, _c_, ***d***", &no_attention)?,
+        "<p>**a** and __b__, _c_, ***d***</p>",
+        "should pass markers through as is when attention is off"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\*a\\* `*b*` [*c*](d) ![*e*](f)", &no_attention)?,
+        "<p>*a* <code>*b*</code> <a href=\"d\">*c*</a> <img src=\"f\" alt=\"*e*\" /></p>",
+        "should not affect other text constructs when attention is off"
+    );
+
+    assert_eq!(
+        to_html_with_options("# *a*\n\n* *b*\n\n***\n\n_c_\n---", &no_attention)?,
+        "<h1>*a*</h1>\n<ul>\n<li>*b*</li>\n</ul>\n<hr />\n<h2>_c_</h2>",
+        "should not affect flow constructs that use the same markers when attention is off"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*a* ~~b~~",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attention: false,
+                        ..Constructs::gfm()
+                    },
+                    ..ParseOptions::gfm()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>*a* <del>b</del></p>",
+        "should support GFM strikethrough when attention is off"
+    );
+
+    assert_eq!(
+        to_mdast("a *b*", &no_attention.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a *b*".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 6, 5))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 6, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 5))
+        }),
+        "should support attention turned off in mdast"
+    );
+
     assert_eq!(
         to_mdast("a *alpha* b **bravo** c.", &Default::default())?,
         Node::Root(Root {
@@ -7,6 +7,8 @@ use markdown::{
 use pretty_assertions::assert_eq;
 
 #[test]
+// `allowed_link_schemes` is deprecated, but still tested.
+#[allow(deprecated)]
 fn autolink() -> Result<(), String> {
     let danger = Options {
         compile: CompileOptions {
@@ -251,6 +253,128 @@ fn autolink() -> Result<(), String> {
         "should not support a dash before a dot in email autolinks"
     );
 
+    assert_eq!(
+        to_html(&format!("<{}:b>", "a".repeat(32))),
+        format!("<p><a href=\"\">{}:b</a></p>", "a".repeat(32)),
+        "should support a scheme of 32 characters in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html(&format!("<{}:b>", "a".repeat(33))),
+        format!("<p>&lt;{}:b&gt;</p>", "a".repeat(33)),
+        "should not support a scheme of 33 characters in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<ab:c>"),
+        "<p><a href=\"\">ab:c</a></p>",
+        "should support a scheme of 2 characters in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<a1+.-:b>"),
+        "<p><a href=\"\">a1+.-:b</a></p>",
+        "should support digits, `+`, `.`, and `-` after the first character of a scheme in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<1a:b>"),
+        "<p>&lt;1a:b&gt;</p>",
+        "should not support a digit as the first character of a scheme in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<a_b:c>"),
+        "<p>&lt;a_b:c&gt;</p>",
+        "should not support other characters in a scheme in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http:>"),
+        "<p><a href=\"http:\">http:</a></p>",
+        "should support an empty rest in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a\u{1}b>"),
+        "<p>&lt;http://a\u{1}b&gt;</p>",
+        "should not support a C0 control character in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a\u{7f}b>"),
+        "<p>&lt;http://a\u{7f}b&gt;</p>",
+        "should not support a delete character in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a\0b>"),
+        "<p>&lt;http://a\u{FFFD}b&gt;</p>",
+        "should not support a NUL character in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a\nb>"),
+        "<p>&lt;http://a\nb&gt;</p>",
+        "should not support a line ending in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a<b>"),
+        "<p>&lt;http://a&lt;b&gt;</p>",
+        "should not support `<` in protocol autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a.b/ü?c=ж#е>"),
+        "<p><a href=\"http://a.b/%C3%BC?c=%D0%B6#%D0%B5\">http://a.b/ü?c=ж#е</a></p>",
+        "should encode non-ASCII characters in the URL, but not in the text, of autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://例子.测试>"),
+        "<p><a href=\"http://%E4%BE%8B%E5%AD%90.%E6%B5%8B%E8%AF%95\">http://例子.测试</a></p>",
+        "should encode non-ASCII domains in the URL, but not in the text, of autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a.b/c%20d>"),
+        "<p><a href=\"http://a.b/c%20d\">http://a.b/c%20d</a></p>",
+        "should not encode percent-encoded characters again in autolinks"
+    );
+
+    assert_eq!(
+        to_html("<http://a.b/{c}|d^e`f\"g>"),
+        "<p><a href=\"http://a.b/%7Bc%7D%7Cd%5Ee%60f%22g\">http://a.b/{c}|d^e`f&quot;g</a></p>",
+        "should encode unsafe ASCII characters in the URL, and escape HTML in the text, of autolinks"
+    );
+
+    assert_eq!(
+        to_html("<mailto:ä@ö.ü>"),
+        "<p><a href=\"mailto:%C3%A4@%C3%B6.%C3%BC\">mailto:ä@ö.ü</a></p>",
+        "should encode non-ASCII characters in the URL, but not in the text, of mailto autolinks"
+    );
+
+    assert_eq!(
+        to_mdast("<http://a.b/ü>", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Link(Link {
+                    url: "http://a.b/ü".into(),
+                    title: None,
+                    children: vec![Node::Text(Text {
+                        value: "http://a.b/ü".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 15, 14))
+                    }),],
+                    position: Some(Position::new(1, 1, 0, 1, 16, 15))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 16, 15))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 16, 15))
+        }),
+        "should not encode the URL of autolinks in mdast"
+    );
+
     assert_eq!(
         to_html_with_options(
             "<a@b.co>",
@@ -269,6 +393,21 @@ fn autolink() -> Result<(), String> {
         "should support turning off autolinks"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "<ftp://a.b> <https://c.d> <e@f.g>",
+            &Options {
+                compile: CompileOptions {
+                    allowed_link_schemes: Some(vec!["https".into()]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>ftp://a.b <a href=\"https://c.d\">https://c.d</a> e@f.g</p>",
+        "should turn autolinks w/ protocols that are not allowed into text (`allowed_link_schemes`)"
+    );
+
     assert_eq!(
         to_mdast(
             "a <https://alpha.com> b <bravo@charlie.com> c.",
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{BlockQuote, Node, Paragraph, Root, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -104,6 +104,48 @@ fn block_quote() -> Result<(), String> {
         "should support a definition, followed by a lazy paragraph"
     );
 
+    assert_eq!(
+        to_html("> [a]:\nb\n\n[a]"),
+        "<blockquote>\n</blockquote>\n<p><a href=\"b\">a</a></p>",
+        "should support a lazy definition destination (after whitespace and an eol)"
+    );
+
+    assert_eq!(
+        to_html("> [a]: b\n\"c\"\n\n[a]"),
+        "<blockquote>\n</blockquote>\n<p><a href=\"b\" title=\"c\">a</a></p>",
+        "should support a lazy definition title (after whitespace and an eol)"
+    );
+
+    assert_eq!(
+        to_html("> [a\n]: b\n\n[a]"),
+        "<blockquote>\n</blockquote>\n<p><a href=\"b\">a</a></p>",
+        "should support a lazy definition label end"
+    );
+
+    assert_eq!(
+        to_html("> a\n# b"),
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<h1>b</h1>",
+        "should not support lazy headings (atx)"
+    );
+
+    assert_eq!(
+        to_html("> a\n***"),
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<hr />",
+        "should not support lazy thematic breaks"
+    );
+
+    assert_eq!(
+        to_html("> a\n<div>"),
+        "<blockquote>\n<p>a</p>\n</blockquote>\n&lt;div&gt;",
+        "should not support lazy HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html("> a  \nb"),
+        "<blockquote>\n<p>a<br />\nb</p>\n</blockquote>",
+        "should support a hard break (trailing) before a lazy line"
+    );
+
     assert_eq!(
         to_html(">"),
         "<blockquote>\n</blockquote>",
@@ -194,12 +236,180 @@ fn block_quote() -> Result<(), String> {
         "should not support interrupting many block quotes w/ paragraphs (2)"
     );
 
+    assert_eq!(
+        to_html("> > a\nb"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> b"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines w/ fewer markers in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> a\n> > b\nc"),
+        "<blockquote>\n<p>a</p>\n<blockquote>\n<p>b\nc</p>\n</blockquote>\n</blockquote>",
+        "should continue the innermost paragraph w/ lazy lines"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> > b\nc\n> d"),
+        "<blockquote>\n<blockquote>\n<p>a\nb\nc\nd</p>\n</blockquote>\n</blockquote>",
+        "should support several lazy lines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n>\nb"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n<p>b</p>",
+        "should not support lazy lines after a blank line in an outer block quote"
+    );
+
+    assert_eq!(
+        to_html("> a\n>\n> > b\nc"),
+        "<blockquote>\n<p>a</p>\n<blockquote>\n<p>b\nc</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines after a blank line before a nested block quote"
+    );
+
+    assert_eq!(
+        to_html("> > a\n---"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n<hr />",
+        "should not support lazy setext heading underlines in nested block quotes (1)"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> ---"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<hr />\n</blockquote>",
+        "should not support lazy setext heading underlines in nested block quotes (2)"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> > ---"),
+        "<blockquote>\n<blockquote>\n<h2>a</h2>\n</blockquote>\n</blockquote>",
+        "should support setext heading underlines at the same depth in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> ```\n> b\n> ```"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<pre><code>b\n</code></pre>\n</blockquote>",
+        "should not support lazy fenced code in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n    b"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>",
+        "should support lazy indented lines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > - a\nb"),
+        "<blockquote>\n<blockquote>\n<ul>\n<li>a\nb</li>\n</ul>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in list items in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("- > > a\nb"),
+        "<ul>\n<li>\n<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>\n</li>\n</ul>",
+        "should support lazy lines in nested block quotes in list items"
+    );
+
     assert_eq!(
         to_html(">     a\n\n>    b"),
         "<blockquote>\n<pre><code>a\n</code></pre>\n</blockquote>\n<blockquote>\n<p>b</p>\n</blockquote>",
         "should support 5 spaces for indented code, not 4"
     );
 
+    assert_eq!(
+        to_html(">a\n >a\n  >a\n   >a"),
+        "<blockquote>\n<p>a\na\na\na</p>\n</blockquote>",
+        "should support 0 to 3 spaces before block quote markers"
+    );
+
+    assert_eq!(
+        to_html("    >a"),
+        "<pre><code>&gt;a\n</code></pre>",
+        "should not support 4 spaces before a block quote marker"
+    );
+
+    assert_eq!(
+        to_html(">\ta"),
+        "<blockquote>\n<p>a</p>\n</blockquote>",
+        "should support a tab after a block quote marker"
+    );
+
+    assert_eq!(
+        to_html("> \ta"),
+        "<blockquote>\n<p>a</p>\n</blockquote>",
+        "should support a space and a tab after a block quote marker"
+    );
+
+    assert_eq!(
+        to_html("   >\tcontent"),
+        "<blockquote>\n<p>content</p>\n</blockquote>",
+        "should support a tab after a block quote marker preceded by spaces"
+    );
+
+    assert_eq!(
+        to_html(" >\ta\n  >\tb"),
+        "<blockquote>\n<p>a\nb</p>\n</blockquote>",
+        "should support tabs after block quote markers at different columns"
+    );
+
+    assert_eq!(
+        to_html(">\t\ta"),
+        "<blockquote>\n<pre><code>  a\n</code></pre>\n</blockquote>",
+        "should support indented code after a tab, with the rest of a tab as spaces"
+    );
+
+    assert_eq!(
+        to_html("   >\t  a"),
+        "<blockquote>\n<pre><code> a\n</code></pre>\n</blockquote>",
+        "should support indented code after a tab after a block quote marker preceded by spaces"
+    );
+
+    assert_eq!(
+        to_html(">\t- a"),
+        "<blockquote>\n<ul>\n<li>a</li>\n</ul>\n</blockquote>",
+        "should support a list item after a tab after a block quote marker"
+    );
+
+    assert_eq!(
+        to_html("> > a\n>\n> > b"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<blockquote>\n<p>b</p>\n</blockquote>\n</blockquote>",
+        "should close nested block quotes at a blank line in the outer block quote"
+    );
+
+    assert_eq!(
+        to_html("> > a\n>\n> b"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>\n</blockquote>",
+        "should continue the outer block quote after a nested one closes"
+    );
+
+    assert_eq!(
+        to_html(">>> a\n> b"),
+        "<blockquote>\n<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>\n</blockquote>",
+        "should support lazy lines w/ fewer markers in block quotes w/o spaces"
+    );
+
+    assert_eq!(
+        to_html(">\n>\n> a\n>\n>"),
+        "<blockquote>\n<p>a</p>\n</blockquote>",
+        "should support several blank lines around content in a block quote"
+    );
+
+    assert_eq!(
+        to_html("> # a\n> b\nc"),
+        "<blockquote>\n<h1>a</h1>\n<p>b\nc</p>\n</blockquote>",
+        "should support lazy lines after a heading in a block quote"
+    );
+
+    assert_eq!(
+        to_html("> - a\n>\n>   b"),
+        "<blockquote>\n<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>\n</blockquote>",
+        "should support blank lines in list items in block quotes"
+    );
+
     assert_eq!(
         to_html_with_options(
             "> # a\n> b\n> c",
@@ -236,5 +446,120 @@ fn block_quote() -> Result<(), String> {
         "should support block quotes as `BlockQuote`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast(">\ta", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 5, 2, 1, 6, 3))
+                    }),],
+                    position: Some(Position::new(1, 3, 1, 1, 6, 3))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 6, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 3))
+        }),
+        "should support positional info after a tab after a block quote marker in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn block_quote_citation() -> Result<(), String> {
+    let citation = Options {
+        compile: CompileOptions {
+            block_quote_citation: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("> a\n> — b"),
+        "<blockquote>\n<p>a\n— b</p>\n</blockquote>",
+        "should not support attributions by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> — b", &citation)?,
+        "<blockquote>\n<p>a</p>\n<footer>— b</footer>\n</blockquote>",
+        "should support an attribution w/ an em dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> -- b", &citation)?,
+        "<blockquote>\n<p>a</p>\n<footer>-- b</footer>\n</blockquote>",
+        "should support an attribution w/ two dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n>    — *b*", &citation)?,
+        "<blockquote>\n<p>a</p>\n<footer>— <em>b</em></footer>\n</blockquote>",
+        "should support an indented attribution w/ phrasing content"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n>\n> — b", &citation)?,
+        "<blockquote>\n<p>a</p>\n<footer>— b</footer>\n</blockquote>",
+        "should support an attribution in its own paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a", &citation)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>",
+        "should not support a quote w/o attribution"
+    );
+
+    assert_eq!(
+        to_html_with_options("> — a", &citation)?,
+        "<blockquote>\n<p>— a</p>\n</blockquote>",
+        "should not support an attribution w/o quote"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> — b\n> c", &citation)?,
+        "<blockquote>\n<p>a\n— b\nc</p>\n</blockquote>",
+        "should not support an attribution marker on a line other than the last"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> — b\n>\n>     c", &citation)?,
+        "<blockquote>\n<p>a\n— b</p>\n<pre><code>c\n</code></pre>\n</blockquote>",
+        "should not support an attribution marker in a paragraph other than the last"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> - b", &citation)?,
+        "<blockquote>\n<p>a</p>\n<ul>\n<li>b</li>\n</ul>\n</blockquote>",
+        "should not support a single dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> *— b*", &citation)?,
+        "<blockquote>\n<p>a\n<em>— b</em></p>\n</blockquote>",
+        "should not support an attribution marker in phrasing content"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n— b", &citation)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<p>— b</p>",
+        "should not support an attribution marker after a block quote"
+    );
+
+    assert_eq!(
+        to_html_with_options("> > a\n> > — b", &citation)?,
+        "<blockquote>\n<blockquote>\n<p>a</p>\n<footer>— b</footer>\n</blockquote>\n</blockquote>",
+        "should support attributions in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("- > a\n  > — b\n- c", &citation)?,
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n<footer>— b</footer>\n</blockquote>\n</li>\n<li>c</li>\n</ul>",
+        "should support attributions in block quotes in tight lists"
+    );
+
     Ok(())
 }
@@ -1,8 +1,8 @@
 use markdown::{
-    mdast::{Code, Node, Root},
+    mdast::{Code, Heading, Node, Paragraph, Root, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -122,6 +122,60 @@ fn code_fenced() -> Result<(), String> {
         "should not support an indented closing sequence w/ 4 spaces"
     );
 
+    assert_eq!(
+        to_html("```\n   a\n```"),
+        "<pre><code>   a\n</code></pre>",
+        "should not remove indent from the content if the opening sequence is not indented"
+    );
+
+    assert_eq!(
+        to_html("  ~~~\n    a\n a\n  ~~~"),
+        "<pre><code>  a\na\n</code></pre>",
+        "should remove up to the indent of the opening sequence from content indented more or less (tildes)"
+    );
+
+    assert_eq!(
+        to_html("   ```js\n      a\n   ```"),
+        "<pre><code class=\"language-js\">   a\n</code></pre>",
+        "should remove the indent of the opening sequence from content indented more (w/ info)"
+    );
+
+    assert_eq!(
+        to_html("  ```\n  a\n     \n\n  b\n  ```"),
+        "<pre><code>a\n   \n\nb\n</code></pre>",
+        "should remove the indent of the opening sequence from whitespace-only lines"
+    );
+
+    assert_eq!(
+        to_html("- ```\n    a\n  ```"),
+        "<ul>\n<li>\n<pre><code>  a\n</code></pre>\n</li>\n</ul>",
+        "should not count the indent of list items as the indent of the opening sequence"
+    );
+
+    assert_eq!(
+        to_html(">  ```\n>   a\n>  b\n>  ```"),
+        "<blockquote>\n<pre><code> a\nb\n</code></pre>\n</blockquote>",
+        "should remove the indent of the opening sequence in block quotes"
+    );
+
+    assert_eq!(
+        to_html("1.  ```\n     a\n    b\n    ```"),
+        "<ol>\n<li>\n<pre><code> a\nb\n</code></pre>\n</li>\n</ol>",
+        "should not count the indent after list item markers as the indent of the opening sequence"
+    );
+
+    assert_eq!(
+        to_html("-\t```\n\ta\n\t ```"),
+        "<ul>\n<li>\n<pre><code>  a\n</code></pre>\n</li>\n</ul>",
+        "should not count a tab after a list item marker as the indent of the opening sequence"
+    );
+
+    assert_eq!(
+        to_html("- \t ```\n   \t a\n    ```"),
+        "<ul>\n<li>\n<pre><code> a\n</code></pre>\n</li>\n</ul>",
+        "should remove the indent of the opening sequence w/ tabs in list items"
+    );
+
     assert_eq!(
         to_html("``` ```\naaa"),
         "<p><code> </code>\naaa</p>",
@@ -146,6 +200,42 @@ fn code_fenced() -> Result<(), String> {
         "should support interrupting other content"
     );
 
+    assert_eq!(
+        to_html("a\n~~~js\nb\n~~~\nc"),
+        "<p>a</p>\n<pre><code class=\"language-js\">b\n</code></pre>\n<p>c</p>",
+        "should support interrupting paragraphs w/ tildes and an info string"
+    );
+
+    assert_eq!(
+        to_html("a\n   ```\nb\n   ```"),
+        "<p>a</p>\n<pre><code>b\n</code></pre>",
+        "should support interrupting paragraphs w/ an indented fence"
+    );
+
+    assert_eq!(
+        to_html("a\n    ~~~\nb\n    ~~~"),
+        "<p>a\n~~~\nb\n~~~</p>",
+        "should not support interrupting paragraphs w/ a fence indented 4 spaces"
+    );
+
+    assert_eq!(
+        to_html("a\n``` b`c\nd"),
+        "<p>a\n``` b`c\nd</p>",
+        "should not support interrupting paragraphs w/ a grave accent in the info string"
+    );
+
+    assert_eq!(
+        to_html("* a\n  ```\n  b\n  ```"),
+        "<ul>\n<li>a\n<pre><code>b\n</code></pre>\n</li>\n</ul>",
+        "should support interrupting paragraphs in list items"
+    );
+
+    assert_eq!(
+        to_html("> a\n```\nb\n```"),
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<pre><code>b\n</code></pre>",
+        "should not support a fence as a lazy line"
+    );
+
     assert_eq!(
         to_html("```ruby\ndef foo(x)\n  return 3\nend\n```"),
         "<pre><code class=\"language-ruby\">def foo(x)\n  return 3\nend\n</code></pre>",
@@ -279,6 +369,56 @@ fn code_fenced() -> Result<(), String> {
         "should support turning off code (fenced)"
     );
 
+    assert_eq!(
+        to_html("```\na\n```\n\n```\na\n\n\n```\n\n```\na"),
+        "<pre><code>a\n</code></pre>\n<pre><code>a\n\n\n</code></pre>\n<pre><code>a\n</code></pre>\n",
+        "should end code (fenced) w/ a line ending, also w/ trailing blank lines or w/o closing fence"
+    );
+
+    let trim = Options {
+        compile: CompileOptions {
+            trim_code_trailing_newline: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("```js\na\nb\n```\nc", &trim)?,
+        "<pre><code class=\"language-js\">a\nb</code></pre>\n<p>c</p>",
+        "should support removing the final line ending (`trim_code_trailing_newline`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\n\n\n```", &trim)?,
+        "<pre><code>a\n\n</code></pre>",
+        "should remove only one line ending before trailing blank lines (`trim_code_trailing_newline`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na", &trim)?,
+        "<pre><code>a</code></pre>\n",
+        "should support removing the final line ending w/o closing fence (`trim_code_trailing_newline`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```\n\n```\n\n```", &trim)?,
+        "<pre><code></code></pre>\n<pre><code></code></pre>",
+        "should support removing the final line ending in empty code (`trim_code_trailing_newline`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```\n> a\n> ```\n\n- ```\n  a", &trim)?,
+        "<blockquote>\n<pre><code>a</code></pre>\n</blockquote>\n<ul>\n<li>\n<pre><code>a</code></pre>\n</li>\n</ul>",
+        "should support removing the final line ending in containers (`trim_code_trailing_newline`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\r\na\r\n```", &trim)?,
+        "<pre><code>a</code></pre>",
+        "should support removing a final carriage return + line feed (`trim_code_trailing_newline`)"
+    );
+
     assert_eq!(
         to_mdast(
             "```js extra\nconsole.log(1)\nconsole.log(2)\n```",
@@ -296,6 +436,20 @@ fn code_fenced() -> Result<(), String> {
         "should support code (fenced) as `Code`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("  ```\n    a\n b\n  ```", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                lang: None,
+                meta: None,
+                value: "  a\nb".into(),
+                position: Some(Position::new(1, 3, 2, 4, 6, 20))
+            })],
+            position: Some(Position::new(1, 1, 0, 4, 6, 20))
+        }),
+        "should remove the indent of the opening sequence from content in mdast"
+    );
+
     assert_eq!(
         to_mdast("```\nasd", &Default::default())?,
         Node::Root(Root {
@@ -338,5 +492,50 @@ fn code_fenced() -> Result<(), String> {
         "should support code (fenced) w/o CR+LF line endings"
     );
 
+    assert_eq!(
+        to_mdast("~~~ j\\+s&copy; x  y\na\n~~~", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                lang: Some("j+s©".into()),
+                meta: Some("x  y".into()),
+                value: "a".into(),
+                position: Some(Position::new(1, 1, 0, 3, 4, 25))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 4, 25))
+        }),
+        "should support escapes and references in the info string of code (fenced) in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("# a\n\n```b\nc\n```\n\nd", &Default::default())?,
+        Node::Root(Root {
+            children: vec![
+                Node::Heading(Heading {
+                    depth: 1,
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                }),
+                Node::Code(Code {
+                    lang: Some("b".into()),
+                    meta: None,
+                    value: "c".into(),
+                    position: Some(Position::new(3, 1, 5, 5, 4, 15))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "d".into(),
+                        position: Some(Position::new(7, 1, 17, 7, 2, 18))
+                    })],
+                    position: Some(Position::new(7, 1, 17, 7, 2, 18))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 7, 2, 18))
+        }),
+        "should support code (fenced) between other flow in mdast"
+    );
+
     Ok(())
 }
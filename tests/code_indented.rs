@@ -50,6 +50,24 @@ fn code_indented() -> Result<(), String> {
         "should not support interrupting paragraphs"
     );
 
+    assert_eq!(
+        to_html("a\n\tb\n\n\tc"),
+        "<p>a\nb</p>\n<pre><code>c\n</code></pre>",
+        "should not support interrupting paragraphs w/ a tab"
+    );
+
+    assert_eq!(
+        to_html("* a\n      b"),
+        "<ul>\n<li>a\nb</li>\n</ul>",
+        "should not support interrupting paragraphs in list items"
+    );
+
+    assert_eq!(
+        to_html("> a\n>     b"),
+        "<blockquote>\n<p>a\nb</p>\n</blockquote>",
+        "should not support interrupting paragraphs in block quotes"
+    );
+
     assert_eq!(
         to_html("    foo\nbar"),
         "<pre><code>foo\n</code></pre>\n<p>bar</p>",
@@ -202,6 +220,21 @@ fn code_indented() -> Result<(), String> {
         "should support turning off code (indented, 9)"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "    a\n\n    b\n\n\nc",
+            &Options {
+                compile: CompileOptions {
+                    trim_code_trailing_newline: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code>a\n\nb</code></pre>\n<p>c</p>",
+        "should support removing the final line ending (`trim_code_trailing_newline`)"
+    );
+
     assert_eq!(
         to_mdast(
             "\tconsole.log(1)\n    console.log(2)\n",
@@ -150,12 +150,111 @@ fn code_text() -> Result<(), String> {
     );
 
     // Extra:
+    assert_eq!(
+        to_html("``a`b``"),
+        "<p><code>a`b</code></p>",
+        "should support a shorter run inside code (2 around 1)"
+    );
+
+    assert_eq!(
+        to_html("`a``b`"),
+        "<p><code>a``b</code></p>",
+        "should support a longer run inside code (1 around 2)"
+    );
+
+    assert_eq!(
+        to_html("`a```b`"),
+        "<p><code>a```b</code></p>",
+        "should support a longer run inside code (1 around 3)"
+    );
+
+    assert_eq!(
+        to_html("``a```b``"),
+        "<p><code>a```b</code></p>",
+        "should support a longer run inside code (2 around 3)"
+    );
+
+    assert_eq!(
+        to_html("```a``b```"),
+        "<p><code>a``b</code></p>",
+        "should support a shorter run inside code (3 around 2)"
+    );
+
+    assert_eq!(
+        to_html("```a`b````c```"),
+        "<p><code>a`b````c</code></p>",
+        "should support shorter and longer runs inside code (3 around 1 and 4)"
+    );
+
+    assert_eq!(
+        to_html("``a`"),
+        "<p>``a`</p>",
+        "should not close code w/ a shorter run (2 then 1)"
+    );
+
+    assert_eq!(
+        to_html("`a``"),
+        "<p>`a``</p>",
+        "should not close code w/ a longer run (1 then 2)"
+    );
+
+    assert_eq!(
+        to_html("```a``b``"),
+        "<p>```a<code>b</code></p>",
+        "should not close code w/ a shorter run, but support code after it (3 then 2)"
+    );
+
     assert_eq!(
         to_html("`foo\t\tbar`"),
         "<p><code>foo\t\tbar</code></p>",
         "should support tabs in code"
     );
 
+    assert_eq!(
+        to_html("`a   b`"),
+        "<p><code>a   b</code></p>",
+        "should keep interior runs of spaces in code"
+    );
+
+    assert_eq!(
+        to_html("`a \t  \t b`"),
+        "<p><code>a \t  \t b</code></p>",
+        "should keep interior runs of spaces and tabs in code"
+    );
+
+    assert_eq!(
+        to_html("`   a   b   `"),
+        "<p><code>  a   b  </code></p>",
+        "should strip only one space on each side of code"
+    );
+
+    assert_eq!(
+        to_html("`\ta  b\t`"),
+        "<p><code>\ta  b\t</code></p>",
+        "should not strip tabs on either side of code"
+    );
+
+    assert_eq!(
+        to_html("`a   \nb   c`"),
+        "<p><code>a    b   c</code></p>",
+        "should keep spaces around a line ending in code"
+    );
+
+    assert_eq!(
+        to_mdast("`a  \t  b`", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineCode(InlineCode {
+                    value: "a  \t  b".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 13, 9))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 13, 9))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 9))
+        }),
+        "should keep interior whitespace in code (text) in mdast"
+    );
+
     assert_eq!(
         to_html("\\``x`"),
         "<p>`<code>x</code></p>",
@@ -207,3 +306,12 @@ fn code_text() -> Result<(), String> {
 
     Ok(())
 }
+
+#[test]
+fn code_text_many() {
+    assert_eq!(
+        to_html(&"`a` b ".repeat(50_000)),
+        format!("<p>{}</p>", "<code>a</code> b ".repeat(50_000).trim_end()),
+        "should support lots of code (text) in one paragraph"
+    );
+}
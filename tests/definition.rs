@@ -77,12 +77,96 @@ fn definition() -> Result<(), String> {
         "should not support definitions w/o destination"
     );
 
+    assert_eq!(
+        to_html("[x]:"),
+        "<p>[x]:</p>",
+        "should not support definitions w/o destination at the end of the document"
+    );
+
+    assert_eq!(
+        to_html("[x]: "),
+        "<p>[x]:</p>",
+        "should not support definitions w/ only whitespace after the marker"
+    );
+
+    assert_eq!(
+        to_html("[x]:\n"),
+        "<p>[x]:</p>\n",
+        "should not support definitions w/ only a line ending after the marker"
+    );
+
+    assert_eq!(
+        to_html("[x]: \n\nb"),
+        "<p>[x]:</p>\n<p>b</p>",
+        "should not swallow content after definitions w/o destination"
+    );
+
+    assert_eq!(
+        to_html("[a]: \t"),
+        "<p>[a]:</p>",
+        "should not support whitespace and then the end of the document as a destination"
+    );
+
+    assert_eq!(
+        to_html("[a]:\n \t\n/b"),
+        "<p>[a]:</p>\n<p>/b</p>",
+        "should not support a whitespace-only line before a destination"
+    );
+
+    assert_eq!(
+        to_html("[a]: /b\n \t\n'c'\n\n[a]"),
+        "<p>'c'</p>\n<p><a href=\"/b\">a</a></p>",
+        "should not support a whitespace-only line before a title"
+    );
+
+    assert_eq!(
+        to_html("[a]: /b\n'c'  "),
+        "",
+        "should support whitespace and then the end of the document after a title"
+    );
+
+    assert_eq!(
+        to_html("[a\n  \nb]: /c"),
+        "<p>[a</p>\n<p>b]: /c</p>",
+        "should not support a whitespace-only line in a label"
+    );
+
+    assert_eq!(
+        to_html("[a b]: /c\n\n[ab] [a  b] [ a\nb ]"),
+        "<p>[ab] <a href=\"/c\">a  b</a> <a href=\"/c\"> a\nb </a></p>",
+        "should not drop whitespace between words in labels when matching"
+    );
+
     assert_eq!(
         to_html("[foo]: <>\n\n[foo]"),
         "<p><a href=\"\">foo</a></p>",
         "should support definitions w/ explicit empty destinations"
     );
 
+    assert_eq!(
+        to_html("[foo]:<>\n\n[foo]"),
+        "<p><a href=\"\">foo</a></p>",
+        "should support explicit empty destinations w/o whitespace before them"
+    );
+
+    assert_eq!(
+        to_html("[foo]: <> \"bar\"\n\n[foo]"),
+        "<p><a href=\"\" title=\"bar\">foo</a></p>",
+        "should support explicit empty destinations w/ titles"
+    );
+
+    assert_eq!(
+        to_html("[foo]: <>\n\n![foo] [bar][foo] [foo][]"),
+        "<p><img src=\"\" alt=\"foo\" /> <a href=\"\">bar</a> <a href=\"\">foo</a></p>",
+        "should support all references to definitions w/ explicit empty destinations"
+    );
+
+    assert_eq!(
+        to_html("[foo]: < >\n\n[foo]"),
+        "<p><a href=\"%20\">foo</a></p>",
+        "should support explicit destinations w/ only whitespace"
+    );
+
     assert_eq!(
         to_html_with_options("[foo]: <bar>(baz)\n\n[foo]", &danger)?,
         "<p>[foo]: <bar>(baz)</p>\n<p>[foo]</p>",
@@ -198,6 +282,42 @@ fn definition() -> Result<(), String> {
         "should not support definitions in paragraphs"
     );
 
+    assert_eq!(
+        to_html("a\n[x]: y\n===\n\n[x]"),
+        "<h1>a\n[x]: y</h1>\n<p>[x]</p>",
+        "should not support definitions in setext headings"
+    );
+
+    assert_eq!(
+        to_html("a\n[x]: y\n---"),
+        "<h2>a\n[x]: y</h2>",
+        "should not support definitions in setext headings (dashes)"
+    );
+
+    assert_eq!(
+        to_html("[x]: y\na\n[z]: w\n\n[x] [z]"),
+        "<p>a\n[z]: w</p>\n<p><a href=\"y\">x</a> [z]</p>",
+        "should support definitions at the start of a paragraph, but not after text"
+    );
+
+    assert_eq!(
+        to_html("a\n\n[x]: y\n[x]"),
+        "<p>a</p>\n<p><a href=\"y\">x</a></p>",
+        "should support definitions after a blank line after a paragraph"
+    );
+
+    assert_eq!(
+        to_html("[x]: y\n[z]: w\n===\n\n[x] [z]"),
+        "<p>===</p>\n<p><a href=\"y\">x</a> <a href=\"w\">z</a></p>",
+        "should not support setext heading underlines after several definitions"
+    );
+
+    assert_eq!(
+        to_html("[x]: y\n'z'\n===\n\n[x]"),
+        "<p>===</p>\n<p><a href=\"y\" title=\"z\">x</a></p>",
+        "should not support setext heading underlines after a definition w/ a title on its own line"
+    );
+
     assert_eq!(
         to_html("# [Foo]\n[foo]: /url\n> bar"),
         "<h1><a href=\"/url\">Foo</a></h1>\n<blockquote>\n<p>bar</p>\n</blockquote>",
@@ -522,5 +642,20 @@ fn definition() -> Result<(), String> {
         "should support definitions as `Definition`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("[a]: <>", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Definition(Definition {
+                url: "".into(),
+                identifier: "a".into(),
+                label: Some("a".into()),
+                title: None,
+                position: Some(Position::new(1, 1, 0, 1, 8, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should support explicit empty destinations in mdast"
+    );
+
     Ok(())
 }
@@ -0,0 +1,148 @@
+use markdown::{
+    mdast::{ContainerDirective, Node, Paragraph, Root, Text},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn directive_container() -> Result<(), String> {
+    let directives = Options::directives();
+
+    assert_eq!(
+        to_html(":::a\nb\n:::"),
+        "<p>:::a\nb\n:::</p>",
+        "should not support directives (container) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::note\na\n:::\nb", &directives)?,
+        "<p>b</p>",
+        "should support a directive (container) w/ a name, and drop it"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a[b]{#c}\nd\n:::\ne", &directives)?,
+        "<p>e</p>",
+        "should support a label and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a b\nc", &directives)?,
+        "<p>:::a b\nc</p>",
+        "should not support other content after the opening fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\nb\n:::", &directives)?,
+        "<p>::: a\nb\n:::</p>",
+        "should not support whitespace between the sequence and the name"
+    );
+
+    assert_eq!(
+        to_html_with_options("::::a\n:::b\nc\n:::\nd\n::::\ne", &directives)?,
+        "<p>e</p>",
+        "should support nested directives (container)"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb\n:::::\nc", &directives)?,
+        "<p>c</p>",
+        "should close a directive (container) w/ a longer closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("::::a\nb\n:::\nc", &directives)?,
+        "",
+        "should not close a directive (container) w/ a shorter closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb", &directives)?,
+        "",
+        "should close directives (container) at the end of the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:::b\nc\n:::\nd", &directives)?,
+        "<p>a</p>\n<p>d</p>",
+        "should support directives (container) interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("  :::a\n  b\n   :::\nc", &directives)?,
+        "<p>c</p>",
+        "should support indented fences"
+    );
+
+    assert_eq!(
+        to_html_with_options("    :::a", &directives)?,
+        "<pre><code>:::a\n</code></pre>",
+        "should not support fences indented by four spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n```\n:::\n```\n:::\nb", &directives)?,
+        "<p>b</p>",
+        "should not close directives (container) in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> :::a\n> b\n> :::\nc", &directives)?,
+        "<blockquote>\n</blockquote>\n<p>c</p>",
+        "should support directives (container) in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("- :::a\n  b\n  :::\n- c", &directives)?,
+        "<ul>\n<li></li>\n<li>c</li>\n</ul>",
+        "should support directives (container) in list items (tight)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            ":::a\n::: {.b}\nc\n:::\nd\n:::\n\n::: {.e}\n:::f\ng\n:::\nh\n:::",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        fenced_div: true,
+                        ..Constructs::directives()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<div class=\"e\">\n<p>h</p>\n</div>",
+        "should support directives (container) and fenced divs in each other"
+    );
+
+    assert_eq!(
+        to_mdast(":::a[b]{#c}\nd\n:::", &ParseOptions::directives())?,
+        Node::Root(Root {
+            children: vec![Node::ContainerDirective(ContainerDirective {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "d".into(),
+                        position: Some(Position::new(2, 1, 12, 2, 2, 13))
+                    })],
+                    position: Some(Position::new(2, 1, 12, 2, 2, 13))
+                })],
+                position: Some(Position::new(1, 1, 0, 3, 4, 17)),
+                name: "a".into(),
+                label: vec![Node::Text(Text {
+                    value: "b".into(),
+                    position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                })],
+                id: Some("c".into()),
+                classes: vec![],
+                attributes: vec![]
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 4, 17))
+        }),
+        "should support directives (container) as `ContainerDirective`s in mdast, w/ their label separate"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use markdown::{
+    mdast::{LeafDirective, Node, Root, Text},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn directive_leaf() -> Result<(), String> {
+    let directives = Options::directives();
+
+    assert_eq!(
+        to_html("::a[b]{#c}"),
+        "<p>::a[b]{#c}</p>",
+        "should not support directives (leaf) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a", &directives)?,
+        "",
+        "should support a directive (leaf) w/ a name, and drop it"
+    );
+
+    assert_eq!(
+        to_html_with_options("::youtube[Video]{#a}", &directives)?,
+        "",
+        "should support a label and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[b] \t", &directives)?,
+        "",
+        "should support trailing whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[b] c", &directives)?,
+        "<p>::a[b] c</p>",
+        "should not support other content after a directive (leaf)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n::b\n\nc", &directives)?,
+        "<p>a</p>\n<p>c</p>",
+        "should drop the line ending after a directive (leaf)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n::b\nc", &directives)?,
+        "<p>a</p>\n<p>c</p>",
+        "should support directives (leaf) interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("   ::a\n    ::b", &directives)?,
+        "<pre><code>::b\n</code></pre>",
+        "should support indented directives (leaf), but not by four spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ::a\nb", &directives)?,
+        "<blockquote>\n</blockquote>\n<p>b</p>",
+        "should support directives (leaf) in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ::a\n- b", &directives)?,
+        "<ul>\n<li></li>\n<li>b</li>\n</ul>",
+        "should support directives (leaf) in list items"
+    );
+
+    assert_eq!(
+        to_mdast("::a[b]{.c}", &ParseOptions::directives())?,
+        Node::Root(Root {
+            children: vec![Node::LeafDirective(LeafDirective {
+                children: vec![Node::Text(Text {
+                    value: "b".into(),
+                    position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                name: "a".into(),
+                id: None,
+                classes: vec!["c".into()],
+                attributes: vec![]
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support directives (leaf) as `LeafDirective`s in mdast, w/ their label as children"
+    );
+
+    Ok(())
+}
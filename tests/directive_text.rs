@@ -0,0 +1,172 @@
+use markdown::{
+    mdast::{Emphasis, Node, Paragraph, Root, Text, TextDirective},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn directive_text() -> Result<(), String> {
+    let directives = Options::directives();
+
+    assert_eq!(
+        to_html("a :b[c]{d=e} f"),
+        "<p>a :b[c]{d=e} f</p>",
+        "should not support directives (text) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b c", &directives)?,
+        "<p>a  c</p>",
+        "should support a directive (text) w/ a name, and drop it"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b[c] d", &directives)?,
+        "<p>a  d</p>",
+        "should support a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b{c=d} e", &directives)?,
+        "<p>a  e</p>",
+        "should support attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :abbr[HTML]{title=\"HyperText Markup Language\"} b",
+            &directives
+        )?,
+        "<p>a  b</p>",
+        "should support a label and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a:b", &directives)?,
+        "<p>a</p>",
+        "should support a directive (text) after a letter"
+    );
+
+    assert_eq!(
+        to_html_with_options("a::b c", &directives)?,
+        "<p>a::b c</p>",
+        "should not support a directive (text) after a colon"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \\::b c", &directives)?,
+        "<p>a : c</p>",
+        "should support a directive (text) after an escaped colon"
+    );
+
+    assert_eq!(
+        to_html_with_options(":1a", &directives)?,
+        "<p>:1a</p>",
+        "should not support a name starting w/ a digit"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a-b_c1", &directives)?,
+        "<p></p>",
+        "should support dashes, underscores, and digits in names"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a- b\n:a_ b", &directives)?,
+        "<p>:a- b\n:a_ b</p>",
+        "should not support a name ending in a dash or underscore"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b[c]d] e", &directives)?,
+        "<p> e</p>",
+        "should support balanced brackets in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b[c] d", &directives)?,
+        "<p>:a[b[c] d</p>",
+        "should not support unbalanced brackets in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b\\]c] d", &directives)?,
+        "<p> d</p>",
+        "should support escaped brackets in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b\nc]", &directives)?,
+        "<p>:a[b\nc]</p>",
+        "should not support line endings in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b=\"c}\"} d", &directives)?,
+        "<p> d</p>",
+        "should support closing braces in quoted attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b=\"c\nd\"}", &directives)?,
+        "<p>:a{b=&quot;c\nd&quot;}</p>",
+        "should not support line endings in attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b", &directives)?,
+        "<p>:a{b</p>",
+        "should not support unclosed attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a [b] {c}", &directives)?,
+        "<p> [b] {c}</p>",
+        "should not support whitespace before labels or attributes"
+    );
+
+    assert_eq!(
+        to_mdast("a :b[c *d*]{#e .f g=h} i", &ParseOptions::directives())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::TextDirective(TextDirective {
+                        children: vec![
+                            Node::Text(Text {
+                                value: "c ".into(),
+                                position: Some(Position::new(1, 6, 5, 1, 8, 7))
+                            }),
+                            Node::Emphasis(Emphasis {
+                                children: vec![Node::Text(Text {
+                                    value: "d".into(),
+                                    position: Some(Position::new(1, 9, 8, 1, 10, 9))
+                                })],
+                                position: Some(Position::new(1, 8, 7, 1, 11, 10))
+                            })
+                        ],
+                        position: Some(Position::new(1, 3, 2, 1, 23, 22)),
+                        name: "b".into(),
+                        id: Some("e".into()),
+                        classes: vec!["f".into()],
+                        attributes: vec![("g".into(), "h".into())]
+                    }),
+                    Node::Text(Text {
+                        value: " i".into(),
+                        position: Some(Position::new(1, 23, 22, 1, 25, 24))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 25, 24))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 25, 24))
+        }),
+        "should support directives (text) as `TextDirective`s in mdast, w/ their label as children"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use markdown::{
+    mdast::{Emphasis, Node, Paragraph, Root, Text},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn emoji() -> Result<(), String> {
+    let emoji = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                emoji: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a :smile: b"),
+        "<p>a :smile: b</p>",
+        "should ignore emoji by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :smile: b", &Options::gfm())?,
+        "<p>a :smile: b</p>",
+        "should ignore emoji in GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :smile: b", &emoji)?,
+        "<p>a 😄 b</p>",
+        "should support emoji"
+    );
+
+    assert_eq!(
+        to_html_with_options(":+1: :-1: :100: :t-rex: :8ball:", &emoji)?,
+        "<p>👍 👎 💯 🦖 🎱</p>",
+        "should support names w/ digits, dashes, and pluses"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :notanemoji: b", &emoji)?,
+        "<p>a :notanemoji: b</p>",
+        "should not support unknown names"
+    );
+
+    assert_eq!(
+        to_html_with_options(":Smile: :SMILE:", &emoji)?,
+        "<p>:Smile: :SMILE:</p>",
+        "should match names sensitive to casing"
+    );
+
+    assert_eq!(
+        to_html_with_options("a:smile:b :smile::tada: ::smile::", &emoji)?,
+        "<p>a😄b 😄🎉 :😄:</p>",
+        "should support emoji next to other characters"
+    );
+
+    assert_eq!(
+        to_html_with_options(":: :smile :smile : smile: :sm ile:", &emoji)?,
+        "<p>:: :smile :smile : smile: :sm ile:</p>",
+        "should not support missing names, missing markers, or whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("`:smile:`\n\n```:smile:\n:tada:\n```", &emoji)?,
+        "<p><code>:smile:</code></p>\n<pre><code class=\"language-:smile:\">:tada:\n</code></pre>",
+        "should not support emoji in code"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\:smile: :smile\\:", &emoji)?,
+        "<p>:smile: :smile:</p>",
+        "should not support escaped markers"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# :tada:\n\n*:smile:* [:smile:](https://a.b/:smile: \":smile:\")",
+            &emoji
+        )?,
+        "<h1>🎉</h1>\n<p><em>😄</em> <a href=\"https://a.b/:smile:\" title=\":smile:\">😄</a></p>",
+        "should support emoji in text, but not in destinations or titles"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "https://a.b/:smile:/c",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        emoji: true,
+                        ..Constructs::gfm()
+                    },
+                    ..ParseOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "<p><a href=\"https://a.b/:smile:/c\">https://a.b/:smile:/c</a></p>",
+        "should not support emoji in GFM autolink literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            ":ferris: :smile: :tada:",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        emoji: true,
+                        ..Constructs::default()
+                    },
+                    emoji_shortcodes: vec![
+                        ("ferris".into(), "🦀".into()),
+                        ("smile".into(), "🙂".into())
+                    ],
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>🦀 🙂 🎉</p>",
+        "should support custom shortcodes (`emoji_shortcodes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            ":ferris:",
+            &Options {
+                parse: ParseOptions {
+                    emoji_shortcodes: vec![("ferris".into(), "🦀".into())],
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>:ferris:</p>",
+        "should not support custom shortcodes w/o `emoji`"
+    );
+
+    assert_eq!(
+        to_mdast("a :smile: *:tada:*", &emoji.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a 😄 ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "🎉".into(),
+                            position: Some(Position::new(1, 12, 11, 1, 18, 17))
+                        })],
+                        position: Some(Position::new(1, 11, 10, 1, 19, 18))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 19, 18))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 19, 18))
+        }),
+        "should support emoji as `Text`s in mdast"
+    );
+
+    Ok(())
+}
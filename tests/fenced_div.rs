@@ -0,0 +1,206 @@
+use markdown::{
+    mdast::{FencedDiv, Node, Paragraph, Root, Text},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn fenced_div() -> Result<(), String> {
+    let fenced_div = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                fenced_div: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("::: a\nb\n:::"),
+        "<p>::: a\nb\n:::</p>",
+        "should not support fenced divs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: warning\na\n:::", &fenced_div)?,
+        "<div class=\"warning\">\n<p>a</p>\n</div>",
+        "should support a fenced div with a word as a class"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            ":::: {.columns}\n::: {.column width=\"50%\"}\nLeft\n:::\n::: {.column width=\"50%\"}\nRight\n:::\n::::",
+            &fenced_div
+        )?,
+        "<div class=\"columns\">\n<div class=\"column\" width=\"50%\">\n<p>Left</p>\n</div>\n<div class=\"column\" width=\"50%\">\n<p>Right</p>\n</div>\n</div>",
+        "should support a two-column grid"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: {#a .b .c d=e f=\"g h\" i='j'}\nk\n:::", &fenced_div)?,
+        "<div id=\"a\" class=\"b c\" d=\"e\" f=\"g h\" i=\"j\">\n<p>k</p>\n</div>",
+        "should support an ID, classes, and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: {id=a class=\"b c\"}\nd\n:::", &fenced_div)?,
+        "<div id=\"a\" class=\"b c\">\n<p>d</p>\n</div>",
+        "should support `id` and `class` attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: {a=\"<&>\"}\nb\n:::", &fenced_div)?,
+        "<div a=\"&lt;&amp;&gt;\">\n<p>b</p>\n</div>",
+        "should encode attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: {onclick=\"a()\" ONLOAD=b c=d}\ne\n:::", &fenced_div)?,
+        "<div c=\"d\">\n<p>e</p>\n</div>",
+        "should drop event handler attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "::: {onclick=\"a()\"}\nb\n:::",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        fenced_div: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<div onclick=\"a()\">\n<p>b</p>\n</div>",
+        "should keep event handler attributes if `allow_dangerous_html` is on"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: {.a} :::\nb\n:::", &fenced_div)?,
+        "<div class=\"a\">\n<p>b</p>\n</div>",
+        "should support a trailing sequence on the opening fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::\na", &fenced_div)?,
+        "<p>:::\na</p>",
+        "should not support a fence w/o attributes as an opening fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":: a\nb\n::", &fenced_div)?,
+        "<p>:: a\nb\n::</p>",
+        "should not support fewer than three colons"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n::: b\nc\n:::", &fenced_div)?,
+        "<p>a</p>\n<div class=\"b\">\n<p>c</p>\n</div>",
+        "should support fenced divs interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\nb", &fenced_div)?,
+        "<div class=\"a\">\n<p>b</p>\n</div>",
+        "should close fenced divs at the end of the document"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::: a\nb\n:::\nc\n::::", &fenced_div)?,
+        "<div class=\"a\">\n<p>b\n:::\nc</p>\n</div>",
+        "should not close a fenced div w/ a shorter closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\nb\n:::::\nc", &fenced_div)?,
+        "<div class=\"a\">\n<p>b</p>\n</div>\n<p>c</p>",
+        "should close a fenced div w/ a longer closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\n::: b\nc\n:::\nd\n:::", &fenced_div)?,
+        "<div class=\"a\">\n<div class=\"b\">\n<p>c</p>\n</div>\n<p>d</p>\n</div>",
+        "should close the innermost fenced div first"
+    );
+
+    assert_eq!(
+        to_html_with_options("  ::: a\n  b\n   :::", &fenced_div)?,
+        "<div class=\"a\">\n<p>b</p>\n</div>",
+        "should support indented fences"
+    );
+
+    assert_eq!(
+        to_html_with_options("    ::: a\nb", &fenced_div)?,
+        "<pre><code>::: a\n</code></pre>\n<p>b</p>",
+        "should not support fences indented by four spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\n```\n:::\n```\n:::", &fenced_div)?,
+        "<div class=\"a\">\n<pre><code>:::\n</code></pre>\n</div>",
+        "should not close fenced divs in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ::: a\n> b\n> :::\nc", &fenced_div)?,
+        "<blockquote>\n<div class=\"a\">\n<p>b</p>\n</div>\n</blockquote>\n<p>c</p>",
+        "should support fenced divs in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\n> b\n:::\nc", &fenced_div)?,
+        "<div class=\"a\">\n<blockquote>\n<p>b</p>\n</blockquote>\n</div>\n<p>c</p>",
+        "should support block quotes in fenced divs"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ::: a\n  b\n  :::\n- c", &fenced_div)?,
+        "<ul>\n<li>\n<div class=\"a\">\n<p>b</p>\n</div>\n</li>\n<li>c</li>\n</ul>",
+        "should support fenced divs in list items (tight)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ::: a\n  b\n  :::\n  c", &fenced_div)?,
+        "<ul>\n<li>\n<div class=\"a\">\n<p>b</p>\n</div>\nc</li>\n</ul>",
+        "should support content after fenced divs in tight list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: a\n- b\n\n- c\n:::", &fenced_div)?,
+        "<div class=\"a\">\n<ul>\n<li>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>\n</div>",
+        "should support lists in fenced divs"
+    );
+
+    assert_eq!(
+        to_mdast("::: {#a .b c=d}\ne\n:::", &fenced_div.parse)?,
+        Node::Root(Root {
+            children: vec![Node::FencedDiv(FencedDiv {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "e".into(),
+                        position: Some(Position::new(2, 1, 16, 2, 2, 17))
+                    }),],
+                    position: Some(Position::new(2, 1, 16, 2, 2, 17))
+                }),],
+                position: Some(Position::new(1, 1, 0, 3, 4, 21)),
+                id: Some("a".into()),
+                classes: vec!["b".into()],
+                attributes: vec![("c".into(), "d".into())]
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 4, 21))
+        }),
+        "should support fenced divs as `FencedDiv`s in mdast"
+    );
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use markdown::{
-    mdast::{Node, Root, Toml, Yaml},
-    to_html, to_html_with_options, to_mdast,
+    mdast::{Node, Paragraph, Root, Text, ThematicBreak, Toml, Yaml},
+    to_frontmatter, to_html, to_html_batch, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Frontmatter, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -67,6 +67,57 @@ fn frontmatter() -> Result<(), String> {
         "should not support 4 markers in a closing fence"
     );
 
+    assert_eq!(
+        to_html_with_options("---\n\n", &frontmatter)?,
+        "<hr />\n",
+        "should not support an unclosed fence followed by a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\na\n\nb", &frontmatter)?,
+        "<hr />\n<p>a</p>\n<p>b</p>",
+        "should not support unclosed frontmatter w/ a blank line in it"
+    );
+
+    assert_eq!(
+        to_html_with_options("+++\n\n", &frontmatter)?,
+        "<p>+++</p>\n",
+        "should not support an unclosed fence followed by a blank line (toml)"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\r\n\r\n", &frontmatter)?,
+        "<hr />\r\n",
+        "should not support an unclosed fence followed by a blank line (crlf)"
+    );
+
+    assert_eq!(
+        to_mdast("---\na\n\nb", &frontmatter.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::ThematicBreak(ThematicBreak {
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(2, 1, 4, 2, 2, 5))
+                    }),],
+                    position: Some(Position::new(2, 1, 4, 2, 2, 5))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(4, 1, 7, 4, 2, 8))
+                    }),],
+                    position: Some(Position::new(4, 1, 7, 4, 2, 8))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 4, 2, 8))
+        }),
+        "should have correct positions after unclosed frontmatter w/ a blank line in it"
+    );
+
     assert_eq!(
         to_html_with_options("---\n---\n## Neptune", &frontmatter)?,
         "<h2>Neptune</h2>",
@@ -127,6 +178,36 @@ fn frontmatter() -> Result<(), String> {
         "should support blank lines in frontmatter"
     );
 
+    assert_eq!(
+        to_html_with_options("---\na\n+++", &frontmatter)?,
+        "<hr />\n<p>a\n+++</p>",
+        "should not support a closing fence w/ another marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("+++\na\n---\n+++\nb", &frontmatter)?,
+        "<p>b</p>",
+        "should support the other fence as content"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\r\na: b\r\n---\r\nc", &frontmatter)?,
+        "<p>c</p>",
+        "should support CRLF line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("\n---\na\n---", &frontmatter)?,
+        "<hr />\n<h2>a</h2>",
+        "should not support frontmatter after a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options("\u{feff}---\na\n---\nb", &frontmatter)?,
+        "<p>b</p>",
+        "should support frontmatter after a byte order mark"
+    );
+
     assert_eq!(
         to_mdast("---\na: b\n---", &frontmatter.parse)?,
         Node::Root(Root {
@@ -151,5 +232,126 @@ fn frontmatter() -> Result<(), String> {
         "should support toml as `Toml`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("---\na: b\nc:\n  - d\n---\ne", &frontmatter.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::Yaml(Yaml {
+                    value: "a: b\nc:\n  - d".into(),
+                    position: Some(Position::new(1, 1, 0, 5, 4, 21))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "e".into(),
+                        position: Some(Position::new(6, 1, 22, 6, 2, 23))
+                    })],
+                    position: Some(Position::new(6, 1, 22, 6, 2, 23))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 6, 2, 23))
+        }),
+        "should expose the raw value of multiline frontmatter in mdast"
+    );
+
+    let title = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                frontmatter: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            frontmatter_title: true,
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: Jupyter\n---\n\na", &title)?,
+        "<h1>Jupyter</h1>\n<p>a</p>",
+        "should support a title in frontmatter w/ `frontmatter_title`"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: Jupyter\n---\na", &title)?,
+        "<h1>Jupyter</h1>\n<p>a</p>",
+        "should support a title in frontmatter directly followed by content"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\na: b\n---\n\nc", &title)?,
+        "<p>c</p>",
+        "should not add a heading w/o a title field"
+    );
+
+    assert_eq!(
+        to_html_with_options("+++\ntitle = \"a\"\n+++\n\nb", &title)?,
+        "<p>b</p>",
+        "should not support titles in toml"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: \"a <b> \\\"c\\\"\" # d\n---", &title)?,
+        "<h1>a &lt;b&gt; &quot;c&quot;</h1>",
+        "should support double-quoted titles, w/ escapes, and a comment"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: 'a''b'\n---", &title)?,
+        "<h1>a'b</h1>",
+        "should support single-quoted titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: a # b\n---\n\n---\ntitle: a#b\n---", &title)?,
+        "<h1>a</h1>\n<hr />\n<h2>title: a#b</h2>",
+        "should support comments after plain titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: |\n  a\n---", &title)?,
+        "",
+        "should not support block scalars"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\na:\n  title: b\ntitle:c\n---", &title)?,
+        "",
+        "should not support indented title fields, or w/o whitespace after the colon"
+    );
+
+    assert_eq!(
+        to_html_batch(&["---\ntitle: a\n---", "---\ntitle: b\n---\nc"], &title)?,
+        vec!["<h1>a</h1>", "<h1>b</h1>\n<p>c</p>"],
+        "should support `frontmatter_title` in batches"
+    );
+
+    assert_eq!(
+        to_frontmatter("---\ntitle: Jupyter\na: b\n---\n\nc", &title.parse)?,
+        Some(Frontmatter {
+            value: "title: Jupyter\na: b".into(),
+            title: Some("Jupyter".into()),
+            position: Position::new(1, 1, 0, 4, 4, 27)
+        }),
+        "should get frontmatter w/ `to_frontmatter`"
+    );
+
+    assert_eq!(
+        to_frontmatter("---\na: b\n---", &title.parse)?,
+        Some(Frontmatter {
+            value: "a: b".into(),
+            title: None,
+            position: Position::new(1, 1, 0, 3, 4, 12)
+        }),
+        "should get frontmatter w/o a title field w/ `to_frontmatter`"
+    );
+
+    assert_eq!(
+        to_frontmatter("a\n\n---\ntitle: b\n---", &title.parse)?,
+        None,
+        "should not get frontmatter if there is none w/ `to_frontmatter`"
+    );
+
     Ok(())
 }
@@ -0,0 +1,132 @@
+use markdown::{to_html, to_html_with_options, Options};
+use pretty_assertions::assert_eq;
+
+// Each input repeats something that starts a construct, which is not closed
+// until the end of the line, if at all.
+// Scanning the rest of the line again for each start is quadratic, which, at
+// this size, takes many seconds instead of milliseconds.
+const SIZE: usize = 10_000;
+
+#[test]
+fn fuzz_pathological_label() {
+    assert_eq!(
+        to_html(&"[a](b".repeat(SIZE)),
+        format!("<p>{}</p>", "[a](b".repeat(SIZE)),
+        "should be linear for unclosed resources"
+    );
+
+    assert_eq!(
+        to_html(&"![a](b".repeat(SIZE)),
+        format!("<p>{}</p>", "![a](b".repeat(SIZE)),
+        "should be linear for unclosed image resources"
+    );
+
+    assert_eq!(
+        to_html(&"[a](<".repeat(SIZE)),
+        format!("<p>{}</p>", "[a](&lt;".repeat(SIZE)),
+        "should be linear for unclosed enclosed destinations"
+    );
+
+    assert_eq!(
+        to_html(&"[".repeat(SIZE)),
+        format!("<p>{}</p>", "[".repeat(SIZE)),
+        "should be linear for unclosed labels"
+    );
+}
+
+#[test]
+fn fuzz_pathological_attention() {
+    assert_eq!(
+        to_html(&"*a_".repeat(SIZE)),
+        format!("<p>{}</p>", "*a_".repeat(SIZE)),
+        "should be linear for unmatched attention"
+    );
+
+    assert_eq!(
+        to_html(&format!("{}{}", "*".repeat(SIZE), "a")),
+        format!("<p>{}a</p>", "*".repeat(SIZE)),
+        "should be linear for a big unmatched sequence"
+    );
+}
+
+#[test]
+fn fuzz_pathological_html_text() {
+    assert_eq!(
+        to_html(&format!("a {}", "<a ".repeat(SIZE))),
+        format!("<p>a {}</p>", "&lt;a ".repeat(SIZE).trim_end()),
+        "should be linear for unclosed tags"
+    );
+
+    assert_eq!(
+        to_html(&format!("a {}", "</a".repeat(SIZE))),
+        format!("<p>a {}</p>", "&lt;/a".repeat(SIZE)),
+        "should be linear for unclosed closing tags"
+    );
+
+    assert_eq!(
+        to_html(&format!("a {}", "<!--".repeat(SIZE))),
+        format!("<p>a {}</p>", "&lt;!--".repeat(SIZE)),
+        "should be linear for unclosed comments"
+    );
+
+    assert_eq!(
+        to_html(&format!("a {}", "<a:".repeat(SIZE))),
+        format!("<p>a {}</p>", "&lt;a:".repeat(SIZE)),
+        "should be linear for unclosed autolinks"
+    );
+
+    assert_eq!(
+        to_html(&format!("a {}", "<a b='".repeat(SIZE))),
+        format!("<p>a {}</p>", "&lt;a b='".repeat(SIZE)),
+        "should be linear for unclosed attribute values"
+    );
+}
+
+#[test]
+fn fuzz_pathological_directive() -> Result<(), String> {
+    let directive = Options::directives();
+
+    assert_eq!(
+        to_html_with_options(&":a[".repeat(SIZE), &directive)?,
+        format!("<p>{}</p>", ":a[".repeat(SIZE)),
+        "should be linear for unclosed directive labels"
+    );
+
+    assert_eq!(
+        to_html_with_options(&":a[[]".repeat(SIZE), &directive)?,
+        format!("<p>{}</p>", ":a[[]".repeat(SIZE)),
+        "should be linear for unclosed directive labels w/ balanced brackets"
+    );
+
+    assert_eq!(
+        to_html_with_options(&":a[\\]".repeat(SIZE), &directive)?,
+        format!("<p>{}</p>", ":a[]".repeat(SIZE)),
+        "should be linear for unclosed directive labels w/ escapes"
+    );
+
+    assert_eq!(
+        to_html_with_options(&":a{".repeat(SIZE), &directive)?,
+        format!("<p>{}</p>", ":a{".repeat(SIZE)),
+        "should be linear for unclosed directive attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(&":a{b=\"".repeat(SIZE), &directive)?,
+        format!("<p>{}</p>", ":a{b=&quot;".repeat(SIZE)),
+        "should be linear for unclosed quoted directive attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(&":a{\"}\" :b{".repeat(SIZE), &directive)?,
+        format!("<p>{}</p>", ":a{&quot;}&quot; :b{".repeat(SIZE)),
+        "should be linear for unclosed directive attributes w/ quoted braces"
+    );
+
+    assert_eq!(
+        to_html_with_options(&format!("{}:b[c] d", ":a[".repeat(SIZE)), &directive)?,
+        format!("<p>{} d</p>", ":a[".repeat(SIZE)),
+        "should still support a closed directive after unclosed ones"
+    );
+
+    Ok(())
+}
@@ -2,11 +2,13 @@ use markdown::{
     mdast::{Link, Node, Paragraph, Root, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Options, ParseOptions,
+    CompileOptions, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
 #[test]
+// `allowed_link_schemes` is deprecated, but still tested.
+#[allow(deprecated)]
 fn gfm_autolink_literal() -> Result<(), String> {
     assert_eq!(
         to_html("https://example.com"),
@@ -55,6 +57,26 @@ fn gfm_autolink_literal() -> Result<(), String> {
         "<p><a href=\"xxx\">user@example.com</a></p>",
         "should not link email urls in links"
     );
+    assert_eq!(
+        to_html_with_options("`https://example.com`, `www.example.com`, `user@example.com`", &Options::gfm())?,
+        "<p><code>https://example.com</code>, <code>www.example.com</code>, <code>user@example.com</code></p>",
+        "should not link urls in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "https://a.com www.b.com ftp://c.com",
+            &Options {
+                compile: CompileOptions {
+                    allowed_link_schemes: Some(vec!["https".into()]),
+                    ..CompileOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "<p><a href=\"https://a.com\">https://a.com</a> www.b.com ftp://c.com</p>",
+        "should turn literal autolinks w/ protocols that are not allowed into text (`allowed_link_schemes`)"
+    );
 
     assert_eq!(
         to_html_with_options("user@example.com", &Options::gfm())?,
@@ -209,6 +231,63 @@ fn gfm_autolink_literal() -> Result<(), String> {
         "should support email links after Unicode punctuation"
     );
 
+    assert_eq!(
+        to_html_with_options("a.www.example.com", &Options::gfm())?,
+        "<p>a.www.example.com</p>",
+        "should not support www links after a period"
+    );
+
+    assert_eq!(
+        to_html_with_options("awww.example.com 1www.example.com", &Options::gfm())?,
+        "<p>awww.example.com 1www.example.com</p>",
+        "should not support www links after alphanumericals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "\"www.a.com 'www.b.com -www.c.com /www.d.com :www.e.com",
+            &Options::gfm()
+        )?,
+        "<p>&quot;www.a.com 'www.b.com -www.c.com /www.d.com :www.e.com</p>",
+        "should not support www links after other ASCII punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "www.a.com\n(www.b.com)\n*www.c.com*\n_www.d.com_\n~www.e.com~\nf\twww.g.com",
+            &Options::gfm()
+        )?,
+        "<p><a href=\"http://www.a.com\">www.a.com</a>\n(<a href=\"http://www.b.com\">www.b.com</a>)\n<em><a href=\"http://www.c.com\">www.c.com</a></em>\n<em><a href=\"http://www.d.com\">www.d.com</a></em>\n<del><a href=\"http://www.e.com\">www.e.com</a></del>\nf\t<a href=\"http://www.g.com\">www.g.com</a></p>",
+        "should support www links at the start, after whitespace, `(`, `*`, `_`, and `~`"
+    );
+
+    assert_eq!(
+        to_html_with_options("éwww.example.com", &Options::gfm())?,
+        "<p>éwww.example.com</p>",
+        "should not support www links after non-ASCII letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("ahttp://example.com Zhttps://example.com", &Options::gfm())?,
+        "<p>ahttp://example.com Zhttps://example.com</p>",
+        "should not support protocol links after ASCII letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("éhttps://example.com", &Options::gfm())?,
+        "<p>é<a href=\"https://example.com\">https://example.com</a></p>",
+        "should support protocol links after non-ASCII letters, like GitHub does"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a.http://a.com 1http://b.com 'http://c.com' x:https://d.com",
+            &Options::gfm()
+        )?,
+        "<p>a.<a href=\"http://a.com\">http://a.com</a> 1<a href=\"http://b.com\">http://b.com</a> '<a href=\"http://c.com\">http://c.com</a>' x:<a href=\"https://d.com\">https://d.com</a></p>",
+        "should support protocol links after digits and punctuation"
+    );
+
     assert_eq!(
         to_html_with_options(
             "http&#x3A;//user:password@host:port/path?key=value#fragment",
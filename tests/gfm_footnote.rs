@@ -121,6 +121,29 @@ fn gfm_footnote() -> Result<(), String> {
         "should support `options.gfm_footnote_clobber_prefix`"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "a[^b] c[^b]\n\n[^b]: d",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    gfm_footnote_roles: true,
+                    ..CompileOptions::gfm()
+                }
+            }
+        )?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\" role=\"doc-noteref\">1</a></sup> c<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\" role=\"doc-noteref\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\" role=\"doc-endnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-b\">
+<p>d <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" role=\"doc-backlink\" class=\"data-footnote-backref\">↩</a> <a href=\"#user-content-fnref-b-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" role=\"doc-backlink\" class=\"data-footnote-backref\">↩<sup>2</sup></a></p>
+</li>
+</ol>
+</section>
+",
+        "should support `options.gfm_footnote_roles`"
+    );
+
     assert_eq!(
         to_html_with_options("A paragraph.\n\n[^a]: whatevs", &Options::gfm())?,
         "<p>A paragraph.</p>\n",
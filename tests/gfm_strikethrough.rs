@@ -62,6 +62,48 @@ fn gfm_strikethrough() -> Result<(), String> {
         "should close if preceded and followed by punctuation"
     );
 
+    assert_eq!(
+        to_html_with_options("a ~~b **c** d~~", &Options::gfm())?,
+        "<p>a <del>b <strong>c</strong> d</del></p>",
+        "should support strong in strikethrough"
+    );
+
+    assert_eq!(
+        to_html_with_options("**~~a~~**", &Options::gfm())?,
+        "<p><strong><del>a</del></strong></p>",
+        "should support strikethrough in strong"
+    );
+
+    assert_eq!(
+        to_html_with_options("*~~a*~~", &Options::gfm())?,
+        "<p><em>~~a</em>~~</p>",
+        "should not support strikethrough across emphasis"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~~~b~~", &Options::gfm())?,
+        "<p>a ~~~b~~</p>",
+        "should not support strikethrough w/ an unbalanced opening run"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~~b~~~", &Options::gfm())?,
+        "<p>a ~~b~~~</p>",
+        "should not support strikethrough w/ an unbalanced closing run"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `~~b~~` c", &Options::gfm())?,
+        "<p>a <code>~~b~~</code> c</p>",
+        "should not support strikethrough in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.com/~~b~~>", &Options::gfm())?,
+        "<p><a href=\"https://a.com/~~b~~\">https://a.com/~~b~~</a></p>",
+        "should not support strikethrough in autolinks"
+    );
+
     assert_eq!(
         to_html_with_options(
             r###"
@@ -20,6 +20,24 @@ fn gfm_table() -> Result<(), String> {
         "should support tables"
     );
 
+    assert_eq!(
+        to_html_with_options("a\n| b |\n| - |\n| c |", &Options::gfm())?,
+        "<p>a</p>\n<table>\n<thead>\n<tr>\n<th>b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c</td>\n</tr>\n</tbody>\n</table>",
+        "should support tables interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a | b |\n| :- | -: |\n| c |\n| d | e | f |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th align=\"left\">a</th>\n<th align=\"right\">b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td align=\"left\">c</td>\n<td align=\"right\"></td>\n</tr>\n<tr>\n<td align=\"left\">d</td>\n<td align=\"right\">e</td>\n</tr>\n</tbody>\n</table>",
+        "should fill rows w/ fewer cells and drop excess cells in rows w/ more cells than the head"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `b\\|c` |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td><code>b|c</code></td>\n</tr>\n</tbody>\n</table>",
+        "should support escaped pipes in code (text) in cells"
+    );
+
     assert_eq!(
         to_html_with_options("| a |", &Options::gfm())?,
         "<p>| a |</p>",
@@ -1837,6 +1855,54 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
         "should match loose escapes like GitHub"
     );
 
+    assert_eq!(
+        to_html_with_options("| a \\| b | c |\n| - | - |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a | b</th>\n<th>c</th>\n</tr>\n</thead>\n</table>",
+        "should support an escaped pipe in a cell"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a \\\\| b |\n| - | - |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a \\</th>\n<th>b</th>\n</tr>\n</thead>\n</table>",
+        "should support an escaped backslash before a delimiter"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a\\\\\\\\| b |\n| - | - |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a\\\\</th>\n<th>b</th>\n</tr>\n</thead>\n</table>",
+        "should support two escaped backslashes before a delimiter"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a\\|\n| - |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a|</th>\n</tr>\n</thead>\n</table>",
+        "should support an escaped pipe at the end of a row"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a\\ | b\\\\ |\n| - | - |\n| c\\ | d\\\\ |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a\\</th>\n<th>b\\</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c\\</td>\n<td>d\\</td>\n</tr>\n</tbody>\n</table>",
+        "should support a backslash at the end of a cell"
+    );
+
+    assert_eq!(
+        to_html_with_options("| \\* \\a \\\\\\* |\n| - |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>* \\a \\*</th>\n</tr>\n</thead>\n</table>",
+        "should support normal escapes before other characters in cells"
+    );
+
+    assert_eq!(
+        to_html_with_options("| [a\\|b](c\\|d) |\n| - |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th><a href=\"c%7Cd\">a|b</a></th>\n</tr>\n</thead>\n</table>",
+        "should support escaped pipes in links in cells"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| `\\\\|` |", &Options::gfm())?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>`\\</td>\n</tr>\n</tbody>\n</table>",
+        "should split cells before parsing code (text) in them"
+    );
+
     assert_eq!(
         to_mdast(
             "| none | left | right | center |\n| - | :- | -: | :-: |\n| a |\n| b | c | d | e | f |",
@@ -1964,5 +2030,132 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
         "should support weird pipe escapes in code in tables"
     );
 
+    let headerless = Options {
+        parse: ParseOptions {
+            constructs: Constructs::gfm(),
+            gfm_table_headerless: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("| - | - |\n| a | b |", &Options::gfm())?,
+        "<p>| - | - |\n| a | b |</p>",
+        "should not support headerless tables by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - | - |\n| a | b |\n| c | d |", &headerless)?,
+        "<table>\n<tbody>\n<tr>\n<td>a</td>\n<td>b</td>\n</tr>\n<tr>\n<td>c</td>\n<td>d</td>\n</tr>\n</tbody>\n</table>",
+        "should support headerless tables w/ `gfm_table_headerless`"
+    );
+
+    assert_eq!(
+        to_html_with_options(":- | -: | :-:\na | b | c", &headerless)?,
+        "<table>\n<tbody>\n<tr>\n<td align=\"left\">a</td>\n<td align=\"right\">b</td>\n<td align=\"center\">c</td>\n</tr>\n</tbody>\n</table>",
+        "should support alignment in headerless tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - | - |\n| a |\n| b | c | d |", &headerless)?,
+        "<table>\n<tbody>\n<tr>\n<td>a</td>\n<td></td>\n</tr>\n<tr>\n<td>b</td>\n<td>c</td>\n</tr>\n</tbody>\n</table>",
+        "should fill and drop cells in headerless tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - |", &headerless)?,
+        "<p>| - |</p>",
+        "should not support headerless tables w/o body"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - |  \n\n:- | -:\n# a\n\n- | -\n\n  b", &headerless)?,
+        "<p>| - |</p>\n<p>:- | -:</p>\n<h1>a</h1>\n<ul>\n<li>\n<p>| -</p>\n<p>b</p>\n</li>\n</ul>",
+        "should not support headerless tables w/o body (blank line, other construct, list item)"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - |\n| - |", &headerless)?,
+        "<table>\n<thead>\n<tr>\n<th>-</th>\n</tr>\n</thead>\n</table>",
+        "should still support tables w/o body that have a head"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |", &headerless)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>",
+        "should still support tables w/ a head row w/ `gfm_table_headerless`"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - |\n| - |\n| a |", &headerless)?,
+        "<table>\n<thead>\n<tr>\n<th>-</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>a</td>\n</tr>\n</tbody>\n</table>",
+        "should prefer a head row over a headerless table"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n| - |\n| b |", &headerless)?,
+        "<p>a</p>\n<table>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>",
+        "should support headerless tables after a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options("a | b\n| - |\n| c |", &headerless)?,
+        "<p>a | b\n| - |\n| c |</p>",
+        "should not support headerless tables interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\n\n--", &headerless)?,
+        "<hr />\n<p>--</p>",
+        "should not support headerless tables w/o pipes or colons"
+    );
+
+    assert_eq!(
+        to_html_with_options("> | - |\n> | a |\n\n- | - |\n  | b |", &headerless)?,
+        "<blockquote>\n<table>\n<tbody>\n<tr>\n<td>a</td>\n</tr>\n</tbody>\n</table>\n</blockquote>\n<ul>\n<li>\n<table>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>\n</li>\n</ul>",
+        "should support headerless tables in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("> | - |\n>\n> a\n\n- | - |\n| b |", &headerless)?,
+        "<blockquote>\n<p>| - |</p>\n<p>a</p>\n</blockquote>\n<ul>\n<li>| - |\n| b |</li>\n</ul>",
+        "should not support headerless tables w/o body in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - |\n    a\n\n| - |\n2. b", &headerless)?,
+        "<table>\n<tbody>\n<tr>\n<td>a</td>\n</tr>\n</tbody>\n</table>\n<table>\n<tbody>\n<tr>\n<td>2. b</td>\n</tr>\n</tbody>\n</table>",
+        "should not support what cannot interrupt a paragraph after a headerless delimiter row"
+    );
+
+    assert_eq!(
+        to_html_with_options("| - |\n| a |\n    b", &headerless)?,
+        "<table>\n<tbody>\n<tr>\n<td>a</td>\n</tr>\n</tbody>\n</table>\n<pre><code>b\n</code></pre>",
+        "should support code (indented) after a body row of a headerless table"
+    );
+
+    assert_eq!(
+        to_mdast("| - |\n| a |", &headerless.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Table(Table {
+                align: vec![AlignKind::None],
+                children: vec![Node::TableRow(TableRow {
+                    children: vec![Node::TableCell(TableCell {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(2, 3, 8, 2, 4, 9))
+                        })],
+                        position: Some(Position::new(2, 1, 6, 2, 6, 11))
+                    })],
+                    position: Some(Position::new(2, 1, 6, 2, 6, 11))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 6, 11))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 6, 11))
+        }),
+        "should support headerless tables as `Table`s w/o a head row in mdast"
+    );
+
     Ok(())
 }
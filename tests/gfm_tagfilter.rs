@@ -130,5 +130,71 @@ javascript:/*--></title></style></textarea></script></xmp><svg/onload='+/"/+/onm
         "should handle things like GitHub"
     );
 
+    let tagfilter = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            gfm_tagfilter: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("<ScRiPt>a</sCrIpT>", &tagfilter)?,
+        "&lt;ScRiPt>a&lt;/sCrIpT>",
+        "should filter case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n<script\nsrc=\"a\">\n</div>", &tagfilter)?,
+        "<div>\n&lt;script\nsrc=\"a\">\n</div>",
+        "should filter tags split across lines in HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<script\nsrc=\"a\"></script>", &tagfilter)?,
+        "&lt;script\nsrc=\"a\">&lt;/script>",
+        "should filter tags split across lines that start HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <textarea\nb></textarea>", &tagfilter)?,
+        "<p>a &lt;textarea\nb>&lt;/textarea></p>",
+        "should filter tags split across lines in HTML (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n<iframe", &tagfilter)?,
+        "<div>\n&lt;iframe",
+        "should filter a tag name at the end of the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n<scripts\n<xmp-a>", &tagfilter)?,
+        "<div>\n<scripts\n<xmp-a>",
+        "should not filter other tag names"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <i title=\"<script>\"\nb>", &tagfilter)?,
+        "<p>a <i title=\"&lt;script>\"\nb></p>",
+        "should filter in attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<div>\n<script\nsrc=\"a\">\n</div>",
+            &Options {
+                compile: CompileOptions {
+                    gfm_tagfilter: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "&lt;div&gt;\n&lt;script\nsrc=&quot;a&quot;&gt;\n&lt;/div&gt;",
+        "should not filter when dangerous HTML is off"
+    );
+
     Ok(())
 }
@@ -26,6 +26,36 @@ fn gfm_task_list_item() -> Result<(), String> {
         "should support unchecked task list item checks"
     );
 
+    assert_eq!(
+        to_html_with_options("- [X] a", &Options::gfm())?,
+        "<ul>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a</li>\n</ul>",
+        "should support uppercase `X` as checked"
+    );
+
+    assert_eq!(
+        to_html_with_options("- [x]a", &Options::gfm())?,
+        "<ul>\n<li>[x]a</li>\n</ul>",
+        "should not support a check w/o whitespace after it"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a [x] b", &Options::gfm())?,
+        "<ul>\n<li>a [x] b</li>\n</ul>",
+        "should not support a check that is not first in the item"
+    );
+
+    assert_eq!(
+        to_html_with_options("> [x] a", &Options::gfm())?,
+        "<blockquote>\n<p>[x] a</p>\n</blockquote>",
+        "should not support a check outside list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("- [x] a\n  - [ ] b\n  - [X] c\n- [ ] d", &Options::gfm())?,
+        "<ul>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a\n<ul>\n<li><input type=\"checkbox\" disabled=\"\" /> b</li>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> c</li>\n</ul>\n</li>\n<li><input type=\"checkbox\" disabled=\"\" /> d</li>\n</ul>",
+        "should support nested lists w/ checked and unchecked items"
+    );
+
     assert_eq!(
         to_html_with_options("*\n    [x]", &Options::gfm())?,
         "<ul>\n<li>[x]</li>\n</ul>",
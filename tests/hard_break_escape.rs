@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Break, Node, Paragraph, Root, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -62,6 +62,51 @@ fn hard_break_escape() -> Result<(), String> {
         "should support turning off hard break (escape)"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "a\\\nb",
+            &Options {
+                compile: CompileOptions {
+                    hard_break_markup: Some("<br>".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a<br>\nb</p>",
+        "should support `options.hard_break_markup`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\\\nb\\\n*c\\\nd*",
+            &Options {
+                compile: CompileOptions {
+                    hard_break_markup: Some("<span class=\"break\"></span>".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a<span class=\"break\"></span>\nb<span class=\"break\"></span>\n<em>c<span class=\"break\"></span>\nd</em></p>",
+        "should support custom markup for hard breaks, used as is"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "![a\\\nb](c)",
+            &Options {
+                compile: CompileOptions {
+                    hard_break_markup: Some("<br>".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><img src=\"c\" alt=\"a\nb\" /></p>",
+        "should not use the markup for hard breaks in image alt text"
+    );
+
     assert_eq!(
         to_mdast("a\\\nb.", &Default::default())?,
         Node::Root(Root {
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Break, Node, Paragraph, Root, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -128,6 +128,21 @@ fn hard_break_trailing() -> Result<(), String> {
         "should support turning off hard break (trailing)"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "a  \nb",
+            &Options {
+                compile: CompileOptions {
+                    hard_break_markup: Some("<br>".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a<br>\nb</p>",
+        "should support `options.hard_break_markup`"
+    );
+
     assert_eq!(
         to_mdast("a  \nb.", &Default::default())?,
         Node::Root(Root {
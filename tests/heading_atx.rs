@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Heading, Node, Root, Text},
+    mdast::{Emphasis, Heading, InlineCode, Link, Node, Root, Strong, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -240,5 +240,70 @@ fn heading_atx() -> Result<(), String> {
         "should support heading (atx) as `Heading`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("# a *b **c***", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 1,
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 5, 4))
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![
+                            Node::Text(Text {
+                                value: "b ".into(),
+                                position: Some(Position::new(1, 6, 5, 1, 8, 7))
+                            }),
+                            Node::Strong(Strong {
+                                children: vec![Node::Text(Text {
+                                    value: "c".into(),
+                                    position: Some(Position::new(1, 10, 9, 1, 11, 10))
+                                })],
+                                position: Some(Position::new(1, 8, 7, 1, 13, 12))
+                            })
+                        ],
+                        position: Some(Position::new(1, 5, 4, 1, 14, 13))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 14, 13))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 14, 13))
+        }),
+        "should support nested attention in heading (atx) in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("## `x` [y](z) #", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 2,
+                children: vec![
+                    Node::InlineCode(InlineCode {
+                        value: "x".into(),
+                        position: Some(Position::new(1, 4, 3, 1, 7, 6))
+                    }),
+                    Node::Text(Text {
+                        value: " ".into(),
+                        position: Some(Position::new(1, 7, 6, 1, 8, 7))
+                    }),
+                    Node::Link(Link {
+                        url: "z".into(),
+                        title: None,
+                        children: vec![Node::Text(Text {
+                            value: "y".into(),
+                            position: Some(Position::new(1, 9, 8, 1, 10, 9))
+                        })],
+                        position: Some(Position::new(1, 8, 7, 1, 14, 13))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 16, 15))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 16, 15))
+        }),
+        "should support code (text) and links in heading (atx) in mdast"
+    );
+
     Ok(())
 }
@@ -86,6 +86,30 @@ fn heading_setext() -> Result<(), String> {
         "should support whitespace before underline"
     );
 
+    assert_eq!(
+        to_html("Title\n===  "),
+        to_html("# Title #"),
+        "should match heading (atx) w/ a closing sequence"
+    );
+
+    assert_eq!(
+        to_html("Title  \n==="),
+        "<h1>Title</h1>",
+        "should not include trailing spaces on the last text line"
+    );
+
+    assert_eq!(
+        to_html("Title\t \n===\t"),
+        "<h1>Title</h1>",
+        "should not include trailing tabs and spaces on the last text line"
+    );
+
+    assert_eq!(
+        to_html("a \nb\t\n---"),
+        "<h2>a\nb</h2>",
+        "should not include trailing whitespace on earlier text lines"
+    );
+
     assert_eq!(
         to_html("Foo\n    ="),
         "<p>Foo\n=</p>",
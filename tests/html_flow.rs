@@ -328,6 +328,18 @@ fn html_flow_2_comment() -> Result<(), String> {
         "should support blank lines in comments"
     );
 
+    assert_eq!(
+        to_html_with_options("<!-- a\nb --> *c*\n*d*", &danger)?,
+        "<!-- a\nb --> *c*\n<p><em>d</em></p>",
+        "should include content after the end of a comment on the same line"
+    );
+
+    assert_eq!(
+        to_html_with_options("<!-- a -- > b\nc --> d\ne", &danger)?,
+        "<!-- a -- > b\nc --> d\n<p>e</p>",
+        "should not end comments at `-- >`"
+    );
+
     assert_eq!(
         to_html_with_options("> <!--\na", &danger)?,
         "<blockquote>\n<!--\n</blockquote>\n<p>a</p>",
@@ -385,6 +397,18 @@ fn html_flow_3_instruction() -> Result<(), String> {
         "should support blank lines in instructions"
     );
 
+    assert_eq!(
+        to_html_with_options("<?a\nb ?> *c*\n*d*", &danger)?,
+        "<?a\nb ?> *c*\n<p><em>d</em></p>",
+        "should include content after the end of an instruction on the same line"
+    );
+
+    assert_eq!(
+        to_html_with_options("<?a ? > b\n>c\nd??> e\nf", &danger)?,
+        "<?a ? > b\n>c\nd??> e\n<p>f</p>",
+        "should end instructions at `?>` only, also after more `?`"
+    );
+
     assert_eq!(
         to_html_with_options("> <?\na", &danger)?,
         "<blockquote>\n<?\n</blockquote>\n<p>a</p>",
@@ -450,6 +474,18 @@ fn html_flow_4_declaration() -> Result<(), String> {
         "should support blank lines in declarations"
     );
 
+    assert_eq!(
+        to_html_with_options("<!a\nb> *c*\n*d*", &danger)?,
+        "<!a\nb> *c*\n<p><em>d</em></p>",
+        "should include content after the end of a declaration on the same line"
+    );
+
+    assert_eq!(
+        to_html_with_options("<!DOCTYPE html -- a ] ?\n>\nb", &danger)?,
+        "<!DOCTYPE html -- a ] ?\n>\n<p>b</p>",
+        "should end declarations at the first `>`"
+    );
+
     assert_eq!(
         to_html_with_options("> <!a\nb", &danger)?,
         "<blockquote>\n<!a\n</blockquote>\n<p>b</p>",
@@ -524,6 +560,30 @@ fn html_flow_5_cdata() -> Result<(), String> {
         "should support blank lines in cdata"
     );
 
+    assert_eq!(
+        to_html_with_options("<![CDATA[a\nb]]> *c*\n*d*", &danger)?,
+        "<![CDATA[a\nb]]> *c*\n<p><em>d</em></p>",
+        "should include content after the end of cdata on the same line"
+    );
+
+    assert_eq!(
+        to_html_with_options("<![CDATA[a] ]> b ]>\nc ]]\n> d\n]]> e\nf", &danger)?,
+        "<![CDATA[a] ]> b ]>\nc ]]\n> d\n]]> e\n<p>f</p>",
+        "should end cdata at `]]>` only"
+    );
+
+    assert_eq!(
+        to_html_with_options("<![CDATA[a]]]>\nb", &danger)?,
+        "<![CDATA[a]]]>\n<p>b</p>",
+        "should end cdata at `]]>` after more `]`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<![CDATA[a]]]]]> b\nc", &danger)?,
+        "<![CDATA[a]]]]]> b\n<p>c</p>",
+        "should end cdata at `]]>` after many `]`"
+    );
+
     assert_eq!(
         to_html_with_options("> <![CDATA[\na", &danger)?,
         "<blockquote>\n<![CDATA[\n</blockquote>\n<p>a</p>",
@@ -823,6 +883,39 @@ okay.",
         "should not support lazyness (2)"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "<details>\n<summary>a</summary>\n\n*b*\n\n</details>",
+            &danger
+        )?,
+        "<details>\n<summary>a</summary>\n<p><em>b</em></p>\n</details>",
+        "should support markdown in `details` after a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options("<details>\n<summary>a</summary>\n*b*\n</details>", &danger)?,
+        "<details>\n<summary>a</summary>\n*b*\n</details>",
+        "should not support markdown in `details` w/o a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<details><summary>a</summary>\n\n- b\n- c\n\n</details>",
+            &danger
+        )?,
+        "<details><summary>a</summary>\n<ul>\n<li>b</li>\n<li>c</li>\n</ul>\n</details>",
+        "should support block markdown in `details` after a blank line"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<details>\n<summary>a</summary>\n\n*b*\n</details>",
+            &danger
+        )?,
+        "<details>\n<summary>a</summary>\n<p><em>b</em></p>\n</details>",
+        "should support a closing `details` tag interrupting a paragraph"
+    );
+
     Ok(())
 }
 
@@ -0,0 +1,245 @@
+use markdown::{
+    mdast::{InlineFootnote, Node, Paragraph, Root, Text},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn inline_footnote() -> Result<(), String> {
+    let inline = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                label_start_inline_footnote: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a^[b] c"),
+        "<p>a^[b] c</p>",
+        "should ignore inline footnotes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b] c", &Options::gfm())?,
+        "<p>a^[b] c</p>",
+        "should ignore inline footnotes in GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b *c*] d", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> d</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <em>c</em> <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^x] b^[c] d[^y] e^[f]\n\n[^x]: g\n[^y]: h", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> b<sup><a href=\"#user-content-fn-inline-2\" id=\"user-content-fnref-inline-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> d<sup><a href=\"#user-content-fn-y\" id=\"user-content-fnref-y\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">3</a></sup> e<sup><a href=\"#user-content-fn-inline-4\" id=\"user-content-fnref-inline-4\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">4</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-x\">
+<p>g <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-inline-2\">
+<p>c <a href=\"#user-content-fnref-inline-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-y\">
+<p>h <a href=\"#user-content-fnref-y\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-inline-4\">
+<p>f <a href=\"#user-content-fnref-inline-4\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should number inline and reference footnotes in the order they are called"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^x] b^[c] d[^x]\n\n[^x]: e", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> b<sup><a href=\"#user-content-fn-inline-2\" id=\"user-content-fnref-inline-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> d<sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-x\">
+<p>e <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a> <a href=\"#user-content-fnref-x-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩<sup>2</sup></a></p>
+</li>
+<li id=\"user-content-fn-inline-2\">
+<p>c <a href=\"#user-content-fnref-inline-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support reference footnotes called again after an inline footnote"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b] c[^Inline-1]\n\n[^Inline-1]: d", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-_inline-1\" id=\"user-content-fnref-_inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-_inline-1\">
+<p>b <a href=\"#user-content-fnref-_inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-inline-1\">
+<p>d <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not use the identifier of a reference footnote for an inline footnote"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^inline] b[^inline] c^[d]\n\n[^inline]: e", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline\" id=\"user-content-fnref-inline\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> b<sup><a href=\"#user-content-fn-inline\" id=\"user-content-fnref-inline-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c<sup><a href=\"#user-content-fn-_inline-2\" id=\"user-content-fnref-_inline-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline\">
+<p>e <a href=\"#user-content-fnref-inline\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a> <a href=\"#user-content-fnref-inline-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩<sup>2</sup></a></p>
+</li>
+<li id=\"user-content-fn-_inline-2\">
+<p>d <a href=\"#user-content-fnref-_inline-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not use the identifier of a second call to a reference footnote for an inline footnote"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b ^[c] d] e", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> e</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <sup><a href=\"#user-content-fn-inline-2\" id=\"user-content-fnref-inline-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> d <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-inline-2\">
+<p>c <a href=\"#user-content-fnref-inline-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support nested inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b [^x] c]\n\n[^x]: d", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <sup><a href=\"#user-content-fn-x\" id=\"user-content-fnref-x\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup> c <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+<li id=\"user-content-fn-x\">
+<p>d <a href=\"#user-content-fnref-x\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support reference footnotes in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b [c](d)] e", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> e</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <a href=\"d\">c</a> <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support links in inline footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a ^[b] c](d)", &inline)?,
+        "<p>[a <sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup> c](d)</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not support inline footnotes in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b](c)", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-inline-1\" id=\"user-content-fnref-inline-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup>(c)</p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-inline-1\">
+<p>b <a href=\"#user-content-fnref-inline-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should not support a resource after an inline footnote"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^[b", &inline)?,
+        "<p>a^[b</p>",
+        "should not support an unclosed inline footnote"
+    );
+
+    assert_eq!(
+        to_html_with_options("a^ [b] c^", &inline)?,
+        "<p>a^ [b] c^</p>",
+        "should not support a caret not followed by a bracket"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\\^[b]", &inline)?,
+        "<p>a^[b]</p>",
+        "should not support an escaped caret"
+    );
+
+    assert_eq!(
+        to_mdast("a^[b] c", &inline.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::InlineFootnote(InlineFootnote {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                        }),],
+                        position: Some(Position::new(1, 2, 1, 1, 6, 5))
+                    }),
+                    Node::Text(Text {
+                        value: " c".into(),
+                        position: Some(Position::new(1, 6, 5, 1, 8, 7))
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 8, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 8, 7))
+        }),
+        "should support inline footnotes as `InlineFootnote`s in mdast"
+    );
+
+    Ok(())
+}
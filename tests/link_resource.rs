@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Image, Link, Node, Paragraph, Root, Text},
+    mdast::{Emphasis, Image, Link, Node, Paragraph, Root, Text},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Options,
@@ -7,6 +7,8 @@ use markdown::{
 use pretty_assertions::assert_eq;
 
 #[test]
+// `allowed_link_schemes` is deprecated, but still tested.
+#[allow(deprecated)]
 fn link_resource() -> Result<(), String> {
     let danger = Options {
         compile: CompileOptions {
@@ -41,6 +43,24 @@ fn link_resource() -> Result<(), String> {
         "should support links w/ empty enclosed destination"
     );
 
+    assert_eq!(
+        to_html("[link](<> \"title\")"),
+        "<p><a href=\"\" title=\"title\">link</a></p>",
+        "should support links w/ empty enclosed destination and a title"
+    );
+
+    assert_eq!(
+        to_html("[link](  <>  )"),
+        "<p><a href=\"\">link</a></p>",
+        "should support links w/ empty enclosed destination and whitespace"
+    );
+
+    assert_eq!(
+        to_html("![image](<>)"),
+        "<p><img src=\"\" alt=\"image\" /></p>",
+        "should support images w/ empty enclosed destination"
+    );
+
     assert_eq!(
         to_html("[link](/my uri)"),
         "<p>[link](/my uri)</p>",
@@ -465,6 +485,135 @@ fn link_resource() -> Result<(), String> {
         "should support a single NUL character as a link resource"
     );
 
+    assert_eq!(
+        to_html("[**bold** link](url)"),
+        "<p><a href=\"url\"><strong>bold</strong> link</a></p>",
+        "should support strong in link text"
+    );
+
+    assert_eq!(
+        to_html("[*a* _b_](c)"),
+        "<p><a href=\"c\"><em>a</em> <em>b</em></a></p>",
+        "should support emphasis in link text"
+    );
+
+    assert_eq!(
+        to_html("[a *b](c) d*"),
+        "<p><a href=\"c\">a *b</a> d*</p>",
+        "should not support emphasis crossing the link text"
+    );
+
+    assert_eq!(
+        to_html("[`a` b](c)"),
+        "<p><a href=\"c\"><code>a</code> b</a></p>",
+        "should support code in link text"
+    );
+
+    assert_eq!(
+        to_html("[a `]` b](c)"),
+        "<p><a href=\"c\">a <code>]</code> b</a></p>",
+        "should support a bracket in code in link text"
+    );
+
+    assert_eq!(
+        to_html("[a ![b](c) d](e)"),
+        "<p><a href=\"e\">a <img src=\"c\" alt=\"b\" /> d</a></p>",
+        "should support images in link text"
+    );
+
+    assert_eq!(
+        to_html("[a\\\nb](c)"),
+        "<p><a href=\"c\">a<br />\nb</a></p>",
+        "should support hard breaks in link text"
+    );
+
+    assert_eq!(
+        to_html("[a [b](c) d](e)"),
+        "<p>[a <a href=\"c\">b</a> d](e)</p>",
+        "should not support links in link text (inner link wins)"
+    );
+
+    assert_eq!(
+        to_html("[a [b](c) d][e]\n\n[e]: f"),
+        "<p>[a <a href=\"c\">b</a> d]<a href=\"f\">e</a></p>\n",
+        "should not support links in link text of references (inner link wins)"
+    );
+
+    let https_only = Options {
+        compile: CompileOptions {
+            allowed_link_schemes: Some(vec!["https".into()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("[a](ftp://b) [c](https://d)", &https_only)?,
+        "<p>a <a href=\"https://d\">c</a></p>",
+        "should turn links w/ protocols that are not allowed into text (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a *b*](ftp://c \"d\")", &https_only)?,
+        "<p>a <em>b</em></p>",
+        "should keep the content of links w/ protocols that are not allowed (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a][b]\n\n[b]: ftp://c", &https_only)?,
+        "<p>a</p>\n",
+        "should turn references to definitions w/ protocols that are not allowed into text (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](HTTPS://b)", &https_only)?,
+        "<p><a href=\"HTTPS://b\">a</a></p>",
+        "should compare protocols case-insensitively (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](./b) [c](#d) [e](/f:g)", &https_only)?,
+        "<p><a href=\"./b\">a</a> <a href=\"#d\">c</a> <a href=\"/f:g\">e</a></p>",
+        "should allow relative links (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](ftp://b)", &https_only)?,
+        "<p><img src=\"\" alt=\"a\" /></p>",
+        "should not affect images (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](javascript:alert(1))",
+            &Options {
+                compile: CompileOptions {
+                    allowed_link_schemes: Some(vec!["javascript".into()]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"\">a</a></p>",
+        "should still drop dangerous protocols if they are allowed (`allowed_link_schemes`)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](ftp://b) [c](https://d)",
+            &Options {
+                compile: CompileOptions {
+                    allowed_link_protocols: Some(vec!["ftp".into()]),
+                    allowed_link_schemes: Some(vec!["https".into()]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"ftp://b\">a</a> <a href=\"\">c</a></p>",
+        "should ignore `allowed_link_schemes` when `allowed_link_protocols` is set"
+    );
+
     assert_eq!(
         to_mdast(
             "a [alpha]() b [bravo](charlie 'delta') c.",
@@ -533,5 +682,34 @@ fn link_resource() -> Result<(), String> {
         "should support nested links in mdast"
     );
 
+    assert_eq!(
+        to_mdast("[*a* b](c)", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Link(Link {
+                    children: vec![
+                        Node::Emphasis(Emphasis {
+                            children: vec![Node::Text(Text {
+                                value: "a".into(),
+                                position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                            }),],
+                            position: Some(Position::new(1, 2, 1, 1, 5, 4)),
+                        }),
+                        Node::Text(Text {
+                            value: " b".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 7, 6)),
+                        }),
+                    ],
+                    url: "c".into(),
+                    title: None,
+                    position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+            }),],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support emphasis in link text in mdast"
+    );
+
     Ok(())
 }
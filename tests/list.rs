@@ -96,6 +96,42 @@ fn list() -> Result<(), String> {
         "should not support ordered item values over 10 digits"
     );
 
+    assert_eq!(
+        to_html("999999999) ok"),
+        "<ol start=\"999999999\">\n<li>ok</li>\n</ol>",
+        "should support ordered item values of 9 digits w/ `)`"
+    );
+
+    assert_eq!(
+        to_html("1234567890) not ok"),
+        "<p>1234567890) not ok</p>",
+        "should not support ordered item values of 10 digits w/ `)`"
+    );
+
+    assert_eq!(
+        to_html("000000001. ok"),
+        "<ol>\n<li>ok</li>\n</ol>",
+        "should support 9 digits including leading `0`s"
+    );
+
+    assert_eq!(
+        to_html("0000000001. not ok"),
+        "<p>0000000001. not ok</p>",
+        "should not support 10 digits including leading `0`s"
+    );
+
+    assert_eq!(
+        to_html("1. a\n1234567890. b"),
+        "<ol>\n<li>a\n1234567890. b</li>\n</ol>",
+        "should not support a 10 digit item after an item (lazy paragraph continuation)"
+    );
+
+    assert_eq!(
+        to_html("a\n1234567890. b"),
+        "<p>a\n1234567890. b</p>",
+        "should not support a 10 digit item after a paragraph"
+    );
+
     assert_eq!(
         to_html("0. ok"),
         "<ol start=\"0\">\n<li>ok</li>\n</ol>",
@@ -174,6 +210,48 @@ fn list() -> Result<(), String> {
         "should support blank first lines (2)"
     );
 
+    assert_eq!(
+        to_html("-     \n      a"),
+        "<ul>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n</ul>",
+        "should use the marker and one space as the indent after a blank first line, regardless of trailing whitespace"
+    );
+
+    assert_eq!(
+        to_html("-\n     a"),
+        "<ul>\n<li>a</li>\n</ul>",
+        "should not support indented code w/ 3 extra spaces after a blank first line"
+    );
+
+    assert_eq!(
+        to_html("-\n       a"),
+        "<ul>\n<li>\n<pre><code> a\n</code></pre>\n</li>\n</ul>",
+        "should support indented code w/ 5 extra spaces after a blank first line"
+    );
+
+    assert_eq!(
+        to_html("-\n a"),
+        "<ul>\n<li></li>\n</ul>\n<p>a</p>",
+        "should not support content indented less than the marker and one space after a blank first line"
+    );
+
+    assert_eq!(
+        to_html("1.\n      a\n\n10)\n     b"),
+        "<ol>\n<li>a</li>\n</ol>\n<ol start=\"10\">\n<li>b</li>\n</ol>",
+        "should not support indented code w/ 3 extra spaces after a blank first line (ordered)"
+    );
+
+    assert_eq!(
+        to_html("1.\n       a\n\n10)\n        b"),
+        "<ol>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n</ol>\n<ol start=\"10\">\n<li>\n<pre><code>b\n</code></pre>\n</li>\n</ol>",
+        "should support indented code w/ 4 extra spaces after a blank first line (ordered)"
+    );
+
+    assert_eq!(
+        to_html("- \n  a\n\n      b"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>b\n</code></pre>\n</li>\n</ul>",
+        "should support indented code later in an item w/ a blank first line"
+    );
+
     assert_eq!(
         to_html("-\n\n  foo"),
         "<ul>\n<li></li>\n</ul>\n<p>foo</p>",
@@ -329,6 +407,60 @@ fn list() -> Result<(), String> {
         "should support a new list by changing the marker (ordered)"
     );
 
+    assert_eq!(
+        to_html("- a\n* b"),
+        "<ul>\n<li>a</li>\n</ul>\n<ul>\n<li>b</li>\n</ul>",
+        "should support a new list by changing the marker (`-` to `*`)"
+    );
+
+    assert_eq!(
+        to_html("* a\n+ b"),
+        "<ul>\n<li>a</li>\n</ul>\n<ul>\n<li>b</li>\n</ul>",
+        "should support a new list by changing the marker (`*` to `+`)"
+    );
+
+    assert_eq!(
+        to_html("- a\n+ b\n* c"),
+        "<ul>\n<li>a</li>\n</ul>\n<ul>\n<li>b</li>\n</ul>\n<ul>\n<li>c</li>\n</ul>",
+        "should support a new list for each change of the marker"
+    );
+
+    assert_eq!(
+        to_html("1. a\n2) b"),
+        "<ol>\n<li>a</li>\n</ol>\n<ol start=\"2\">\n<li>b</li>\n</ol>",
+        "should support a new list by changing the delimiter (`.` to `)`)"
+    );
+
+    assert_eq!(
+        to_html("1) a\n2. b"),
+        "<ol>\n<li>a</li>\n</ol>\n<ol start=\"2\">\n<li>b</li>\n</ol>",
+        "should support a new list by changing the delimiter (`)` to `.`)"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n* b"),
+        "<ul>\n<li>a</li>\n</ul>\n<ul>\n<li>b</li>\n</ul>",
+        "should support a new list by changing the marker after a blank line"
+    );
+
+    assert_eq!(
+        to_html("- a\n1. b"),
+        "<ul>\n<li>a</li>\n</ul>\n<ol>\n<li>b</li>\n</ol>",
+        "should support a new list by changing from unordered to ordered"
+    );
+
+    assert_eq!(
+        to_html("1. a\n- b"),
+        "<ol>\n<li>a</li>\n</ol>\n<ul>\n<li>b</li>\n</ul>",
+        "should support a new list by changing from ordered to unordered"
+    );
+
+    assert_eq!(
+        to_html("- a\n  * b"),
+        "<ul>\n<li>a\n<ul>\n<li>b</li>\n</ul>\n</li>\n</ul>",
+        "should support a nested list with a different marker"
+    );
+
     assert_eq!(
         to_html("Foo\n- bar\n- baz"),
         "<p>Foo</p>\n<ul>\n<li>bar</li>\n<li>baz</li>\n</ul>",
@@ -371,6 +503,24 @@ fn list() -> Result<(), String> {
         "should support blank lines between items (2)"
     );
 
+    assert_eq!(
+        to_html("- a\n\n\n"),
+        "<ul>\n<li>a</li>\n</ul>\n",
+        "should not make a list loose w/ final blank lines"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n\n\nc"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<p>c</p>",
+        "should not make a list loose w/ blank lines after the last item"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n\n  b"),
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "should make a list loose w/ blank lines followed by more content in an item"
+    );
+
     assert_eq!(
         to_html_with_options("- foo\n- bar\n\n<!-- -->\n\n- baz\n- bim", &danger)?,
         "<ul>\n<li>foo</li>\n<li>bar</li>\n</ul>\n<!-- -->\n<ul>\n<li>baz</li>\n<li>bim</li>\n</ul>",
@@ -571,6 +721,106 @@ fn list() -> Result<(), String> {
         "should support the common list breaking comment method"
     );
 
+    assert_eq!(
+        to_html("- ```\n- b"),
+        "<ul>\n<li>\n<pre><code></code></pre>\n</li>\n<li>b</li>\n</ul>",
+        "should support an unclosed, empty fenced code in an item before the next item"
+    );
+
+    assert_eq!(
+        to_html("- ```\n  a\n- b"),
+        "<ul>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n<li>b</li>\n</ul>",
+        "should support unclosed fenced code in an item before the next item"
+    );
+
+    assert_eq!(
+        to_html("1. a\n2. ```\n3. b"),
+        "<ol>\n<li>a</li>\n<li>\n<pre><code></code></pre>\n</li>\n<li>b</li>\n</ol>",
+        "should support unclosed fenced code in a middle item"
+    );
+
+    assert_eq!(
+        to_html("- ```\n\n- b"),
+        "<ul>\n<li>\n<pre><code>\n</code></pre>\n</li>\n<li>b</li>\n</ul>",
+        "should support unclosed fenced code w/ a blank line in an item before the next item"
+    );
+
+    assert_eq!(
+        to_html("> ```\n> a\n- b"),
+        "<blockquote>\n<pre><code>a\n</code></pre>\n</blockquote>\n<ul>\n<li>b</li>\n</ul>",
+        "should support unclosed fenced code in a block quote before a list"
+    );
+
+    assert_eq!(
+        to_html("- a\n  - b\n\n    c\n- d"),
+        "<ul>\n<li>a\n<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ul>\n</li>\n<li>d</li>\n</ul>",
+        "should support a loose nested item in a tight item"
+    );
+
+    assert_eq!(
+        to_html("-\n\n\n"),
+        "<ul>\n<li></li>\n</ul>\n",
+        "should support an empty item followed by blank lines"
+    );
+
+    assert_eq!(
+        to_html("-\n\n-"),
+        "<ul>\n<li></li>\n<li></li>\n</ul>",
+        "should support empty items separated by a blank line"
+    );
+
+    assert_eq!(
+        to_html("1.\n\n\n2.\n3."),
+        "<ol>\n<li></li>\n<li></li>\n<li></li>\n</ol>",
+        "should support empty ordered items separated by several blank lines"
+    );
+
+    assert_eq!(
+        to_html("-\n\n  a"),
+        "<ul>\n<li></li>\n</ul>\n<p>a</p>",
+        "should not support content after a blank line in an empty item"
+    );
+
+    assert_eq!(
+        to_html("- a\n-\n\n- b"),
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li></li>\n<li>\n<p>b</p>\n</li>\n</ul>",
+        "should support a blank line after an empty item making a list loose"
+    );
+
+    assert_eq!(
+        to_html("- a\n-\n- b"),
+        "<ul>\n<li>a</li>\n<li></li>\n<li>b</li>\n</ul>",
+        "should support an empty item in a tight list"
+    );
+
+    assert_eq!(
+        to_mdast("-\n\n-", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::List(List {
+                ordered: false,
+                spread: true,
+                start: None,
+                children: vec![
+                    Node::ListItem(ListItem {
+                        spread: false,
+                        checked: None,
+                        children: vec![],
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::ListItem(ListItem {
+                        spread: false,
+                        checked: None,
+                        children: vec![],
+                        position: Some(Position::new(3, 1, 3, 3, 2, 4))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 3, 2, 4))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 2, 4))
+        }),
+        "should support empty items separated by a blank line as a spread `List` in mdast"
+    );
+
     assert_eq!(
         to_html_with_options(
             "- one\n\n two",
@@ -589,6 +839,56 @@ fn list() -> Result<(), String> {
         "should support turning off lists"
     );
 
+    let compact = Options {
+        compile: CompileOptions {
+            compact_list_items: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("- ```\n  a\n  ```\n- b"),
+        "<ul>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n<li>b</li>\n</ul>",
+        "should add line endings around block content in tight items by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ```\n  a\n  ```\n- b", &compact)?,
+        "<ul>\n<li><pre><code>a\n</code></pre></li>\n<li>b</li>\n</ul>",
+        "should not add line endings around block content in tight items w/ `compact_list_items`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- b", &compact)?,
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>",
+        "should not change paragraphs in tight items w/ `compact_list_items`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n  - b\n- c", &compact)?,
+        "<ul>\n<li>a\n<ul>\n<li>b</li>\n</ul></li>\n<li>c</li>\n</ul>",
+        "should keep line endings between children in tight items w/ `compact_list_items`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- - a\n  - b\n- c", &compact)?,
+        "<ul>\n<li><ul>\n<li>a</li>\n<li>b</li>\n</ul></li>\n<li>c</li>\n</ul>",
+        "should support nested tight lists w/ `compact_list_items`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n- b", &compact)?,
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n</ul>",
+        "should not change loose items w/ `compact_list_items`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n- > b\n  - c\n  - ```\n    d\n    ```", &compact)?,
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<blockquote>\n<p>b</p>\n</blockquote>\n<ul>\n<li>c</li>\n<li><pre><code>d\n</code></pre></li>\n</ul>\n</li>\n</ul>",
+        "should support tight lists in loose lists w/ `compact_list_items`"
+    );
+
     assert_eq!(
         to_mdast("* a", &Default::default())?,
         Node::Root(Root {
@@ -704,5 +1004,174 @@ fn list() -> Result<(), String> {
         "should support `spread` fields on `List`, `ListItem`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("- a\n* b", &Default::default())?,
+        Node::Root(Root {
+            children: vec![
+                Node::List(List {
+                    ordered: false,
+                    spread: false,
+                    start: None,
+                    children: vec![Node::ListItem(ListItem {
+                        checked: None,
+                        spread: false,
+                        children: vec![Node::Paragraph(Paragraph {
+                            children: vec![Node::Text(Text {
+                                value: "a".into(),
+                                position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                            }),],
+                            position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        })],
+                        position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                }),
+                Node::List(List {
+                    ordered: false,
+                    spread: false,
+                    start: None,
+                    children: vec![Node::ListItem(ListItem {
+                        checked: None,
+                        spread: false,
+                        children: vec![Node::Paragraph(Paragraph {
+                            children: vec![Node::Text(Text {
+                                value: "b".into(),
+                                position: Some(Position::new(2, 3, 6, 2, 4, 7))
+                            }),],
+                            position: Some(Position::new(2, 3, 6, 2, 4, 7))
+                        })],
+                        position: Some(Position::new(2, 1, 4, 2, 4, 7))
+                    })],
+                    position: Some(Position::new(2, 1, 4, 2, 4, 7))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 2, 4, 7))
+        }),
+        "should support adjacent `List`s when changing the marker in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_alpha() -> Result<(), String> {
+    let alpha = Options {
+        parse: ParseOptions {
+            alpha_ordered_lists: true,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a. b\ni. c"),
+        "<p>a. b\ni. c</p>",
+        "should not support letters or roman numerals by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a. b\nb. c", &alpha)?,
+        "<ol type=\"a\">\n<li>b</li>\n<li>c</li>\n</ol>",
+        "should support lowercase letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("A) b\nB) c", &alpha)?,
+        "<ol type=\"A\">\n<li>b</li>\n<li>c</li>\n</ol>",
+        "should support uppercase letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("c. d", &alpha)?,
+        "<ol type=\"a\" start=\"3\">\n<li>d</li>\n</ol>",
+        "should support a start for letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("i. a\nii. b\niii. c\niv. d", &alpha)?,
+        "<ol type=\"i\">\n<li>a</li>\n<li>b</li>\n<li>c</li>\n<li>d</li>\n</ol>",
+        "should support lowercase roman numerals"
+    );
+
+    assert_eq!(
+        to_html_with_options("XIV. a", &alpha)?,
+        "<ol type=\"I\" start=\"14\">\n<li>a</li>\n</ol>",
+        "should support uppercase roman numerals, and a start for them"
+    );
+
+    assert_eq!(
+        to_html_with_options("h. a\ni. b\nj. c", &alpha)?,
+        "<ol type=\"a\" start=\"8\">\n<li>a</li>\n<li>b</li>\n<li>c</li>\n</ol>",
+        "should support `i` as a letter in lists of letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("iv. a\nv. b", &alpha)?,
+        "<ol type=\"i\" start=\"4\">\n<li>a</li>\n<li>b</li>\n</ol>",
+        "should support `v` as a roman numeral in lists of roman numerals"
+    );
+
+    assert_eq!(
+        to_html_with_options("a. b\nA. c", &alpha)?,
+        "<ol type=\"a\">\n<li>b</li>\n</ol>\n<ol type=\"A\">\n<li>c</li>\n</ol>",
+        "should not support mixed case in a list"
+    );
+
+    assert_eq!(
+        to_html_with_options("1. a\na. b", &alpha)?,
+        "<ol>\n<li>a</li>\n</ol>\n<ol type=\"a\">\n<li>b</li>\n</ol>",
+        "should not support decimals and letters in a list"
+    );
+
+    assert_eq!(
+        to_html_with_options("ab. c\niI. d\na.b", &alpha)?,
+        "<p>ab. c\niI. d\na.b</p>",
+        "should not support several letters that are not roman numerals of the same case, or a missing space"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\na. b\n\nc\nI. d", &alpha)?,
+        "<p>a</p>\n<ol type=\"a\">\n<li>b</li>\n</ol>\n<p>c</p>\n<ol type=\"I\">\n<li>d</li>\n</ol>",
+        "should support `a.`, `A.`, `i.`, and `I.` interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb. c\nii. d", &alpha)?,
+        "<p>a\nb. c\nii. d</p>",
+        "should not support other values interrupting paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n\n  b. c", &alpha)?,
+        "<ul>\n<li>\n<p>a</p>\n<ol type=\"a\" start=\"2\">\n<li>c</li>\n</ol>\n</li>\n</ul>",
+        "should support letters in nested lists"
+    );
+
+    assert_eq!(
+        to_mdast("iv. a", &alpha.parse)?,
+        Node::Root(Root {
+            children: vec![Node::List(List {
+                ordered: true,
+                spread: false,
+                start: Some(4),
+                children: vec![Node::ListItem(ListItem {
+                    checked: None,
+                    spread: false,
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                        }),],
+                        position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 6, 5))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 6, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 5))
+        }),
+        "should support roman numerals as `start` in mdast"
+    );
+
     Ok(())
 }
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Math, Node, Root},
     to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -255,6 +255,27 @@ fn math_flow() -> Result<(), String> {
         "should not support lazyness (3)"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "$$\na\n$$",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        math_flow: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    trim_code_trailing_newline: true,
+                    ..Default::default()
+                }
+            }
+        )?,
+        "<pre><code class=\"language-math math-display\">a</code></pre>",
+        "should support removing the final line ending (`trim_code_trailing_newline`)"
+    );
+
     assert_eq!(
         to_mdast("$$extra\nabc\ndef\n$$", &math.parse)?,
         Node::Root(Root {
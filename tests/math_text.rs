@@ -207,6 +207,74 @@ fn math_text() -> Result<(), String> {
         "should support an escaped initial dollar"
     );
 
+    let strict = Options::math();
+
+    assert_eq!(
+        to_html_with_options("$20 and $30", &math)?,
+        "<p><code class=\"language-math math-inline\">20 and </code>30</p>",
+        "should support dollar amounts as math (text) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("$20 and $30", &strict)?,
+        "<p>$20 and $30</p>",
+        "should not support dollar amounts as math (text), w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$x^2$", &strict)?,
+        "<p><code class=\"language-math math-inline\">x^2</code></p>",
+        "should support math (text) w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$ a$", &strict)?,
+        "<p>$ a$</p>",
+        "should not support an opening dollar followed by whitespace, w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$\na$", &strict)?,
+        "<p>$\na$</p>",
+        "should not support an opening dollar followed by a line ending, w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$a $", &strict)?,
+        "<p>$a $</p>",
+        "should not support a closing dollar preceded by whitespace, w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$a\n$", &strict)?,
+        "<p>$a\n$</p>",
+        "should not support a closing dollar preceded by a line ending, w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$a$1", &strict)?,
+        "<p>$a$1</p>",
+        "should not support a closing dollar followed by a digit, w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$a $b$", &strict)?,
+        "<p><code class=\"language-math math-inline\">a $b</code></p>",
+        "should support a later closing dollar, w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$a\nb$", &strict)?,
+        "<p><code class=\"language-math math-inline\">a b</code></p>",
+        "should support line endings in math (text), w/ `math_text_single_dollar_strict`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$ a $$1", &strict)?,
+        "<p><code class=\"language-math math-inline\">a</code>1</p>",
+        "should not affect double dollars, w/ `math_text_single_dollar_strict`"
+    );
+
     assert_eq!(
         to_mdast("a $alpha$ b.", &math.parse)?,
         Node::Root(Root {
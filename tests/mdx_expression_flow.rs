@@ -1,6 +1,6 @@
 mod test_utils;
 use markdown::{
-    mdast::{MdxFlowExpression, Node, Root},
+    mdast::{BlockQuote, MdxFlowExpression, Node, Root},
     to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -27,6 +27,12 @@ fn mdx_expression_flow_agnostic() -> Result<(), String> {
         "should support an empty expression"
     );
 
+    assert_eq!(
+        to_html_with_options("{a {b} c}\nd", &mdx)?,
+        "<p>d</p>",
+        "should support nested braces"
+    );
+
     // Note: in MDX, indented code is turned off:
     assert_eq!(
         to_html_with_options(
@@ -141,6 +147,22 @@ fn mdx_expression_flow_agnostic() -> Result<(), String> {
         "should support mdx expressions (flow) as `MdxFlowExpression`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("> {a\n>\t\tb}", &mdx.parse)?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::MdxFlowExpression(MdxFlowExpression {
+                    value: "a\n\tb".into(),
+                    position: Some(Position::new(1, 3, 2, 2, 11, 10)),
+                    stops: vec![(0, 3), (1, 4), (2, 7)]
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 11, 10))
+        }),
+        "should keep partially consumed tabs as tabs, so that stops map to the source"
+    );
+
     Ok(())
 }
 
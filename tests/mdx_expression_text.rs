@@ -192,6 +192,44 @@ fn mdx_expression_text_agnostic() -> Result<(), String> {
         "should support just a closing brace"
     );
 
+    assert_eq!(
+        to_mdast("a {b {c} d} e", &mdx.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::MdxTextExpression(MdxTextExpression {
+                        value: "b {c} d".into(),
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11)),
+                        stops: vec![(0, 3)]
+                    }),
+                    Node::Text(Text {
+                        value: " e".into(),
+                        position: Some(Position::new(1, 12, 11, 1, 14, 13))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 14, 13))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 14, 13))
+        }),
+        "should support nested braces"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {\"}\"} b", &mdx)?,
+        "<p>a &quot;} b</p>",
+        "should count braces in strings (agnostic)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {b\n\nc} d", &mdx).err().unwrap(),
+        "1:5: Unexpected end of file in expression, expected a corresponding closing brace for `{`",
+        "should crash on a blank line in an expression"
+    );
+
     assert_eq!(
         to_html_with_options("{ a } b", &mdx)?,
         "<p> b</p>",
@@ -1,5 +1,8 @@
 use markdown::{
-    mdast::{List, ListItem, MdxJsxFlowElement, Node, Paragraph, Root, Text},
+    mdast::{
+        AttributeContent, AttributeValue, List, ListItem, MdxJsxAttribute, MdxJsxFlowElement, Node,
+        Paragraph, Root, Text,
+    },
     to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -82,6 +85,41 @@ fn mdx_jsx_flow_agnostic() -> Result<(), String> {
         "should support attributes"
     );
 
+    assert_eq!(
+        to_html_with_options("<Box>\n  content\n</Box>", &mdx)?,
+        "<p>content</p>\n",
+        "should support an element w/ indented content"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n*a*\n</div>", &mdx)?,
+        "<p><em>a</em></p>\n",
+        "should support HTML as JSX, as HTML is turned off in MDX"
+    );
+
+    assert_eq!(
+        to_mdast("<Box id=\"a\">\n  b\n</Box>", &mdx.parse)?,
+        Node::Root(Root {
+            children: vec![Node::MdxJsxFlowElement(MdxJsxFlowElement {
+                name: Some("Box".into()),
+                attributes: vec![AttributeContent::Property(MdxJsxAttribute {
+                    name: "id".into(),
+                    value: Some(AttributeValue::Literal("a".into())),
+                })],
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(2, 3, 15, 2, 4, 16))
+                    })],
+                    position: Some(Position::new(2, 1, 13, 2, 4, 16))
+                })],
+                position: Some(Position::new(1, 1, 0, 3, 7, 23))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 7, 23))
+        }),
+        "should support an element w/ attributes and content as `MdxJsxFlowElement`s in mdast"
+    );
+
     Ok(())
 }
 
@@ -48,6 +48,18 @@ fn mdx_jsx_text_core() -> Result<(), String> {
         "should support markdown inside elements"
     );
 
+    assert_eq!(
+        to_html_with_options("a <Component prop=\"x\" /> b", &mdx)?,
+        "<p>a  b</p>",
+        "should support a self-closing element w/ an attribute"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <i>b</i> c", &mdx)?,
+        "<p>a b c</p>",
+        "should support HTML as JSX, as HTML is turned off in MDX"
+    );
+
     assert_eq!(
         to_mdast("a <b /> c.", &mdx.parse)?,
         Node::Root(Root {
@@ -0,0 +1,99 @@
+use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn auto_dir() -> Result<(), String> {
+    let auto_dir = Options {
+        compile: CompileOptions {
+            auto_dir: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("مرحبا"),
+        "<p>مرحبا</p>",
+        "should not add `dir` by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("مرحبا بالعالم", &auto_dir)?,
+        "<p dir=\"rtl\">مرحبا بالعالم</p>",
+        "should support Arabic in paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("שלום עולם", &auto_dir)?,
+        "<p dir=\"rtl\">שלום עולם</p>",
+        "should support Hebrew in paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("Hello, world", &auto_dir)?,
+        "<p dir=\"ltr\">Hello, world</p>",
+        "should support Latin in paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("Hello, שלום", &auto_dir)?,
+        "<p dir=\"ltr\">Hello, שלום</p>",
+        "should use the first letter (Latin first)"
+    );
+
+    assert_eq!(
+        to_html_with_options("שלום, hello", &auto_dir)?,
+        "<p dir=\"rtl\">שלום, hello</p>",
+        "should use the first letter (Hebrew first)"
+    );
+
+    assert_eq!(
+        to_html_with_options("2024: مرحبا!", &auto_dir)?,
+        "<p dir=\"rtl\">2024: مرحبا!</p>",
+        "should skip digits and punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("123 !?", &auto_dir)?,
+        "<p>123 !?</p>",
+        "should not add `dir` w/o letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("*שלום* `code`", &auto_dir)?,
+        "<p dir=\"rtl\"><em>שלום</em> <code>code</code></p>",
+        "should look into phrasing content"
+    );
+
+    assert_eq!(
+        to_html_with_options("[](https://example.com) مرحبا", &auto_dir)?,
+        "<p dir=\"rtl\"><a href=\"https://example.com\"></a> مرحبا</p>",
+        "should ignore destinations"
+    );
+
+    assert_eq!(
+        to_html_with_options("# مرحبا\n\nHello\n=====", &auto_dir)?,
+        "<h1 dir=\"rtl\">مرحبا</h1>\n<h1 dir=\"ltr\">Hello</h1>",
+        "should support headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("- שלום\n- hello", &auto_dir)?,
+        "<ul>\n<li dir=\"rtl\">שלום</li>\n<li dir=\"ltr\">hello</li>\n</ul>",
+        "should support list items (tight)"
+    );
+
+    assert_eq!(
+        to_html_with_options("1. שלום\n\n   hello", &auto_dir)?,
+        "<ol>\n<li dir=\"rtl\">\n<p dir=\"rtl\">שלום</p>\n<p dir=\"ltr\">hello</p>\n</li>\n</ol>",
+        "should support list items (loose)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> שלום\n\n```\nhello\n```", &auto_dir)?,
+        "<blockquote>\n<p dir=\"rtl\">שלום</p>\n</blockquote>\n<pre><code>hello\n</code></pre>",
+        "should not add `dir` to other elements"
+    );
+
+    Ok(())
+}
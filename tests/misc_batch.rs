@@ -0,0 +1,117 @@
+mod test_utils;
+use markdown::{
+    to_html, to_html_batch, to_html_with_options, CompileOptions, Constructs, Options,
+    ParseOptions, TextTransform,
+};
+use pretty_assertions::assert_eq;
+use test_utils::swc::{parse_esm, parse_expression};
+
+#[test]
+fn batch() -> Result<(), String> {
+    let values = [
+        "# a",
+        "*b* and **c**",
+        "[d]\n\n[d]: e",
+        "- f\n- g",
+        "<i>h</i>",
+        "",
+    ];
+
+    assert_eq!(
+        to_html_batch(&[], &Options::default())?,
+        Vec::<String>::new(),
+        "should support no documents"
+    );
+
+    assert_eq!(
+        to_html_batch(&values, &Options::default())?,
+        values.iter().map(|d| to_html(d)).collect::<Vec<_>>(),
+        "should compile like `to_html`, in order"
+    );
+
+    assert_eq!(
+        to_html_batch(&["[a]: b", "[a]"], &Options::default())?,
+        vec!["", "<p>[a]</p>"],
+        "should not share definitions between documents"
+    );
+
+    let values = [
+        "# a\n\n> - b\n>   *c* [d][]\n\n[d]: e\n\n```f\ng\n```",
+        "h",
+        "",
+        "- i\n\n  j",
+        "k",
+    ];
+
+    assert_eq!(
+        to_html_batch(&values, &Options::default())?,
+        values.iter().map(|d| to_html(d)).collect::<Vec<_>>(),
+        "should compile each document on its own when reusing memory between documents"
+    );
+
+    let values = ["~a~ www.b.com", "| c |\n| - |\n| d |", "[^e]\n\n[^e]: f"];
+
+    assert_eq!(
+        to_html_batch(&values, &Options::gfm())?,
+        values
+            .iter()
+            .map(|d| to_html_with_options(d, &Options::gfm()))
+            .collect::<Result<Vec<_>, _>>()?,
+        "should support parse and compile options"
+    );
+
+    let danger = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_batch(&["<i>a</i>", "<b>"], &danger)?,
+        vec!["<p><i>a</i></p>", "<b>"],
+        "should support compile options"
+    );
+
+    let transform = Options {
+        compile: CompileOptions {
+            text_transform: Some(TextTransform::new(|d| d.to_uppercase())),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_batch(&["a *b*", "`c` d"], &transform)?,
+        vec!["<p>A <em>B</em></p>", "<p><code>c</code> D</p>"],
+        "should support compile functions"
+    );
+
+    let swc = Options {
+        parse: ParseOptions {
+            constructs: Constructs::mdx(),
+            mdx_esm_parse: Some(Box::new(parse_esm)),
+            mdx_expression_parse: Some(Box::new(parse_expression)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_batch(&["import a from 'b'\n\nc", "{1 + 1} d"], &swc)?,
+        vec!["<p>c</p>", "<p> d</p>"],
+        "should support MDX parse functions"
+    );
+
+    assert_eq!(
+        to_html_batch(&["a", "{b", "<c"], &Options {
+            parse: ParseOptions::mdx(),
+            ..Default::default()
+        }),
+        Err("1:3: Unexpected end of file in expression, expected a corresponding closing brace for `{`".into()),
+        "should return the first error"
+    );
+
+    Ok(())
+}
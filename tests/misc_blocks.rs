@@ -0,0 +1,81 @@
+use markdown::{
+    mdast::{Definition, Heading, List, ListItem, Node, Paragraph, Root, Text},
+    to_mdast, to_mdast_blocks,
+    unist::Position,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn blocks() -> Result<(), String> {
+    assert_eq!(
+        to_mdast_blocks("# a *b* `c`", &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 1,
+                children: vec![Node::Text(Text {
+                    value: "a *b* `c`".into(),
+                    position: Some(Position::new(1, 3, 2, 1, 12, 11))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 12, 11))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 12, 11))
+        }),
+        "should leave attention and code (text) in headings literal"
+    );
+
+    assert_eq!(
+        to_mdast_blocks("* [a](b) &amp; <c>", &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::List(List {
+                ordered: false,
+                spread: false,
+                start: None,
+                children: vec![Node::ListItem(ListItem {
+                    checked: None,
+                    spread: false,
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "[a](b) &amp; <c>".into(),
+                            position: Some(Position::new(1, 3, 2, 1, 19, 18))
+                        })],
+                        position: Some(Position::new(1, 3, 2, 1, 19, 18))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 19, 18))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 19, 18))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 19, 18))
+        }),
+        "should leave links, character references, and html in paragraphs literal"
+    );
+
+    assert_eq!(
+        to_mdast_blocks("[a]: b \"c\"", &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::Definition(Definition {
+                url: "b".into(),
+                identifier: "a".into(),
+                label: Some("a".into()),
+                title: Some("c".into()),
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should still parse definitions"
+    );
+
+    assert_eq!(
+        to_mdast_blocks("a\n===\n\n> b\n\n- c", &ParseOptions::gfm())?
+            .children()
+            .unwrap()
+            .len(),
+        to_mdast("a\n===\n\n> b\n\n- c", &ParseOptions::gfm())?
+            .children()
+            .unwrap()
+            .len(),
+        "should produce the same blocks as a full parse"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn breaks() -> Result<(), String> {
+    let breaks = Options {
+        compile: CompileOptions {
+            breaks: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a\nb"),
+        "<p>a\nb</p>",
+        "should not support breaks by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb", &breaks)?,
+        "<p>a<br />\nb</p>",
+        "should support a soft break as a hard break"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\nc", &breaks)?,
+        "<p>a<br />\nb<br />\nc</p>",
+        "should support several soft breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\r\nb", &breaks)?,
+        "<p>a<br />\r\nb</p>",
+        "should support a carriage return + line feed as one break"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\rb", &breaks)?,
+        "<p>a<br />\rb</p>",
+        "should support a carriage return"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \n  b", &breaks)?,
+        "<p>a<br />\nb</p>",
+        "should drop whitespace around breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \nb", &breaks)?,
+        "<p>a<br />\nb</p>",
+        "should not double breaks after hard break (trailing)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\\\nb", &breaks)?,
+        "<p>a<br />\nb</p>",
+        "should not double breaks after hard break (escape)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n", &breaks)?,
+        "<p>a<br />\nb</p>\n",
+        "should not support a break at the end of a paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\nb", &breaks)?,
+        "<p>a</p>\n<p>b</p>",
+        "should not support breaks between blocks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n=", &breaks)?,
+        "<h1>a<br />\nb</h1>",
+        "should support breaks in headings (setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a\nb*", &breaks)?,
+        "<p><em>a<br />\nb</em></p>",
+        "should support breaks in attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a\nb](c)", &breaks)?,
+        "<p><a href=\"c\">a<br />\nb</a></p>",
+        "should support breaks in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a\nb](c)", &breaks)?,
+        "<p><img src=\"c\" alt=\"a\nb\" /></p>",
+        "should not support breaks in image text"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b\n'c\nd')", &breaks)?,
+        "<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should not support breaks in titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("`a\nb`", &breaks)?,
+        "<p><code>a b</code></p>",
+        "should not support breaks in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<a\nb>", &breaks)?,
+        "<p>&lt;a\nb&gt;</p>",
+        "should not support breaks in HTML (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\nb\n```", &breaks)?,
+        "<pre><code>a\nb\n</code></pre>",
+        "should not support breaks in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n    b", &breaks)?,
+        "<pre><code>a\nb\n</code></pre>",
+        "should not support breaks in code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\nb", &breaks)?,
+        "<blockquote>\n<p>a<br />\nb</p>\n</blockquote>",
+        "should support breaks in lazy lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n  b", &breaks)?,
+        "<ul>\n<li>a<br />\nb</li>\n</ul>",
+        "should support breaks in tight list items"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\nb",
+            &Options {
+                compile: CompileOptions {
+                    breaks: true,
+                    hard_break_markup: Some("<br>".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a<br>\nb</p>",
+        "should support `hard_break_markup`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> a\n> b\n> — c",
+            &Options {
+                compile: CompileOptions {
+                    breaks: true,
+                    block_quote_citation: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<blockquote>\n<p>a<br />\nb</p>\n<footer>— c</footer>\n</blockquote>",
+        "should not support a break before a citation"
+    );
+
+    Ok(())
+}
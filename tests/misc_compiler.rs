@@ -0,0 +1,68 @@
+use markdown::{to_html_with_options, CompileOptions, Compiler, MdxSignal, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn compiler() -> Result<(), String> {
+    let options = Options::gfm();
+    let mut compiler = Compiler::new();
+
+    for value in [
+        "# a",
+        "",
+        &"- b *c*\n\n  [d]\n\n[d]: e\n\n".repeat(1000),
+        "f",
+        "g[^h]\n\n[^h]: i",
+        "\u{1}\r\nj",
+        "k\r\n\r\n| l |\n| - |",
+    ] {
+        assert_eq!(
+            compiler.render(value, &options)?,
+            to_html_with_options(value, &options)?,
+            "should be the same as `to_html_with_options` ({:?})",
+            value
+        );
+    }
+
+    assert_eq!(
+        compiler.render(
+            "<x>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<x>",
+        "should support different options for each document"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn compiler_mdx() -> Result<(), String> {
+    let options = Options {
+        parse: ParseOptions {
+            mdx_expression_parse: Some(Box::new(|_value, _kind| MdxSignal::Error("a".into(), 1))),
+            ..ParseOptions::mdx()
+        },
+        ..Options::default()
+    };
+    let mut compiler = Compiler::new();
+
+    assert_eq!(
+        compiler.render("{d}", &options),
+        Err("1:3: a".into()),
+        "should crash on MDX errors"
+    );
+
+    assert_eq!(
+        compiler.render("*e*", &Options::default())?,
+        "<p><em>e</em></p>",
+        "should work after a crash"
+    );
+
+    Ok(())
+}
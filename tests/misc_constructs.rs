@@ -0,0 +1,111 @@
+use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn constructs() -> Result<(), String> {
+    let chat = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                autolink: false,
+                code_indented: false,
+                html_flow: false,
+                html_text: false,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+    let no_headings_or_breaks = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                heading_atx: false,
+                heading_setext: false,
+                thematic_break: false,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("# hi\n\n    a\n\n<https://example.com>"),
+        "<h1>hi</h1>\n<pre><code>a\n</code></pre>\n<p><a href=\"https://example.com\">https://example.com</a></p>",
+        "should support all CommonMark constructs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# hi", &no_headings_or_breaks)?,
+        "<p># hi</p>",
+        "should support turning off heading (atx)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n===\nb\n---", &no_headings_or_breaks)?,
+        "<p>a\n===\nb\n---</p>",
+        "should support turning off heading (setext), w/o thematic breaks taking over"
+    );
+
+    assert_eq!(
+        to_html_with_options("***", &no_headings_or_breaks)?,
+        "<p>***</p>",
+        "should support turning off thematic breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("* * *", &no_headings_or_breaks)?,
+        "<ul>\n<li>\n<ul>\n<li>\n<ul>\n<li></li>\n</ul>\n</li>\n</ul>\n</li>\n</ul>",
+        "should fall through to other constructs when thematic breaks are off"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a", &chat)?,
+        "<p>a</p>",
+        "should support a reduced dialect (code (indented) as a paragraph)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n    b", &chat)?,
+        "<blockquote>\n<p>a\nb</p>\n</blockquote>",
+        "should support a reduced dialect (indented lazy lines)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n      b", &chat)?,
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "should support a reduced dialect (indented content in list items)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n*a*\n</div>", &chat)?,
+        "<p>&lt;div&gt;\n<em>a</em>\n&lt;/div&gt;</p>",
+        "should support a reduced dialect (HTML (flow) as a paragraph)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <b>c</b> d", &chat)?,
+        "<p>a &lt;b&gt;c&lt;/b&gt; d</p>",
+        "should support a reduced dialect (HTML (text) as data)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://example.com>", &chat)?,
+        "<p>&lt;https://example.com&gt;</p>",
+        "should support a reduced dialect (autolinks as data)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n<div>\n```", &chat)?,
+        "<pre><code>&lt;div&gt;\n</code></pre>",
+        "should support a reduced dialect (code (fenced) still on)"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a* [b](c) `d`", &chat)?,
+        "<p><em>a</em> <a href=\"c\">b</a> <code>d</code></p>",
+        "should support a reduced dialect (attention, links, code (text) still on)"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use markdown::{to_html, to_html_with_options, CompileOptions, ControlCharacterPolicy, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn control_characters() -> Result<(), String> {
+    let policy = |control_character_policy: ControlCharacterPolicy| Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            control_character_policy,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+    let replace = policy(ControlCharacterPolicy::Replace);
+    let remove = policy(ControlCharacterPolicy::Remove);
+
+    assert_eq!(
+        to_html("a\u{1}b\u{8}c\u{1F}d\u{7F}e\u{85}f"),
+        "<p>a\u{1}b\u{8}c\u{1F}d\u{7F}e\u{85}f</p>",
+        "should keep control characters by default"
+    );
+
+    assert_eq!(
+        to_html("a\u{0}b"),
+        "<p>a\u{FFFD}b</p>",
+        "should replace NUL by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\u{1}b\u{8}c\u{B}d\u{E}e\u{1F}f\u{7F}g\u{80}h\u{9F}i",
+            &replace
+        )?,
+        "<p>a\u{FFFD}b\u{FFFD}c\u{FFFD}d\u{FFFD}e\u{FFFD}f\u{FFFD}g\u{FFFD}h\u{FFFD}i</p>",
+        "should support replacing control characters"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\u{1}b\u{8}c\u{B}d\u{E}e\u{1F}f\u{7F}g\u{80}h\u{9F}i",
+            &remove
+        )?,
+        "<p>abcdefghi</p>",
+        "should support removing control characters"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\u{0}b", &remove)?,
+        "<p>a\u{FFFD}b</p>",
+        "should still replace NUL when removing control characters"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\tb\u{C}c\r\nd", &remove)?,
+        "<p>a\tb\u{C}c\r\nd</p>",
+        "should keep tabs, form feeds, and line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\u{FFFD}b\u{A0}c\u{200B}d", &remove)?,
+        "<p>a\u{FFFD}b\u{A0}c\u{200B}d</p>",
+        "should keep other characters, such as the replacement character"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a\u{2}* `b\u{3}`", &replace)?,
+        "<p><em>a\u{FFFD}</em> <code>b\u{FFFD}</code></p>",
+        "should support control characters in phrasing content"
+    );
+
+    assert_eq!(
+        to_html_with_options("<i title=\"\u{4}\">", &remove)?,
+        "<i title=\"\">",
+        "should support control characters in HTML"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use markdown::to_html;
+use markdown::{to_html, to_html_with_options, CompileOptions, Options};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -195,3 +195,131 @@ fn dangerous_protocol_link() {
         "should allow a colon in a path"
     );
 }
+
+#[test]
+fn dangerous_protocol_allowed() -> Result<(), String> {
+    let links = Options {
+        compile: CompileOptions {
+            allowed_link_protocols: Some(vec!["https".into(), "magnet".into(), "web+app".into()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let images = Options {
+        compile: CompileOptions {
+            allowed_image_protocols: Some(vec!["HTTPS".into(), "data".into()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("[a](magnet:?xt=b) [c](web+app:d)", &links)?,
+        "<p><a href=\"magnet:?xt=b\">a</a> <a href=\"web+app:d\">c</a></p>",
+        "should support `allowed_link_protocols`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<magnet:?xt=a> <web+app:b>", &links)?,
+        "<p><a href=\"magnet:?xt=a\">magnet:?xt=a</a> <a href=\"web+app:b\">web+app:b</a></p>",
+        "should support `allowed_link_protocols` in autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a][b]\n\n[b]: magnet:?xt=c", &links)?,
+        "<p><a href=\"magnet:?xt=c\">a</a></p>\n",
+        "should support `allowed_link_protocols` in references"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](javascript:alert(1)) <javascript:alert(1)>", &links)?,
+        "<p><a href=\"\">a</a> <a href=\"\">javascript:alert(1)</a></p>",
+        "should drop other protocols w/ `allowed_link_protocols`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](http://b) [c](mailto:d)", &links)?,
+        "<p><a href=\"\">a</a> <a href=\"\">c</a></p>",
+        "should not add the default protocols to `allowed_link_protocols`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](MAGNET:?xt=b) [c](Web+App:d)", &links)?,
+        "<p><a href=\"MAGNET:?xt=b\">a</a> <a href=\"Web+App:d\">c</a></p>",
+        "should compare protocols in URLs case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("![](https://a) ![](DATA:b) ![](data:c)", &images)?,
+        "<p><img src=\"https://a\" alt=\"\" /> <img src=\"DATA:b\" alt=\"\" /> <img src=\"data:c\" alt=\"\" /></p>",
+        "should compare allowed protocols case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("![](http://a) [b](data:c)", &images)?,
+        "<p><img src=\"\" alt=\"\" /> <a href=\"\">b</a></p>",
+        "should support `allowed_image_protocols` for images only"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](magnet:?xt=b) ![](magnet:?xt=c)", &links)?,
+        "<p><a href=\"magnet:?xt=b\">a</a> <img src=\"\" alt=\"\" /></p>",
+        "should support `allowed_link_protocols` for links only"
+    );
+
+    assert_eq!(
+        to_html("[a](//b.c/d:e) ![](//b.c/d:e)"),
+        "<p><a href=\"//b.c/d:e\">a</a> <img src=\"//b.c/d:e\" alt=\"\" /></p>",
+        "should allow protocol-relative URLs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](//b.c) ![](//b.c)",
+            &Options {
+                compile: CompileOptions {
+                    allowed_image_protocols: Some(vec![]),
+                    allowed_link_protocols: Some(vec![]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"//b.c\">a</a> <img src=\"//b.c\" alt=\"\" /></p>",
+        "should allow protocol-relative URLs w/o allowed protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](./b) [c](https://d)",
+            &Options {
+                compile: CompileOptions {
+                    allowed_link_protocols: Some(vec![]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"./b\">a</a> <a href=\"\">c</a></p>",
+        "should only allow relative URLs w/ an empty list"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](javascript:b) ![](javascript:c)",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_protocol: true,
+                    allowed_image_protocols: Some(vec!["https".into()]),
+                    allowed_link_protocols: Some(vec!["https".into()]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"javascript:b\">a</a> <img src=\"javascript:c\" alt=\"\" /></p>",
+        "should allow all protocols w/ `allow_dangerous_protocol`"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+use markdown::{
+    detect_line_ending, to_html, to_html_with_options, CompileOptions, LineEnding, Options,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -59,5 +61,121 @@ fn default_line_ending() -> Result<(), String> {
         "should support the given line ending, even if line endings exist"
     );
 
+    assert_eq!(
+        detect_line_ending(""),
+        None,
+        "should not detect a line ending in an empty document"
+    );
+
+    assert_eq!(
+        detect_line_ending("a b"),
+        None,
+        "should not detect a line ending in a document w/o line endings"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\nb\nc\n"),
+        Some(LineEnding::LineFeed),
+        "should detect line feeds"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\r\nb\r\nc\r\n"),
+        Some(LineEnding::CarriageReturnLineFeed),
+        "should detect carriage return + line feeds"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\rb\rc\r"),
+        Some(LineEnding::CarriageReturn),
+        "should detect carriage returns"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\r"),
+        Some(LineEnding::CarriageReturn),
+        "should detect a carriage return at the end"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\nb\r\nc\r\nd\r\n"),
+        Some(LineEnding::LineFeed),
+        "should detect the first line ending in mixed documents (1)"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\r\nb\nc\nd\n"),
+        Some(LineEnding::CarriageReturnLineFeed),
+        "should detect the first line ending in mixed documents (2)"
+    );
+
+    assert_eq!(
+        detect_line_ending("a\r\r\nb"),
+        Some(LineEnding::CarriageReturn),
+        "should detect the first line ending in mixed documents (3)"
+    );
+
+    assert_eq!(
+        detect_line_ending(b"a\xFF\r\nb"),
+        Some(LineEnding::CarriageReturnLineFeed),
+        "should detect line endings in bytes"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn default_line_ending_generated() -> Result<(), String> {
+    let crlf = Options {
+        compile: CompileOptions {
+            default_line_ending: LineEnding::CarriageReturnLineFeed,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let cr = Options {
+        compile: CompileOptions {
+            default_line_ending: LineEnding::CarriageReturn,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("```js"),
+        "<pre><code class=\"language-js\"></code></pre>\n",
+        "should use `\\n` after fenced code w/o line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js", &crlf)?,
+        "<pre><code class=\"language-js\"></code></pre>\r\n",
+        "should use the given line ending after fenced code w/o line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a", &cr)?,
+        "<pre><code>a\r</code></pre>",
+        "should use the given line ending in indented code w/o line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("* > a", &crlf)?,
+        "<ul>\r\n<li>\r\n<blockquote>\r\n<p>a</p>\r\n</blockquote>\r\n</li>\r\n</ul>",
+        "should use the given line ending between containers w/o line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("> * ```", &cr)?,
+        "<blockquote>\r<ul>\r<li>\r<pre><code></code></pre>\r</li>\r</ul>\r</blockquote>",
+        "should use the given line ending for fenced code in containers w/o line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\\\nb", &crlf)?,
+        "<p>a<br />\nb</p>",
+        "should prefer the first line ending in the document over the given one"
+    );
+
     Ok(())
 }
@@ -0,0 +1,74 @@
+use markdown::{to_definitions, unist::Position, Definition, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn definitions() -> Result<(), String> {
+    assert_eq!(
+        to_definitions("[a]\n\n*b*", &ParseOptions::default())?,
+        vec![],
+        "should support documents w/o definitions"
+    );
+
+    assert_eq!(
+        to_definitions("[a]: b\n[c]: <d e> 'f'", &ParseOptions::default())?,
+        vec![
+            Definition {
+                identifier: "a".into(),
+                label: "a".into(),
+                url: "b".into(),
+                title: None,
+                position: Position::new(1, 1, 0, 1, 7, 6),
+                shadowed: false
+            },
+            Definition {
+                identifier: "c".into(),
+                label: "c".into(),
+                url: "d e".into(),
+                title: Some("f".into()),
+                position: Position::new(2, 1, 7, 2, 15, 21),
+                shadowed: false
+            }
+        ],
+        "should support definitions"
+    );
+
+    assert_eq!(
+        to_definitions("[A\\&  b]: c&amp;d", &ParseOptions::default())?,
+        vec![Definition {
+            identifier: "a\\& b".into(),
+            label: "A&  b".into(),
+            url: "c&d".into(),
+            title: None,
+            position: Position::new(1, 1, 0, 1, 18, 17),
+            shadowed: false
+        }],
+        "should normalize identifiers, and decode labels and URLs"
+    );
+
+    assert_eq!(
+        to_definitions("[a]: b\n\n> [A]: c\n\n* [ a ]: d", &ParseOptions::default())?
+            .iter()
+            .map(|d| (d.url.as_str(), d.position.start.line, d.shadowed))
+            .collect::<Vec<_>>(),
+        vec![("b", 1, false), ("c", 3, true), ("d", 5, true)],
+        "should mark later definitions with the same identifier as shadowed"
+    );
+
+    assert_eq!(
+        to_definitions("[a]: b\n\n[a]: c\n\n[a]", &ParseOptions::default())?
+            .iter()
+            .filter(|d| !d.shadowed)
+            .map(|d| d.url.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b"],
+        "should match which definition references use"
+    );
+
+    assert_eq!(
+        to_definitions("```\n[a]: b\n```\n\n[^a]: b", &ParseOptions::gfm())?,
+        vec![],
+        "should not include definitions in code, or GFM footnote definitions"
+    );
+
+    Ok(())
+}
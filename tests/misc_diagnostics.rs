@@ -0,0 +1,184 @@
+use markdown::{to_diagnostics, unist::Position, Diagnostic, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn diagnostics() -> Result<(), String> {
+    assert_eq!(
+        to_diagnostics("*a* **b** _c_ __d__", &ParseOptions::default())?,
+        vec![],
+        "should not warn for matched attention"
+    );
+
+    assert_eq!(
+        to_diagnostics("a * b, snake_case_name, 2 * 3", &ParseOptions::default())?,
+        vec![],
+        "should not warn for sequences that cannot open or close"
+    );
+
+    assert_eq!(
+        to_diagnostics("a **b", &ParseOptions::default())?,
+        vec![Diagnostic {
+            position: Position::new(1, 3, 2, 1, 5, 4),
+            reason: "Unexpected unmatched attention sequence `**`".into(),
+            rule_id: "unmatched-attention".into()
+        }],
+        "should warn for an unclosed sequence"
+    );
+
+    assert_eq!(
+        to_diagnostics("a_ b", &ParseOptions::default())?,
+        vec![Diagnostic {
+            position: Position::new(1, 2, 1, 1, 3, 2),
+            reason: "Unexpected unmatched attention sequence `_`".into(),
+            rule_id: "unmatched-attention".into()
+        }],
+        "should warn for an unopened sequence"
+    );
+
+    assert_eq!(
+        to_diagnostics("**bold*", &ParseOptions::default())?,
+        vec![Diagnostic {
+            position: Position::new(1, 1, 0, 1, 2, 1),
+            reason: "Unexpected unmatched attention sequence `*`".into(),
+            rule_id: "unmatched-attention".into()
+        }],
+        "should warn for the unused part of a sequence (opening)"
+    );
+
+    assert_eq!(
+        to_diagnostics("*bold**", &ParseOptions::default())?,
+        vec![Diagnostic {
+            position: Position::new(1, 7, 6, 1, 8, 7),
+            reason: "Unexpected unmatched attention sequence `*`".into(),
+            rule_id: "unmatched-attention".into()
+        }],
+        "should warn for the unused part of a sequence (closing)"
+    );
+
+    assert_eq!(
+        to_diagnostics("> a\n> *b *c*", &ParseOptions::default())?
+            .iter()
+            .map(|d| d.position.clone())
+            .collect::<Vec<_>>(),
+        vec![Position::new(2, 3, 6, 2, 4, 7)],
+        "should warn w/ positional info in containers"
+    );
+
+    assert_eq!(
+        to_diagnostics("# a **b\n\nc_\n\nd __e", &ParseOptions::default())?
+            .iter()
+            .map(|d| d.position.start.line)
+            .collect::<Vec<_>>(),
+        vec![1, 3, 5],
+        "should warn in order"
+    );
+
+    assert_eq!(
+        to_diagnostics("a ~~b", &ParseOptions::default())?,
+        vec![],
+        "should not warn for tildes if strikethrough is off"
+    );
+
+    assert_eq!(
+        to_diagnostics("a ~~b~", &ParseOptions::gfm())?
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "1:3: Unexpected unmatched attention sequence `~~` (unmatched-attention)",
+            "1:6: Unexpected unmatched attention sequence `~` (unmatched-attention)"
+        ],
+        "should warn for unmatched strikethrough"
+    );
+
+    assert_eq!(
+        to_diagnostics("[a **b](c)**", &ParseOptions::default())?.len(),
+        2,
+        "should warn for sequences that cannot match across links"
+    );
+
+    let a11y = ParseOptions {
+        accessibility_lints: true,
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(
+        to_diagnostics("![](x)", &ParseOptions::default())?,
+        vec![],
+        "should not warn for accessibility problems by default"
+    );
+
+    assert_eq!(
+        to_diagnostics("![](x)", &a11y)?,
+        vec![Diagnostic {
+            position: Position::new(1, 1, 0, 1, 7, 6),
+            reason: "Unexpected image without alt text".into(),
+            rule_id: "missing-image-alt".into()
+        }],
+        "should warn for images w/o alt text"
+    );
+
+    assert_eq!(
+        to_diagnostics("![ ][x]\n\n[x]: y", &a11y)?
+            .iter()
+            .map(|d| d.rule_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["missing-image-alt"],
+        "should warn for image references w/ whitespace as alt text"
+    );
+
+    assert_eq!(
+        to_diagnostics(
+            "![A cat](cat.png) and ![*a* `dog`](dog.png), see [the docs](x).",
+            &a11y
+        )?,
+        vec![],
+        "should not warn for images w/ alt text and descriptive links"
+    );
+
+    assert_eq!(
+        to_diagnostics("For more info, [click here](x).", &a11y)?,
+        vec![Diagnostic {
+            position: Position::new(1, 16, 15, 1, 31, 30),
+            reason: "Unexpected non-descriptive link text `click here`".into(),
+            rule_id: "non-descriptive-link-text".into()
+        }],
+        "should warn for non-descriptive link text"
+    );
+
+    assert_eq!(
+        to_diagnostics("[Click *Here*!](x), [read\nmore](y), [](z)", &a11y)?
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "1:1: Unexpected non-descriptive link text `click here` (non-descriptive-link-text)",
+            "1:21: Unexpected non-descriptive link text `read more` (non-descriptive-link-text)",
+            "2:11: Unexpected link without text (non-descriptive-link-text)"
+        ],
+        "should warn for non-descriptive link text, ignoring case, markup, whitespace, and punctuation"
+    );
+
+    assert_eq!(
+        to_diagnostics("[![](x)](y)", &a11y)?
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "1:1: Unexpected link without text (non-descriptive-link-text)",
+            "1:2: Unexpected image without alt text (missing-image-alt)"
+        ],
+        "should warn for images in links"
+    );
+
+    assert_eq!(
+        to_diagnostics("> a **b\n>\n> [here](x)", &a11y)?
+            .iter()
+            .map(|d| (d.rule_id.as_str(), d.position.start.line))
+            .collect::<Vec<_>>(),
+        vec![("unmatched-attention", 1), ("non-descriptive-link-text", 3)],
+        "should warn in order, w/ other diagnostics"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use markdown::{
+    to_html, to_html_batch, to_html_with_options, CompileOptions, Constructs, ElementAttributes,
+    ElementKind, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn element_attributes() -> Result<(), String> {
+    let itemprop = Options {
+        compile: CompileOptions {
+            element_attributes: Some(ElementAttributes::new(|kind| match kind {
+                ElementKind::Heading(1) => vec![("itemprop".into(), "headline".into())],
+                ElementKind::Heading(_) => vec![("itemprop".into(), "alternativeHeadline".into())],
+                _ => vec![],
+            })),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    let all = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                fenced_div: true,
+                math_flow: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            element_attributes: Some(ElementAttributes::new(|kind| {
+                vec![("data-kind".into(), format!("{:?}", kind))]
+            })),
+            ..CompileOptions::default()
+        },
+    };
+
+    assert_eq!(
+        to_html("# a"),
+        "<h1>a</h1>",
+        "should not add attributes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\n-", &itemprop)?,
+        "<h1 itemprop=\"headline\">a</h1>\n<h2 itemprop=\"alternativeHeadline\">b</h2>",
+        "should add attributes to headings (ATX, setext), w/ their rank"
+    );
+
+    assert_eq!(
+        to_html_with_options("a", &itemprop)?,
+        "<p>a</p>",
+        "should not add attributes if none are returned"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a",
+            &Options {
+                compile: CompileOptions {
+                    element_attributes: Some(ElementAttributes::new(|_| vec![(
+                        "title".into(),
+                        "<\"&>".into()
+                    )])),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p title=\"&lt;&quot;&amp;&gt;\">a</p>",
+        "should encode attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n***", &all)?,
+        "<blockquote data-kind=\"BlockQuote\">\n<p data-kind=\"Paragraph\">a</p>\n</blockquote>\n<hr data-kind=\"ThematicBreak\" />",
+        "should add attributes to block quotes, paragraphs, and thematic breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n3. b", &all)?,
+        "<ul data-kind=\"UnorderedList\">\n<li data-kind=\"ListItem\">a</li>\n</ul>\n<ol data-kind=\"OrderedList\" start=\"3\">\n<li data-kind=\"ListItem\">b</li>\n</ol>",
+        "should add attributes to lists and list items, but not to paragraphs in tight lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```\n\n    b\n\n$$\nc\n$$", &all)?,
+        "<pre data-kind=\"Code\"><code class=\"language-js\">a\n</code></pre>\n<pre data-kind=\"Code\"><code>b\n</code></pre>\n<pre data-kind=\"Math\"><code class=\"language-math math-display\">c\n</code></pre>",
+        "should add attributes to code (fenced, indented) and math (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |", &all)?,
+        "<table data-kind=\"Table\">\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n</table>",
+        "should add attributes to tables (GFM)"
+    );
+
+    assert_eq!(
+        to_html_with_options("::: {.a}\n:::", &all)?,
+        "<div class=\"a\" data-kind=\"FencedDiv\">\n</div>",
+        "should add attributes to fenced divs"
+    );
+
+    assert_eq!(
+        to_html_batch(&["# a", "## b"], &itemprop)?,
+        vec![
+            "<h1 itemprop=\"headline\">a</h1>",
+            "<h2 itemprop=\"alternativeHeadline\">b</h2>"
+        ],
+        "should support `element_attributes` in batches"
+    );
+
+    Ok(())
+}
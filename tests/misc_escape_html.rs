@@ -0,0 +1,62 @@
+use markdown::{escape_html, to_html};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn escape() {
+    assert_eq!(escape_html(""), "", "should support an empty string");
+
+    assert_eq!(
+        escape_html("a b\nc 🦀"),
+        "a b\nc 🦀",
+        "should not change safe characters"
+    );
+
+    assert_eq!(escape_html("<"), "&lt;", "should encode `<`");
+
+    assert_eq!(escape_html(">"), "&gt;", "should encode `>`");
+
+    assert_eq!(escape_html("&"), "&amp;", "should encode `&`");
+
+    assert_eq!(escape_html("\""), "&quot;", "should encode `\"`");
+
+    assert_eq!(escape_html("'"), "'", "should not encode `'`");
+
+    assert_eq!(
+        escape_html("&amp; &#x3C;"),
+        "&amp;amp; &amp;#x3C;",
+        "should encode character references again"
+    );
+
+    assert_eq!(
+        escape_html("a\0b"),
+        "a\u{FFFD}b",
+        "should replace NUL with the replacement character"
+    );
+
+    assert_eq!(
+        escape_html("\u{1}\t\u{1F}\u{7F}"),
+        "\u{1}\t\u{1F}\u{7F}",
+        "should not change other control characters"
+    );
+
+    assert_eq!(
+        escape_html("<a href=\"b\">c & d</a>"),
+        "&lt;a href=&quot;b&quot;&gt;c &amp; d&lt;/a&gt;",
+        "should encode markup"
+    );
+
+    assert_eq!(
+        format!("<p>{}</p>", escape_html("a < b & \"c\"\0")),
+        to_html("a < b & \"c\"\0"),
+        "should encode like the HTML compiler encodes text"
+    );
+
+    assert_eq!(
+        format!(
+            "<p><a href=\"b\" title=\"{}\">a</a></p>",
+            escape_html("<c> & \"d\"")
+        ),
+        to_html("[a](b '<c> & \"d\"')"),
+        "should encode like the HTML compiler encodes attribute values"
+    );
+}
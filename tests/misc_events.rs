@@ -0,0 +1,186 @@
+use markdown::{
+    event::{source, to_source, Kind, Name},
+    parse_into, to_events, Constructs, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn events() -> Result<(), String> {
+    let value = "a *b* c";
+    let events = to_events(value, &ParseOptions::default())?;
+
+    assert_eq!(
+        events
+            .iter()
+            .map(|d| (d.kind.clone(), d.name.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (Kind::Enter, Name::Paragraph),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Enter, Name::Emphasis),
+            (Kind::Enter, Name::EmphasisSequence),
+            (Kind::Exit, Name::EmphasisSequence),
+            (Kind::Enter, Name::EmphasisText),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::EmphasisText),
+            (Kind::Enter, Name::EmphasisSequence),
+            (Kind::Exit, Name::EmphasisSequence),
+            (Kind::Exit, Name::Emphasis),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::Paragraph),
+        ],
+        "should expand text in paragraphs in place"
+    );
+
+    assert_eq!(
+        (events[3].point.column, events[12].point.index),
+        (3, 5),
+        "should include points"
+    );
+
+    assert_eq!(
+        source(value, &events, 3),
+        "*b*",
+        "should get the source of an enter event"
+    );
+
+    assert_eq!(
+        source(value, &events, 12),
+        "*b*",
+        "should get the source of an exit event"
+    );
+
+    let value = "* a\n  * b\n* c";
+    let events = to_events(value, &ParseOptions::default())?;
+    let ends = events
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| d.kind == Kind::Exit && d.name == Name::ListUnordered)
+        .map(|(index, _)| source(value, &events, index))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        ends,
+        vec!["* b", "* a\n  * b\n* c"],
+        "should support nested events with the same name"
+    );
+
+    let value = ">\t\ta";
+    let events = to_events(value, &ParseOptions::default())?;
+    let index = events
+        .iter()
+        .position(|d| d.name == Name::CodeIndented)
+        .unwrap();
+
+    assert_eq!(
+        source(value, &events, index),
+        "\t\ta",
+        "should include partial tabs whole"
+    );
+
+    let value = "* a *b*\n\n  c";
+    let events = to_events(value, &ParseOptions::default())?;
+    let enter = events
+        .iter()
+        .position(|d| d.name == Name::Emphasis)
+        .unwrap();
+    let exit = events
+        .iter()
+        .rposition(|d| d.name == Name::Emphasis)
+        .unwrap();
+
+    assert_eq!(
+        to_source(value, &events[enter..=exit]),
+        "*b*",
+        "should get the source of a part of the events"
+    );
+
+    let mut events = vec![];
+    let mut capacity = 0;
+
+    for value in ["# a *b*\n\n> c\n> - d", "e", "[f]\n\n[f]: g"] {
+        parse_into(value, &ParseOptions::default(), &mut events)?;
+        capacity = capacity.max(events.capacity());
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|d| (d.kind.clone(), d.name.clone(), d.point.index, d.point.vs))
+                .collect::<Vec<_>>(),
+            to_events(value, &ParseOptions::default())?
+                .into_iter()
+                .map(|d| (d.kind, d.name, d.point.index, d.point.vs))
+                .collect::<Vec<_>>(),
+            "should be the same as `to_events` when reusing a list ({:?})",
+            value
+        );
+    }
+
+    assert_eq!(
+        events.capacity(),
+        capacity,
+        "should keep the capacity of a reused list"
+    );
+
+    assert_eq!(
+        to_events(
+            "{a",
+            &ParseOptions {
+                constructs: Constructs::mdx(),
+                ..ParseOptions::default()
+            }
+        )
+        .err()
+        .unwrap(),
+        "1:3: Unexpected end of file in expression, expected a corresponding closing brace for `{`",
+        "should crash on syntax errors"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn events_to_source() -> Result<(), String> {
+    let documents = [
+        "",
+        "\u{feff}# a\n",
+        "---\ntitle: a\n---\n\nb\n",
+        "a  \nb\\\nc\n\n\n",
+        "  \t\n\n    a\n\t\tb\n",
+        "> a\r\n> - b\r\n>\t\tc\r\n\r\nd\re\r",
+        "1. a\n\n   b\n2. c\n\n- [ ] d\n- [x] e\n",
+        "```js\na\n\n```\n\n~~~\nb\n",
+        "a\n===\n\nb\n---\n\n***\n\n# c #\n",
+        "<div>\na\n</div>\n\n<!-- b -->\n",
+        "*a* **b** _c_ `d` [e](f \"g\") ![h][i] <j@k.l> <http://m>\n\n[i]: n\n",
+        "| a | b |\n| :- | -: |\n| c | d |\n\ne[^f] ~~g~~ www.h.i\n\n[^f]: j\n    k\n",
+        "Calls may not be empty: [^].\n\n[^^]: a\n\n![^]()\n",
+        "$$\na\n$$\n\n$b$ &amp; &#123; \\* c\n",
+        "a\u{0}b\n",
+    ];
+    let options = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            math_flow: true,
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+
+    for value in documents {
+        for options in [&ParseOptions::default(), &options] {
+            assert_eq!(
+                to_source(value, &to_events(value, options)?),
+                value,
+                "should get the same markdown back ({:?})",
+                value
+            );
+        }
+    }
+
+    Ok(())
+}
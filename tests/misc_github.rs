@@ -0,0 +1,153 @@
+use markdown::{to_headings, to_html, to_html_with_options, CompileOptions, Options, SlugPolicy};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn github() -> Result<(), String> {
+    assert_eq!(
+        to_html("# a"),
+        "<h1>a</h1>",
+        "should not add heading `id`s by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a", &Options::gfm())?,
+        "<h1>a</h1>",
+        "should not add heading `id`s in GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# Hello, *world*!\n\nb\n-",
+            &Options {
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<h1 id=\"hello-world\">Hello, <em>world</em>!</h1>\n<h2 id=\"b\">b</h2>",
+        "should add heading `id`s w/ `heading_ids` (ATX, setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\n## a\n\n> # a", &Options::github())?,
+        "<h1 id=\"a\">a</h1>\n<h2 id=\"a-1\">a</h2>\n<blockquote>\n<h1 id=\"a-2\">a</h1>\n</blockquote>",
+        "should make heading `id`s unique"
+    );
+
+    assert_eq!(
+        to_html_with_options("# <b>x</b> y z", &{
+            let mut options = Options::github();
+            options.compile.allow_dangerous_html = true;
+            options
+        })?,
+        "<h1 id=\"x-y-z\"><b>x</b> y z</h1>",
+        "should ignore HTML in heading `id`s"
+    );
+
+    let value = "# a\n\n> b\n> c\n> ---\n\n- # a\n\n[^d]\n\n[^d]: # a\n\n    b\n    -\n\n# *b*";
+    let mut ids = vec![];
+    let html = to_html_with_options(value, &Options::github())?;
+    let mut rest = html.as_str();
+
+    while let Some(index) = rest.find(" id=\"") {
+        rest = &rest[index + 5..];
+        let end = rest.find('"').unwrap();
+        if rest[..end].starts_with("user-content-") || rest[..end] == *"footnote-label" {
+            continue;
+        }
+        ids.push(rest[..end].to_string());
+    }
+
+    let mut slugs = to_headings(value, &Options::github())?
+        .into_iter()
+        .map(|d| d.slug)
+        .collect::<Vec<_>>();
+
+    // Footnote definitions are moved to the end in HTML.
+    ids.sort();
+    slugs.sort();
+
+    assert_eq!(
+        ids, slugs,
+        "should use the same slugs as `to_headings` (containers, footnotes)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# Café",
+            &Options {
+                compile: CompileOptions {
+                    heading_ids: true,
+                    slug_policy: SlugPolicy::Transliterate,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<h1 id=\"cafe\">Café</h1>",
+        "should use `slug_policy` for heading `id`s"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# Project
+
+Some ~~old~~ text, see www.example.com.
+Next line.
+
+## Tasks
+
+- [x] done
+- [ ] todo
+
+## Tasks
+
+| a | b |
+| - | :-: |
+| 1 | 2 |
+
+A note.[^1]
+
+[^1]: More.
+",
+            &Options::github()
+        )?,
+        "<h1 id=\"project\">Project</h1>
+<p>Some <del>old</del> text, see <a href=\"http://www.example.com\">www.example.com</a>.
+Next line.</p>
+<h2 id=\"tasks\">Tasks</h2>
+<ul>
+<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> done</li>
+<li><input type=\"checkbox\" disabled=\"\" /> todo</li>
+</ul>
+<h2 id=\"tasks-1\">Tasks</h2>
+<table>
+<thead>
+<tr>
+<th>a</th>
+<th align=\"center\">b</th>
+</tr>
+</thead>
+<tbody>
+<tr>
+<td>1</td>
+<td align=\"center\">2</td>
+</tr>
+</tbody>
+</table>
+<p>A note.<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
+<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>
+<ol>
+<li id=\"user-content-fn-1\">
+<p>More. <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should render a document like GitHub"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use markdown::{
+    to_headings, unist::Position, CompileOptions, Heading, Options, ParseOptions, SlugPolicy,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn headings() -> Result<(), String> {
+    assert_eq!(
+        to_headings("a\n\n*b*", &Options::default())?,
+        vec![],
+        "should support documents w/o headings"
+    );
+
+    assert_eq!(
+        to_headings("# a\n\n###### b", &Options::default())?,
+        vec![
+            Heading {
+                depth: 1,
+                text: "a".into(),
+                slug: "a".into(),
+                position: Position::new(1, 1, 0, 1, 4, 3)
+            },
+            Heading {
+                depth: 6,
+                text: "b".into(),
+                slug: "b".into(),
+                position: Position::new(3, 1, 5, 3, 9, 13)
+            }
+        ],
+        "should support ATX headings"
+    );
+
+    assert_eq!(
+        to_headings("a\n===\n\nb\nc\n---", &Options::default())?,
+        vec![
+            Heading {
+                depth: 1,
+                text: "a".into(),
+                slug: "a".into(),
+                position: Position::new(1, 1, 0, 2, 4, 5)
+            },
+            Heading {
+                depth: 2,
+                text: "b\nc".into(),
+                slug: "bc".into(),
+                position: Position::new(4, 1, 7, 6, 4, 14)
+            }
+        ],
+        "should support setext headings"
+    );
+
+    assert_eq!(
+        to_headings("> # a\n\n- b\n  ---", &Options::default())?
+            .iter()
+            .map(|d| (d.depth, d.text.as_str(), d.position.start.line))
+            .collect::<Vec<_>>(),
+        vec![(1, "a", 1), (2, "b", 3)],
+        "should support headings in containers"
+    );
+
+    assert_eq!(
+        to_headings(
+            "# *a* **b** `c` [d](e) ![f](g) &amp; \\#",
+            &Options::default()
+        )?
+        .iter()
+        .map(|d| d.text.as_str())
+        .collect::<Vec<_>>(),
+        vec!["a b c d f & #"],
+        "should use the text of headings, w/o markup"
+    );
+
+    assert_eq!(
+        to_headings("# <b>x</b> y z", &Options::default())?
+            .iter()
+            .map(|d| (d.text.as_str(), d.slug.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("x y z", "x-y-z")],
+        "should ignore HTML in the text of headings"
+    );
+
+    assert_eq!(
+        to_headings(
+            "# Hello, World!\n# a_b-c  d\n# Ünïcödé 日本語\n# 1.2.3",
+            &Options::default()
+        )?
+        .iter()
+        .map(|d| d.slug.as_str())
+        .collect::<Vec<_>>(),
+        vec!["hello-world", "a_b-c--d", "ünïcödé-日本語", "123"],
+        "should make slugs like GitHub"
+    );
+
+    assert_eq!(
+        to_headings("# a\n# a\n# a-1\n# A\n# !", &Options::default())?
+            .iter()
+            .map(|d| d.slug.as_str())
+            .collect::<Vec<_>>(),
+        vec!["a", "a-1", "a-1-1", "a-2", ""],
+        "should make slugs unique"
+    );
+
+    assert_eq!(
+        to_headings("#\n\n# !", &Options::default())?
+            .iter()
+            .map(|d| d.slug.as_str())
+            .collect::<Vec<_>>(),
+        vec!["", "-1"],
+        "should make empty slugs unique"
+    );
+
+    let value =
+        "# Crème Brûlée\n# Straße Æsir\n# 日本語 テスト\n# Rocket 🚀 launch\n# Ünïcödé 2 😀";
+
+    let slugs = |policy: SlugPolicy| -> Result<Vec<String>, String> {
+        Ok(to_headings(
+            value,
+            &Options {
+                compile: CompileOptions {
+                    slug_policy: policy,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            },
+        )?
+        .into_iter()
+        .map(|d| d.slug)
+        .collect())
+    };
+
+    assert_eq!(
+        slugs(SlugPolicy::Keep)?,
+        vec![
+            "crème-brûlée",
+            "straße-æsir",
+            "日本語-テスト",
+            "rocket--launch",
+            "ünïcödé-2-"
+        ],
+        "should keep non-ASCII letters w/ `SlugPolicy::Keep`"
+    );
+
+    assert_eq!(
+        slugs(SlugPolicy::Transliterate)?,
+        vec![
+            "creme-brulee",
+            "strasse-aesir",
+            "-",
+            "rocket--launch",
+            "unicode-2-"
+        ],
+        "should transliterate Latin letters and remove others w/ `SlugPolicy::Transliterate`"
+    );
+
+    assert_eq!(
+        slugs(SlugPolicy::Strip)?,
+        vec!["crme-brle", "strae-sir", "-", "rocket--launch", "ncd-2-"],
+        "should remove non-ASCII letters w/ `SlugPolicy::Strip`"
+    );
+
+    assert_eq!(
+        to_headings(
+            "# é\n# e",
+            &Options {
+                compile: CompileOptions {
+                    slug_policy: SlugPolicy::Transliterate,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?
+        .iter()
+        .map(|d| d.slug.as_str())
+        .collect::<Vec<_>>(),
+        vec!["e", "e-1"],
+        "should make transliterated slugs unique"
+    );
+
+    assert_eq!(
+        Options::default().compile.slug_policy,
+        SlugPolicy::Keep,
+        "should keep non-ASCII letters by default"
+    );
+
+    assert_eq!(
+        to_headings("a\n\n# ~b~", &Options::gfm())?
+            .iter()
+            .map(|d| d.text.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b"],
+        "should support parse options"
+    );
+
+    assert_eq!(
+        to_headings(
+            "# {a",
+            &Options {
+                parse: ParseOptions::mdx(),
+                ..Options::default()
+            }
+        ),
+        Err("1:5: Unexpected end of file in expression, expected a corresponding closing brace for `{`".into()),
+        "should crash on invalid MDX"
+    );
+
+    Ok(())
+}
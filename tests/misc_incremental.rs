@@ -0,0 +1,162 @@
+use markdown::{to_mdast, to_mdast_incremental, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn incremental() -> Result<(), String> {
+    let options = ParseOptions::default();
+    let gfm = ParseOptions::gfm();
+
+    let before = "[a]\n\nb\n";
+    let after = "[a]\n\nb\n\n[a]: c\n";
+    assert_eq!(
+        to_mdast_incremental(
+            after,
+            &to_mdast(before, &options)?,
+            6..after.len(),
+            &options
+        )?,
+        to_mdast(after, &options)?,
+        "should resolve references when a definition is added"
+    );
+
+    let before = "[a]\n\n[a]: b\n";
+    let after = "[a]\n\n";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &options)?, 5..5, &options)?,
+        to_mdast(after, &options)?,
+        "should unresolve references when a definition is removed"
+    );
+
+    let before = "[a]\n\n[a]: b\n";
+    let after = "[a]\n\n[c]: b\n";
+    assert_eq!(
+        to_mdast_incremental(
+            after,
+            &to_mdast(before, &options)?,
+            5..after.len(),
+            &options
+        )?,
+        to_mdast(after, &options)?,
+        "should unresolve references when a definition is renamed"
+    );
+
+    let before = "[x]: y\n\n[a] [x]\n\n[a]: b\n";
+    let after = "[x]: y\n\n[a] [x]\n\n[a]: b\n\n[x]: z\n";
+    assert_eq!(
+        to_mdast_incremental(
+            after,
+            &to_mdast(before, &options)?,
+            21..after.len(),
+            &options
+        )?,
+        to_mdast(after, &options)?,
+        "should not change anything when a duplicate definition is added"
+    );
+
+    let before = "a\n\nb *c*\n\nd [e]\n\n[e]: f\n";
+    let after = "a\n\nbb\nx *c*\n\nd [e]\n\n[e]: f\n";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &options)?, 3..12, &options)?,
+        to_mdast(after, &options)?,
+        "should move positional info of blocks after the range"
+    );
+
+    let before = "* [Foo\n  Bar]\n\n> a [foo bar]\n\nc\n";
+    let after = "* [Foo\n  Bar]\n\n> a [foo bar]\n\n[FOO BAR]: d\n";
+    assert_eq!(
+        to_mdast_incremental(
+            after,
+            &to_mdast(before, &options)?,
+            30..after.len(),
+            &options
+        )?,
+        to_mdast(after, &options)?,
+        "should resolve references w/ different casing and whitespace in containers"
+    );
+
+    let before = "a\n\n* b\n\nc\n";
+    let between = "a\n\n* b\n* [d]\n\nc\n";
+    let after = "a\n\n* b\n* [d]\n\nc\n\n[d]: e\n";
+    let tree = to_mdast_incremental(between, &to_mdast(before, &options)?, 3..13, &options)?;
+    assert_eq!(
+        to_mdast_incremental(after, &tree, between.len()..after.len(), &options)?,
+        to_mdast(after, &options)?,
+        "should support several edits in a row"
+    );
+
+    let before = "a[^b]\n\nc\n";
+    let after = "a[^b]\n\n[^b]: c\n";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &gfm)?, 7..after.len(), &gfm)?,
+        to_mdast(after, &gfm)?,
+        "should resolve footnote references when a footnote definition is added"
+    );
+
+    let before = "a[^b]\n\n[^b]: c\n";
+    let after = "a[^b]\n\n[b]: c\n";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &gfm)?, 7..after.len(), &gfm)?,
+        to_mdast(after, &gfm)?,
+        "should unresolve footnote references when a footnote definition is removed"
+    );
+
+    let before = "[a]\r\n\r\nb\r\n\r\nc\r\n";
+    let after = "[a]\r\n\r\n[a]: b\r\n\r\nc\r\n";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &options)?, 7..15, &options)?,
+        to_mdast(after, &options)?,
+        "should support CRLF line endings"
+    );
+
+    let before = "a\nb\n\n[c]\n";
+    let after = "a\nbb\n\n[c]\n\n[c]: d\n";
+    assert_eq!(
+        to_mdast_incremental(
+            after,
+            &to_mdast(before, &options)?,
+            2..after.len(),
+            &options
+        )?,
+        to_mdast(after, &options)?,
+        "should parse everything when the range cuts through a block"
+    );
+
+    let before = "a\n";
+    let after = "ab\n";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &options)?, 1..2, &options)?,
+        to_mdast(after, &options)?,
+        "should parse everything when the range is not at line boundaries"
+    );
+
+    let before = "a\n\n";
+    let after = "a\n\n\t[^a]";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &gfm)?, 3..after.len(), &gfm)?,
+        to_mdast(after, &gfm)?,
+        "should expand tabs in the end of the document"
+    );
+
+    let before = "a\n\n\tb";
+    let after = "aa\n\n\tb";
+    assert_eq!(
+        to_mdast_incremental(after, &to_mdast(before, &options)?, 0..3, &options)?,
+        to_mdast(after, &options)?,
+        "should keep the end of the document after the range"
+    );
+
+    let before = "a\n\nb\n";
+    let after = "a\n\n{\n";
+    assert_eq!(
+        to_mdast_incremental(
+            after,
+            &to_mdast(before, &ParseOptions::mdx())?,
+            3..after.len(),
+            &ParseOptions::mdx()
+        ),
+        to_mdast(after, &ParseOptions::mdx()),
+        "should report errors like `to_mdast`"
+    );
+
+    Ok(())
+}
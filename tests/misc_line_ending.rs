@@ -1,4 +1,9 @@
-use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{
+    mdast::{Code, Node, Root},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Options,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -36,6 +41,36 @@ fn line_ending() -> Result<(), String> {
         "should support just two carriage return + line feeds"
     );
 
+    assert_eq!(
+        to_html("\n\n\n"),
+        "",
+        "should support just several line feeds"
+    );
+
+    assert_eq!(
+        to_html("\n\n\na"),
+        "<p>a</p>",
+        "should not add line endings for initial blank lines"
+    );
+
+    assert_eq!(
+        to_html("a\n\n\n"),
+        "<p>a</p>\n",
+        "should add one line ending for final blank lines"
+    );
+
+    assert_eq!(
+        to_html("\n\n# a\n\n\nb\n\n\n"),
+        "<h1>a</h1>\n<p>b</p>\n",
+        "should not add line endings for blank lines around and between blocks"
+    );
+
+    assert_eq!(
+        to_html("\r\n\r\na\r\n\r\n\r\n"),
+        "<p>a</p>\r\n",
+        "should not add line endings for blank lines (carriage return + line feed)"
+    );
+
     assert_eq!(
         to_html("a\nb"),
         "<p>a\nb</p>",
@@ -186,5 +221,75 @@ fn line_ending() -> Result<(), String> {
         "should support a non-blank line w/ carriage return + line feed in html"
     );
 
+    assert_eq!(
+        to_html("```\ra\rb\r```\r"),
+        "<pre><code>a\rb\r</code></pre>\r",
+        "should support carriage returns in fenced code, and after the closing fence"
+    );
+
+    assert_eq!(
+        to_html("~~~ js\r<a>\r~~~~\rb"),
+        "<pre><code class=\"language-js\">&lt;a&gt;\r</code></pre>\r<p>b</p>",
+        "should support carriage returns after an info string and a longer closing fence"
+    );
+
+    assert_eq!(
+        to_html("  ```\r  a\r b\rc\r  ```\r"),
+        "<pre><code>a\rb\rc\r</code></pre>\r",
+        "should support carriage returns when stripping the indent of fenced code"
+    );
+
+    assert_eq!(
+        to_html("```\r\ra\r"),
+        "<pre><code>\ra\r</code></pre>\r",
+        "should support carriage returns in unclosed fenced code"
+    );
+
+    assert_eq!(
+        to_html("> ```\r> a\r\rb"),
+        "<blockquote>\r<pre><code>a\r</code></pre>\r</blockquote>\r<p>b</p>",
+        "should support carriage returns in fenced code in a block quote"
+    );
+
+    assert_eq!(
+        to_html("- ```\r  a\r\r  b\r  ```\r- c"),
+        "<ul>\r<li>\r<pre><code>a\r\rb\r</code></pre>\r</li>\r<li>c</li>\r</ul>",
+        "should support carriage returns in fenced code in a list item"
+    );
+
+    assert_eq!(
+        to_html("    a\r\r    b\r"),
+        "<pre><code>a\r\rb\r</code></pre>\r",
+        "should support carriage returns and blank lines in indented code"
+    );
+
+    assert_eq!(
+        to_mdast("```\ra\r\rb\r```\r", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                lang: None,
+                meta: None,
+                value: "a\r\rb".into(),
+                position: Some(Position::new(1, 1, 0, 5, 4, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 6, 1, 13))
+        }),
+        "should keep carriage returns in the value of fenced code in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("    a\r\r    b\r", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                lang: None,
+                meta: None,
+                value: "a\r\rb".into(),
+                position: Some(Position::new(1, 1, 0, 3, 6, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 4, 1, 13))
+        }),
+        "should keep carriage returns in the value of indented code in mdast"
+    );
+
     Ok(())
 }
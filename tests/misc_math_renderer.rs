@@ -0,0 +1,95 @@
+use markdown::{
+    to_html_batch, to_html_with_options, CompileOptions, Constructs, MathRenderer, Options,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn math_renderer() -> Result<(), String> {
+    let math = || ParseOptions {
+        constructs: Constructs {
+            math_flow: true,
+            math_text: true,
+            ..Constructs::default()
+        },
+        ..ParseOptions::default()
+    };
+
+    let stub = Options {
+        parse: math(),
+        compile: CompileOptions {
+            math_renderer: Some(MathRenderer::new(|value, display| {
+                format!("[{}:{}]", if display { "display" } else { "inline" }, value)
+            })),
+            ..CompileOptions::default()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "$a$\n\n$$\nb\n$$",
+            &Options {
+                parse: math(),
+                ..Options::default()
+            }
+        )?,
+        "<p><code class=\"language-math math-inline\">a</code></p>\n<pre><code class=\"language-math math-display\">b\n</code></pre>",
+        "should pass math through as code by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a $b$ c", &stub)?,
+        "<p>a [inline:b] c</p>",
+        "should render math (text) w/ `display: false`"
+    );
+
+    assert_eq!(
+        to_html_with_options("$a < b & c$", &stub)?,
+        "<p>[inline:a < b & c]</p>",
+        "should pass the raw value of math (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a $$ b\nc $$", &stub)?,
+        "<p>a [inline:b c]</p>",
+        "should pass math (text) w/ padding removed and line endings as spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a $b$ c](d)", &stub)?,
+        "<p><img src=\"d\" alt=\"a b c\" /></p>",
+        "should not render math (text) in image alt"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$\na < b\n\nc\n$$\nd", &stub)?,
+        "[display:a < b\n\nc]\n<p>d</p>",
+        "should render math (flow) w/ `display: true`, passing the raw value"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$\n$$", &stub)?,
+        "[display:]",
+        "should render empty math (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$\na", &stub)?,
+        "[display:a]\n",
+        "should render unclosed math (flow), like code"
+    );
+
+    assert_eq!(
+        to_html_with_options("> $$\n> a\n> $$\n\n- $$\n  b\n  $$", &stub)?,
+        "<blockquote>\n[display:a]\n</blockquote>\n<ul>\n<li>\n[display:b]\n</li>\n</ul>",
+        "should render math (flow) in containers"
+    );
+
+    assert_eq!(
+        to_html_batch(&["$a$", "$$\nb\n$$"], &stub)?,
+        vec!["<p>[inline:a]</p>", "[display:b]"],
+        "should support `math_renderer` in batches"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use markdown::{to_html, to_html_with_options, to_mdast, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_attempts() -> Result<(), String> {
+    let limit = |max: usize| Options {
+        parse: ParseOptions {
+            max_attempts: Some(max),
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        ParseOptions::default().max_attempts,
+        None,
+        "should not limit attempts by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\n- b\n\n> c *d* [e](f)", &limit(0))?,
+        "<p># a</p>\n<p>- b</p>\n<p>&gt; c *d* [e](f)</p>",
+        "should fall back to paragraphs and text w/o attempts"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n\n  \nb\n", &limit(0))?,
+        "<p>a</p>\n<p>b</p>\n",
+        "should still support blank lines w/o attempts"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n# b", &limit(1000))?,
+        to_html("# a\n# b"),
+        "should not affect documents w/ fewer attempts than the limit"
+    );
+
+    let value = "# a\n\n# b\n\n# c\n\n# d";
+    let html = to_html_with_options(value, &limit(12))?;
+
+    assert!(
+        html.starts_with("<h1>a</h1>") && html.ends_with("<p># d</p>"),
+        "should parse normally until the limit is reached, and fall back after it ({:?})",
+        html
+    );
+
+    let value = format!("{}{}", "[".repeat(2000), "a](".repeat(1000));
+
+    assert_eq!(
+        to_html_with_options(&value, &limit(100))?,
+        format!("<p>{}</p>", value),
+        "should bound speculative parsing of pathological input"
+    );
+
+    assert!(
+        to_mdast(
+            &"[a]: <\n".repeat(100),
+            &ParseOptions {
+                max_attempts: Some(10),
+                ..ParseOptions::default()
+            }
+        )
+        .is_ok(),
+        "should support limiting attempts in mdast"
+    );
+
+    Ok(())
+}
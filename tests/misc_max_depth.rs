@@ -0,0 +1,73 @@
+use markdown::{to_html, to_html_with_options, to_mdast, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_depth() -> Result<(), String> {
+    let limit = |max: usize| Options {
+        parse: ParseOptions {
+            max_depth: Some(max),
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        ParseOptions::default().max_depth,
+        None,
+        "should not limit passes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# *a*\n\n- b\n\n> c *d* [e](f)\n\n[g]: h", &limit(0))?,
+        "<h1>*a*</h1>\n<ul>\n<li>b</li>\n</ul>\n<blockquote>\n<p>c *d* [e](f)</p>\n</blockquote>\n",
+        "should still parse structure, paragraphs, and definitions, but not text, w/o passes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a &amp; b", &limit(0))?,
+        "<p>a &amp;amp; b</p>",
+        "should leave text that is not parsed as is"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a *b*](/c&amp;d \"e&amp;f\")", &limit(1))?,
+        "<p><a href=\"/c&amp;amp;d\" title=\"e&amp;amp;f\">a <em>b</em></a></p>",
+        "should parse text, but not strings in text, w/ one pass"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a *b*](/c&amp;d \"e&amp;f\")", &limit(2))?,
+        to_html("[a *b*](/c&amp;d \"e&amp;f\")"),
+        "should parse strings in text w/ two passes"
+    );
+
+    let value = format!("{}[a][b]\n\n[b]: /c&amp;d\n", "> - ".repeat(100));
+
+    assert_eq!(
+        to_html_with_options(&value, &limit(2))?,
+        to_html(&value),
+        "should not need more passes for deeply nested containers"
+    );
+
+    let value = format!("{}a{}\n\n[b]: /c", "[[b]".repeat(100), "](/d)".repeat(100));
+
+    assert_eq!(
+        to_html_with_options(&value, &limit(2))?,
+        to_html(&value),
+        "should not need more passes for deeply nested links and references"
+    );
+
+    assert!(
+        to_mdast(
+            "*a* [b](c)",
+            &ParseOptions {
+                max_depth: Some(0),
+                ..ParseOptions::default()
+            }
+        )
+        .is_ok(),
+        "should support limiting passes in mdast"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use markdown::{
+    to_html, to_html_with_options, to_mdast, CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_nesting() -> Result<(), String> {
+    let limit = |max: usize| Options {
+        parse: ParseOptions {
+            max_nesting: Some(max),
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        ParseOptions::default().max_nesting,
+        Some(1024),
+        "should limit nesting to 1024 by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("> > > a", &limit(2))?,
+        "<blockquote>\n<blockquote>\n<p>&gt; a</p>\n</blockquote>\n</blockquote>",
+        "should not nest containers deeper than the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options("- > - a\n\n  > b", &limit(2))?,
+        "<ul>\n<li>\n<blockquote>\n<p>- a</p>\n</blockquote>\n<blockquote>\n<p>b</p>\n</blockquote>\n</li>\n</ul>",
+        "should count different containers together"
+    );
+
+    assert_eq!(
+        to_html_with_options("![![![a](b)](c)](d)", &limit(2))?,
+        "<p><img src=\"c\" alt=\"![a\" />](d)</p>",
+        "should not nest labels deeper than the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a _b *c* b_ a*", &limit(2))?,
+        "<p>*a <em>b <em>c</em> b</em> a*</p>",
+        "should not nest attention deeper than the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options("***a***", &limit(1))?,
+        "<p>*<strong>a</strong>*</p>",
+        "should not nest attention from one sequence deeper than the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a* *b* [c](d) ![e](f)\n\n> g\n\n- h", &limit(1))?,
+        "<p><em>a</em> <em>b</em> <a href=\"d\">c</a> <img src=\"f\" alt=\"e\" /></p>\n<blockquote>\n<p>g</p>\n</blockquote>\n<ul>\n<li>h</li>\n</ul>",
+        "should not limit siblings"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> > a",
+            &Options {
+                parse: ParseOptions {
+                    max_nesting: None,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        to_html("> > a"),
+        "should support `None` to not limit nesting"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn max_nesting_pathological() -> Result<(), String> {
+    let options = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_container: true,
+                directive_leaf: true,
+                directive_text: true,
+                fenced_div: true,
+                label_start_inline_footnote: true,
+                math_flow: true,
+                math_text: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..CompileOptions::gfm()
+        },
+    };
+    let size = 1100;
+    let mut values = vec![];
+
+    for open in [
+        ">", "> ", "- ", "1. ", "> - ", "[^a]: ", "[", "![", "^[", "[^", "*", "_", "~", "<a>",
+        ":a[", "*a ", "_a ", "~~a ", "**a ",
+    ] {
+        values.push(open.repeat(size));
+    }
+
+    for (open, close) in [
+        ("[", "](b)"),
+        ("![", "](b)"),
+        ("^[", "]"),
+        ("*", "*"),
+        ("*a ", " a*"),
+        ("_a ", " a_"),
+        ("~~a ", " a~~"),
+        (":a[", "]"),
+        ("[a](", ")"),
+    ] {
+        values.push(format!("{}b{}", open.repeat(size), close.repeat(size)));
+    }
+
+    values.push(format!(
+        "{}{}{}a{}{}",
+        "> - ".repeat(size),
+        "![".repeat(size),
+        "*a ".repeat(size),
+        " a*".repeat(size),
+        "](b)".repeat(size)
+    ));
+    values.push(":::a\n".repeat(size));
+    values.push(format!("{}| a |\n| - |\n| b |", "> ".repeat(size)));
+
+    for value in values {
+        // Parsing and dropping deep trees is what can overflow the stack.
+        to_html_with_options(&value, &options)?;
+        to_mdast(&value, &options.parse)?;
+    }
+
+    Ok(())
+}
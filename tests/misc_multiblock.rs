@@ -0,0 +1,137 @@
+use markdown::{is_multiblock, to_html_with_options, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn multiblock() -> Result<(), String> {
+    assert!(
+        !is_multiblock(""),
+        "should not see blocks in an empty string"
+    );
+
+    assert!(
+        !is_multiblock("a *b* **c** `d` [e](f) <https://g>"),
+        "should not see blocks in a single line paragraph"
+    );
+
+    assert!(
+        !is_multiblock("a\nb\r\nc\rd"),
+        "should not see blocks in a paragraph w/ line endings"
+    );
+
+    assert!(
+        !is_multiblock("\n\n  a\n      b\n\n"),
+        "should not see blocks in a paragraph w/ surrounding blank lines and indented lines"
+    );
+
+    assert!(
+        !is_multiblock("*a*, **b**, _c_, __d__\n+1, 2 - 3, 4.5, 6) `e`, ~f~, $g$, :h:, [i]"),
+        "should not see blocks for markers not followed by whitespace"
+    );
+
+    assert!(
+        is_multiblock("a\n\nb"),
+        "should see blocks in paragraphs separated by a blank line"
+    );
+
+    assert!(
+        is_multiblock("a\n \t\nb"),
+        "should see blocks in paragraphs separated by a whitespace-only line"
+    );
+
+    assert!(is_multiblock("# a"), "should see headings (atx)");
+
+    assert!(is_multiblock("a\n==="), "should see headings (setext)");
+
+    assert!(
+        is_multiblock("a\n---"),
+        "should see headings (setext) or thematic breaks"
+    );
+
+    assert!(is_multiblock("* * *"), "should see thematic breaks");
+
+    assert!(is_multiblock("> a"), "should see block quotes");
+
+    assert!(is_multiblock("   > a"), "should see indented block quotes");
+
+    assert!(is_multiblock("- a"), "should see list items (unordered)");
+
+    assert!(
+        is_multiblock("a\n+ b"),
+        "should see list items on later lines"
+    );
+
+    assert!(
+        is_multiblock("1. a"),
+        "should see list items (ordered, `.`)"
+    );
+
+    assert!(
+        is_multiblock("123) a"),
+        "should see list items (ordered, `)`)"
+    );
+
+    assert!(
+        is_multiblock("```js\na\n```"),
+        "should see code (fenced, backticks)"
+    );
+
+    assert!(is_multiblock("~~~\na"), "should see code (fenced, tildes)");
+
+    assert!(is_multiblock("    a"), "should see code (indented)");
+
+    assert!(is_multiblock("\ta"), "should see code (indented) w/ tabs");
+
+    assert!(is_multiblock("<div>\na"), "should see HTML (flow)");
+
+    assert!(is_multiblock("[a]: b"), "should see definitions");
+
+    assert!(
+        is_multiblock("[a\nb]: c"),
+        "should see definitions w/ line endings in labels"
+    );
+
+    assert!(is_multiblock("a | b\n--|--"), "should see tables (GFM)");
+
+    assert!(
+        is_multiblock("| a |\n| :-: |"),
+        "should see tables (GFM) w/ pipes"
+    );
+
+    assert!(is_multiblock("$$\na\n$$"), "should see math (flow)");
+
+    assert!(is_multiblock("::: a\nb\n:::"), "should see fenced divs");
+
+    assert!(
+        is_multiblock("a. b") && is_multiblock("IV) a") && !is_multiblock("Mr. Smith"),
+        "should see list items (ordered, letters or roman numerals)"
+    );
+
+    assert!(is_multiblock("import a from 'b'"), "should see ESM (MDX)");
+
+    assert!(is_multiblock("{a}"), "should see expressions (MDX)");
+
+    let options = Options::gfm();
+
+    for value in [
+        "",
+        "a *b* **c**",
+        "a\nb\nc",
+        "  a\n    b",
+        "*a*\n+1\n2.5 b",
+        "a ~b~ c",
+        "a\n\n",
+    ] {
+        let html = to_html_with_options(value, &options)?;
+        assert_eq!(
+            (
+                is_multiblock(value),
+                html.is_empty() || html.starts_with("<p>") && html.matches("<p>").count() == 1
+            ),
+            (false, true),
+            "should agree w/ the parser on a single paragraph (`{:?}`)",
+            value
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use markdown::{to_mdast, ParseOptions, ParseResult};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn parse_result() -> Result<(), String> {
+    let options = ParseOptions::default();
+
+    let mut result = ParseResult::new("a\n\nb\n\nc\n", &options)?;
+    result.edit(3..4, "x *y*")?;
+    assert_eq!(
+        result.value(),
+        "a\n\nx *y*\n\nc\n",
+        "should replace the range"
+    );
+    assert_eq!(
+        result.tree(),
+        &to_mdast(result.value(), &options)?,
+        "should update the tree of a block"
+    );
+
+    let mut result = ParseResult::new("a\n\nb\n\nc\n", &options)?;
+    result.edit(1..3, "\n")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("a\nb\n\nc\n", &options)?,
+        "should join blocks when a blank line is removed"
+    );
+
+    let mut result = ParseResult::new("a\n\nb\n\nc\n\nd\n", &options)?;
+    result.edit(3..3, "```\n")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("a\n\n```\nb\n\nc\n\nd\n", &options)?,
+        "should parse what follows when a fence is opened"
+    );
+
+    let mut result = ParseResult::new("- a\n\nb\n\n  c\n", &options)?;
+    result.edit(3..6, "")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("- a\n\n  c\n", &options)?,
+        "should parse what follows when it becomes part of a list"
+    );
+
+    let mut result = ParseResult::new("1. \nword ---\n1. `c` ", &options)?;
+    result.edit(15..19, "")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("1. \nword ---\n1. ", &options)?,
+        "should parse what precedes when it changes the edit (list)"
+    );
+
+    let mut result = ParseResult::new("[a]: x\n1. \n\n\n```\n", &options)?;
+    result.edit(11..16, "- ")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("[a]: x\n1. \n- \n", &options)?,
+        "should parse what precedes when it changes the edit (definition)"
+    );
+
+    let mut result = ParseResult::new("[a]\n\nb\n\nc\n", &options)?;
+    result.edit(8..9, "[a]: d")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("[a]\n\nb\n\n[a]: d\n", &options)?,
+        "should resolve references when a definition is added"
+    );
+
+    let mut result = ParseResult::new("a\r\n\r\nb\r\n", &options)?;
+    result.edit(2..3, "\n\r")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("a\r\n\r\r\nb\r\n", &options)?,
+        "should support edits in CRLFs"
+    );
+
+    let mut result = ParseResult::new("a", &options)?;
+    result.edit(0..1, "\t[^a]")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("\t[^a]", &options)?,
+        "should expand tabs in the end of the document"
+    );
+
+    let mut result = ParseResult::new("", &options)?;
+    result.edit(0..0, "# a")?;
+    assert_eq!(
+        result.tree(),
+        &to_mdast("# a", &options)?,
+        "should support edits in empty documents"
+    );
+
+    let mut result = ParseResult::new("ö", &options)?;
+    assert_eq!(
+        result.edit(1..2, "a"),
+        Err("Cannot edit `1..2`, expected a range in the document (2 bytes), at character boundaries".into()),
+        "should crash on ranges that are not at character boundaries"
+    );
+    assert_eq!(
+        result.edit(0..3, "a"),
+        Err("Cannot edit `0..3`, expected a range in the document (2 bytes), at character boundaries".into()),
+        "should crash on ranges that are not in the document"
+    );
+    assert_eq!(
+        result.value(),
+        "ö",
+        "should not change anything after a crash"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_result_fuzz() -> Result<(), String> {
+    let documents = [
+        "# a\n\nb *c* [d]\n\n- e\n- f\n\n  g\n\n> h\n> i\n\n[d]: j\n",
+        "a\n===\n\n```js\nb\n\n```\n\n1. c\n\n   d\n2. e\n\n***\n\n<div>\nf\n</div>\n",
+        "| a | b |\n| - | - |\n| c | d |\n\ne[^f]\n\n[^f]: g\n\n    h\n\n~~i~~ www.j.k\n",
+        "a\r\nb\r\n\r\n    c\r\n\r\nd\re\r\r- f\r",
+    ];
+    let snippets = [
+        "",
+        "\n",
+        "\n\n",
+        "a",
+        " b ",
+        "- ",
+        "1. ",
+        "> ",
+        "# ",
+        "```",
+        "~~~\n",
+        "    ",
+        "===",
+        "---\n",
+        "*",
+        "[d]",
+        "[d]: x\n",
+        "[^f]: y\n",
+        "[^f]",
+        "word ---",
+        "`c` ",
+        "|",
+        "<div>\n",
+        "\r",
+        "\r\n",
+        "ö",
+    ];
+    let options = ParseOptions::gfm();
+    // Seeded, to make failures reproducible.
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut random = |max: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % max as u64) as usize
+    };
+
+    for document in documents {
+        let mut result = ParseResult::new(document, &options)?;
+
+        for _ in 0..1000 {
+            let value = result.value().to_string();
+            let boundaries: Vec<usize> = value
+                .char_indices()
+                .map(|(index, _)| index)
+                .chain([value.len()])
+                .collect();
+            let mut start = boundaries[random(boundaries.len())];
+            let mut end = boundaries[random(boundaries.len())];
+            if start > end {
+                std::mem::swap(&mut start, &mut end);
+            }
+            // Mostly small deletions, like typing.
+            if end - start > 8 && random(4) != 0 {
+                end = start;
+            }
+            let snippet = snippets[random(snippets.len())];
+
+            result.edit(start..end, snippet)?;
+
+            assert_eq!(
+                result.tree(),
+                &to_mdast(result.value(), &options)?,
+                "should be the same as `to_mdast` after replacing `{}..{}` with {:?} in {:?}",
+                start,
+                end,
+                snippet,
+                value
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use markdown::{to_plaintext, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn plaintext() -> Result<(), String> {
+    let gfm = ParseOptions::gfm();
+
+    assert_eq!(
+        to_plaintext("", &ParseOptions::default())?,
+        "",
+        "should support empty documents"
+    );
+
+    assert_eq!(
+        to_plaintext("# a\n\nb *c* **d**", &ParseOptions::default())?,
+        "a\nb c d",
+        "should separate blocks with a line ending, and drop markup"
+    );
+
+    assert_eq!(
+        to_plaintext("a\nb  \nc\\\nd", &ParseOptions::default())?,
+        "a\nb\nc\nd",
+        "should keep line endings and breaks in paragraphs"
+    );
+
+    assert_eq!(
+        to_plaintext(
+            "- a\n\n  b\n- c\n  - d\n\n> e\n> > f",
+            &ParseOptions::default()
+        )?,
+        "a\nb\nc\nd\ne\nf",
+        "should put blocks in containers on their own lines"
+    );
+
+    assert_eq!(
+        to_plaintext("```js\nx\n\n  y\n```\n\n    z", &ParseOptions::default())?,
+        "x\n\n  y\nz",
+        "should keep code as is"
+    );
+
+    assert_eq!(
+        to_plaintext("a `b` c", &ParseOptions::default())?,
+        "a b c",
+        "should keep code (text) without its markers"
+    );
+
+    assert_eq!(
+        to_plaintext("[a](b \"c\") <https://d> ![e](f)", &ParseOptions::default())?,
+        "a https://d e",
+        "should keep the text of links and the alt text of images, but not URLs"
+    );
+
+    assert_eq!(
+        to_plaintext("[a]: b\n\n[a] ![c][a] [d][e]", &ParseOptions::default())?,
+        "a c [d][e]",
+        "should drop definitions, and support references"
+    );
+
+    assert_eq!(
+        to_plaintext("&copy; &#x41; \\* &amp;lt;", &ParseOptions::default())?,
+        "© A * &lt;",
+        "should decode character escapes and character references"
+    );
+
+    assert_eq!(
+        to_plaintext("<div>\na\n</div>\n\nb <i>c</i>", &ParseOptions::default())?,
+        "b c",
+        "should drop html"
+    );
+
+    assert_eq!(
+        to_plaintext("a\n\n***\n\nb", &ParseOptions::default())?,
+        "a\nb",
+        "should drop thematic breaks"
+    );
+
+    assert_eq!(
+        to_plaintext("| a | b |\n| - | - |\n| *c* | |", &gfm)?,
+        "a\tb\nc\t",
+        "should separate table cells with a tab"
+    );
+
+    assert_eq!(
+        to_plaintext("a[^b] ~~c~~\n\n[^b]: d", &gfm)?,
+        "a c",
+        "should drop footnotes"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use markdown::{
+    to_html, to_html_batch, to_html_with_options, CompileOptions, ElementAttributes, ElementKind,
+    Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn source_positions() -> Result<(), String> {
+    let positions = Options {
+        compile: CompileOptions {
+            source_positions: true,
+            ..CompileOptions::default()
+        },
+        parse: ParseOptions::gfm(),
+    };
+
+    assert_eq!(
+        to_html("# a\n\nb"),
+        "<h1>a</h1>\n<p>b</p>",
+        "should not add positional info by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\nc\n\n***\n\nd\n===", &positions)?,
+        "<h1 data-sourcepos=\"1:1-1:3\">a</h1>\n<p data-sourcepos=\"3:1-4:1\">b\nc</p>\n<hr data-sourcepos=\"6:1-6:3\" />\n<h1 data-sourcepos=\"8:1-9:3\">d</h1>",
+        "should add positional info to headings, paragraphs, and thematic breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\nx\n```\n\n    y", &positions)?,
+        "<pre data-sourcepos=\"1:1-3:3\"><code class=\"language-js\">x\n</code></pre>\n<pre data-sourcepos=\"5:1-5:5\"><code>y\n</code></pre>",
+        "should add positional info to code"
+    );
+
+    assert_eq!(
+        to_html_with_options("> - a\n>   - b", &positions)?,
+        "<blockquote data-sourcepos=\"1:1-2:7\">\n<ul data-sourcepos=\"1:3-2:7\">\n<li data-sourcepos=\"1:3-2:7\">a\n<ul data-sourcepos=\"2:5-2:7\">\n<li data-sourcepos=\"2:5-2:7\">b</li>\n</ul>\n</li>\n</ul>\n</blockquote>",
+        "should add positional info to block quotes, lists, and list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- b\n\n  c\n\n\n1. d", &positions)?,
+        "<ul data-sourcepos=\"1:1-4:3\">\n<li data-sourcepos=\"1:1-1:3\">\n<p data-sourcepos=\"1:3-1:3\">a</p>\n</li>\n<li data-sourcepos=\"2:1-4:3\">\n<p data-sourcepos=\"2:3-2:3\">b</p>\n<p data-sourcepos=\"4:3-4:3\">c</p>\n</li>\n</ul>\n<ol data-sourcepos=\"7:1-7:4\">\n<li data-sourcepos=\"7:1-7:4\">d</li>\n</ol>",
+        "should not include trailing blank lines in list items and lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |", &positions)?,
+        "<table data-sourcepos=\"1:1-3:5\">\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>",
+        "should add positional info to tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\n\na", &positions)?,
+        "&lt;div&gt;\n<p data-sourcepos=\"3:1-3:1\">a</p>",
+        "should not add positional info to html (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\tb\n\tc\n\n\t# d", &positions)?,
+        "<p data-sourcepos=\"1:1-2:5\">a\tb\nc</p>\n<pre data-sourcepos=\"4:1-4:7\"><code># d\n</code></pre>",
+        "should count tabs up to the next tab stop"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\r\n\r\nb\r\nc\r\n\r\n- d\r\n\r\n\r\n***", &positions)?,
+        "<h1 data-sourcepos=\"1:1-1:3\">a</h1>\r\n<p data-sourcepos=\"3:1-4:1\">b\r\nc</p>\r\n<ul data-sourcepos=\"6:1-6:3\">\r\n<li data-sourcepos=\"6:1-6:3\">d</li>\r\n</ul>\r\n<hr data-sourcepos=\"9:1-9:3\" />",
+        "should support carriage return + line feeds"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\r> b\r\r```\rc\r```", &positions)?,
+        "<blockquote data-sourcepos=\"1:1-2:3\">\r<p data-sourcepos=\"1:3-2:3\">a\rb</p>\r</blockquote>\r<pre data-sourcepos=\"4:1-6:3\"><code>c\r</code></pre>",
+        "should support carriage returns"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a",
+            &Options {
+                compile: CompileOptions {
+                    source_positions: true,
+                    element_attributes: Some(ElementAttributes::new(|kind| match kind {
+                        ElementKind::Paragraph => vec![("class".into(), "b".into())],
+                        _ => vec![],
+                    })),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p data-sourcepos=\"1:1-1:1\" class=\"b\">a</p>",
+        "should support `element_attributes` too"
+    );
+
+    let citation = Options {
+        compile: CompileOptions {
+            source_positions: true,
+            block_quote_citation: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("> a\n> b\n> — c", &citation)?,
+        "<blockquote data-sourcepos=\"1:1-3:7\">\n<p data-sourcepos=\"1:3-2:3\">a\nb</p>\n<footer data-sourcepos=\"3:3-3:7\">— c</footer>\n</blockquote>",
+        "should add positional info to a paragraph w/o its attribution, and to the attribution"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n>\n> -- b", &citation)?,
+        "<blockquote data-sourcepos=\"1:1-3:6\">\n<p data-sourcepos=\"1:3-1:3\">a</p>\n<footer data-sourcepos=\"3:3-3:6\">-- b</footer>\n</blockquote>",
+        "should add positional info to a paragraph that is an attribution"
+    );
+
+    assert_eq!(
+        to_html_batch(&["# a", "b\nc"], &positions)?,
+        vec![
+            "<h1 data-sourcepos=\"1:1-1:3\">a</h1>",
+            "<p data-sourcepos=\"1:1-2:1\">b\nc</p>"
+        ],
+        "should support `source_positions` in batches"
+    );
+
+    Ok(())
+}
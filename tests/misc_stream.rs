@@ -0,0 +1,152 @@
+use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions, Parser};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn stream() -> Result<(), String> {
+    let options = Options::default();
+
+    assert_eq!(
+        Parser::new(&options).finish()?,
+        "",
+        "should support no chunks"
+    );
+
+    let mut parser = Parser::new(&options);
+    parser.push("");
+    parser.push("*a");
+    parser.push("");
+    parser.push("*");
+    assert_eq!(
+        parser.finish()?,
+        "<p><em>a</em></p>",
+        "should support empty chunks"
+    );
+
+    let mut parser = Parser::new(&options);
+    parser.push("[a]\n\n");
+    parser.push("[a]: b");
+    assert_eq!(
+        parser.finish()?,
+        "<p><a href=\"b\">a</a></p>\n",
+        "should support definitions in later chunks"
+    );
+
+    let mut parser = Parser::new(&options);
+    parser.push(b"a \xF0\x9F");
+    parser.push(b"\x91");
+    parser.push(b"\x8D b");
+    assert_eq!(
+        parser.finish()?,
+        "<p>a 👍 b</p>",
+        "should support UTF-8 sequences split over several chunks"
+    );
+
+    let mut parser = Parser::new(&options);
+    parser.push(b"a \xFF b \xF0\x9F c");
+    assert_eq!(
+        parser.finish()?,
+        "<p>a \u{FFFD} b \u{FFFD} c</p>",
+        "should replace invalid UTF-8 with the replacement character"
+    );
+
+    let mut parser = Parser::new(&options);
+    parser.push(b"a \xF0\x9F");
+    parser.push("b");
+    assert_eq!(
+        parser.finish()?,
+        "<p>a \u{FFFD}b</p>",
+        "should replace an incomplete UTF-8 sequence followed by other characters"
+    );
+
+    let mut parser = Parser::new(&options);
+    parser.push(b"a \xF0\x9F");
+    assert_eq!(
+        parser.finish()?,
+        "<p>a \u{FFFD}</p>",
+        "should replace an incomplete UTF-8 sequence at the end"
+    );
+
+    let mdx = Options {
+        parse: ParseOptions::mdx(),
+        ..Options::default()
+    };
+    let mut parser = Parser::new(&mdx);
+    parser.push("{a");
+    assert_eq!(
+        parser.finish(),
+        Err("1:3: Unexpected end of file in expression, expected a corresponding closing brace for `{`".into()),
+        "should crash on MDX errors"
+    );
+
+    let gfm = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    for value in [
+        "# a\r\n\r\nb *c*\rd\n\n```js\r\ne\r\n```",
+        "> a\n> - b\n>   c\n\n1. d\n\n    e\n\n***",
+        "[a], [b][], ![c][a]\r\n\r\n[a]: <d> \"e\"\r\n[b]: f",
+        "| a | b |\n| - | - |\n| c | ~~d~~ |\n\n<div>\n*e*\n</div>",
+        "a[^b] www.c.d 👍 ö\t&amp; `e`\n\n[^b]: f",
+    ] {
+        let expected = to_html_with_options(value, &gfm)?;
+
+        for (index, _) in value.char_indices().skip(1) {
+            let mut parser = Parser::new(&gfm);
+            parser.push(&value[..index]);
+            parser.push(&value[index..]);
+
+            assert_eq!(
+                parser.finish()?,
+                expected,
+                "should be the same as `to_html_with_options` when split at {} ({:?})",
+                index,
+                value
+            );
+        }
+
+        for index in 1..value.len() {
+            let mut parser = Parser::new(&gfm);
+            parser.push(&value.as_bytes()[..index]);
+            parser.push(&value.as_bytes()[index..]);
+
+            assert_eq!(
+                parser.finish()?,
+                expected,
+                "should be the same as `to_html_with_options` when split at byte {} ({:?})",
+                index,
+                value
+            );
+        }
+
+        let mut parser = Parser::new(&gfm);
+        for byte in value.as_bytes() {
+            parser.push([*byte]);
+        }
+
+        assert_eq!(
+            parser.finish()?,
+            expected,
+            "should be the same as `to_html_with_options` when pushing each byte ({:?})",
+            value
+        );
+
+        let mut parser = Parser::new(&gfm);
+        for char in value.chars() {
+            parser.push(&char.to_string());
+        }
+
+        assert_eq!(
+            parser.finish()?,
+            expected,
+            "should be the same as `to_html_with_options` when pushing each character ({:?})",
+            value
+        );
+    }
+
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{
+    mdast::{BlockQuote, Node, Paragraph, Root, Text},
+    to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Options,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -254,6 +259,42 @@ fn tabs_text() {
         "<p><a href=\"y\" title=\"z\">x</a></p>",
         "should support a tab between a link destination and title"
     );
+
+    assert_eq!(
+        to_html("a\tb"),
+        "<p>a\tb</p>",
+        "should keep a tab in a paragraph"
+    );
+
+    assert_eq!(
+        to_html("abc\t\td"),
+        "<p>abc\t\td</p>",
+        "should not expand tabs to spaces in a paragraph"
+    );
+
+    assert_eq!(
+        to_html("a\t"),
+        "<p>a</p>",
+        "should strip a final tab in a paragraph"
+    );
+
+    assert_eq!(
+        to_html("# a\tb\t#\n\nc\td\n==="),
+        "<h1>a\tb</h1>\n<h1>c\td</h1>",
+        "should keep a tab in headings"
+    );
+
+    assert_eq!(
+        to_html("*a\tb*"),
+        "<p><em>a\tb</em></p>",
+        "should keep a tab in attention"
+    );
+
+    assert_eq!(
+        to_html("> a\tb\n\n- c\td"),
+        "<blockquote>\n<p>a\tb</p>\n</blockquote>\n<ul>\n<li>c\td</li>\n</ul>",
+        "should keep a tab in paragraphs in containers"
+    );
 }
 
 #[test]
@@ -284,9 +325,30 @@ fn tabs_virtual_spaces() {
 
     assert_eq!(
         to_html("-\ta\n\n\tb"),
-        "<ul>\n<li>\n<p>a</p>\n<p>\tb</p>\n</li>\n</ul>",
-        // To do: CM.js does not output the tab before `b`. See if that makes sense?
-        // "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
         "should support a part of a tab as a container, and the rest of a tab as flow"
     );
 }
+
+#[test]
+fn tabs_positions() -> Result<(), String> {
+    assert_eq!(
+        to_mdast(">\tö\r\n>\t\tc", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "ö\r\nc".into(),
+                        position: Some(Position::new(1, 5, 2, 2, 10, 10))
+                    })],
+                    position: Some(Position::new(1, 3, 1, 2, 10, 10))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 10, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 10, 10))
+        }),
+        "should use byte offsets, and columns that expand tabs, in positions"
+    );
+
+    Ok(())
+}
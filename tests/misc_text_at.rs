@@ -0,0 +1,84 @@
+use markdown::{
+    mdast::{Emphasis, InlineCode, Node, Root, Text},
+    to_mdast_text_at,
+    unist::{Point, Position},
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn text_at() -> Result<(), String> {
+    assert_eq!(
+        to_mdast_text_at("a *b*", &Point::new(3, 5, 20), &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![
+                Node::Text(Text {
+                    value: "a ".into(),
+                    position: Some(Position::new(3, 5, 20, 3, 7, 22))
+                }),
+                Node::Emphasis(Emphasis {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(3, 8, 23, 3, 9, 24))
+                    })],
+                    position: Some(Position::new(3, 7, 22, 3, 10, 25))
+                })
+            ],
+            position: Some(Position::new(3, 5, 20, 3, 10, 25))
+        }),
+        "should offset positions by `point`"
+    );
+
+    assert_eq!(
+        to_mdast_text_at("a\nb", &Point::new(3, 5, 20), &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::Text(Text {
+                value: "a\nb".into(),
+                position: Some(Position::new(3, 5, 20, 4, 2, 23))
+            })],
+            position: Some(Position::new(3, 5, 20, 4, 2, 23))
+        }),
+        "should start later lines at column 1"
+    );
+
+    assert_eq!(
+        to_mdast_text_at("`a`\tb", &Point::new(1, 3, 2), &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![
+                Node::InlineCode(InlineCode {
+                    value: "a".into(),
+                    position: Some(Position::new(1, 3, 2, 1, 6, 5))
+                }),
+                Node::Text(Text {
+                    value: "\tb".into(),
+                    position: Some(Position::new(1, 6, 5, 1, 10, 7))
+                })
+            ],
+            position: Some(Position::new(1, 3, 2, 1, 10, 7))
+        }),
+        "should expand tabs from the starting column"
+    );
+
+    assert_eq!(
+        to_mdast_text_at("", &Point::new(2, 4, 9), &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![],
+            position: Some(Position::new(2, 4, 9, 2, 4, 9))
+        }),
+        "should support empty values"
+    );
+
+    assert_eq!(
+        to_mdast_text_at("# a", &Point::new(1, 1, 0), &ParseOptions::default())?,
+        Node::Root(Root {
+            children: vec![Node::Text(Text {
+                value: "# a".into(),
+                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+        }),
+        "should not parse flow constructs"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use markdown::{to_html, to_html_with_options, CompileOptions, Options, TextTransform};
+use pretty_assertions::assert_eq;
+
+/// Turn emoji shortcodes, such as `:smile:`, into emoji.
+fn emoji(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find(':') {
+        let after = &rest[start + 1..];
+        let emoji = after.find(':').and_then(|end| match &after[..end] {
+            "smile" => Some(("😄", end)),
+            "tada" => Some(("🎉", end)),
+            _ => None,
+        });
+
+        if let Some((emoji, end)) = emoji {
+            result.push_str(&rest[..start]);
+            result.push_str(emoji);
+            rest = &after[end + 1..];
+        } else {
+            result.push_str(&rest[..=start]);
+            rest = after;
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[test]
+fn text_transform() -> Result<(), String> {
+    let shortcodes = Options {
+        compile: CompileOptions {
+            text_transform: Some(TextTransform::new(emoji)),
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a :smile: b"),
+        "<p>a :smile: b</p>",
+        "should not transform text by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :smile: b :tada: c :x: d", &shortcodes)?,
+        "<p>a 😄 b 🎉 c :x: d</p>",
+        "should transform text"
+    );
+
+    assert_eq!(
+        to_html_with_options("# :tada:\n\n*:smile:* ![:smile:](a)", &shortcodes)?,
+        "<h1>🎉</h1>\n<p><em>😄</em> <img src=\"a\" alt=\"😄\" /></p>",
+        "should transform text in headings, emphasis, and image alt"
+    );
+
+    assert_eq!(
+        to_html_with_options("`:smile:`\n\n```:smile:\n:tada:\n```", &shortcodes)?,
+        "<p><code>:smile:</code></p>\n<pre><code class=\"language-:smile:\">:tada:\n</code></pre>",
+        "should not transform code"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[:smile:](a/:smile: \":smile:\") [:tada:][b]\n\n[b]: c/:tada: \":tada:\"",
+            &shortcodes
+        )?,
+        "<p><a href=\"a/:smile:\" title=\":smile:\">😄</a> <a href=\"c/:tada:\" title=\":tada:\">🎉</a></p>\n",
+        "should not transform destinations or titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b/:smile:> https://c.d/:tada:/e", &shortcodes)?,
+        "<p><a href=\"https://a.b/:smile:\">https://a.b/:smile:</a> <a href=\"https://c.d/:tada:/e\">https://c.d/:tada:/e</a></p>",
+        "should not transform autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options(":smile\\: a &amp; <b>", &shortcodes)?,
+        "<p>:smile: a &amp; &lt;b&gt;</p>",
+        "should not transform character escapes, character references, or HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a < b",
+            &Options {
+                compile: CompileOptions {
+                    text_transform: Some(TextTransform::new(|d| d.replace('a', "<a>"))),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>&lt;a&gt; &lt; b</p>",
+        "should encode the result"
+    );
+
+    Ok(())
+}
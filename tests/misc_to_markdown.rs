@@ -0,0 +1,396 @@
+use markdown::{
+    mdast::{ListItem, Node, Paragraph, Text},
+    to_html_with_options, to_markdown, to_mdast, CompileOptions, Constructs, MarkdownOptions,
+    Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Parse `value` and serialize it again.
+fn format(value: &str, options: &ParseOptions) -> Result<String, String> {
+    to_markdown(&to_mdast(value, options)?, &MarkdownOptions::default())
+}
+
+#[test]
+fn to_markdown_blocks() -> Result<(), String> {
+    let default = ParseOptions::default();
+    let gfm = ParseOptions::gfm();
+
+    assert_eq!(format("", &default)?, "", "should support empty documents");
+
+    assert_eq!(
+        format("a\n===\n\nb\n---\n\n### c ###", &default)?,
+        "# a\n\n## b\n\n### c\n",
+        "should serialize headings as ATX"
+    );
+
+    assert_eq!(
+        format("a\nb\n===\n\nc\\\nd\n---", &default)?,
+        "a\nb\n===\n\nc\\\nd\n---\n",
+        "should serialize headings w/ line endings as setext"
+    );
+
+    assert_eq!(
+        format("* a\n* b\n\n+ c", &default)?,
+        "- a\n- b\n\n* c\n",
+        "should use `-` for bullets, and another bullet for an adjacent list"
+    );
+
+    assert_eq!(
+        format("3) a\n4) b\n\n\n1. c", &default)?,
+        "3. a\n4. b\n\n1) c\n",
+        "should number ordered lists from their start, and use another delimiter for an adjacent list"
+    );
+
+    assert_eq!(
+        format("- a\n\n  b\n- c\n  - d\n\n    e", &default)?,
+        "- a\n\n  b\n- c\n  - d\n\n    e\n",
+        "should indent content in list items"
+    );
+
+    assert_eq!(
+        format("* [x] a\n* [ ] b", &gfm)?,
+        "- [x] a\n- [ ] b\n",
+        "should serialize tasks (GFM)"
+    );
+
+    assert_eq!(
+        format("> a\n> > b\n>\n> c", &default)?,
+        "> a\n>\n> > b\n>\n> c\n",
+        "should prefix lines in block quotes"
+    );
+
+    assert_eq!(
+        format("    a\n\n~~~js b\n```\n~~~\n\n~~~c `d`\n~~~", &default)?,
+        "```\na\n```\n\n````js b\n```\n````\n\n~~~c `d`\n~~~\n",
+        "should serialize code as fenced, with a fence that is long enough"
+    );
+
+    assert_eq!(
+        format("```a\\\\*b&amp;c d\\\\e&amp;f\n```", &default)?,
+        "```a\\\\*b\\&c d\\e\\&f\n```\n",
+        "should escape backslashes and ampersands in the info string of code"
+    );
+
+    assert_eq!(
+        format("***\n\n- - -\n\n___", &default)?,
+        "***\n\n***\n\n***\n",
+        "should serialize thematic breaks as `***`"
+    );
+
+    assert_eq!(
+        format("[a]: <b c> 'd'\n[e]: f(g)\n\n[a] [e]", &default)?,
+        "[a]: <b c> \"d\"\n\n[e]: f\\(g\\)\n\n[a] [e]\n",
+        "should serialize definitions"
+    );
+
+    assert_eq!(
+        format("- [a]: b\n  c\n- [d]: e\n  \\\"f\\\"", &default)?,
+        "- [a]: b\n  c\n- [d]: e\n  \\\"f\"\n",
+        "should not add a blank line between a definition and a paragraph in a tight list item"
+    );
+
+    assert_eq!(
+        format(
+            "| a | b | c |\n| :- | :-: | - |\n| d \\| e | `f\\|g` |",
+            &gfm
+        )?,
+        "| a | b | c |\n| :-- | :-: | --- |\n| d \\| e | `f\\|g` |\n",
+        "should serialize tables (GFM)"
+    );
+
+    assert_eq!(
+        format("a[^b]\n\n[^b]: c\n\n    d", &gfm)?,
+        "a[^b]\n\n[^b]: c\n\n    d\n",
+        "should serialize footnote definitions (GFM)"
+    );
+
+    assert_eq!(
+        format(
+            "---\na: b\n---\n\n$$\nc\n$$",
+            &ParseOptions {
+                constructs: Constructs {
+                    frontmatter: true,
+                    math_flow: true,
+                    ..Constructs::default()
+                },
+                ..ParseOptions::default()
+            }
+        )?,
+        "---\na: b\n---\n\n$$\nc\n$$\n",
+        "should serialize frontmatter and math (flow)"
+    );
+
+    assert_eq!(
+        format(
+            ":::a{#b .c d=\"e f\"}\n::b[*c*]\n\n:::d\ne\n:::\n:::",
+            &ParseOptions {
+                constructs: Constructs {
+                    directive_container: true,
+                    directive_leaf: true,
+                    ..Constructs::default()
+                },
+                ..ParseOptions::default()
+            }
+        )?,
+        "::::a{#b .c d=\"e f\"}\n::b[*c*]\n\n:::d\ne\n:::\n::::\n",
+        "should serialize directives, with more colons for outer fences"
+    );
+
+    assert_eq!(
+        format(
+            "::: a\nb\n:::",
+            &ParseOptions {
+                constructs: Constructs {
+                    fenced_div: true,
+                    ..Constructs::default()
+                },
+                ..ParseOptions::default()
+            }
+        )?,
+        "::: {.a}\nb\n:::\n",
+        "should serialize fenced divs, with attributes in braces"
+    );
+
+    assert_eq!(
+        format(
+            "import a from 'b'\n\n<A b=\"c\" d={e}>\n  *f*\n</A>\n\n{1 + 1} <B />",
+            &ParseOptions::mdx()
+        )?,
+        "import a from 'b'\n\n<A b=\"c\" d={e}>\n*f*\n</A>\n\n{1 + 1} <B />\n",
+        "should serialize MDX"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_phrasing() -> Result<(), String> {
+    let default = ParseOptions::default();
+
+    assert_eq!(
+        format("_a_ __b__ *c **d** e*", &default)?,
+        "*a* **b** *c **d** e*\n",
+        "should use `*` for emphasis and strong"
+    );
+
+    assert_eq!(
+        format("*_a_* a_b_c", &default)?,
+        "*_a_* a_b_c\n",
+        "should use another marker for emphasis directly in emphasis"
+    );
+
+    assert_eq!(
+        format("`a` `` b`c `` ` `` `", &default)?,
+        "`a` `` b`c `` ` `` `\n",
+        "should serialize code (text) with a sequence that does not occur in it"
+    );
+
+    assert_eq!(
+        format("a\\\nb  \nc", &default)?,
+        "a\\\nb\\\nc\n",
+        "should serialize breaks with a backslash"
+    );
+
+    assert_eq!(
+        format("[a](b \"c\") ![d](<e f>) <https://g.h> <i@j.k>", &default)?,
+        "[a](b \"c\") ![d](<e f>) <https://g.h> <i@j.k>\n",
+        "should serialize links, images, and autolinks"
+    );
+
+    assert_eq!(
+        format(
+            "[a][b] [c][] [d] ![e][b] ![f][]\n\n[b]: g\n[c]: h\n[d]: i\n[f]: j",
+            &default
+        )?,
+        "[a][b] [c][] [d] ![e][b] ![f][]\n\n[b]: g\n\n[c]: h\n\n[d]: i\n\n[f]: j\n",
+        "should preserve the kind of references"
+    );
+
+    assert_eq!(
+        format("[*a* \\[b&amp;][]\n\n[*A* \\[b&amp;]: c", &default)?,
+        "[*a* \\[b&][*a* \\[b&amp;]\n\n[*a* \\[b&amp;]: c\n",
+        "should use a full reference when the text cannot be the label"
+    );
+
+    assert_eq!(
+        format("~~a~~ b[^c]\n\n[^c]: d", &ParseOptions::gfm())?,
+        "~~a~~ b[^c]\n\n[^c]: d\n",
+        "should serialize strikethrough and footnote references (GFM)"
+    );
+
+    assert_eq!(
+        format(
+            "$a$ and $$ b$c $$",
+            &ParseOptions {
+                constructs: Constructs {
+                    math_text: true,
+                    ..Constructs::default()
+                },
+                ..ParseOptions::default()
+            }
+        )?,
+        "$a$ and $$ b$c $$\n",
+        "should serialize math (text)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_escape() -> Result<(), String> {
+    let default = ParseOptions::default();
+
+    assert_eq!(
+        format("\\# a\n\\> b\n\\- c\n\\+ d\n1\\. e\n2\\) f", &default)?,
+        "\\# a\n\\> b\n\\- c\n\\+ d\n1\\. e\n2\\) f\n",
+        "should escape what would start a block at the start of a line"
+    );
+
+    assert_eq!(
+        format("a\n\\=\n\n\\-\\-\\-", &default)?,
+        "a\n\\=\n\n\\---\n",
+        "should escape what would be a setext underline or thematic break"
+    );
+
+    assert_eq!(
+        format(
+            "\\*a\\* \\_b\\_ \\[c\\] \\`d\\` \\<e> \\&amp; f\\\\g",
+            &default
+        )?,
+        "\\*a\\* \\_b\\_ \\[c\\] \\`d\\` \\<e> \\&amp; f\\g\n",
+        "should escape what would be phrasing markup"
+    );
+
+    assert_eq!(
+        format("a_b 1 < 2 & 3 c\\d 4.5", &default)?,
+        "a_b 1 < 2 & 3 c\\d 4.5\n",
+        "should not escape what cannot be markup"
+    );
+
+    assert_eq!(
+        format("&#x20; a\n\na&#10;&#10;b", &default)?,
+        "&#x20; a\n\na\n&#10;b\n",
+        "should encode initial whitespace and blank lines"
+    );
+
+    assert_eq!(
+        format("\\![a][]\n\n[a]: b", &default)?,
+        "\\![a][]\n\n[a]: b\n",
+        "should escape an exclamation mark before a link"
+    );
+
+    assert_eq!(
+        format("# a \\#", &default)?,
+        "# a \\#\n",
+        "should escape a final number sign in a heading"
+    );
+
+    assert_eq!(
+        format("a <b c\n    > d", &default)?,
+        "a <b c\n    > d\n",
+        "should indent lines in HTML (text) that would start a block"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_options() -> Result<(), String> {
+    let tree = to_mdast("* a\n\n*b* **c**\n\n```\nd\n```", &ParseOptions::default())?;
+
+    assert_eq!(
+        to_markdown(
+            &tree,
+            &MarkdownOptions {
+                bullet: '+',
+                emphasis: '_',
+                fence: '~',
+            }
+        )?,
+        "+ a\n\n_b_ __c__\n\n~~~\nd\n~~~\n",
+        "should support other markers"
+    );
+
+    assert_eq!(
+        to_markdown(
+            &to_mdast("a*b*c", &ParseOptions::default())?,
+            &MarkdownOptions {
+                emphasis: '_',
+                ..MarkdownOptions::default()
+            }
+        )?,
+        "a*b*c\n",
+        "should use `*` for emphasis in words, even when `_` is preferred"
+    );
+
+    assert_eq!(
+        to_markdown(
+            &tree,
+            &MarkdownOptions {
+                bullet: '.',
+                ..MarkdownOptions::default()
+            }
+        ),
+        Err("Cannot serialize with `.` as bullet, expected `*`, `+`, or `-`".into()),
+        "should crash on an invalid bullet"
+    );
+
+    assert_eq!(
+        to_markdown(
+            &Node::ListItem(ListItem {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: None
+                    })],
+                    position: None
+                })],
+                position: None,
+                spread: false,
+                checked: None
+            }),
+            &MarkdownOptions::default()
+        ),
+        Err("Cannot serialize `ListItem` outside of `List`".into()),
+        "should crash on a list item outside of a list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_round_trip() -> Result<(), String> {
+    let options = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    for value in [
+        "# a *b*\n\n> c\n> - d\n>   1. e\n\n```js\nf\n```",
+        "- a\n\n  b\n- c\n\n***\n\n[d]: e 'f'\n\n[g][d] ![h](i)",
+        "| a | b |\n| - | -: |\n| `c` | ~~d~~ |\n\n<div>\ne\n</div>",
+        "\\# 1\\. \\* \\_ \\` \\[a\\] \\<b> \\&amp; c\\\\d",
+        "*a **b** c* _d_e_ **f*g*h**",
+        "```a\\\\*b&amp;c d\\\\e&amp;f\n```\n\n~~~a`b c`\\\\*\n~~~",
+        "- [a]: b\n  c\n- [d]: e\n  \\\"f\\\"\n- g",
+        "a <b c\n    > d <!--e\n    # f-->",
+        "a\nb\n===\n\nc\\\nd\n---",
+    ] {
+        let markdown = to_markdown(
+            &to_mdast(value, &options.parse)?,
+            &MarkdownOptions::default(),
+        )?;
+
+        assert_eq!(
+            to_html_with_options(&markdown, &options)?,
+            to_html_with_options(&format!("{}\n", value), &options)?,
+            "should serialize to equivalent markdown ({:?})",
+            value
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use markdown::{
+    to_html, to_html_with_options, CompileOptions, Options, ParseOptions, UrlKind, UrlRewriter,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn url_rewriter() -> Result<(), String> {
+    let rewrite = Options {
+        compile: CompileOptions {
+            url_rewriter: Some(UrlRewriter::new(|url, kind| match kind {
+                UrlKind::Image => format!("https://camo.example/?{}", url),
+                UrlKind::Link => format!("[{}]", url),
+            })),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("[a](b) ![c](d)"),
+        "<p><a href=\"b\">a</a> <img src=\"d\" alt=\"c\" /></p>",
+        "should not rewrite by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b)", &rewrite)?,
+        "<p><a href=\"%5Bb%5D\">a</a></p>",
+        "should rewrite links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b)", &rewrite)?,
+        "<p><img src=\"https://camo.example/?b\" alt=\"a\" /></p>",
+        "should rewrite images"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a] ![b][a]\n\n[a]: c", &rewrite)?,
+        "<p><a href=\"%5Bc%5D\">a</a> <img src=\"https://camo.example/?c\" alt=\"b\" /></p>\n",
+        "should rewrite URLs from definitions, by the kind of element"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a> <b@c.d>", &rewrite)?,
+        "<p><a href=\"%5Bhttps://a%5D\">https://a</a> <a href=\"%5Bmailto:b@c.d%5D\">b@c.d</a></p>",
+        "should rewrite autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "www.a.b",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    url_rewriter: Some(UrlRewriter::new(|url, _| format!("{}/", url))),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<p><a href=\"http://www.a.b/\">www.a.b</a></p>",
+        "should rewrite autolink literals"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b&amp;c\\)d%20e)", &rewrite)?,
+        "<p><a href=\"%5Bb&amp;c)d%20e%5D\">a</a></p>",
+        "should pass decoded URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](javascript:b) ![c](javascript:d)", &rewrite)?,
+        "<p><a href=\"\">a</a> <img src=\"\" alt=\"c\" /></p>",
+        "should drop dangerous protocols before rewriting"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](b) [c](d)",
+            &Options {
+                compile: CompileOptions {
+                    url_rewriter: Some(UrlRewriter::new(|url, _| {
+                        if url == "b" {
+                            "javascript:alert(1)".into()
+                        } else {
+                            "\"><script>👍".into()
+                        }
+                    })),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"javascript:alert(1)\">a</a> <a href=\"%22%3E%3Cscript%3E%F0%9F%91%8D\">c</a></p>",
+        "should encode, but not check, what is returned"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c", &{
+            let mut options = Options::gfm();
+            options.compile.url_rewriter = Some(UrlRewriter::new(|_, _| "x".into()));
+            options
+        })?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>c <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should not rewrite generated URLs (footnotes)"
+    );
+
+    assert!(
+        format!("{:?}", rewrite.compile).contains("url_rewriter: Some(\"[Function]\")"),
+        "should support `Debug`"
+    );
+
+    Ok(())
+}